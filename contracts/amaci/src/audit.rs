@@ -0,0 +1,441 @@
+//! Offline audit of a finished round. `audit` replays the deactivate, process and tally batches
+//! and the `AddNewKey` calls of a `RoundDump` from the public inputs the contract had, re-derives
+//! every input hash the way `execute_process_deactivate_message`, `execute_add_new_key`,
+//! `execute_process_message` and `execute_process_tally` do, verifies every proof with the keys
+//! `match_vkeys` picks and checks the results against the last tally commitment.
+//!
+//! The state tree is not rebuilt: the state roots the deactivate batches and the processing were
+//! checked against are taken from the dump, and `AuditReport::unverified` says so.
+
+use crate::circuit_params::match_vkeys;
+use crate::contract::hash_message_and_enc_pub_key;
+use crate::error::ContractError;
+use crate::msg::Groth16ProofType;
+use crate::state::{
+    Groth16PreparedVkeyStr, Groth16ProofStr, Groth16VkeyStr, MaciParameters, MessageData, PubKey,
+};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint256;
+use maci_core::groth16_parser::{parse_groth16_proof, parse_groth16_vkey, parse_public_input};
+use maci_core::groth16_verifier::{prepare_groth16_vkey, verify_groth16_prepared};
+use maci_core::quinary_tree::{compute_root, zero_hashes};
+use maci_core::utils::{
+    hash_256_uint256_list, snark_scalar_field, try_hash2, uint256_from_hex_string,
+};
+use maci_core::CoreError;
+use pairing_ce::bn256::Bn256;
+use thiserror::Error;
+
+/// The public state of one message chain of a round, as read from the contract and its events
+#[cw_serde]
+pub struct RoundDump {
+    /// the question whose messages, batches and results the dump holds, 0 for the first ballot.
+    /// The deactivate messages are shared by every question
+    pub question_id: u64,
+    pub parameters: MaciParameters,
+    pub coordinator: PubKey,
+    pub circuit_type: Uint256,
+    pub num_sign_ups: Uint256,
+    pub max_vote_options: Uint256,
+    /// the state root when the processing period started
+    pub state_root: Uint256,
+    pub deactivate_messages: Vec<PublishedMessage>,
+    pub deactivate_batches: Vec<DeactivateBatch>,
+    pub add_new_keys: Vec<AddNewKeyCall>,
+    pub messages: Vec<PublishedMessage>,
+    pub process_batches: Vec<ProcessBatch>,
+    pub tally_batches: Vec<TallyBatch>,
+    pub results: Vec<Uint256>,
+    pub salt: Uint256,
+}
+
+#[cw_serde]
+pub struct PublishedMessage {
+    pub message: MessageData,
+    pub enc_pub_key: PubKey,
+}
+
+/// A `ProcessDeactivateMessage` call, in the order they were accepted
+#[cw_serde]
+pub struct DeactivateBatch {
+    pub size: Uint256,
+    pub new_deactivate_commitment: Uint256,
+    pub new_deactivate_root: Uint256,
+    /// the state root recorded when the last deactivate message of the batch was published
+    pub state_root: Uint256,
+    pub proof: Groth16ProofType,
+}
+
+/// An `AddNewKey` call, in the order they were accepted
+#[cw_serde]
+pub struct AddNewKeyCall {
+    pub pubkey: PubKey,
+    pub nullifier: Uint256,
+    pub d: [Uint256; 4],
+    /// the number of deactivate batches processed before the call, the proof is against the
+    /// deactivate root of the last of them
+    pub deactivate_batches: u64,
+    pub proof: Groth16ProofType,
+}
+
+/// A `ProcessMessage` call, in the order they were accepted
+#[cw_serde]
+pub struct ProcessBatch {
+    pub question_id: u64,
+    pub new_state_commitment: Uint256,
+    pub proof: Groth16ProofType,
+}
+
+/// A `ProcessTally` call, in the order they were accepted
+#[cw_serde]
+pub struct TallyBatch {
+    pub question_id: u64,
+    pub new_tally_commitment: Uint256,
+    pub proof: Groth16ProofType,
+}
+
+#[cw_serde]
+pub struct AuditReport {
+    pub question_id: u64,
+    pub deactivate_batches: u64,
+    pub add_new_keys: u64,
+    pub process_batches: u64,
+    pub tally_batches: u64,
+    /// the checks the audit could not make, whose inputs it took from the dump
+    pub unverified: Vec<String>,
+    pub state_commitment: Uint256,
+    pub tally_commitment: Uint256,
+    pub total_result: Uint256,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum AuditError {
+    #[error("{0}")]
+    Contract(#[from] ContractError),
+
+    #[error("{0}")]
+    Core(#[from] CoreError),
+
+    #[error("invalid {step} proof in batch {batch}")]
+    InvalidProof { step: String, batch: usize },
+
+    #[error("AddNewKey call {call} follows {deactivate_batches} deactivate batches of {count}")]
+    UnknownDeactivateBatch {
+        call: usize,
+        deactivate_batches: u64,
+        count: usize,
+    },
+
+    #[error("AddNewKey call {call} reuses the nullifier {nullifier}")]
+    NullifierReused { call: usize, nullifier: Uint256 },
+
+    #[error("{step} batch {batch} has nothing left to process")]
+    ExtraBatch { step: String, batch: usize },
+
+    #[error("deactivate batch {batch} of {size} messages exceeds the batch size {batch_size}")]
+    BatchTooLarge {
+        batch: usize,
+        size: Uint256,
+        batch_size: Uint256,
+    },
+
+    #[error("{step} batch {batch} is for question {found}, the dump for question {expected}")]
+    QuestionMismatch {
+        step: String,
+        batch: usize,
+        expected: u64,
+        found: u64,
+    },
+
+    #[error("{step} stopped with {left} left to process")]
+    Unprocessed { step: String, left: Uint256 },
+
+    #[error("{count} results for {max_vote_options} vote options")]
+    TooManyResults {
+        count: usize,
+        max_vote_options: Uint256,
+    },
+
+    #[error("results commit to {found}, the tally proofs to {expected}")]
+    TallyCommitmentMismatch { expected: Uint256, found: Uint256 },
+}
+
+pub fn audit(dump: &RoundDump) -> Result<AuditReport, AuditError> {
+    let parameters = &dump.parameters;
+    let vkeys = match_vkeys(parameters)?;
+    let coordinator_hash = try_hash2([dump.coordinator.x, dump.coordinator.y])?;
+    let state_tree_depth: u32 = parse_depth(parameters.state_tree_depth)?;
+
+    // Deactivate messages, replayed as execute_process_deactivate_message
+    let deactivate_vkey = prepare(vkeys.deactivate_vkey)?;
+    let dmsg_hashes = message_chain(&dump.deactivate_messages)?;
+    let dmsg_chain_length = dmsg_hashes.len() - 1;
+    let zeros = zero_hashes(Uint256::zero(), state_tree_depth + 2)?;
+    let mut deactivate_commitment = try_hash2([
+        zeros[state_tree_depth as usize],
+        zeros[state_tree_depth as usize + 2],
+    ])?;
+    let mut processed_dmsg_count = 0usize;
+    for (batch, data) in dump.deactivate_batches.iter().enumerate() {
+        if processed_dmsg_count >= dmsg_chain_length {
+            return Err(extra_batch("deactivate", batch));
+        }
+        if data.size > parameters.message_batch_size {
+            return Err(AuditError::BatchTooLarge {
+                batch,
+                size: data.size,
+                batch_size: parameters.message_batch_size,
+            });
+        }
+        let size: usize = data.size.to_string().parse().unwrap_or(usize::MAX);
+        let batch_end_index = processed_dmsg_count
+            .saturating_add(size)
+            .min(dmsg_chain_length);
+        let input = [
+            data.new_deactivate_root,
+            coordinator_hash,
+            dmsg_hashes[processed_dmsg_count],
+            dmsg_hashes[batch_end_index],
+            deactivate_commitment,
+            data.new_deactivate_commitment,
+            data.state_root,
+        ];
+        verify(&deactivate_vkey, &data.proof, &input, "deactivate", batch)?;
+        deactivate_commitment = data.new_deactivate_commitment;
+        processed_dmsg_count = batch_end_index;
+    }
+    if processed_dmsg_count != dmsg_chain_length {
+        let left = (dmsg_chain_length - processed_dmsg_count) as u128;
+        return Err(unprocessed("deactivate", Uint256::from_u128(left)));
+    }
+
+    // New keys, replayed as execute_add_new_key
+    let add_key_vkey = prepare(vkeys.add_key_vkey)?;
+    let mut nullifiers = vec![];
+    for (call, data) in dump.add_new_keys.iter().enumerate() {
+        // The deactivate root is 0 until the first deactivate batch is processed
+        let deactivate_root = match data.deactivate_batches as usize {
+            0 => Uint256::zero(),
+            n if n <= dump.deactivate_batches.len() => {
+                dump.deactivate_batches[n - 1].new_deactivate_root
+            }
+            _ => {
+                return Err(AuditError::UnknownDeactivateBatch {
+                    call,
+                    deactivate_batches: data.deactivate_batches,
+                    count: dump.deactivate_batches.len(),
+                })
+            }
+        };
+        if nullifiers.contains(&data.nullifier) {
+            return Err(AuditError::NullifierReused {
+                call,
+                nullifier: data.nullifier,
+            });
+        }
+        nullifiers.push(data.nullifier);
+        let input = [
+            deactivate_root,
+            coordinator_hash,
+            data.nullifier,
+            data.d[0],
+            data.d[1],
+            data.d[2],
+            data.d[3],
+        ];
+        verify(&add_key_vkey, &data.proof, &input, "add_new_key", call)?;
+    }
+
+    // Messages, replayed as execute_process_message
+    let process_vkey = prepare(vkeys.process_vkey)?;
+    let msg_hashes = message_chain(&dump.messages)?;
+    let msg_chain_length = Uint256::from_u128((msg_hashes.len() - 1) as u128);
+    let batch_size = parameters.message_batch_size;
+    let packed_vals = if dump.circuit_type == Uint256::from_u128(0u128) {
+        (dump.num_sign_ups << 32) + dump.max_vote_options
+    } else if dump.circuit_type == Uint256::from_u128(1u128) {
+        (dump.num_sign_ups << 32) + (dump.circuit_type << 64) + dump.max_vote_options
+    } else {
+        Uint256::zero()
+    };
+    let mut state_commitment = try_hash2([dump.state_root, Uint256::zero()])?;
+    let mut processed_msg_count = Uint256::zero();
+    for (batch, data) in dump.process_batches.iter().enumerate() {
+        if processed_msg_count >= msg_chain_length {
+            return Err(extra_batch("process", batch));
+        }
+        check_question(dump.question_id, data.question_id, "process", batch)?;
+        let batch_start_index =
+            (msg_chain_length - processed_msg_count - Uint256::one()) / batch_size * batch_size;
+        let batch_end_index = (batch_start_index + batch_size).min(msg_chain_length);
+        let input = [
+            packed_vals,
+            coordinator_hash,
+            msg_hashes[index(batch_start_index)],
+            msg_hashes[index(batch_end_index)],
+            state_commitment,
+            data.new_state_commitment,
+            deactivate_commitment,
+        ];
+        verify(&process_vkey, &data.proof, &input, "process", batch)?;
+        state_commitment = data.new_state_commitment;
+        processed_msg_count += batch_end_index - batch_start_index;
+    }
+    if processed_msg_count != msg_chain_length {
+        return Err(unprocessed(
+            "process",
+            msg_chain_length - processed_msg_count,
+        ));
+    }
+
+    // State leaves, replayed as execute_process_tally
+    let tally_vkey = prepare(vkeys.tally_vkey)?;
+    let batch_size = Uint256::from_u128(5u128).pow(parse_depth(parameters.int_state_tree_depth)?);
+    let mut tally_commitment = Uint256::zero();
+    let mut processed_user_count = Uint256::zero();
+    for (batch, data) in dump.tally_batches.iter().enumerate() {
+        if processed_user_count >= dump.num_sign_ups {
+            return Err(extra_batch("tally", batch));
+        }
+        check_question(dump.question_id, data.question_id, "tally", batch)?;
+        let batch_num = processed_user_count / batch_size;
+        let input = [
+            (dump.num_sign_ups << 32) + batch_num,
+            state_commitment,
+            tally_commitment,
+            data.new_tally_commitment,
+        ];
+        verify(&tally_vkey, &data.proof, &input, "tally", batch)?;
+        tally_commitment = data.new_tally_commitment;
+        processed_user_count += batch_size;
+    }
+    if processed_user_count < dump.num_sign_ups {
+        return Err(unprocessed(
+            "tally",
+            dump.num_sign_ups - processed_user_count,
+        ));
+    }
+
+    // Results, checked as execute_stop_tallying_period
+    if Uint256::from_u128(dump.results.len() as u128) > dump.max_vote_options {
+        return Err(AuditError::TooManyResults {
+            count: dump.results.len(),
+            max_vote_options: dump.max_vote_options,
+        });
+    }
+    let results_root = compute_root(
+        &dump.results,
+        Uint256::zero(),
+        parse_depth(parameters.vote_option_tree_depth)?,
+    )?;
+    let results_commitment = try_hash2([results_root, dump.salt])?;
+    if tally_commitment != Uint256::zero() && results_commitment != tally_commitment {
+        return Err(AuditError::TallyCommitmentMismatch {
+            expected: tally_commitment,
+            found: results_commitment,
+        });
+    }
+
+    let mut unverified = vec![String::from(
+        "the state root at the start of processing is taken from the dump",
+    )];
+    if !dump.deactivate_batches.is_empty() {
+        unverified.push(String::from(
+            "the state roots of the deactivate batches are taken from the dump",
+        ));
+    }
+
+    Ok(AuditReport {
+        question_id: dump.question_id,
+        deactivate_batches: dump.deactivate_batches.len() as u64,
+        add_new_keys: dump.add_new_keys.len() as u64,
+        process_batches: dump.process_batches.len() as u64,
+        tally_batches: dump.tally_batches.len() as u64,
+        unverified,
+        state_commitment,
+        tally_commitment,
+        total_result: dump.results.iter().fold(Uint256::zero(), |sum, r| sum + *r),
+    })
+}
+
+/// The hashes of a message chain, starting with the 0 of the empty chain
+fn message_chain(messages: &[PublishedMessage]) -> Result<Vec<Uint256>, ContractError> {
+    let mut hashes = vec![Uint256::zero()];
+    for published in messages {
+        let prev_hash = *hashes.last().unwrap();
+        hashes.push(hash_message_and_enc_pub_key(
+            published.message.clone(),
+            published.enc_pub_key.clone(),
+            prev_hash,
+        )?);
+    }
+    Ok(hashes)
+}
+
+fn prepare(vkey: Groth16VkeyStr) -> Result<Groth16PreparedVkeyStr, CoreError> {
+    Ok(prepare_groth16_vkey(&parse_groth16_vkey::<Bn256>(vkey)?))
+}
+
+fn verify(
+    vkey: &Groth16PreparedVkeyStr,
+    proof: &Groth16ProofType,
+    input: &[Uint256],
+    step: &str,
+    batch: usize,
+) -> Result<(), AuditError> {
+    let input_hash = uint256_from_hex_string(&hash_256_uint256_list(input)) % snark_scalar_field();
+
+    let proof_str = Groth16ProofStr {
+        pi_a: hex::decode(&proof.a).map_err(|_| ContractError::HexDecodingError {})?,
+        pi_b: hex::decode(&proof.b).map_err(|_| ContractError::HexDecodingError {})?,
+        pi_c: hex::decode(&proof.c).map_err(|_| ContractError::HexDecodingError {})?,
+    };
+    let proof = parse_groth16_proof::<Bn256>(proof_str)?;
+    if !verify_groth16_prepared(vkey, &proof, &[parse_public_input(input_hash)?])? {
+        return Err(AuditError::InvalidProof {
+            step: step.to_string(),
+            batch,
+        });
+    }
+    Ok(())
+}
+
+fn parse_depth(depth: Uint256) -> Result<u32, CoreError> {
+    depth
+        .to_string()
+        .parse()
+        .map_err(|_| CoreError::TreeOverflow {
+            depth: u32::MAX,
+            leaves: 0,
+        })
+}
+
+fn index(value: Uint256) -> usize {
+    value.to_string().parse().unwrap()
+}
+
+fn check_question(expected: u64, found: u64, step: &str, batch: usize) -> Result<(), AuditError> {
+    if expected != found {
+        return Err(AuditError::QuestionMismatch {
+            step: step.to_string(),
+            batch,
+            expected,
+            found,
+        });
+    }
+    Ok(())
+}
+
+fn extra_batch(step: &str, batch: usize) -> AuditError {
+    AuditError::ExtraBatch {
+        step: step.to_string(),
+        batch,
+    }
+}
+
+fn unprocessed(step: &str, left: Uint256) -> AuditError {
+    AuditError::Unprocessed {
+        step: step.to_string(),
+        left,
+    }
+}
@@ -0,0 +1,29 @@
+//! Re-verifies a finished round from a dump of its public state.
+//!
+//! cargo run --bin audit -- round.json
+//!
+//! The dump is the JSON of `cw_amaci::audit::RoundDump` for one question of the round: the round
+//! parameters and coordinator key, the published messages and deactivate messages, the accepted
+//! process, deactivate and tally batches and `AddNewKey` calls with their proofs, and the results
+//! with their salt. Every input hash is re-derived from the dump and every proof is verified, so
+//! the operator is not trusted. The state roots are taken from the dump, the report lists them in
+//! `unverified`. The report is printed as JSON; the first failed check is printed as an error.
+
+use std::error::Error;
+use std::fs;
+
+use cosmwasm_std::{from_json, to_json_string};
+use cw_amaci::audit::{audit, RoundDump};
+
+const USAGE: &str = "usage: audit <round dump>";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [path] = args.as_slice() else {
+        return Err(USAGE.into());
+    };
+    let dump: RoundDump = from_json(fs::read(path)?)?;
+    let report = audit(&dump)?;
+    println!("{}", to_json_string(&report)?);
+    Ok(())
+}
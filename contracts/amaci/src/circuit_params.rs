@@ -1,4 +1,4 @@
-use crate::groth16_parser::parse_groth16_vkey;
+use maci_core::groth16_parser::parse_groth16_vkey;
 
 use crate::ContractError;
 use crate::{
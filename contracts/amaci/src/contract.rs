@@ -1,41 +1,51 @@
 use crate::circuit_params::match_vkeys;
 use crate::error::ContractError;
-use crate::groth16_parser::{parse_groth16_proof, parse_groth16_vkey};
+use maci_core::groth16_parser::{parse_groth16_proof, parse_groth16_vkey, parse_public_input};
+use maci_core::groth16_verifier::{prepare_groth16_vkey, verify_groth16_prepared};
+use crate::migrates::migrate_v0_1_1::migrate_v0_1_1;
+use crate::migrates::migrate_v0_1_2::migrate_v0_1_2;
+use crate::migrates::migrate_v0_1_3::migrate_v0_1_3;
 use crate::msg::{
-    ExecuteMsg, Groth16ProofType, InstantiateMsg, InstantiationData, QueryMsg, TallyDelayInfo,
-    WhitelistBase,
+    ExecuteMsg, Groth16ProofType, InstantiateMsg, InstantiationData, MigrateMsg, QueryMsg,
+    RegistryExecuteMsg, RoundPerformance, TallyDelayInfo, VotingTimeResponse, WhitelistBase,
 };
 use crate::state::{
-    Admin, DelayRecord, DelayRecords, DelayType, Groth16ProofStr, MaciParameters, MessageData,
-    Period, PeriodStatus, PubKey, QuinaryTreeRoot, RoundInfo, StateLeaf, VotingTime, Whitelist,
+    Admin, BatchRecord, BatchStep, DelayRecord, DelayRecords, DelayType, Groth16PreparedVkeyStr,
+    Groth16ProofStr, Groth16VkeyStr, IncrementalQuinaryTree, MaciParameters, MessageData, Period,
+    PeriodStatus, PubKey, Question, QuinaryTreeRoot, RoundInfo, StateLeaf, VotingTime, Whitelist,
     WhitelistConfig, ADMIN, CERTSYSTEM, CIRCUITTYPE, COORDINATORHASH, CREATE_ROUND_WINDOW,
     CURRENT_DEACTIVATE_COMMITMENT, CURRENT_STATE_COMMITMENT, CURRENT_TALLY_COMMITMENT,
     DEACTIVATE_COUNT, DEACTIVATE_DELAY, DELAY_RECORDS, DMSG_CHAIN_LENGTH, DMSG_HASHES, DNODES,
-    FEEGRANTS, FIRST_DMSG_TIMESTAMP, GROTH16_DEACTIVATE_VKEYS, GROTH16_NEWKEY_VKEYS,
-    GROTH16_PROCESS_VKEYS, GROTH16_TALLY_VKEYS, LEAF_IDX_0, MACIPARAMETERS,
-    MACI_DEACTIVATE_MESSAGE, MACI_OPERATOR, MAX_LEAVES_COUNT, MAX_VOTE_OPTIONS, MSG_CHAIN_LENGTH,
-    MSG_HASHES, NODES, NULLIFIERS, NUMSIGNUPS, PENALTY_RATE, PERIOD, PRE_DEACTIVATE_ROOT,
-    PROCESSED_DMSG_COUNT, PROCESSED_MSG_COUNT, PROCESSED_USER_COUNT, QTR_LIB, RESULT, ROUNDINFO,
-    SIGNUPED, STATEIDXINC, STATE_ROOT_BY_DMSG, TALLY_TIMEOUT, TOTAL_RESULT,
-    VOICECREDITBALANCE, VOICE_CREDIT_AMOUNT, VOTEOPTIONMAP, VOTINGTIME, WHITELIST, ZEROS,
-    ZEROS_H10, TALLY_DELAY_MAX_HOURS, FEE_RECIPIENT
+    FEEGRANTS, FIRST_DMSG_TIMESTAMP, GROTH16_DEACTIVATE_PREPARED_VKEYS, GROTH16_DEACTIVATE_VKEYS,
+    GROTH16_NEWKEY_PREPARED_VKEYS, GROTH16_NEWKEY_VKEYS, GROTH16_PROCESS_PREPARED_VKEYS,
+    GROTH16_PROCESS_VKEYS, GROTH16_TALLY_PREPARED_VKEYS, GROTH16_TALLY_VKEYS, LEAF_IDX_0,
+    MACIPARAMETERS, MACI_DEACTIVATE_MESSAGE, MACI_OPERATOR, MAX_LEAVES_COUNT, MAX_VOTE_OPTIONS,
+    MSG_CHAIN_LENGTH, MSG_HASHES, NULLIFIERS, NUMSIGNUPS, PENALTY_RATE, PERIOD,
+    PRE_DEACTIVATE_ROOT, PROCESSED_DMSG_COUNT, PROCESSED_MSG_COUNT, PROCESSED_USER_COUNT, QTR_LIB,
+    RESULT, ROUNDINFO, SIGNUPED, SIGNUPTIME, STATEIDXINC, STATE_ROOT_BY_DMSG, STATE_TREE,
+    TALLY_TIMEOUT, TOTAL_RESULT, VOICECREDITBALANCE, VOICE_CREDIT_AMOUNT, VOTEOPTIONMAP,
+    VOTINGTIME, WHITELIST, ZEROS, ZEROS_H10, TALLY_DELAY_MAX_HOURS, FEE_RECIPIENT, QUESTIONS,
+    QUESTION_COUNT, QUESTION_MSG_HASHES, QUESTION_RESULT, BATCH_RECORDS, BATCH_RECORD_COUNT,
+    REGISTRY, PUBLISHED_MESSAGES,
 };
 use cosmwasm_schema::cw_serde;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cw2::set_contract_version;
+use cw_storage_plus::{Bound, Item};
 
 use pairing_ce::bn256::Bn256;
 
-use crate::utils::{hash2, hash5, hash_256_uint256_list, uint256_from_hex_string};
+use maci_core::utils::{
+    hash2, hash_256_uint256_list, try_hash2, try_hash5, uint256_from_hex_string,
+};
 use cosmwasm_std::{
-    attr, coins, to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-    Response, StdResult, Timestamp, Uint128, Uint256, Decimal,
+    attr, coins, to_json_binary, to_json_string, Addr, BankMsg, Binary, CosmosMsg, Decimal, Deps,
+    DepsMut, Env, Event, MessageInfo, Order, Reply, Response, StdResult, Storage, SubMsg,
+    SubMsgResult, Timestamp, Uint128, Uint256, WasmMsg,
 };
 
-use bellman_ce_verifier::{prepare_verifying_key, verify_proof as groth16_verify};
 
-use ff_ce::PrimeField as Fr;
 
 use hex;
 
@@ -43,6 +53,8 @@ use hex;
 const CONTRACT_NAME: &str = "crates.io:cw-amaci";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+pub const REPORT_PERFORMANCE_REPLY_ID: u64 = 1;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -74,6 +86,16 @@ pub fn instantiate(
         });
     }
 
+    let extra_questions = msg.extra_questions.clone().unwrap_or_default();
+    for question in &extra_questions {
+        if question.max_vote_options > vote_option_max_amount {
+            return Err(ContractError::MaxVoteOptionsExceeded {
+                current: question.max_vote_options,
+                max_allowed: vote_option_max_amount,
+            });
+        }
+    }
+
     if msg.voting_time.end_time < env.block.time {
         return Err(ContractError::WrongTimeSet {});
     }
@@ -95,6 +117,15 @@ pub fn instantiate(
         return Err(ContractError::WrongTimeSet {});
     }
 
+    if let Some(sign_up_time) = &msg.sign_up_time {
+        if sign_up_time.start_time >= sign_up_time.end_time
+            || sign_up_time.end_time > msg.voting_time.end_time
+        {
+            return Err(ContractError::WrongSignUpTimeSet {});
+        }
+        SIGNUPTIME.save(deps.storage, sign_up_time)?;
+    }
+
     match msg.whitelist {
         Some(content) => {
             let max_voter_amount = Uint256::from_u128(
@@ -168,12 +199,12 @@ pub fn instantiate(
     GROTH16_NEWKEY_VKEYS.save(deps.storage, &vkey.add_key_vkey)?;
 
     // Compute the coordinator hash from the coordinator values in the message
-    let coordinator_hash = hash2([msg.coordinator.x, msg.coordinator.y]);
+    let coordinator_hash = try_hash2([msg.coordinator.x, msg.coordinator.y])?;
     COORDINATORHASH.save(deps.storage, &coordinator_hash)?;
 
     // Compute the maximum number of leaves based on the state tree depth
     let max_leaves_count =
-        Uint256::from_u128(5u128.pow(msg.parameters.state_tree_depth.to_string().parse().unwrap()));
+        Uint256::from_u128(5u128.pow(state_tree_depth(msg.parameters.state_tree_depth)?));
     MAX_LEAVES_COUNT.save(deps.storage, &max_leaves_count)?;
 
     // Calculate the index of the first leaf in the tree
@@ -199,10 +230,11 @@ pub fn instantiate(
     ];
     ZEROS_H10.save(deps.storage, &zeros_h10)?;
 
-    NODES.save(
+    // Sign ups append to the state tree, its root is only hashed when a period needs it
+    let state_tree_depth = state_tree_depth(msg.parameters.state_tree_depth)?;
+    STATE_TREE.save(
         deps.storage,
-        Uint256::from_u128(0u128).to_be_bytes().to_vec(),
-        &Uint256::from_u128(0u128),
+        &IncrementalQuinaryTree::new(state_tree_depth, &zeros_h10)?,
     )?;
 
     // Define an array of zero values
@@ -276,10 +308,42 @@ pub fn instantiate(
     )?;
 
     let mut vote_option_map: Vec<String> = Vec::new();
-    for _ in 0..msg.max_vote_options.to_string().parse().unwrap() {
+    for _ in 0..uint256_to_usize(msg.max_vote_options)? {
         vote_option_map.push(String::new());
     }
     VOTEOPTIONMAP.save(deps.storage, &vote_option_map)?;
+
+    // Question 0 is the primary ballot saved above, extra questions share its state tree
+    for (i, question) in extra_questions.iter().enumerate() {
+        let question_id = i as u64 + 1;
+        let mut vote_option_map: Vec<String> = Vec::new();
+        for _ in 0..uint256_to_usize(question.max_vote_options)? {
+            vote_option_map.push(String::new());
+        }
+        QUESTIONS.save(
+            deps.storage,
+            question_id,
+            &Question {
+                vote_option_map,
+                max_vote_options: question.max_vote_options,
+                msg_chain_length: Uint256::from_u128(0u128),
+                processed_msg_count: Uint256::from_u128(0u128),
+                state_commitment: Uint256::from_u128(0u128),
+                tally_commitment: Uint256::from_u128(0u128),
+                processed_user_count: Uint256::from_u128(0u128),
+                total_result: None,
+            },
+        )?;
+        QUESTION_MSG_HASHES.save(
+            deps.storage,
+            (
+                question_id,
+                Uint256::from_u128(0u128).to_be_bytes().to_vec(),
+            ),
+            &Uint256::from_u128(0u128),
+        )?;
+    }
+    QUESTION_COUNT.save(deps.storage, &(extra_questions.len() as u64 + 1))?;
     ROUNDINFO.save(deps.storage, &msg.round_info)?;
 
     VOTINGTIME.save(deps.storage, &msg.voting_time)?;
@@ -295,6 +359,9 @@ pub fn instantiate(
     MACI_OPERATOR.save(deps.storage, &msg.operator)?;
     
     FEE_RECIPIENT.save(deps.storage, &msg.fee_recipient)?;
+    if let Some(registry) = &msg.registry {
+        REGISTRY.save(deps.storage, registry)?;
+    }
 
     let circuit_type = if msg.circuit_type == Uint256::from_u128(0u128) {
         "0" // 1p1v
@@ -340,6 +407,8 @@ pub fn instantiate(
         voice_credit_amount: msg.voice_credit_amount.clone(),
         round_info: msg.round_info.clone(),
         voting_time: msg.voting_time.clone(),
+        sign_up_time: msg.sign_up_time.clone(),
+        extra_questions: extra_questions.clone(),
         pre_deactivate_root: msg.pre_deactivate_root.clone(),
         circuit_type: circuit_type.to_string(),
         certification_system: certification_system.to_string(),
@@ -362,6 +431,7 @@ pub fn instantiate(
         attr("coordinator_pubkey_x", &msg.coordinator.x.to_string()),
         attr("coordinator_pubkey_y", &msg.coordinator.y.to_string()),
         attr("max_vote_options", &msg.max_vote_options.to_string()),
+        attr("question_count", (extra_questions.len() + 1).to_string()),
         attr("voice_credit_amount", &msg.voice_credit_amount.to_string()),
         attr("pre_deactivate_root", &msg.pre_deactivate_root.to_string()),
         attr(
@@ -393,6 +463,17 @@ pub fn instantiate(
         ),
     ];
 
+    if let Some(sign_up_time) = &msg.sign_up_time {
+        attributes.push(attr(
+            "sign_up_start",
+            sign_up_time.start_time.nanos().to_string(),
+        ));
+        attributes.push(attr(
+            "sign_up_end",
+            sign_up_time.end_time.nanos().to_string(),
+        ));
+    }
+
     if msg.round_info.description != "" {
         attributes.push(attr("round_description", msg.round_info.description))
     }
@@ -420,9 +501,16 @@ pub fn execute(
         ExecuteMsg::SetWhitelists { whitelists } => {
             execute_set_whitelists(deps, env, info, whitelists)
         }
-        ExecuteMsg::SetVoteOptionsMap { vote_option_map } => {
-            execute_set_vote_options_map(deps, env, info, vote_option_map)
-        }
+        ExecuteMsg::SetVoteOptionsMap {
+            vote_option_map,
+            question_id,
+        } => execute_set_vote_options_map(
+            deps,
+            env,
+            info,
+            vote_option_map,
+            question_id.unwrap_or_default(),
+        ),
         // ExecuteMsg::StartVotingPeriod {} => execute_start_voting_period(deps, env, info),
         ExecuteMsg::SignUp { pubkey } => execute_sign_up(deps, env, info, pubkey),
         // ExecuteMsg::StopVotingPeriod {} => execute_stop_voting_period(deps, env, info),
@@ -445,37 +533,86 @@ pub fn execute(
             size,
             new_deactivate_commitment,
             new_deactivate_root,
-            groth16_proof,
+            groth16_proof.into_uncompressed()?,
         ),
         ExecuteMsg::AddNewKey {
             pubkey,
             nullifier,
             d,
             groth16_proof,
-        } => execute_add_new_key(deps, env, info, pubkey, nullifier, d, groth16_proof),
+        } => execute_add_new_key(
+            deps,
+            env,
+            info,
+            pubkey,
+            nullifier,
+            d,
+            groth16_proof.into_uncompressed()?,
+        ),
         ExecuteMsg::PreAddNewKey {
             pubkey,
             nullifier,
             d,
             groth16_proof,
-        } => execute_pre_add_new_key(deps, env, info, pubkey, nullifier, d, groth16_proof),
+        } => execute_pre_add_new_key(
+            deps,
+            env,
+            info,
+            pubkey,
+            nullifier,
+            d,
+            groth16_proof.into_uncompressed()?,
+        ),
         ExecuteMsg::PublishMessage {
             message,
             enc_pub_key,
-        } => execute_publish_message(deps, env, info, message, enc_pub_key),
+            question_id,
+        } => execute_publish_message(
+            deps,
+            env,
+            info,
+            message,
+            enc_pub_key,
+            question_id.unwrap_or_default(),
+        ),
         ExecuteMsg::StartProcessPeriod {} => execute_start_process_period(deps, env, info),
         ExecuteMsg::ProcessMessage {
             new_state_commitment,
             groth16_proof,
-        } => execute_process_message(deps, env, info, new_state_commitment, groth16_proof),
+            question_id,
+        } => execute_process_message(
+            deps,
+            env,
+            info,
+            new_state_commitment,
+            groth16_proof.into_uncompressed()?,
+            question_id.unwrap_or_default(),
+        ),
         ExecuteMsg::StopProcessingPeriod {} => execute_stop_processing_period(deps, env, info),
         ExecuteMsg::ProcessTally {
             new_tally_commitment,
             groth16_proof,
-        } => execute_process_tally(deps, env, info, new_tally_commitment, groth16_proof),
-        ExecuteMsg::StopTallyingPeriod { results, salt } => {
-            execute_stop_tallying_period(deps, env, info, results, salt)
-        }
+            question_id,
+        } => execute_process_tally(
+            deps,
+            env,
+            info,
+            new_tally_commitment,
+            groth16_proof.into_uncompressed()?,
+            question_id.unwrap_or_default(),
+        ),
+        ExecuteMsg::StopTallyingPeriod {
+            results,
+            salt,
+            question_id,
+        } => execute_stop_tallying_period(
+            deps,
+            env,
+            info,
+            results,
+            salt,
+            question_id.unwrap_or_default(),
+        ),
         ExecuteMsg::Claim {} => execute_claim(deps, env, info),
     }
 }
@@ -563,6 +700,7 @@ pub fn execute_set_vote_options_map(
     env: Env,
     info: MessageInfo,
     vote_option_map: Vec<String>,
+    question_id: u64,
 ) -> Result<Response, ContractError> {
     let voting_time = VOTINGTIME.load(deps.storage)?;
 
@@ -586,11 +724,14 @@ pub fn execute_set_vote_options_map(
             });
         }
 
-        VOTEOPTIONMAP.save(deps.storage, &vote_option_map)?;
+        let mut question = load_question(deps.storage, question_id)?;
+        question.vote_option_map = vote_option_map.clone();
         // Save the maximum vote options
-        MAX_VOTE_OPTIONS.save(deps.storage, &Uint256::from_u128(max_vote_options))?;
+        question.max_vote_options = Uint256::from_u128(max_vote_options);
+        save_question(deps.storage, question_id, &question)?;
         let res = Response::new()
             .add_attribute("action", "set_vote_option")
+            .add_attribute("question_id", question_id.to_string())
             .add_attribute("vote_option_map", format!("{:?}", vote_option_map))
             .add_attribute("max_vote_options", max_vote_options.to_string());
         Ok(res)
@@ -604,8 +745,7 @@ pub fn execute_sign_up(
     info: MessageInfo,
     pubkey: PubKey,
 ) -> Result<Response, ContractError> {
-    let voting_time = VOTINGTIME.load(deps.storage)?;
-    check_voting_time(env, voting_time)?;
+    check_sign_up_time(deps.as_ref(), env)?;
     if !is_whitelist(deps.as_ref(), &info.sender)? {
         return Err(ContractError::Unauthorized {});
     }
@@ -633,10 +773,9 @@ pub fn execute_sign_up(
     // Check if the number of sign-ups is less than the maximum number of leaves
     assert!(num_sign_ups < max_leaves_count, "full");
     // Check if the pubkey values are within the allowed range
-    assert!(
-        pubkey.x < snark_scalar_field && pubkey.y < snark_scalar_field,
-        "MACI: pubkey values should be less than the snark scalar field"
-    );
+    if pubkey.x >= snark_scalar_field || pubkey.y >= snark_scalar_field {
+        return Err(ContractError::InvalidPrimeField {});
+    }
 
     // Create a state leaf with the provided pubkey and amount
     let state_leaf = StateLeaf {
@@ -654,11 +793,11 @@ pub fn execute_sign_up(
 
     // Save the updated state index, voice credit balance, and number of sign-ups
     // STATEIDXINC.save(deps.storage, &info.sender, &num_sign_ups)?;
-    // VOICECREDITBALANCE.save(
-    //     deps.storage,
-    //     state_index.to_be_bytes().to_vec(),
-    //     &voice_credit_amount,
-    // )?;
+    VOICECREDITBALANCE.save(
+        deps.storage,
+        state_index.to_be_bytes().to_vec(),
+        &voice_credit_amount,
+    )?;
     NUMSIGNUPS.save(deps.storage, &num_sign_ups)?;
     SIGNUPED.save(deps.storage, pubkey.x.to_be_bytes().to_vec(), &num_sign_ups)?;
 
@@ -673,7 +812,12 @@ pub fn execute_sign_up(
             "pubkey",
             format!("{:?},{:?}", pubkey.x.to_string(), pubkey.y.to_string()),
         )
-        .add_attribute("balance", voice_credit_amount.to_string()))
+        .add_attribute("balance", voice_credit_amount.to_string())
+        .add_event(Event::from(maci_events::SignUp {
+            state_idx: state_index,
+            pubkey: [pubkey.x, pubkey.y],
+            balance: voice_credit_amount,
+        })))
 }
 
 // in voting
@@ -683,10 +827,12 @@ pub fn execute_publish_message(
     _info: MessageInfo,
     message: MessageData,
     enc_pub_key: PubKey,
+    question_id: u64,
 ) -> Result<Response, ContractError> {
     // Check if the period status is Voting
     let voting_time = VOTINGTIME.load(deps.storage)?;
     check_voting_time(env, voting_time)?;
+    let mut question = load_question(deps.storage, question_id)?;
     // Load the scalar field value
     let snark_scalar_field =
         uint256_from_hex_string("30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001");
@@ -701,26 +847,43 @@ pub fn execute_publish_message(
         && enc_pub_key.x < snark_scalar_field
         && enc_pub_key.y < snark_scalar_field
     {
-        let mut msg_chain_length = MSG_CHAIN_LENGTH.load(deps.storage)?;
-        let old_msg_hashes =
-            MSG_HASHES.load(deps.storage, msg_chain_length.to_be_bytes().to_vec())?;
+        if question_count(deps.storage)? > 1 {
+            let key = hash_message_and_enc_pub_key(
+                message.clone(),
+                enc_pub_key.clone(),
+                Uint256::zero(),
+            )?
+            .to_be_bytes()
+            .to_vec();
+            match PUBLISHED_MESSAGES.may_load(deps.storage, key.clone())? {
+                Some(published) if published != question_id => {
+                    return Err(ContractError::MessageReplayed {
+                        question_id: published,
+                    })
+                }
+                _ => PUBLISHED_MESSAGES.save(deps.storage, key, &question_id)?,
+            }
+        }
+
+        let msg_chain_length = question.msg_chain_length;
+        let old_msg_hashes = load_msg_hash(deps.storage, question_id, msg_chain_length)?;
 
         // Compute the new message hash using the provided message, encrypted public key, and previous hash
-        MSG_HASHES.save(
+        save_msg_hash(
             deps.storage,
-            (msg_chain_length + Uint256::from_u128(1u128))
-                .to_be_bytes()
-                .to_vec(),
-            &hash_message_and_enc_pub_key(message.clone(), enc_pub_key.clone(), old_msg_hashes),
+            question_id,
+            msg_chain_length + Uint256::from_u128(1u128),
+            &hash_message_and_enc_pub_key(message.clone(), enc_pub_key.clone(), old_msg_hashes)?,
         )?;
 
         let old_chain_length = msg_chain_length;
         // Update the message chain length
-        msg_chain_length += Uint256::from_u128(1u128);
-        MSG_CHAIN_LENGTH.save(deps.storage, &msg_chain_length)?;
+        question.msg_chain_length += Uint256::from_u128(1u128);
+        save_question(deps.storage, question_id, &question)?;
         // Return a success response
         Ok(Response::new()
             .add_attribute("action", "publish_message")
+            .add_attribute("question_id", question_id.to_string())
             .add_attribute("msg_chain_length", old_chain_length.to_string())
             .add_attribute("message", format!("{:?}", message.data))
             .add_attribute(
@@ -730,7 +893,13 @@ pub fn execute_publish_message(
                     enc_pub_key.x.to_string(),
                     enc_pub_key.y.to_string()
                 ),
-            ))
+            )
+            .add_event(Event::from(maci_events::PublishMessage {
+                msg_chain_length: old_chain_length,
+                message: message.data.to_vec(),
+                enc_pub_key: [enc_pub_key.x, enc_pub_key.y],
+                question_id: Some(question_id),
+            })))
     } else {
         // Return an error response for invalid user or encrypted public key
         Ok(Response::new()
@@ -797,10 +966,10 @@ pub fn execute_publish_deactivate_message(
         n[3] = enc_pub_key.y;
         n[4] = old_msg_hashes;
 
-        let m_hash = hash5(m);
+        let m_hash = try_hash5(m)?;
 
-        let n_hash = hash5(n);
-        let m_n_hash = hash2([m_hash, n_hash]);
+        let n_hash = try_hash5(n)?;
+        let m_n_hash = try_hash2([m_hash, n_hash])?;
 
         // Compute the new message hash using the provided message, encrypted public key, and previous hash
         DMSG_HASHES.save(
@@ -811,7 +980,7 @@ pub fn execute_publish_deactivate_message(
             &m_n_hash,
         )?;
 
-        let state_root = state_root(deps.as_ref());
+        let state_root = state_root(deps.as_ref())?;
 
         STATE_ROOT_BY_DMSG.save(
             deps.storage,
@@ -844,7 +1013,13 @@ pub fn execute_publish_deactivate_message(
                     enc_pub_key.x.to_string(),
                     enc_pub_key.y.to_string()
                 ),
-            ))
+            )
+            .add_event(Event::from(maci_events::PublishDeactivateMessage {
+                dmsg_chain_length: old_chain_length,
+                num_sign_ups,
+                message: message.data.to_vec(),
+                enc_pub_key: [enc_pub_key.x, enc_pub_key.y],
+            })))
     } else {
         // Return an error response for invalid user or encrypted public key
         Ok(Response::new()
@@ -880,7 +1055,11 @@ pub fn execute_upload_deactivate_message(
             .add_attribute(
                 "deactivate_message",
                 format!("{:?}", deactivate_format_data),
-            ))
+            )
+            .add_event(Event::from(maci_events::UploadDeactivateMessage {
+                operator: info.sender,
+                deactivate_message,
+            })))
     }
 }
 
@@ -888,7 +1067,7 @@ pub fn execute_upload_deactivate_message(
 pub fn execute_process_deactivate_message(
     deps: DepsMut,
     env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     size: Uint256,
     new_deactivate_commitment: Uint256,
     new_deactivate_root: Uint256,
@@ -930,7 +1109,8 @@ pub fn execute_process_deactivate_message(
     input[2] = DMSG_HASHES.load(deps.storage, batch_start_index.to_be_bytes().to_vec())?;
     input[3] = DMSG_HASHES.load(deps.storage, batch_end_index.to_be_bytes().to_vec())?;
 
-    input[4] = CURRENT_DEACTIVATE_COMMITMENT.load(deps.storage)?;
+    let current_deactivate_commitment = CURRENT_DEACTIVATE_COMMITMENT.load(deps.storage)?;
+    input[4] = current_deactivate_commitment;
     input[5] = new_deactivate_commitment;
     input[6] = STATE_ROOT_BY_DMSG.load(deps.storage, batch_end_index.to_be_bytes().to_vec())?;
 
@@ -943,8 +1123,6 @@ pub fn execute_process_deactivate_message(
 
     // Compute the hash of the input values
     let input_hash = uint256_from_hex_string(&hash_256_uint256_list(&input)) % snark_scalar_field;
-    // Load the process verification keys
-    let deactivate_vkeys_str = GROTH16_DEACTIVATE_VKEYS.load(deps.storage)?;
 
     // Parse the SNARK proof
     let proof_str = Groth16ProofStr {
@@ -956,20 +1134,22 @@ pub fn execute_process_deactivate_message(
             .map_err(|_| ContractError::HexDecodingError {})?,
     };
 
-    // Parse the verification key and prepare for verification
-    let vkey = parse_groth16_vkey::<Bn256>(deactivate_vkeys_str)?;
-    let pvk = prepare_verifying_key(&vkey);
+    // Load the verification key, prepared on its first use
+    let pvk = load_prepared_vkey(
+        deps.storage,
+        &GROTH16_DEACTIVATE_VKEYS,
+        &GROTH16_DEACTIVATE_PREPARED_VKEYS,
+    )?;
 
     // Parse the proof and prepare for verification
     let pof = parse_groth16_proof::<Bn256>(proof_str.clone())?;
 
     // Verify the SNARK proof using the input hash
-    let is_passed = groth16_verify(
+    let is_passed = verify_groth16_prepared(
         &pvk,
         &pof,
-        &[Fr::from_str(&input_hash.to_string()).unwrap()],
-    )
-    .unwrap();
+        &[parse_public_input(input_hash)?],
+    )?;
 
     // If the proof verification fails, return an error
     if !is_passed {
@@ -983,12 +1163,30 @@ pub fn execute_process_deactivate_message(
         deps.storage,
         &(processed_dmsg_count + batch_end_index - batch_start_index),
     )?;
+    let batch_record_id = save_batch_record(
+        deps.storage,
+        BatchRecord {
+            id: 0,
+            step: BatchStep::ProcessDeactivateMessage,
+            question_id: None,
+            start_index: batch_start_index,
+            end_index: batch_end_index,
+            old_commitment: current_deactivate_commitment,
+            new_commitment: new_deactivate_commitment,
+            input_hash,
+            certification_system: String::from("groth16"),
+            proof: to_json_string(&groth16_proof)?,
+            block_time: env.block.time,
+            submitter: info.sender,
+        },
+    )?;
     let mut attributes = vec![
         attr("zk_verify", is_passed.to_string()),
         attr("commitment", new_deactivate_commitment.to_string()),
         attr("proof", format!("{:?}", groth16_proof)),
         attr("certification_system", "groth16"),
         attr("processed_dmsg_count", processed_dmsg_count.to_string()),
+        attr("batch_record_id", batch_record_id.to_string()),
     ];
 
     let first_dmsg_time: Timestamp = FIRST_DMSG_TIMESTAMP.load(deps.storage)?;
@@ -1031,7 +1229,12 @@ pub fn execute_process_deactivate_message(
 
     Ok(Response::new()
         .add_attribute("action", "process_deactivate_message")
-        .add_attributes(attributes))
+        .add_attributes(attributes)
+        .add_event(Event::from(maci_events::ProcessDeactivateMessage {
+            processed_dmsg_count,
+            new_deactivate_commitment,
+            proof: to_json_string(&groth16_proof)?,
+        })))
 }
 
 // in voting
@@ -1065,10 +1268,9 @@ pub fn execute_add_new_key(
 
     assert!(num_sign_ups < max_leaves_count, "full");
     // Check if the pubkey values are within the allowed range
-    assert!(
-        pubkey.x < snark_scalar_field && pubkey.y < snark_scalar_field,
-        "MACI: pubkey values should be less than the snark scalar field"
-    );
+    if pubkey.x >= snark_scalar_field || pubkey.y >= snark_scalar_field {
+        return Err(ContractError::InvalidPrimeField {});
+    }
 
     let mut input: [Uint256; 7] = [Uint256::zero(); 7];
     input[0] = DNODES.load(
@@ -1090,9 +1292,6 @@ pub fn execute_add_new_key(
     // Compute the hash of the input values
     let input_hash = uint256_from_hex_string(&hash_256_uint256_list(&input)) % snark_scalar_field; // input hash
 
-    // Load the process verification keys
-    let process_vkeys_str = GROTH16_NEWKEY_VKEYS.load(deps.storage)?;
-
     // Parse the SNARK proof
     let proof_str = Groth16ProofStr {
         pi_a: hex::decode(groth16_proof.a.clone())
@@ -1103,20 +1302,22 @@ pub fn execute_add_new_key(
             .map_err(|_| ContractError::HexDecodingError {})?,
     };
 
-    // Parse the verification key and prepare for verification
-    let vkey = parse_groth16_vkey::<Bn256>(process_vkeys_str)?;
-    let pvk = prepare_verifying_key(&vkey);
+    // Load the verification key, prepared on its first use
+    let pvk = load_prepared_vkey(
+        deps.storage,
+        &GROTH16_NEWKEY_VKEYS,
+        &GROTH16_NEWKEY_PREPARED_VKEYS,
+    )?;
 
     // Parse the proof and prepare for verification
     let pof = parse_groth16_proof::<Bn256>(proof_str.clone())?;
 
     // Verify the SNARK proof using the input hash
-    let is_passed = groth16_verify(
+    let is_passed = verify_groth16_prepared(
         &pvk,
         &pof,
-        &[Fr::from_str(&input_hash.to_string()).unwrap()],
-    )
-    .unwrap();
+        &[parse_public_input(input_hash)?],
+    )?;
 
     // If the proof verification fails, return an error
     if !is_passed {
@@ -1148,6 +1349,11 @@ pub fn execute_add_new_key(
 
     num_sign_ups += Uint256::from_u128(1u128);
 
+    VOICECREDITBALANCE.save(
+        deps.storage,
+        state_index.to_be_bytes().to_vec(),
+        &voice_credit_amount,
+    )?;
     NUMSIGNUPS.save(deps.storage, &num_sign_ups)?;
     SIGNUPED.save(deps.storage, pubkey.x.to_be_bytes().to_vec(), &num_sign_ups)?;
 
@@ -1162,7 +1368,14 @@ pub fn execute_add_new_key(
         .add_attribute("d0", d[0].to_string())
         .add_attribute("d1", d[1].to_string())
         .add_attribute("d2", d[2].to_string())
-        .add_attribute("d3", d[3].to_string()))
+        .add_attribute("d3", d[3].to_string())
+        .add_event(Event::from(maci_events::AddKey {
+            state_idx: state_index,
+            pubkey: [pubkey.x, pubkey.y],
+            balance: voice_credit_amount,
+            d,
+            pre_deactivate: false,
+        })))
 }
 
 // in voting
@@ -1196,10 +1409,9 @@ pub fn execute_pre_add_new_key(
 
     assert!(num_sign_ups < max_leaves_count, "full");
     // Check if the pubkey values are within the allowed range
-    assert!(
-        pubkey.x < snark_scalar_field && pubkey.y < snark_scalar_field,
-        "MACI: pubkey values should be less than the snark scalar field"
-    );
+    if pubkey.x >= snark_scalar_field || pubkey.y >= snark_scalar_field {
+        return Err(ContractError::InvalidPrimeField {});
+    }
 
     let mut input: [Uint256; 7] = [Uint256::zero(); 7];
 
@@ -1221,9 +1433,6 @@ pub fn execute_pre_add_new_key(
     // Compute the hash of the input values
     let input_hash = uint256_from_hex_string(&hash_256_uint256_list(&input)) % snark_scalar_field; // input hash
 
-    // Load the process verification keys
-    let process_vkeys_str = GROTH16_NEWKEY_VKEYS.load(deps.storage)?;
-
     // Parse the SNARK proof
     let proof_str = Groth16ProofStr {
         pi_a: hex::decode(groth16_proof.a.clone())
@@ -1234,20 +1443,22 @@ pub fn execute_pre_add_new_key(
             .map_err(|_| ContractError::HexDecodingError {})?,
     };
 
-    // Parse the verification key and prepare for verification
-    let vkey = parse_groth16_vkey::<Bn256>(process_vkeys_str)?;
-    let pvk = prepare_verifying_key(&vkey);
+    // Load the verification key, prepared on its first use
+    let pvk = load_prepared_vkey(
+        deps.storage,
+        &GROTH16_NEWKEY_VKEYS,
+        &GROTH16_NEWKEY_PREPARED_VKEYS,
+    )?;
 
     // Parse the proof and prepare for verification
     let pof = parse_groth16_proof::<Bn256>(proof_str.clone())?;
 
     // Verify the SNARK proof using the input hash
-    let is_passed = groth16_verify(
+    let is_passed = verify_groth16_prepared(
         &pvk,
         &pof,
-        &[Fr::from_str(&input_hash.to_string()).unwrap()],
-    )
-    .unwrap();
+        &[parse_public_input(input_hash)?],
+    )?;
 
     // If the proof verification fails, return an error
     if !is_passed {
@@ -1274,6 +1485,11 @@ pub fn execute_pre_add_new_key(
 
     num_sign_ups += Uint256::from_u128(1u128);
 
+    VOICECREDITBALANCE.save(
+        deps.storage,
+        state_index.to_be_bytes().to_vec(),
+        &voice_credit_amount,
+    )?;
     NUMSIGNUPS.save(deps.storage, &num_sign_ups)?;
     SIGNUPED.save(deps.storage, pubkey.x.to_be_bytes().to_vec(), &num_sign_ups)?;
 
@@ -1284,7 +1500,14 @@ pub fn execute_pre_add_new_key(
             "pubkey",
             format!("{:?},{:?}", pubkey.x.to_string(), pubkey.y.to_string()),
         )
-        .add_attribute("balance", voice_credit_amount.to_string()))
+        .add_attribute("balance", voice_credit_amount.to_string())
+        .add_event(Event::from(maci_events::AddKey {
+            state_idx: state_index,
+            pubkey: [pubkey.x, pubkey.y],
+            balance: voice_credit_amount,
+            d,
+            pre_deactivate: true,
+        })))
 }
 
 pub fn execute_start_process_period(
@@ -1320,12 +1543,17 @@ pub fn execute_start_process_period(
     };
     PERIOD.save(deps.storage, &period)?;
     // Compute the state root
-    let state_root = state_root(deps.as_ref());
+    let state_root = state_root(deps.as_ref())?;
     // Compute the current state commitment as the hash of the state root and 0
-    CURRENT_STATE_COMMITMENT.save(
-        deps.storage,
-        &hash2([state_root, Uint256::from_u128(0u128)]),
-    )?;
+    let state_commitment = hash2([state_root, Uint256::from_u128(0u128)]);
+    CURRENT_STATE_COMMITMENT.save(deps.storage, &state_commitment)?;
+
+    // Every question starts processing its messages from the same voter state
+    for question_id in 1..question_count(deps.storage)? {
+        let mut question = load_question(deps.storage, question_id)?;
+        question.state_commitment = state_commitment;
+        save_question(deps.storage, question_id, &question)?;
+    }
 
     // Return a success response
     Ok(Response::new().add_attribute("action", "start_process_period"))
@@ -1333,18 +1561,20 @@ pub fn execute_start_process_period(
 
 pub fn execute_process_message(
     deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
+    env: Env,
+    info: MessageInfo,
     new_state_commitment: Uint256,
     groth16_proof: Groth16ProofType,
+    question_id: u64,
 ) -> Result<Response, ContractError> {
     let period = PERIOD.load(deps.storage)?;
     // Check if the period status is Processing
     if period.status != PeriodStatus::Processing {
         return Err(ContractError::PeriodError {});
     }
-    let mut processed_msg_count = PROCESSED_MSG_COUNT.load(deps.storage)?;
-    let msg_chain_length = MSG_CHAIN_LENGTH.load(deps.storage)?;
+    let mut question = load_question(deps.storage, question_id)?;
+    let processed_msg_count = question.processed_msg_count;
+    let msg_chain_length = question.msg_chain_length;
     // Check that all messages have not been processed yet
     assert!(
         processed_msg_count < msg_chain_length,
@@ -1355,7 +1585,7 @@ pub fn execute_process_message(
     let mut input: [Uint256; 7] = [Uint256::zero(); 7];
 
     let num_sign_ups = NUMSIGNUPS.load(deps.storage)?;
-    let max_vote_options = MAX_VOTE_OPTIONS.load(deps.storage)?;
+    let max_vote_options = question.max_vote_options;
 
     let circuit_type = CIRCUITTYPE.load(deps.storage)?;
     if circuit_type == Uint256::from_u128(0u128) {
@@ -1387,17 +1617,13 @@ pub fn execute_process_message(
     }
 
     // Load the hash of the message at the batch start index
-    input[2] = MSG_HASHES.load(
-        deps.storage,
-        batch_start_index.clone().to_be_bytes().to_vec(),
-    )?; // batchStartHash
+    input[2] = load_msg_hash(deps.storage, question_id, batch_start_index)?; // batchStartHash
 
     // Load the hash of the message at the batch end index
-    input[3] = MSG_HASHES.load(deps.storage, batch_end_index.to_be_bytes().to_vec())?; // batchEndHash
+    input[3] = load_msg_hash(deps.storage, question_id, batch_end_index)?; // batchEndHash
 
     // Load the current state commitment
-    let current_state_commitment = CURRENT_STATE_COMMITMENT.load(deps.storage)?;
-    input[4] = current_state_commitment;
+    input[4] = question.state_commitment;
 
     // Set the new state commitment
     input[5] = new_state_commitment;
@@ -1412,8 +1638,6 @@ pub fn execute_process_message(
     let input_hash = uint256_from_hex_string(&hash_256_uint256_list(&input)) % snark_scalar_field; // input hash
 
     let groth16_proof_data = groth16_proof;
-    // Load the process verification keys
-    let process_vkeys_str = GROTH16_PROCESS_VKEYS.load(deps.storage)?;
 
     // Parse the SNARK proof
     let proof_str = Groth16ProofStr {
@@ -1425,20 +1649,22 @@ pub fn execute_process_message(
             .map_err(|_| ContractError::HexDecodingError {})?,
     };
 
-    // Parse the verification key and prepare for verification
-    let vkey = parse_groth16_vkey::<Bn256>(process_vkeys_str)?;
-    let pvk = prepare_verifying_key(&vkey);
+    // Load the verification key, prepared on its first use
+    let pvk = load_prepared_vkey(
+        deps.storage,
+        &GROTH16_PROCESS_VKEYS,
+        &GROTH16_PROCESS_PREPARED_VKEYS,
+    )?;
 
     // Parse the proof and prepare for verification
     let pof = parse_groth16_proof::<Bn256>(proof_str.clone())?;
 
     // Verify the SNARK proof using the input hash
-    let is_passed = groth16_verify(
+    let is_passed = verify_groth16_prepared(
         &pvk,
         &pof,
-        &[Fr::from_str(&input_hash.to_string()).unwrap()],
-    )
-    .unwrap();
+        &[parse_public_input(input_hash)?],
+    )?;
 
     // If the proof verification fails, return an error
     if !is_passed {
@@ -1453,18 +1679,45 @@ pub fn execute_process_message(
         attr("proof", format!("{:?}", groth16_proof_data)),
         attr("certification_system", "groth16"),
         attr("processed_msg_count", processed_msg_count.to_string()),
+        attr("question_id", question_id.to_string()),
     ];
 
     // Proof verify success
+    let batch_record_id = save_batch_record(
+        deps.storage,
+        BatchRecord {
+            id: 0,
+            step: BatchStep::ProcessMessage,
+            question_id: Some(question_id),
+            start_index: batch_start_index,
+            end_index: batch_end_index,
+            old_commitment: question.state_commitment,
+            new_commitment: new_state_commitment,
+            input_hash,
+            certification_system: String::from("groth16"),
+            proof: to_json_string(&groth16_proof_data)?,
+            block_time: env.block.time,
+            submitter: info.sender,
+        },
+    )?;
+
     // Update the current state commitment
-    CURRENT_STATE_COMMITMENT.save(deps.storage, &new_state_commitment)?;
+    question.state_commitment = new_state_commitment;
 
     // Update the count of processed messages
-    processed_msg_count += batch_end_index - batch_start_index;
-    PROCESSED_MSG_COUNT.save(deps.storage, &processed_msg_count)?;
+    question.processed_msg_count += batch_end_index - batch_start_index;
+    save_question(deps.storage, question_id, &question)?;
     Ok(Response::new()
         .add_attribute("action", "process_message")
-        .add_attributes(attributes))
+        .add_attributes(attributes)
+        .add_attribute("batch_record_id", batch_record_id.to_string())
+        .add_event(Event::from(maci_events::ProcessMessage {
+            processed_msg_count,
+            new_state_commitment,
+            certification_system: String::from("groth16"),
+            proof: to_json_string(&groth16_proof_data)?,
+            question_id: Some(question_id),
+        })))
 }
 
 pub fn execute_stop_processing_period(
@@ -1478,11 +1731,12 @@ pub fn execute_stop_processing_period(
         return Err(ContractError::PeriodError {});
     }
 
-    let processed_msg_count = PROCESSED_MSG_COUNT.load(deps.storage)?;
-    let msg_chain_length = MSG_CHAIN_LENGTH.load(deps.storage)?;
-
-    if processed_msg_count != msg_chain_length {
-        return Err(ContractError::MsgLeftProcess {});
+    // Every question must have processed all of its messages
+    for question_id in 0..question_count(deps.storage)? {
+        let question = load_question(deps.storage, question_id)?;
+        if question.processed_msg_count != question.msg_chain_length {
+            return Err(ContractError::MsgLeftProcess {});
+        }
     }
 
     let period = Period {
@@ -1497,10 +1751,11 @@ pub fn execute_stop_processing_period(
 
 pub fn execute_process_tally(
     deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
+    env: Env,
+    info: MessageInfo,
     new_tally_commitment: Uint256,
     groth16_proof: Groth16ProofType,
+    question_id: u64,
 ) -> Result<Response, ContractError> {
     let period = PERIOD.load(deps.storage)?;
     // Check if the period status is Tallying
@@ -1508,7 +1763,8 @@ pub fn execute_process_tally(
         return Err(ContractError::PeriodError {});
     }
 
-    let mut processed_user_count = PROCESSED_USER_COUNT.load(deps.storage)?;
+    let mut question = load_question(deps.storage, question_id)?;
+    let processed_user_count = question.processed_user_count;
     let num_sign_ups = NUMSIGNUPS.load(deps.storage)?;
     // Check that all users have not been processed yet
     assert!(
@@ -1529,11 +1785,8 @@ pub fn execute_process_tally(
     input[0] = (num_sign_ups << 32) + batch_num; // packedVals
 
     // Load the current state commitment and current tally commitment
-    let current_state_commitment = CURRENT_STATE_COMMITMENT.load(deps.storage)?;
-    let current_tally_commitment = CURRENT_TALLY_COMMITMENT.load(deps.storage)?;
-
-    input[1] = current_state_commitment; // stateCommitment
-    input[2] = current_tally_commitment; // tallyCommitment
+    input[1] = question.state_commitment; // stateCommitment
+    input[2] = question.tally_commitment; // tallyCommitment
     input[3] = new_tally_commitment; // newTallyCommitment
 
     // Load the scalar field value
@@ -1547,8 +1800,6 @@ pub fn execute_process_tally(
     let input_hash = uint256_from_hex_string(&hash_256_uint256_list(&input)) % snark_scalar_field;
 
     let groth16_proof_data = groth16_proof;
-    // Load the tally verification keys
-    let tally_vkeys_str = GROTH16_TALLY_VKEYS.load(deps.storage)?;
 
     // Parse the SNARK proof
     let proof_str = Groth16ProofStr {
@@ -1560,20 +1811,22 @@ pub fn execute_process_tally(
             .map_err(|_| ContractError::HexDecodingError {})?,
     };
 
-    // Parse the verification key and prepare for verification
-    let vkey = parse_groth16_vkey::<Bn256>(tally_vkeys_str)?;
-    let pvk = prepare_verifying_key(&vkey);
+    // Load the verification key, prepared on its first use
+    let pvk = load_prepared_vkey(
+        deps.storage,
+        &GROTH16_TALLY_VKEYS,
+        &GROTH16_TALLY_PREPARED_VKEYS,
+    )?;
 
     // Parse the proof and prepare for verification
     let pof = parse_groth16_proof::<Bn256>(proof_str.clone())?;
 
     // Verify the SNARK proof using the input hash
-    let is_passed = groth16_verify(
+    let is_passed = verify_groth16_prepared(
         &pvk,
         &pof,
-        &[Fr::from_str(&input_hash.to_string()).unwrap()],
-    )
-    .unwrap();
+        &[parse_public_input(input_hash)?],
+    )?;
 
     // If the proof verification fails, return an error
     if !is_passed {
@@ -1588,24 +1841,47 @@ pub fn execute_process_tally(
         attr("proof", format!("{:?}", groth16_proof_data)),
         attr("certification_system", "groth16"),
         attr("processed_user_count", processed_user_count.to_string()),
+        attr("question_id", question_id.to_string()),
     ];
 
     // Proof verify success
+    let batch_record_id = save_batch_record(
+        deps.storage,
+        BatchRecord {
+            id: 0,
+            step: BatchStep::ProcessTally,
+            question_id: Some(question_id),
+            start_index: processed_user_count,
+            end_index: (processed_user_count + batch_size).min(num_sign_ups),
+            old_commitment: question.tally_commitment,
+            new_commitment: new_tally_commitment,
+            input_hash,
+            certification_system: String::from("groth16"),
+            proof: to_json_string(&groth16_proof_data)?,
+            block_time: env.block.time,
+            submitter: info.sender,
+        },
+    )?;
+
     // Update the current tally commitment
-    CURRENT_TALLY_COMMITMENT
-        .save(deps.storage, &new_tally_commitment)
-        .unwrap();
+    question.tally_commitment = new_tally_commitment;
 
     // Update the count of processed users
-    processed_user_count += batch_size;
+    question.processed_user_count += batch_size;
 
-    PROCESSED_USER_COUNT
-        .save(deps.storage, &processed_user_count)
-        .unwrap();
+    save_question(deps.storage, question_id, &question)?;
 
     Ok(Response::new()
         .add_attribute("action", "process_tally")
-        .add_attributes(attributes))
+        .add_attributes(attributes)
+        .add_attribute("batch_record_id", batch_record_id.to_string())
+        .add_event(Event::from(maci_events::ProcessTally {
+            processed_user_count,
+            new_tally_commitment,
+            certification_system: String::from("groth16"),
+            proof: to_json_string(&groth16_proof_data)?,
+            question_id: Some(question_id),
+        })))
 }
 
 fn execute_stop_tallying_period(
@@ -1614,6 +1890,7 @@ fn execute_stop_tallying_period(
     _info: MessageInfo,
     results: Vec<Uint256>,
     salt: Uint256,
+    question_id: u64,
 ) -> Result<Response, ContractError> {
     let period = PERIOD.load(deps.storage)?;
     // Check if the period status is Tallying
@@ -1621,143 +1898,123 @@ fn execute_stop_tallying_period(
         return Err(ContractError::PeriodError {});
     }
 
-    // Get the final signup count and message count
-    let num_sign_ups = NUMSIGNUPS.load(deps.storage)?;
-    let msg_chain_length = MSG_CHAIN_LENGTH.load(deps.storage)?;
-
-    // Calculate total workload (signup and message have same weight)
-    let total_work = num_sign_ups + msg_chain_length;
-
-    let total_work_u128 = total_work
-        .try_into() // Uint256 -> Uint128
-        .map(|x: Uint128| x.u128()) // Uint128 -> u128
-        .map_err(|_| ContractError::ValueTooLarge {})?;
-
-    // Calculate actual delay timeout (linear change between min hours to max hours)
-    let actual_delay: TallyDelayInfo = calculate_tally_delay(deps.as_ref())?;
-    let voting_time = VOTINGTIME.load(deps.storage)?;
-    let current_time = env.block.time;
-    let different_time = current_time.seconds() - voting_time.end_time.seconds();
-
-    let mut attributes = vec![
-        attr("total_work", total_work_u128.to_string()),
-        attr(
-            "actual_delay_seconds",
-            actual_delay.delay_seconds.to_string(),
-        ),
-    ];
-
-    if different_time > actual_delay.delay_seconds {
-        let delay_timestamp = voting_time.end_time;
-        let delay_duration = different_time;
-        let delay_reason = format!(
-            "Tallying has timed out after {} seconds (total process: {}, allowed: {} seconds)",
-            different_time, total_work_u128, actual_delay.delay_seconds
-        );
-        let delay_process_dmsg_count = Uint256::from_u128(0u128);
-        let delay_type = DelayType::TallyDelay;
-
-        let mut delay_records = DELAY_RECORDS.load(deps.storage)?;
-        let delay_record = DelayRecord {
-            delay_timestamp: delay_timestamp.clone(),
-            delay_duration: delay_duration.clone(),
-            delay_reason: delay_reason.clone(),
-            delay_process_dmsg_count,
-            delay_type,
-        };
-        delay_records.records.push(delay_record);
-        DELAY_RECORDS.save(deps.storage, &delay_records)?;
-
-        attributes.extend(vec![
-            attr("delay_timestamp", delay_timestamp.seconds().to_string()),
-            attr("delay_duration", delay_duration.to_string()),
-            attr("delay_reason", delay_reason),
-            attr("delay_type", "tally_delay"),
-        ]);
+    let mut question = load_question(deps.storage, question_id)?;
+    if question.total_result.is_some() {
+        return Err(ContractError::QuestionAlreadyTallied { question_id });
     }
 
-    let processed_user_count = PROCESSED_USER_COUNT.load(deps.storage)?;
     let num_sign_ups = NUMSIGNUPS.load(deps.storage)?;
-    let max_vote_options = MAX_VOTE_OPTIONS.load(deps.storage)?;
 
     // Check that all users have been processed
-    assert!(processed_user_count >= num_sign_ups);
+    assert!(question.processed_user_count >= num_sign_ups);
 
     // Check that the number of results is not greater than the maximum vote options
-    assert!(Uint256::from_u128(results.len() as u128) <= max_vote_options);
+    assert!(Uint256::from_u128(results.len() as u128) <= question.max_vote_options);
 
     // Load the QTR library and MACI parameters
     let qtr_lib = QTR_LIB.load(deps.storage)?;
     let parameters = MACIPARAMETERS.load(deps.storage)?;
 
     // Calculate the results root
-    let results_root = qtr_lib.root_of(parameters.vote_option_tree_depth, results.clone());
+    let results_root = qtr_lib.try_root_of(parameters.vote_option_tree_depth, results.clone())?;
 
     // Calculate the tally commitment
-    let tally_commitment = hash2([results_root, salt]);
-
-    // Load the current tally commitment
-    let current_tally_commitment = CURRENT_TALLY_COMMITMENT.load(deps.storage)?;
-    if current_tally_commitment == Uint256::from_u128(0u128) {
-        let mut sum = Uint256::zero();
-
-        // Save the results and calculate the sum
-        for i in 0..results.len() {
-            RESULT.save(
-                deps.storage,
-                Uint256::from_u128(i as u128).to_be_bytes().to_vec(),
-                &results[i],
-            )?;
-            sum += results[i];
-        }
-
-        // Save the total result
-        TOTAL_RESULT.save(deps.storage, &sum)?;
-
-        // Update the period status to Ended
-        let period = Period {
-            status: PeriodStatus::Ended,
-        };
-        PERIOD.save(deps.storage, &period)?;
+    let tally_commitment = try_hash2([results_root, salt])?;
 
-        return Ok(Response::new()
-            .add_attribute("action", "stop_tallying_period")
-            .add_attribute(
-                "results",
-                format!(
-                    "{:?}",
-                    results
-                        .iter()
-                        .map(|x| x.to_string())
-                        .collect::<Vec<String>>()
-                ),
-            )
-            .add_attribute("all_result", sum.to_string())
-            .add_attributes(attributes));
-    }
     // Check that the tally commitment matches the current tally commitment
-    assert_eq!(tally_commitment, current_tally_commitment);
+    if question.tally_commitment != Uint256::from_u128(0u128) {
+        assert_eq!(tally_commitment, question.tally_commitment);
+    }
 
     let mut sum = Uint256::zero();
 
     // Save the results and calculate the sum
     for i in 0..results.len() {
-        RESULT.save(
+        save_result(
             deps.storage,
-            Uint256::from_u128(i as u128).to_be_bytes().to_vec(),
+            question_id,
+            Uint256::from_u128(i as u128),
             &results[i],
         )?;
         sum += results[i];
     }
 
     // Save the total result
-    TOTAL_RESULT.save(deps.storage, &sum)?;
+    question.total_result = Some(sum);
+    save_question(deps.storage, question_id, &question)?;
 
-    // Update the period status to Ended
-    let period = Period {
-        status: PeriodStatus::Ended,
-    };
-    PERIOD.save(deps.storage, &period)?;
+    let mut attributes = vec![attr("question_id", question_id.to_string())];
+
+    // The round ends once the last question has been tallied
+    let mut round_ended = true;
+    for id in 0..question_count(deps.storage)? {
+        if load_question(deps.storage, id)?.total_result.is_none() {
+            round_ended = false;
+            break;
+        }
+    }
+
+    if round_ended {
+        // Get the final signup count and message count
+        let msg_chain_length = total_msg_chain_length(deps.storage)?;
+
+        // Calculate total workload (signup and message have same weight)
+        let total_work = num_sign_ups + msg_chain_length;
+
+        let total_work_u128 = total_work
+            .try_into() // Uint256 -> Uint128
+            .map(|x: Uint128| x.u128()) // Uint128 -> u128
+            .map_err(|_| ContractError::ValueTooLarge {})?;
+
+        // Calculate actual delay timeout (linear change between min hours to max hours)
+        let actual_delay: TallyDelayInfo = calculate_tally_delay(deps.as_ref())?;
+        let voting_time = VOTINGTIME.load(deps.storage)?;
+        let current_time = env.block.time;
+        let different_time = current_time.seconds() - voting_time.end_time.seconds();
+
+        attributes.extend(vec![
+            attr("total_work", total_work_u128.to_string()),
+            attr(
+                "actual_delay_seconds",
+                actual_delay.delay_seconds.to_string(),
+            ),
+        ]);
+
+        if different_time > actual_delay.delay_seconds {
+            let delay_timestamp = voting_time.end_time;
+            let delay_duration = different_time;
+            let delay_reason = format!(
+                "Tallying has timed out after {} seconds (total process: {}, allowed: {} seconds)",
+                different_time, total_work_u128, actual_delay.delay_seconds
+            );
+            let delay_process_dmsg_count = Uint256::from_u128(0u128);
+            let delay_type = DelayType::TallyDelay;
+
+            let mut delay_records = DELAY_RECORDS.load(deps.storage)?;
+            let delay_record = DelayRecord {
+                delay_timestamp: delay_timestamp.clone(),
+                delay_duration: delay_duration.clone(),
+                delay_reason: delay_reason.clone(),
+                delay_process_dmsg_count,
+                delay_type,
+            };
+            delay_records.records.push(delay_record);
+            DELAY_RECORDS.save(deps.storage, &delay_records)?;
+
+            attributes.extend(vec![
+                attr("delay_timestamp", delay_timestamp.seconds().to_string()),
+                attr("delay_duration", delay_duration.to_string()),
+                attr("delay_reason", delay_reason),
+                attr("delay_type", "tally_delay"),
+            ]);
+        }
+
+        // Update the period status to Ended
+        let period = Period {
+            status: PeriodStatus::Ended,
+        };
+        PERIOD.save(deps.storage, &period)?;
+    }
 
     Ok(Response::new()
         .add_attribute("action", "stop_tallying_period")
@@ -1772,7 +2029,12 @@ fn execute_stop_tallying_period(
             ),
         )
         .add_attribute("all_result", sum.to_string())
-        .add_attributes(attributes))
+        .add_attributes(attributes)
+        .add_event(Event::from(maci_events::Tally {
+            results,
+            total_result: sum,
+            question_id: Some(question_id),
+        })))
 }
 
 fn execute_claim(deps: DepsMut, env: Env, _info: MessageInfo) -> Result<Response, ContractError> {
@@ -1800,8 +2062,10 @@ fn execute_claim(deps: DepsMut, env: Env, _info: MessageInfo) -> Result<Response
             amount: coins(contract_balance_amount, denom),
         };
 
+        let report = report_performance(deps.as_ref(), true)?;
         return Ok(Response::new()
             .add_message(message)
+            .add_submessages(report)
             .add_attribute("action", "claim")
             .add_attribute(
                 "is_ended",
@@ -1810,7 +2074,14 @@ fn execute_claim(deps: DepsMut, env: Env, _info: MessageInfo) -> Result<Response
             .add_attribute("operator_reward", "0")
             .add_attribute("penalty_amount", contract_balance_amount.to_string())
             .add_attribute("miss_rate", Uint256::from_u128(0u128).to_string())
-            .add_attribute("is_tally_timeout", "true"));
+            .add_attribute("is_tally_timeout", "true")
+            .add_event(Event::from(maci_events::Claim {
+                operator_reward: Uint128::zero(),
+                penalty_amount: Uint128::from(contract_balance_amount),
+                fee_to_recipient: Uint128::zero(),
+                miss_rate: Uint256::zero(),
+                is_tally_timeout: true,
+            })));
     }
 
     // If less than timeout and status is not Ended, return an error
@@ -1869,15 +2140,64 @@ fn execute_claim(deps: DepsMut, env: Env, _info: MessageInfo) -> Result<Response
         }));
     }
 
+    let report = report_performance(deps.as_ref(), false)?;
+
     Ok(Response::new()
         .add_messages(messages)
+        .add_submessages(report)
         .add_attribute("action", "claim")
         .add_attribute("is_ended", "true")
         .add_attribute("fee_to_recipient", fee_amount.to_string())
         .add_attribute("operator_reward", operator_reward_u128_amount.to_string())
         .add_attribute("penalty_amount", penalty_u128_amount.to_string())
         .add_attribute("miss_rate", performance.miss_rate.to_string())
-        .add_attribute("is_tally_timeout", "false"))
+        .add_attribute("is_tally_timeout", "false")
+        .add_event(Event::from(maci_events::Claim {
+            operator_reward: Uint128::from(operator_reward_u128_amount),
+            penalty_amount: Uint128::from(penalty_u128_amount),
+            fee_to_recipient: fee_amount,
+            miss_rate: performance.miss_rate,
+            is_tally_timeout: false,
+        })))
+}
+
+// The registry that created the round keeps the operator's statistics and may slash its bond
+// A failed report is handled in `reply`, so a registry error cannot block the claim
+fn report_performance(deps: Deps, tally_timeout: bool) -> Result<Option<SubMsg>, ContractError> {
+    let registry = match REGISTRY.may_load(deps.storage)? {
+        Some(registry) => registry,
+        None => return Ok(None),
+    };
+
+    let delay_records = DELAY_RECORDS.load(deps.storage)?.records;
+    let count = |delay_type: DelayType| {
+        delay_records
+            .iter()
+            .filter(|record| record.delay_type == delay_type)
+            .count() as u64
+    };
+    let operator_performance = calculate_operator_performance(deps)?;
+    let performance = RoundPerformance {
+        tally_timeout,
+        deactivate_delays: count(DelayType::DeactivateDelay),
+        tally_delays: count(DelayType::TallyDelay),
+        delayed_deactivate_messages: operator_performance.delay_deactivate_count,
+        // the performance holds the share the operator earned, the registry wants the penalty
+        miss_rate: if tally_timeout {
+            Uint256::from_u128(100u128)
+        } else {
+            Uint256::from_u128(100u128) - operator_performance.miss_rate
+        },
+    };
+
+    Ok(Some(SubMsg::reply_on_error(
+        WasmMsg::Execute {
+            contract_addr: registry.to_string(),
+            msg: to_json_binary(&RegistryExecuteMsg::ReportRoundPerformance { performance })?,
+            funds: vec![],
+        },
+        REPORT_PERFORMANCE_REPLY_ID,
+    )))
 }
 
 fn can_sign_up(deps: Deps, sender: &Addr) -> StdResult<bool> {
@@ -1887,89 +2207,22 @@ fn can_sign_up(deps: Deps, sender: &Addr) -> StdResult<bool> {
     Ok(is_whitelist && !is_register)
 }
 
-// Load the root node of the state tree
-fn state_root(deps: Deps) -> Uint256 {
-    let root = NODES
-        .load(
-            deps.storage,
-            Uint256::from_u128(0u128).to_be_bytes().to_vec(),
-        )
-        .unwrap();
-    root
+// Compute the root of the state tree
+fn state_root(deps: Deps) -> Result<Uint256, ContractError> {
+    let tree = STATE_TREE.load(deps.storage)?;
+    // The root node of older rounds was stored as 0 until the first sign up
+    if tree.leaf_count == 0 {
+        return Ok(Uint256::zero());
+    }
+    Ok(tree.root()?)
 }
 
 // Enqueues the state leaf into the tree
-fn state_enqueue(deps: &mut DepsMut, leaf: Uint256) -> Result<bool, ContractError> {
-    let leaf_idx0 = LEAF_IDX_0.load(deps.storage).unwrap();
-    let num_sign_ups = NUMSIGNUPS.load(deps.storage).unwrap();
-
-    let leaf_idx = leaf_idx0 + num_sign_ups;
-    NODES.save(deps.storage, leaf_idx.to_be_bytes().to_vec(), &leaf)?;
-    state_update_at(deps, leaf_idx)
-}
-
-// Updates the state at the given index in the tree
-fn state_update_at(deps: &mut DepsMut, index: Uint256) -> Result<bool, ContractError> {
-    let leaf_idx0 = LEAF_IDX_0.load(deps.storage).unwrap();
-    if index < leaf_idx0 {
-        return Err(ContractError::MustUpdate {});
-    }
-
-    let mut idx = index.clone();
-
-    let mut height = 0;
-
-    let zeros = ZEROS_H10.load(deps.storage).unwrap();
-
-    while idx > Uint256::from_u128(0u128) {
-        let parent_idx = (idx - Uint256::one()) / Uint256::from(5u8);
-        let children_idx0 = parent_idx * Uint256::from(5u8) + Uint256::one();
-
-        let zero = zeros[height];
-
-        let mut inputs: [Uint256; 5] = [Uint256::zero(); 5];
-
-        for i in 0..5 {
-            let node_value = NODES
-                .may_load(
-                    deps.storage,
-                    (children_idx0 + Uint256::from_u128(i as u128))
-                        .to_be_bytes()
-                        .to_vec(),
-                )
-                .unwrap();
-
-            let child = match node_value {
-                Some(value) => value,
-                None => zero,
-            };
-
-            inputs[i] = child;
-        }
-
-        if NODES.has(deps.storage, parent_idx.to_be_bytes().to_vec()) {
-            NODES
-                .update(
-                    deps.storage,
-                    parent_idx.to_be_bytes().to_vec(),
-                    |_c: Option<Uint256>| -> StdResult<_> { Ok(hash5(inputs)) },
-                )
-                .unwrap();
-        } else {
-            NODES
-                .save(
-                    deps.storage,
-                    parent_idx.to_be_bytes().to_vec(),
-                    &hash5(inputs),
-                )
-                .unwrap();
-        }
-
-        height += 1;
-        idx = parent_idx;
-    }
-
-    Ok(true)
+fn state_enqueue(deps: &mut DepsMut, leaf: Uint256) -> Result<(), ContractError> {
+    let mut tree = STATE_TREE.load(deps.storage)?;
+    tree.push(leaf)?;
+    STATE_TREE.save(deps.storage, &tree)?;
+    Ok(())
 }
 
 fn check_voting_time(env: Env, voting_time: VotingTime) -> Result<(), ContractError> {
@@ -1983,11 +2236,39 @@ fn check_voting_time(env: Env, voting_time: VotingTime) -> Result<(), ContractEr
     Ok(())
 }
 
+// Sign up uses its own window when one is configured, otherwise the voting window.
+fn check_sign_up_time(deps: Deps, env: Env) -> Result<(), ContractError> {
+    match SIGNUPTIME.may_load(deps.storage)? {
+        Some(sign_up_time) => {
+            let current_time = env.block.time;
+            if current_time < sign_up_time.start_time || current_time > sign_up_time.end_time {
+                return Err(ContractError::PeriodError {});
+            }
+            Ok(())
+        }
+        None => check_voting_time(env, VOTINGTIME.load(deps.storage)?),
+    }
+}
+
+fn uint256_to_usize(value: Uint256) -> Result<usize, ContractError> {
+    value
+        .to_string()
+        .parse()
+        .map_err(|_| ContractError::ValueTooLarge {})
+}
+
+pub(crate) fn state_tree_depth(depth: Uint256) -> Result<u32, ContractError> {
+    depth
+        .to_string()
+        .parse()
+        .map_err(|_| ContractError::InvalidStateTreeDepth { depth })
+}
+
 pub fn hash_message_and_enc_pub_key(
     message: MessageData,
     enc_pub_key: PubKey,
     prev_hash: Uint256,
-) -> Uint256 {
+) -> Result<Uint256, ContractError> {
     let mut m: [Uint256; 5] = [Uint256::zero(); 5];
     m[0] = message.data[0];
     m[1] = message.data[1];
@@ -2002,11 +2283,11 @@ pub fn hash_message_and_enc_pub_key(
     n[3] = enc_pub_key.y;
     n[4] = prev_hash;
 
-    let m_hash = hash5(m);
+    let m_hash = try_hash5(m)?;
 
-    let n_hash = hash5(n);
-    let m_n_hash = hash2([m_hash, n_hash]);
-    return m_n_hash;
+    let n_hash = try_hash5(n)?;
+    let m_n_hash = try_hash2([m_hash, n_hash])?;
+    Ok(m_n_hash)
 }
 
 // Only admin can execute
@@ -2023,6 +2304,20 @@ fn is_operator(deps: Deps, sender: &str) -> StdResult<bool> {
     Ok(can_operator)
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        // The funds are already paid out, only the operator's statistics miss this round
+        REPORT_PERFORMANCE_REPLY_ID => match msg.result {
+            SubMsgResult::Err(error) => Ok(Response::new()
+                .add_attribute("action", "report_performance")
+                .add_attribute("error", error)),
+            SubMsgResult::Ok(_) => Ok(Response::new()),
+        },
+        id => Err(ContractError::UnRecognizedReplyIdErr { id }),
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -2032,7 +2327,12 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             to_json_binary::<RoundInfo>(&ROUNDINFO.load(deps.storage).unwrap())
         }
         QueryMsg::GetVotingTime {} => {
-            to_json_binary::<VotingTime>(&VOTINGTIME.load(deps.storage).unwrap())
+            let voting_time = VOTINGTIME.load(deps.storage)?;
+            to_json_binary::<VotingTimeResponse>(&VotingTimeResponse {
+                start_time: voting_time.start_time,
+                end_time: voting_time.end_time,
+                sign_up_time: SIGNUPTIME.may_load(deps.storage)?,
+            })
         }
         QueryMsg::GetPeriod {} => to_json_binary::<Period>(&PERIOD.load(deps.storage).unwrap()),
         QueryMsg::GetNumSignUp {} => {
@@ -2126,7 +2426,51 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 .map_err(|e| cosmwasm_std::StdError::generic_err(e.to_string()))?;
             to_json_binary(&delay_info)
         }
+        QueryMsg::GetQuestionCount {} => to_json_binary::<u64>(&question_count(deps.storage)?),
+        QueryMsg::GetQuestion { question_id } => {
+            let question = load_question(deps.storage, question_id)
+                .map_err(|e| cosmwasm_std::StdError::generic_err(e.to_string()))?;
+            to_json_binary(&question)
+        }
+        QueryMsg::GetQuestionResult { question_id, index } => {
+            let result = if question_id == 0 {
+                RESULT.may_load(deps.storage, index.to_be_bytes().to_vec())?
+            } else {
+                QUESTION_RESULT
+                    .may_load(deps.storage, (question_id, index.to_be_bytes().to_vec()))?
+            };
+            to_json_binary::<Uint256>(&result.unwrap_or_default())
+        }
+        QueryMsg::BatchRecords { start_after, limit } => {
+            to_json_binary::<Vec<BatchRecord>>(&query_batch_records(deps, start_after, limit)?)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored_version =
+        cw2::ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Apply every storage upgrade newer than the stored version, oldest first
+    let stored = (
+        stored_version.major,
+        stored_version.minor,
+        stored_version.patch,
+    );
+    let mut attributes = vec![attr("action", "migrate")];
+    if stored < (0, 1, 1) {
+        attributes.extend(migrate_v0_1_1(deps.branch())?);
+    }
+    if stored < (0, 1, 2) {
+        attributes.extend(migrate_v0_1_2(deps.branch())?);
     }
+    if stored < (0, 1, 3) {
+        attributes.extend(migrate_v0_1_3(deps.branch())?);
+    }
+
+    attributes.push(attr("from_version", stored_version.to_string()));
+    Ok(Response::new().add_attributes(attributes))
 }
 
 pub fn query_white_list(deps: Deps) -> StdResult<Whitelist> {
@@ -2140,6 +2484,20 @@ pub fn query_can_sign_up(deps: Deps, sender: &Addr) -> StdResult<bool> {
     Ok(can_sign_up(deps, &sender)?)
 }
 
+pub fn query_batch_records(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<BatchRecord>> {
+    let limit = limit.unwrap_or(30).min(100) as usize;
+    let start = start_after.map(Bound::exclusive);
+    BATCH_RECORDS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, record)| record))
+        .collect()
+}
+
 pub fn is_whitelist(deps: Deps, sender: &Addr) -> StdResult<bool> {
     let cfg = WHITELIST.load(deps.storage)?;
     let is_whitelist = cfg.is_whitelist(sender);
@@ -2234,7 +2592,7 @@ pub fn calculate_operator_performance(deps: Deps) -> Result<OperatorPerformance,
 
 pub fn calculate_tally_delay(deps: Deps) -> Result<TallyDelayInfo, ContractError> {
     let num_sign_ups = NUMSIGNUPS.load(deps.storage)?;
-    let msg_chain_length = MSG_CHAIN_LENGTH.load(deps.storage)?;
+    let msg_chain_length = total_msg_chain_length(deps.storage)?;
 
     // Calculate total workload (signup and message have same weight)
     let total_work = num_sign_ups + msg_chain_length;
@@ -2265,3 +2623,117 @@ pub fn calculate_tally_delay(deps: Deps) -> Result<TallyDelayInfo, ContractError
         calculated_hours,
     })
 }
+
+// Rounds created before multi-question support only have the primary ballot
+pub fn question_count(storage: &dyn Storage) -> StdResult<u64> {
+    Ok(QUESTION_COUNT.may_load(storage)?.unwrap_or(1u64))
+}
+
+pub fn load_question(storage: &dyn Storage, question_id: u64) -> Result<Question, ContractError> {
+    if question_id != 0 {
+        return QUESTIONS
+            .may_load(storage, question_id)?
+            .ok_or(ContractError::QuestionNotFound { question_id });
+    }
+
+    Ok(Question {
+        vote_option_map: VOTEOPTIONMAP.load(storage)?,
+        max_vote_options: MAX_VOTE_OPTIONS.load(storage)?,
+        msg_chain_length: MSG_CHAIN_LENGTH.load(storage)?,
+        processed_msg_count: PROCESSED_MSG_COUNT.load(storage)?,
+        state_commitment: CURRENT_STATE_COMMITMENT
+            .may_load(storage)?
+            .unwrap_or_default(),
+        tally_commitment: CURRENT_TALLY_COMMITMENT.load(storage)?,
+        processed_user_count: PROCESSED_USER_COUNT.load(storage)?,
+        total_result: TOTAL_RESULT.may_load(storage)?,
+    })
+}
+
+pub fn save_question(
+    storage: &mut dyn Storage,
+    question_id: u64,
+    question: &Question,
+) -> StdResult<()> {
+    if question_id != 0 {
+        return QUESTIONS.save(storage, question_id, question);
+    }
+
+    VOTEOPTIONMAP.save(storage, &question.vote_option_map)?;
+    MAX_VOTE_OPTIONS.save(storage, &question.max_vote_options)?;
+    MSG_CHAIN_LENGTH.save(storage, &question.msg_chain_length)?;
+    PROCESSED_MSG_COUNT.save(storage, &question.processed_msg_count)?;
+    CURRENT_STATE_COMMITMENT.save(storage, &question.state_commitment)?;
+    CURRENT_TALLY_COMMITMENT.save(storage, &question.tally_commitment)?;
+    PROCESSED_USER_COUNT.save(storage, &question.processed_user_count)?;
+    if let Some(total_result) = question.total_result {
+        TOTAL_RESULT.save(storage, &total_result)?;
+    }
+    Ok(())
+}
+
+// Batch records are numbered from 0 in the order they were accepted
+fn save_batch_record(storage: &mut dyn Storage, record: BatchRecord) -> StdResult<u64> {
+    let id = BATCH_RECORD_COUNT.may_load(storage)?.unwrap_or_default();
+    BATCH_RECORDS.save(storage, id, &BatchRecord { id, ..record })?;
+    BATCH_RECORD_COUNT.save(storage, &(id + 1))?;
+    Ok(id)
+}
+
+// Verification keys are prepared once and kept next to the raw key, rounds created before
+// the prepared keys existed prepare them on their first verification
+fn load_prepared_vkey(
+    storage: &mut dyn Storage,
+    vkeys: &Item<Groth16VkeyStr>,
+    prepared_vkeys: &Item<Groth16PreparedVkeyStr>,
+) -> Result<Groth16PreparedVkeyStr, ContractError> {
+    if let Some(prepared) = prepared_vkeys.may_load(storage)? {
+        return Ok(prepared);
+    }
+    let vkey = parse_groth16_vkey::<Bn256>(vkeys.load(storage)?)?;
+    let prepared = prepare_groth16_vkey(&vkey);
+    prepared_vkeys.save(storage, &prepared)?;
+    Ok(prepared)
+}
+
+fn load_msg_hash(storage: &dyn Storage, question_id: u64, index: Uint256) -> StdResult<Uint256> {
+    if question_id == 0 {
+        MSG_HASHES.load(storage, index.to_be_bytes().to_vec())
+    } else {
+        QUESTION_MSG_HASHES.load(storage, (question_id, index.to_be_bytes().to_vec()))
+    }
+}
+
+fn save_msg_hash(
+    storage: &mut dyn Storage,
+    question_id: u64,
+    index: Uint256,
+    hash: &Uint256,
+) -> StdResult<()> {
+    if question_id == 0 {
+        MSG_HASHES.save(storage, index.to_be_bytes().to_vec(), hash)
+    } else {
+        QUESTION_MSG_HASHES.save(storage, (question_id, index.to_be_bytes().to_vec()), hash)
+    }
+}
+
+fn save_result(
+    storage: &mut dyn Storage,
+    question_id: u64,
+    index: Uint256,
+    result: &Uint256,
+) -> StdResult<()> {
+    if question_id == 0 {
+        RESULT.save(storage, index.to_be_bytes().to_vec(), result)
+    } else {
+        QUESTION_RESULT.save(storage, (question_id, index.to_be_bytes().to_vec()), result)
+    }
+}
+
+fn total_msg_chain_length(storage: &dyn Storage) -> Result<Uint256, ContractError> {
+    let mut msg_chain_length = Uint256::zero();
+    for question_id in 0..question_count(storage)? {
+        msg_chain_length += load_question(storage, question_id)?.msg_chain_length;
+    }
+    Ok(msg_chain_length)
+}
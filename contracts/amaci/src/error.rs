@@ -1,4 +1,5 @@
 use cosmwasm_std::{StdError, Uint256};
+use maci_core::CoreError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -57,6 +58,9 @@ pub enum ContractError {
     #[error("The end_time must be greater than the start_time and more than 10 minutes apart.")]
     WrongTimeSet {},
 
+    #[error("The sign up end_time must be greater than its start_time and no later than the voting end_time.")]
+    WrongSignUpTimeSet {},
+
     #[error("round title can not be empty")]
     TitleIsEmpty,
 
@@ -72,6 +76,24 @@ pub enum ContractError {
     #[error("could not convert into prime field")]
     InvalidPrimeField {},
 
+    #[error("hex value of {len} digits does not fit in 32 bytes")]
+    HexTooLong { len: usize },
+
+    #[error("invalid decimal number")]
+    InvalidDecimal {},
+
+    #[error("{leaves} leaves do not fit in a quinary tree of depth {depth}")]
+    TreeOverflow { depth: u32, leaves: u64 },
+
+    #[error("leaf {index} is outside a quinary tree of depth {depth}")]
+    LeafIndexOutOfRange { depth: u32, index: u64 },
+
+    #[error("state tree depth {depth} is not supported")]
+    InvalidStateTreeDepth { depth: Uint256 },
+
+    #[error("{count} sign ups do not fit in a quinary tree")]
+    InvalidSignUpCount { count: Uint256 },
+
     #[error("SynthesisError of zk verify")]
     SynthesisError {},
 
@@ -128,4 +150,35 @@ pub enum ContractError {
 
     #[error("Maximum number of deactivate messages ({max_deactivate_messages}) has been reached")]
     MaxDeactivateMessagesReached { max_deactivate_messages: Uint256 },
+
+    #[error("Question {question_id} not found")]
+    QuestionNotFound { question_id: u64 },
+
+    #[error("Question {question_id} has already been tallied")]
+    QuestionAlreadyTallied { question_id: u64 },
+
+    #[error("The message was already published for question {question_id}")]
+    MessageReplayed { question_id: u64 },
+
+    #[error("Un recognized reply id {id}")]
+    UnRecognizedReplyIdErr { id: u64 },
+}
+
+impl From<CoreError> for ContractError {
+    fn from(err: CoreError) -> Self {
+        match err {
+            CoreError::ErrorVerificationKey {} => ContractError::ErrorVerificationKey {},
+            CoreError::ErrorProof {} => ContractError::ErrorProof {},
+            CoreError::InvalidPrimeField {} => ContractError::InvalidPrimeField {},
+            CoreError::InvalidHex {} => ContractError::HexDecodingError {},
+            CoreError::HexTooLong { len } => ContractError::HexTooLong { len },
+            CoreError::InvalidDecimal {} => ContractError::InvalidDecimal {},
+            CoreError::TreeOverflow { depth, leaves } => {
+                ContractError::TreeOverflow { depth, leaves }
+            }
+            CoreError::LeafIndexOutOfRange { depth, index } => {
+                ContractError::LeafIndexOutOfRange { depth, index }
+            }
+        }
+    }
 }
@@ -1,10 +1,11 @@
+#[cfg(not(target_arch = "wasm32"))]
+pub mod audit;
 pub mod circuit_params;
 pub mod contract;
 mod error;
-pub mod groth16_parser;
+mod migrates;
 pub mod msg;
 pub mod state;
-pub mod utils;
 
 #[cfg(any(feature = "mt", test))]
 pub mod multitest;
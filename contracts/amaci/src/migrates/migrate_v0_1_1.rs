@@ -0,0 +1,21 @@
+use crate::error::ContractError;
+use crate::state::QUESTION_COUNT;
+use cosmwasm_std::{Attribute, DepsMut};
+
+pub fn migrate_v0_1_1(deps: DepsMut) -> Result<Vec<Attribute>, ContractError> {
+    // Rounds created before multi-question support only carry the primary ballot
+    let question_count = match QUESTION_COUNT.may_load(deps.storage)? {
+        Some(question_count) => question_count,
+        None => {
+            QUESTION_COUNT.save(deps.storage, &1u64)?;
+            1u64
+        }
+    };
+
+    let attributes: Vec<Attribute> = vec![
+        Attribute::new("version", "0.1.1"),
+        Attribute::new("question_count", question_count.to_string()),
+    ];
+
+    Ok(attributes)
+}
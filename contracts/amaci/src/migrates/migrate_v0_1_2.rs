@@ -0,0 +1,49 @@
+use crate::error::ContractError;
+use crate::state::{MAX_VOTE_OPTIONS, RESULT, TOTAL_RESULT, VOICECREDITBALANCE};
+use cosmwasm_std::{Attribute, DepsMut, StdResult, Storage, Uint256};
+use cw_storage_plus::Map;
+
+// Before v0.1.2 `RESULT` shared the "voice_credit_balance" namespace
+const LEGACY_RESULT: Map<Vec<u8>, Uint256> = Map::new("voice_credit_balance");
+
+pub fn migrate_v0_1_2(deps: DepsMut) -> Result<Vec<Attribute>, ContractError> {
+    let relocated = relocate_results(deps.storage)?;
+
+    // Voice credit balances were never stored before v0.1.2, so every legacy key was a result
+    for i in 0..relocated {
+        VOICECREDITBALANCE.remove(deps.storage, Uint256::from_u128(i).to_be_bytes().to_vec());
+    }
+
+    let attributes: Vec<Attribute> = vec![
+        Attribute::new("version", "0.1.2"),
+        Attribute::new("relocated_results", relocated.to_string()),
+    ];
+
+    Ok(attributes)
+}
+
+// Copies the tallied results into their own namespace and returns how many leading
+// indices held a result. Results are a contiguous run starting at index 0 whose sum is
+// the stored total, followed only by zero-valued options.
+fn relocate_results(storage: &mut dyn Storage) -> StdResult<u128> {
+    let total_result = match TOTAL_RESULT.may_load(storage)? {
+        Some(total_result) => total_result,
+        None => return Ok(0),
+    };
+    let max_vote_options = MAX_VOTE_OPTIONS.load(storage)?;
+
+    let mut sum = Uint256::zero();
+    let mut count = 0u128;
+    while Uint256::from_u128(count) < max_vote_options {
+        let key = Uint256::from_u128(count).to_be_bytes().to_vec();
+        let result = match LEGACY_RESULT.may_load(storage, key.clone())? {
+            Some(result) if sum < total_result || result.is_zero() => result,
+            _ => break,
+        };
+        RESULT.save(storage, key, &result)?;
+        sum += result;
+        count += 1;
+    }
+
+    Ok(count)
+}
@@ -0,0 +1,43 @@
+use crate::contract::state_tree_depth;
+use crate::error::ContractError;
+use crate::state::{IncrementalQuinaryTree, MACIPARAMETERS, NUMSIGNUPS, STATE_TREE, ZEROS_H10};
+use cosmwasm_std::{Attribute, DepsMut, StdError, Uint256};
+use cw_storage_plus::Map;
+
+// Before v0.1.3 every node of the state tree was stored, keyed by its index. The root is 0,
+// the children of node i are 5i + 1 to 5i + 5.
+const LEGACY_NODES: Map<Vec<u8>, Uint256> = Map::new("nodes");
+
+pub fn migrate_v0_1_3(deps: DepsMut) -> Result<Vec<Attribute>, ContractError> {
+    let mut attributes: Vec<Attribute> = vec![Attribute::new("version", "0.1.3")];
+
+    // Only rounds that were instantiated have a state tree to rebuild
+    if let Some(parameters) = MACIPARAMETERS.may_load(deps.storage)? {
+        let depth = state_tree_depth(parameters.state_tree_depth)?;
+        let zeros = ZEROS_H10.load(deps.storage)?;
+        let num_sign_ups = NUMSIGNUPS.load(deps.storage)?;
+        let leaf_count = num_sign_ups.to_string().parse::<u64>().map_err(|_| {
+            ContractError::InvalidSignUpCount {
+                count: num_sign_ups,
+            }
+        })?;
+
+        // The frontier only needs nodes of full subtrees, which every older version stored
+        let tree = IncrementalQuinaryTree::from_nodes(
+            depth,
+            &zeros,
+            leaf_count,
+            |height, position| -> Result<Uint256, ContractError> {
+                let first = (5u128.pow(depth - height) - 1) / 4;
+                let index = Uint256::from_u128(first + position as u128);
+                LEGACY_NODES
+                    .may_load(deps.storage, index.to_be_bytes().to_vec())?
+                    .ok_or_else(|| StdError::not_found(format!("state tree node {index}")).into())
+            },
+        )?;
+        STATE_TREE.save(deps.storage, &tree)?;
+        attributes.push(Attribute::new("state_tree_leaves", leaf_count.to_string()));
+    }
+
+    Ok(attributes)
+}
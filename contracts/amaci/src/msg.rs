@@ -1,9 +1,11 @@
 use crate::state::{
-    DelayRecords, MaciParameters, MessageData, PeriodStatus, PubKey, RoundInfo, VotingTime,
-    Whitelist,
+    BatchRecord, DelayRecords, MaciParameters, MessageData, PeriodStatus, PubKey, Question,
+    QuestionConfig, RoundInfo, SignUpTime, VotingTime, Whitelist,
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Timestamp, Uint128, Uint256};
+use maci_core::groth16_parser::parse_snarkjs_proof;
+use maci_core::{CoreError, SnarkjsProof};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -22,12 +24,19 @@ pub struct InstantiateMsg {
 
     pub round_info: RoundInfo,
     pub voting_time: VotingTime,
+    pub sign_up_time: Option<SignUpTime>,
     pub whitelist: Option<WhitelistBase>,
 
     pub pre_deactivate_root: Uint256,
 
     pub circuit_type: Uint256,         // <0: 1p1v | 1: pv>
     pub certification_system: Uint256, // <0: groth16 | 1: plonk>
+
+    // questions voted on in addition to the primary ballot, sharing its sign ups
+    pub extra_questions: Option<Vec<QuestionConfig>>,
+
+    // set by the registry, which `Claim` reports the performance of the operator to
+    pub registry: Option<Addr>,
 }
 
 #[cw_serde]
@@ -57,6 +66,37 @@ pub struct Groth16ProofType {
     pub c: String,
 }
 
+/// A groth16 proof, either as uncompressed hex points or as snarkjs writes it to `proof.json`
+#[cw_serde]
+#[serde(untagged)]
+pub enum Groth16ProofInput {
+    Uncompressed(Groth16ProofType),
+    Snarkjs(SnarkjsProof),
+}
+
+impl Groth16ProofInput {
+    /// normalize to the uncompressed hex points that are verified and emitted in events
+    pub fn into_uncompressed(self) -> Result<Groth16ProofType, CoreError> {
+        match self {
+            Groth16ProofInput::Uncompressed(proof) => Ok(proof),
+            Groth16ProofInput::Snarkjs(proof) => {
+                let proof = parse_snarkjs_proof(&proof)?;
+                Ok(Groth16ProofType {
+                    a: hex::encode(proof.pi_a),
+                    b: hex::encode(proof.pi_b),
+                    c: hex::encode(proof.pi_c),
+                })
+            }
+        }
+    }
+}
+
+impl From<Groth16ProofType> for Groth16ProofInput {
+    fn from(proof: Groth16ProofType) -> Self {
+        Groth16ProofInput::Uncompressed(proof)
+    }
+}
+
 #[cw_serde]
 pub enum ExecuteMsg {
     SetRoundInfo {
@@ -67,6 +107,7 @@ pub enum ExecuteMsg {
     },
     SetVoteOptionsMap {
         vote_option_map: Vec<String>,
+        question_id: Option<u64>,
     },
     SignUp {
         pubkey: PubKey, // user's pubkey
@@ -83,36 +124,40 @@ pub enum ExecuteMsg {
         size: Uint256,
         new_deactivate_commitment: Uint256,
         new_deactivate_root: Uint256,
-        groth16_proof: Groth16ProofType,
+        groth16_proof: Groth16ProofInput,
     },
     AddNewKey {
         pubkey: PubKey,
         nullifier: Uint256,
         d: [Uint256; 4],
-        groth16_proof: Groth16ProofType,
+        groth16_proof: Groth16ProofInput,
     },
     PreAddNewKey {
         pubkey: PubKey,
         nullifier: Uint256,
         d: [Uint256; 4],
-        groth16_proof: Groth16ProofType,
+        groth16_proof: Groth16ProofInput,
     },
     PublishMessage {
         message: MessageData,
         enc_pub_key: PubKey,
+        question_id: Option<u64>,
     },
     ProcessMessage {
         new_state_commitment: Uint256,
-        groth16_proof: Groth16ProofType,
+        groth16_proof: Groth16ProofInput,
+        question_id: Option<u64>,
     },
     StopProcessingPeriod {},
     ProcessTally {
         new_tally_commitment: Uint256,
-        groth16_proof: Groth16ProofType,
+        groth16_proof: Groth16ProofInput,
+        question_id: Option<u64>,
     },
     StopTallyingPeriod {
         results: Vec<Uint256>,
         salt: Uint256,
+        question_id: Option<u64>,
     },
     Claim {},
 }
@@ -122,6 +167,9 @@ pub struct Period {
     pub status: PeriodStatus,
 }
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -134,7 +182,7 @@ pub enum QueryMsg {
     #[returns(RoundInfo)]
     GetRoundInfo {},
 
-    #[returns(VotingTime)]
+    #[returns(VotingTimeResponse)]
     GetVotingTime {},
 
     #[returns(Period)]
@@ -215,6 +263,29 @@ pub enum QueryMsg {
 
     #[returns(TallyDelayInfo)]
     GetTallyDelay {},
+
+    #[returns(u64)]
+    GetQuestionCount {},
+
+    #[returns(Question)]
+    GetQuestion { question_id: u64 },
+
+    #[returns(Uint256)]
+    GetQuestionResult { question_id: u64, index: Uint256 },
+
+    /// Accepted deactivate, process and tally batches, in the order they were accepted
+    #[returns(Vec<BatchRecord>)]
+    BatchRecords {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct VotingTimeResponse {
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
+    pub sign_up_time: Option<SignUpTime>,
 }
 
 #[cw_serde]
@@ -237,6 +308,8 @@ pub struct InstantiationData {
     pub voice_credit_amount: Uint256,
     pub round_info: RoundInfo,
     pub voting_time: VotingTime,
+    pub sign_up_time: Option<SignUpTime>,
+    pub extra_questions: Vec<QuestionConfig>,
     pub pre_deactivate_root: Uint256,
     pub circuit_type: String,
     pub certification_system: String,
@@ -244,3 +317,23 @@ pub struct InstantiationData {
     pub deactivate_timeout: Timestamp,
     pub tally_timeout: Timestamp,
 }
+
+/// How the operator did in a round created by the registry, reported when the round is claimed
+#[cw_serde]
+pub struct RoundPerformance {
+    /// the round was not tallied before the tally timeout
+    pub tally_timeout: bool,
+    /// the number of `DeactivateDelay` records
+    pub deactivate_delays: u64,
+    /// the number of `TallyDelay` records
+    pub tally_delays: u64,
+    pub delayed_deactivate_messages: Uint256,
+    /// the percentage of the reward the operator lost to delays, 100 after a tally timeout
+    pub miss_rate: Uint256,
+}
+
+/// The registry message a round sends from `Claim`
+#[cw_serde]
+pub enum RegistryExecuteMsg {
+    ReportRoundPerformance { performance: RoundPerformance },
+}
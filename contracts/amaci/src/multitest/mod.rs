@@ -4,16 +4,17 @@ mod tests;
 use anyhow::Result as AnyResult;
 
 use crate::state::{
-    DelayRecords, MaciParameters, MessageData, Period, PubKey, RoundInfo, VotingTime,
+    BatchRecord, DelayRecords, MaciParameters, MessageData, Period, PubKey, Question,
+    QuestionConfig, RoundInfo, SignUpTime, VotingTime,
 };
-use crate::utils::uint256_from_hex_string;
+use maci_core::utils::uint256_from_hex_string;
 use crate::{
-    contract::{execute, instantiate, query},
+    contract::{execute, instantiate, query, reply},
     msg::*,
 };
 
 use cosmwasm_std::testing::{MockApi, MockStorage};
-use cosmwasm_std::{Addr, Empty, StdResult, Timestamp, Uint128, Uint256};
+use cosmwasm_std::{Addr, Coin, Empty, StdResult, Timestamp, Uint128, Uint256};
 use cw_multi_test::App as DefaultApp;
 use cw_multi_test::{
     no_init, AppBuilder, AppResponse, BankKeeper, ContractWrapper, DistributionKeeper, Executor,
@@ -67,16 +68,14 @@ impl MaciCodeId {
     }
 
     pub fn store_default_code(app: &mut DefaultApp) -> Self {
-        let contract =
-            // ContractWrapper::new(execute, instantiate, query).with_reply(reply);
-        ContractWrapper::new(execute, instantiate, query);
+        let contract = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
 
         let code_id = app.store_code(Box::new(contract));
         Self(code_id)
     }
 
     pub fn store_code(app: &mut App) -> Self {
-        let contract = ContractWrapper::new(execute, instantiate, query);
+        let contract = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
         let code_id = app.store_code(Box::new(contract));
         Self(code_id)
     }
@@ -123,6 +122,77 @@ impl MaciCodeId {
         )
     }
 
+    pub fn instantiate_with_questions(
+        self,
+        app: &mut App,
+        sender: Addr,
+        user1: Addr,
+        user2: Addr,
+        extra_questions: Vec<QuestionConfig>,
+        label: &str,
+    ) -> AnyResult<MaciContract> {
+        let round_info = RoundInfo {
+            title: String::from("HackWasm Berlin"),
+            description: String::from("Hack In Brelin"),
+            link: String::from("https://baidu.com"),
+        };
+        let whitelist = Some(WhitelistBase {
+            users: vec![
+                WhitelistBaseConfig { addr: user1 },
+                WhitelistBaseConfig { addr: user2 },
+            ],
+        });
+
+        let start_time = Timestamp::from_nanos(1571797424879000000);
+        let end_time = start_time.plus_minutes(11);
+        let voting_time = VotingTime {
+            start_time,
+            end_time,
+        };
+        MaciContract::instantiate_with_questions(
+            app,
+            self,
+            sender,
+            round_info,
+            whitelist,
+            voting_time,
+            extra_questions,
+            label,
+        )
+    }
+
+    /// A round created by `registry`, paid with `funds`
+    pub fn instantiate_with_registry(
+        self,
+        app: &mut App,
+        sender: Addr,
+        registry: Addr,
+        funds: &[Coin],
+        label: &str,
+    ) -> AnyResult<MaciContract> {
+        let round_info = RoundInfo {
+            title: String::from("HackWasm Berlin"),
+            description: String::from("Hack In Brelin"),
+            link: String::from("https://baidu.com"),
+        };
+        let start_time = Timestamp::from_nanos(1571797424879000000);
+        let end_time = start_time.plus_minutes(11);
+        let voting_time = VotingTime {
+            start_time,
+            end_time,
+        };
+        MaciContract::instantiate_with_registry(
+            app,
+            self,
+            sender,
+            round_info,
+            voting_time,
+            registry,
+            funds,
+            label,
+        )
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn instantiate_with_wrong_voting_time(
         self,
@@ -194,6 +264,49 @@ impl MaciCodeId {
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn instantiate_with_sign_up_time(
+        self,
+        app: &mut App,
+        sender: Addr,
+        user1: Addr,
+        user2: Addr,
+        sign_up_time: SignUpTime,
+        label: &str,
+    ) -> AnyResult<MaciContract> {
+        let round_info = RoundInfo {
+            title: String::from("HackWasm Berlin"),
+            description: String::from("Hack In Brelin"),
+            link: String::from("https://baidu.com"),
+        };
+        let whitelist = Some(WhitelistBase {
+            users: vec![
+                WhitelistBaseConfig { addr: user1 },
+                WhitelistBaseConfig { addr: user2 },
+            ],
+        });
+        let start_time = Timestamp::from_nanos(1571797424879000000).plus_minutes(6);
+        let end_time = start_time.plus_minutes(11);
+        let voting_time = VotingTime {
+            start_time,
+            end_time,
+        };
+        let circuit_type = Uint256::from_u128(0u128);
+        let certification_system = Uint256::from_u128(0u128);
+        MaciContract::instantiate_with_sign_up_time(
+            app,
+            self,
+            sender,
+            round_info,
+            whitelist,
+            voting_time,
+            Some(sign_up_time),
+            circuit_type,
+            certification_system,
+            label,
+        )
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn instantiate_with_voting_time_isqv(
         self,
@@ -308,6 +421,34 @@ impl MaciContract {
         circuit_type: Uint256,
         certification_system: Uint256,
         label: &str,
+    ) -> AnyResult<Self> {
+        Self::instantiate_with_sign_up_time(
+            app,
+            code_id,
+            sender,
+            round_info,
+            whitelist,
+            voting_time,
+            None,
+            circuit_type,
+            certification_system,
+            label,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[track_caller]
+    pub fn instantiate_with_sign_up_time(
+        app: &mut App,
+        code_id: MaciCodeId,
+        sender: Addr,
+        round_info: RoundInfo,
+        whitelist: Option<WhitelistBase>,
+        voting_time: VotingTime,
+        sign_up_time: Option<SignUpTime>,
+        circuit_type: Uint256,
+        certification_system: Uint256,
+        label: &str,
     ) -> AnyResult<Self> {
         let parameters = MaciParameters {
             state_tree_depth: Uint256::from_u128(2u128),
@@ -331,11 +472,69 @@ impl MaciContract {
             round_info,
             whitelist,
             voting_time,
+            sign_up_time,
             circuit_type,
             certification_system,
             operator: operator(),
             admin: owner(),
             fee_recipient: fee_recipient(),
+            extra_questions: None,
+            registry: None,
+        };
+
+        app.instantiate_contract(
+            code_id.0,
+            Addr::unchecked(sender),
+            &init_msg,
+            &[],
+            label,
+            None,
+        )
+        .map(Self::from)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[track_caller]
+    pub fn instantiate_with_questions(
+        app: &mut App,
+        code_id: MaciCodeId,
+        sender: Addr,
+        round_info: RoundInfo,
+        whitelist: Option<WhitelistBase>,
+        voting_time: VotingTime,
+        extra_questions: Vec<QuestionConfig>,
+        label: &str,
+    ) -> AnyResult<Self> {
+        let parameters = MaciParameters {
+            state_tree_depth: Uint256::from_u128(2u128),
+            int_state_tree_depth: Uint256::from_u128(1u128),
+            message_batch_size: Uint256::from_u128(5u128),
+            vote_option_tree_depth: Uint256::from_u128(1u128),
+        };
+        let init_msg = InstantiateMsg {
+            parameters,
+            coordinator: PubKey {
+                x: uint256_from_decimal_string(
+                    "3557592161792765812904087712812111121909518311142005886657252371904276697771",
+                ),
+                y: uint256_from_decimal_string(
+                    "4363822302427519764561660537570341277214758164895027920046745209970137856681",
+                ),
+            },
+            max_vote_options: Uint256::from_u128(5u128),
+            voice_credit_amount: Uint256::from_u128(100u128),
+            pre_deactivate_root: Uint256::from_u128(0u128),
+            round_info,
+            whitelist,
+            voting_time,
+            sign_up_time: None,
+            circuit_type: Uint256::from_u128(0u128),
+            certification_system: Uint256::from_u128(0u128),
+            operator: operator(),
+            admin: owner(),
+            fee_recipient: fee_recipient(),
+            extra_questions: Some(extra_questions),
+            registry: None,
         };
 
         app.instantiate_contract(
@@ -349,6 +548,61 @@ impl MaciContract {
         .map(Self::from)
     }
 
+    #[allow(clippy::too_many_arguments)]
+    #[track_caller]
+    pub fn instantiate_with_registry(
+        app: &mut App,
+        code_id: MaciCodeId,
+        sender: Addr,
+        round_info: RoundInfo,
+        voting_time: VotingTime,
+        registry: Addr,
+        funds: &[Coin],
+        label: &str,
+    ) -> AnyResult<Self> {
+        let parameters = MaciParameters {
+            state_tree_depth: Uint256::from_u128(2u128),
+            int_state_tree_depth: Uint256::from_u128(1u128),
+            message_batch_size: Uint256::from_u128(5u128),
+            vote_option_tree_depth: Uint256::from_u128(1u128),
+        };
+        let init_msg = InstantiateMsg {
+            parameters,
+            coordinator: PubKey {
+                x: uint256_from_decimal_string(
+                    "3557592161792765812904087712812111121909518311142005886657252371904276697771",
+                ),
+                y: uint256_from_decimal_string(
+                    "4363822302427519764561660537570341277214758164895027920046745209970137856681",
+                ),
+            },
+            max_vote_options: Uint256::from_u128(5u128),
+            voice_credit_amount: Uint256::from_u128(100u128),
+            pre_deactivate_root: Uint256::from_u128(0u128),
+            round_info,
+            whitelist: None,
+            voting_time,
+            sign_up_time: None,
+            circuit_type: Uint256::from_u128(0u128),
+            certification_system: Uint256::from_u128(0u128),
+            operator: operator(),
+            admin: owner(),
+            fee_recipient: fee_recipient(),
+            extra_questions: None,
+            registry: Some(registry),
+        };
+
+        app.instantiate_contract(
+            code_id.0,
+            Addr::unchecked(sender),
+            &init_msg,
+            funds,
+            label,
+            None,
+        )
+        .map(Self::from)
+    }
+
     #[allow(clippy::too_many_arguments)]
     #[track_caller]
     pub fn instantiate_decative_and_add_new_key_zkey(
@@ -384,11 +638,14 @@ impl MaciContract {
             round_info,
             whitelist,
             voting_time,
+            sign_up_time: None,
             circuit_type,
             certification_system,
             operator: operator(),
             admin: owner(),
             fee_recipient: fee_recipient(),
+            extra_questions: None,
+            registry: None,
         };
 
         app.instantiate_contract(
@@ -421,6 +678,7 @@ impl MaciContract {
             &ExecuteMsg::PublishMessage {
                 message,
                 enc_pub_key,
+                question_id: None,
             },
             &[],
         )
@@ -488,6 +746,47 @@ impl MaciContract {
                     String::from("no_with_veto"),
                     String::from("abstain"),
                 ],
+                question_id: None,
+            },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn publish_question_message(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        message: MessageData,
+        enc_pub_key: PubKey,
+        question_id: u64,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::PublishMessage {
+                message,
+                enc_pub_key,
+                question_id: Some(question_id),
+            },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn set_question_vote_option_map(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        vote_option_map: Vec<String>,
+        question_id: u64,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::SetVoteOptionsMap {
+                vote_option_map,
+                question_id: Some(question_id),
             },
             &[],
         )
@@ -529,7 +828,7 @@ impl MaciContract {
                 size,
                 new_deactivate_commitment,
                 new_deactivate_root,
-                groth16_proof: proof,
+                groth16_proof: proof.into(),
             },
             &[],
         )
@@ -552,7 +851,7 @@ impl MaciContract {
                 pubkey,
                 nullifier,
                 d,
-                groth16_proof: proof,
+                groth16_proof: proof.into(),
             },
             &[],
         )
@@ -575,7 +874,7 @@ impl MaciContract {
                 pubkey,
                 nullifier,
                 d,
-                groth16_proof: proof,
+                groth16_proof: proof.into(),
             },
             &[],
         )
@@ -599,7 +898,8 @@ impl MaciContract {
             self.addr(),
             &ExecuteMsg::ProcessMessage {
                 new_state_commitment,
-                groth16_proof: proof,
+                groth16_proof: proof.into(),
+                question_id: None,
             },
             &[],
         )
@@ -628,7 +928,8 @@ impl MaciContract {
             self.addr(),
             &ExecuteMsg::ProcessTally {
                 new_tally_commitment,
-                groth16_proof: proof,
+                groth16_proof: proof.into(),
+                question_id: None,
             },
             &[],
         )
@@ -645,7 +946,32 @@ impl MaciContract {
         app.execute_contract(
             sender,
             self.addr(),
-            &ExecuteMsg::StopTallyingPeriod { results, salt },
+            &ExecuteMsg::StopTallyingPeriod {
+                results,
+                salt,
+                question_id: None,
+            },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn stop_tallying_question(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        results: Vec<Uint256>,
+        salt: Uint256,
+        question_id: u64,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::StopTallyingPeriod {
+                results,
+                salt,
+                question_id: Some(question_id),
+            },
             &[],
         )
     }
@@ -690,7 +1016,34 @@ impl MaciContract {
             .query_wasm_smart(self.addr(), &QueryMsg::GetAllResult {})
     }
 
-    pub fn get_voting_time(&self, app: &App) -> StdResult<VotingTime> {
+    pub fn get_question(&self, app: &App, question_id: u64) -> StdResult<Question> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetQuestion { question_id })
+    }
+
+    pub fn get_question_result(
+        &self,
+        app: &App,
+        question_id: u64,
+        index: Uint256,
+    ) -> StdResult<Uint256> {
+        app.wrap().query_wasm_smart(
+            self.addr(),
+            &QueryMsg::GetQuestionResult { question_id, index },
+        )
+    }
+
+    pub fn batch_records(
+        &self,
+        app: &App,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<BatchRecord>> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::BatchRecords { start_after, limit })
+    }
+
+    pub fn get_voting_time(&self, app: &App) -> StdResult<VotingTimeResponse> {
         app.wrap()
             .query_wasm_smart(self.addr(), &QueryMsg::GetVotingTime {})
     }
@@ -754,6 +1107,7 @@ impl MaciContract {
             &ExecuteMsg::PublishMessage {
                 message,
                 enc_pub_key,
+                question_id: None,
             },
             &[],
         )
@@ -837,6 +1191,7 @@ impl MaciContract {
                     String::from("no_with_veto"),
                     String::from("abstain"),
                 ],
+                question_id: None,
             },
             &[],
         )
@@ -878,7 +1233,7 @@ impl MaciContract {
                 size,
                 new_deactivate_commitment,
                 new_deactivate_root,
-                groth16_proof: proof,
+                groth16_proof: proof.into(),
             },
             &[],
         )
@@ -901,7 +1256,7 @@ impl MaciContract {
                 pubkey,
                 nullifier,
                 d,
-                groth16_proof: proof,
+                groth16_proof: proof.into(),
             },
             &[],
         )
@@ -924,7 +1279,7 @@ impl MaciContract {
                 pubkey,
                 nullifier,
                 d,
-                groth16_proof: proof,
+                groth16_proof: proof.into(),
             },
             &[],
         )
@@ -952,7 +1307,8 @@ impl MaciContract {
             self.addr(),
             &ExecuteMsg::ProcessMessage {
                 new_state_commitment,
-                groth16_proof: proof,
+                groth16_proof: proof.into(),
+                question_id: None,
             },
             &[],
         )
@@ -985,7 +1341,8 @@ impl MaciContract {
             self.addr(),
             &ExecuteMsg::ProcessTally {
                 new_tally_commitment,
-                groth16_proof: proof,
+                groth16_proof: proof.into(),
+                question_id: None,
             },
             &[],
         )
@@ -1002,7 +1359,11 @@ impl MaciContract {
         app.execute_contract(
             sender,
             self.addr(),
-            &ExecuteMsg::StopTallyingPeriod { results, salt },
+            &ExecuteMsg::StopTallyingPeriod {
+                results,
+                salt,
+                question_id: None,
+            },
             &[],
         )
     }
@@ -1047,7 +1408,7 @@ impl MaciContract {
             .query_wasm_smart(self.addr(), &QueryMsg::GetAllResult {})
     }
 
-    pub fn amaci_get_voting_time(&self, app: &DefaultApp) -> StdResult<VotingTime> {
+    pub fn amaci_get_voting_time(&self, app: &DefaultApp) -> StdResult<VotingTimeResponse> {
         app.wrap()
             .query_wasm_smart(self.addr(), &QueryMsg::GetVotingTime {})
     }
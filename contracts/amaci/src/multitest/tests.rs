@@ -1,15 +1,36 @@
 #[cfg(test)]
 mod test {
+    use crate::audit::{
+        audit, AddNewKeyCall, AuditError, DeactivateBatch, ProcessBatch, PublishedMessage,
+        RoundDump, TallyBatch,
+    };
+    use crate::contract::{hash_message_and_enc_pub_key, migrate, query};
     use crate::error::ContractError;
-    use crate::msg::Groth16ProofType;
+    use crate::msg::{ExecuteMsg, Groth16ProofInput, Groth16ProofType, MigrateMsg, QueryMsg};
     use crate::multitest::{
-        create_app, owner, uint256_from_decimal_string, user1, user2, user3, MaciCodeId,
+        create_app, owner, uint256_from_decimal_string, user1, user2, user3, App, MaciCodeId,
     };
     use crate::state::{
-        DelayRecord, DelayRecords, DelayType, MessageData, Period, PeriodStatus, PubKey,
+        BatchStep, DelayRecord, DelayRecords, DelayType, Groth16PreparedVkeyStr, Groth16ProofStr,
+        Groth16VkeyStr, MaciParameters, MessageData, Period, PeriodStatus, PubKey, QuestionConfig,
+        SignUpTime, CURRENT_DEACTIVATE_COMMITMENT, DMSG_HASHES, GROTH16_PROCESS_PREPARED_VKEYS,
+        GROTH16_PROCESS_VKEYS, MACIPARAMETERS, MAX_VOTE_OPTIONS, MSG_HASHES, NUMSIGNUPS,
+        QUESTION_COUNT, RESULT, STATE_TREE, TOTAL_RESULT, VOICECREDITBALANCE, ZEROS_H10,
+    };
+    use bellman_ce_verifier::{prepare_verifying_key, verify_proof};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cosmwasm_std::{
+        attr, coins, from_json, to_json_vec, Addr, BlockInfo, Storage, Timestamp, Uint128, Uint256,
     };
-    use cosmwasm_std::{Addr, BlockInfo, Timestamp, Uint256};
-    use cw_multi_test::next_block;
+    use cw_multi_test::{next_block, Executor};
+    use cw_storage_plus::Map;
+    use ff_ce::PrimeField;
+    use maci_client::{build_message, message_hash, Command, Keypair};
+    use maci_core::groth16_parser::{parse_groth16_proof, parse_groth16_vkey};
+    use maci_core::groth16_verifier::{prepare_groth16_vkey, verify_groth16_prepared};
+    use maci_core::quinary_tree::zero_hashes;
+    use maci_core::utils::hash5;
+    use pairing_ce::bn256::{Bn256, Fr};
     use serde::{Deserialize, Serialize};
     use serde_json;
     use std::fs;
@@ -370,9 +391,16 @@ mod test {
             "process_message new state commitment {:?}",
             new_state_commitment
         );
+        let prepared_vkey = |app: &App| {
+            app.wrap()
+                .query_wasm_raw(contract.addr(), GROTH16_PROCESS_PREPARED_VKEYS.as_slice())
+                .unwrap()
+        };
+        assert_eq!(prepared_vkey(&app), None);
         _ = contract
             .process_message(&mut app, owner(), new_state_commitment, proof)
             .unwrap();
+        assert!(prepared_vkey(&app).is_some());
 
         _ = contract.stop_processing(&mut app, owner());
         println!(
@@ -1107,6 +1135,213 @@ mod test {
         }
     }
 
+    #[test]
+    fn batch_records_cover_deactivate_process_and_tally() {
+        let mut logs_content = String::new();
+        fs::File::open("./src/test/amaci_test/logs.json")
+            .expect("Failed to open file")
+            .read_to_string(&mut logs_content)
+            .expect("Failed to read file");
+        let logs_data: Vec<AMaciLogEntry> =
+            serde_json::from_str(&logs_content).expect("Failed to parse JSON");
+        let mut pubkey_content = String::new();
+        fs::File::open("./src/test/user_pubkey.json")
+            .expect("Failed to open file")
+            .read_to_string(&mut pubkey_content)
+            .expect("Failed to read file");
+        let pubkey_data: UserPubkeyData =
+            serde_json::from_str(&pubkey_content).expect("Failed to parse JSON");
+        let decimals = |values: &[String]| -> Vec<Uint256> {
+            values
+                .iter()
+                .map(|value| uint256_from_decimal_string(value))
+                .collect()
+        };
+
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let contract = code_id
+            .instantiate_with_voting_time_isqv_amaci(
+                &mut app,
+                owner(),
+                user1(),
+                user2(),
+                user3(),
+                "Group",
+            )
+            .unwrap();
+        app.update_block(next_block); // Start Voting
+        for (i, pubkey) in pubkey_data.pubkeys.iter().take(2).enumerate() {
+            let pubkey = decimals(pubkey);
+            contract
+                .sign_up(
+                    &mut app,
+                    Addr::unchecked(i.to_string()),
+                    PubKey {
+                        x: pubkey[0],
+                        y: pubkey[1],
+                    },
+                )
+                .unwrap();
+        }
+        let initial_deactivate_commitment = CURRENT_DEACTIVATE_COMMITMENT
+            .query(&app.wrap(), contract.addr())
+            .unwrap();
+
+        let mut deactivate_commitment = Uint256::zero();
+        let mut initial_state_commitment = Uint256::zero();
+        let mut state_commitment = Uint256::zero();
+        let mut tally_commitment = Uint256::zero();
+        for entry in &logs_data {
+            match entry.log_type.as_str() {
+                "publishDeactivateMessage" | "publishMessage" => {
+                    let data: PublishMessageData = deserialize_data(&entry.data);
+                    let message = MessageData {
+                        data: decimals(&data.message).try_into().unwrap(),
+                    };
+                    let enc_pub_key = decimals(&data.enc_pub_key);
+                    let enc_pub_key = PubKey {
+                        x: enc_pub_key[0],
+                        y: enc_pub_key[1],
+                    };
+                    if entry.log_type == "publishMessage" {
+                        contract
+                            .publish_message(&mut app, user2(), message, enc_pub_key)
+                            .unwrap();
+                    } else {
+                        contract
+                            .publish_deactivate_message(&mut app, user2(), message, enc_pub_key)
+                            .unwrap();
+                    }
+                }
+                "proofDeactivate" => {
+                    let data: ProofDeactivateData = deserialize_data(&entry.data);
+                    deactivate_commitment =
+                        uint256_from_decimal_string(&data.new_deactivate_commitment);
+                    let proof = Groth16ProofType {
+                        a: "132a36c4e9653de9ebe2f131e3452319fc4b0f19339083ce52c6dbd5d1d583190f79d3cf25dbf173a959631330f358a334f3977ae2fcfe2e93fb5c5e86dc6ef4".to_string(),
+                        b: "17c61aea44885cf09a35b41fed13916e8a712cfdc2da041a0c29578d102c559f1bd5a1ae12404f47f8fe3f9cba289f9f9fcdf6e60fb64fe17335a65f00f82eda2a5f55a8181bc191a242a60cb27d7c303059895065219d7e436d95e1dbedec182ffa368e7e99494c75e230452fee2a6b2136444b91bf7cfe7581fea055805dbd".to_string(),
+                        c: "138d241e6ca289a65ac398af0c1b68b455184a3735e68dd0d5966d8c5ed9629415cab9376a35f9e33a1be5957e8b696e4a3b43363c8df9a460ff70831b63f69b".to_string()
+                    };
+                    contract
+                        .process_deactivate_message(
+                            &mut app,
+                            owner(),
+                            uint256_from_decimal_string(&data.size),
+                            deactivate_commitment,
+                            uint256_from_decimal_string(&data.new_deactivate_root),
+                            proof,
+                        )
+                        .unwrap();
+                }
+                "proofAddNewKey" => {
+                    let data: ProofAddNewKeyData = deserialize_data(&entry.data);
+                    let pub_key = decimals(&data.pub_key);
+                    let proof = Groth16ProofType {
+                        a: "053eb9bf62de01898e5d7049bfeaee4611b78b54f516ff4b0fd93ffcdc491d8b170e2c3de370f8eeec93ebb57e49279adc68fb137f4aafe1b4206d7186592673".to_string(),
+                        b: "2746ba15cb4478a1a90bd512844cd0e57070357ff17ad90964b699f962f4f24817ce4dcc89d350df5d63ae7f05f0069272c3d352cb92237e682222e68d52da0f00551f58de3a3cac33d6af2fb052e4ff4d42008b5f33b310756a5e7017919087284dc00b9753a3891872ee599467348976ec2d72703d46949a9b8093a97718eb".to_string(),
+                        c: "1832b7d8607c041bd1437f43fe1d207ad64bea58f346cc91d0c72d9c02bbc4031decf433ecafc3874f4bcedbfae591caaf87834ad6867c7d342b96b6299ddd0a".to_string()
+                    };
+                    contract
+                        .pre_add_key(
+                            &mut app,
+                            owner(),
+                            PubKey {
+                                x: pub_key[0],
+                                y: pub_key[1],
+                            },
+                            uint256_from_decimal_string(&data.nullifier),
+                            decimals(&data.d).try_into().unwrap(),
+                            proof,
+                        )
+                        .unwrap();
+                }
+                "processMessage" => {
+                    let data: ProcessMessageData = deserialize_data(&entry.data);
+                    app.update_block(next_block);
+                    app.update_block(next_block);
+                    contract.start_process(&mut app, owner()).unwrap();
+                    initial_state_commitment =
+                        contract.get_question(&app, 0).unwrap().state_commitment;
+                    state_commitment = uint256_from_decimal_string(&data.new_state_commitment);
+                    let proof = Groth16ProofType {
+                        a: "11c744b43710eb925e5b81217de65d15a2388882c6fb82f85c8addb1367c69b02dec496b413ae73174333bf29117513239d1cc583c00f24a6c93d1082834b477".to_string(),
+                        b: "2cbb0e1085abef8077ccbbdea230c99064f9a8e9f9385932ee74ebe58964781a1dabe6292b711ec05d40afb380f64564a77b24185333d7f4ed7065e37fc9479e01d9949cdb9e682c6574951070711eae504d12ab10e1d20f733882edd65c2c4a18737f0837fccda1a5d6c08828cc62060cb9f650fd4598baf548921bf93e2632".to_string(),
+                        c: "2c4e66dd6e47abc6aa343d3eae4f2cf7360147ec28f402829e8fbc6db079741011fe98e27342b42f5cf9a4dfc8a31b2e1d42dc5630cf11e97b92536da978b0c7".to_string()
+                    };
+                    contract
+                        .process_message(&mut app, owner(), state_commitment, proof)
+                        .unwrap();
+                }
+                "processTally" => {
+                    let data: ProcessTallyData = deserialize_data(&entry.data);
+                    contract.stop_processing(&mut app, owner()).unwrap();
+                    tally_commitment = uint256_from_decimal_string(&data.new_tally_commitment);
+                    let proof = Groth16ProofType {
+                        a: "24eefd06494531734508ae412053ed5688072c5fb4cf71fc3c8ec0d31f7d563f093e8b9a311e0caa1ba02de27e75c143f958248b5f486190edd8817f636f0ce8".to_string(),
+                        b: "1fc5e9cdc59c37c88c2a148ac2418659d6eea3448698b57d35c78c7c08b4c52921aa37dca6de3851abe0843338440de8024a6ece04d284e8abf2061a70be713f295339ddce483a56315c3feec141938028a544e62e38bb5bf050dd19146d9ab72b32fe75e87e6bd44ce0476177ebf796fc7eba01bcbf175ccbbd10e2f04a90f0".to_string(),
+                        c: "0ba9c3647f448b9ba9fcf39900c380dff4c9f0328529795f4013912b25a45b9f18f6ca48d63751f67800108105b7b34f88ddda72234ff7eda5c63de7bb90da48".to_string()
+                    };
+                    contract
+                        .process_tally(&mut app, owner(), tally_commitment, proof)
+                        .unwrap();
+                }
+                _ => {}
+            }
+        }
+
+        let records = contract.batch_records(&app, None, None).unwrap();
+        assert_eq!(
+            records.iter().map(|record| record.id).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+
+        // The deactivate batch is shared by every question
+        assert_eq!(records[0].step, BatchStep::ProcessDeactivateMessage);
+        assert_eq!(records[0].question_id, None);
+        assert_eq!(records[0].start_index, Uint256::zero());
+        assert_eq!(records[0].end_index, Uint256::one());
+        assert_eq!(records[0].old_commitment, initial_deactivate_commitment);
+        assert_eq!(records[0].new_commitment, deactivate_commitment);
+        assert_eq!(records[0].submitter, owner());
+
+        assert_eq!(records[1].step, BatchStep::ProcessMessage);
+        assert_eq!(records[1].question_id, Some(0));
+        assert_eq!(records[1].start_index, Uint256::zero());
+        assert_eq!(records[1].end_index, contract.msg_length(&app).unwrap());
+        assert_eq!(records[1].old_commitment, initial_state_commitment);
+        assert_eq!(records[1].new_commitment, state_commitment);
+        assert_eq!(records[1].certification_system, "groth16");
+
+        assert_eq!(records[2].step, BatchStep::ProcessTally);
+        assert_eq!(records[2].question_id, Some(0));
+        assert_eq!(records[2].start_index, Uint256::zero());
+        assert_eq!(records[2].end_index, contract.num_sign_up(&app).unwrap());
+        assert_eq!(records[2].old_commitment, Uint256::zero());
+        assert_eq!(records[2].new_commitment, tally_commitment);
+
+        let question = contract.get_question(&app, 0).unwrap();
+        assert_eq!(question.state_commitment, records[1].new_commitment);
+        assert_eq!(question.tally_commitment, records[2].new_commitment);
+
+        assert_eq!(
+            contract.batch_records(&app, None, Some(2)).unwrap(),
+            records[..2].to_vec()
+        );
+        assert_eq!(
+            contract.batch_records(&app, Some(0), Some(1)).unwrap(),
+            vec![records[1].clone()]
+        );
+        assert_eq!(
+            contract.batch_records(&app, Some(1), None).unwrap(),
+            vec![records[2].clone()]
+        );
+        assert!(contract
+            .batch_records(&app, Some(2), None)
+            .unwrap()
+            .is_empty());
+    }
+
     // #[test]
     fn instantiate_with_voting_time_qv_amaci_should_works() {
         let msg_file_path = "./src/test/qv_test/msg.json";
@@ -1782,4 +2017,956 @@ mod test {
             }
         );
     }
+
+    #[test]
+    fn sign_up_window_closes_before_voting_opens() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let label = "Group";
+        let sign_up_start = Timestamp::from_nanos(1571797424879000000);
+        let sign_up_time = SignUpTime {
+            start_time: sign_up_start,
+            end_time: sign_up_start.plus_minutes(5),
+        };
+        let contract = code_id
+            .instantiate_with_sign_up_time(
+                &mut app,
+                owner(),
+                user1(),
+                user2(),
+                sign_up_time.clone(),
+                label,
+            )
+            .unwrap();
+
+        let voting_time = contract.get_voting_time(&app).unwrap();
+        assert_eq!(voting_time.start_time, sign_up_start.plus_minutes(6));
+        assert_eq!(voting_time.sign_up_time, Some(sign_up_time));
+
+        let pubkey = PubKey {
+            x: uint256_from_decimal_string(
+                "3557592161792765812904087712812111121909518311142005886657252371904276697771",
+            ),
+            y: uint256_from_decimal_string(
+                "4363822302427519764561660537570341277214758164895027920046745209970137856681",
+            ),
+        };
+
+        // Before the sign up window opens.
+        let err = contract
+            .sign_up(&mut app, user1(), pubkey.clone())
+            .unwrap_err();
+        assert_eq!(ContractError::PeriodError {}, err.downcast().unwrap());
+
+        app.update_block(next_block);
+        contract.sign_up(&mut app, user1(), pubkey.clone()).unwrap();
+        assert_eq!(
+            contract.num_sign_up(&app).unwrap(),
+            Uint256::from_u128(1u128)
+        );
+
+        // Voting has not started yet even though sign up is open.
+        let err = contract
+            .publish_message(
+                &mut app,
+                user1(),
+                MessageData {
+                    data: [Uint256::from_u128(1u128); 7],
+                },
+                pubkey.clone(),
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::PeriodError {}, err.downcast().unwrap());
+
+        // Inside the voting window, registration is already closed.
+        app.update_block(next_block_11_min);
+        let err = contract.sign_up(&mut app, user2(), pubkey).unwrap_err();
+        assert_eq!(ContractError::PeriodError {}, err.downcast().unwrap());
+    }
+
+    #[test]
+    fn instantiate_with_sign_up_after_voting_end_error() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let label = "Group";
+        let sign_up_start = Timestamp::from_nanos(1571797424879000000);
+        let sign_up_time = SignUpTime {
+            start_time: sign_up_start,
+            end_time: sign_up_start.plus_minutes(30),
+        };
+        let err = code_id
+            .instantiate_with_sign_up_time(&mut app, owner(), user1(), user2(), sign_up_time, label)
+            .unwrap_err();
+
+        assert_eq!(
+            ContractError::WrongSignUpTimeSet {},
+            err.downcast().unwrap()
+        );
+    }
+
+    #[test]
+    fn questions_keep_separate_message_chains() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let label = "Group";
+        let contract = code_id
+            .instantiate_with_questions(
+                &mut app,
+                owner(),
+                user1(),
+                user2(),
+                vec![QuestionConfig {
+                    max_vote_options: Uint256::from_u128(3u128),
+                }],
+                label,
+            )
+            .unwrap();
+
+        let vote_option_map = vec![
+            String::from("yes"),
+            String::from("no"),
+            String::from("abstain"),
+        ];
+        contract
+            .set_question_vote_option_map(&mut app, owner(), vote_option_map.clone(), 1)
+            .unwrap();
+        let err = contract
+            .set_question_vote_option_map(&mut app, owner(), vote_option_map.clone(), 2)
+            .unwrap_err();
+        assert_eq!(
+            ContractError::QuestionNotFound { question_id: 2 },
+            err.downcast().unwrap()
+        );
+
+        let question = contract.get_question(&app, 1).unwrap();
+        assert_eq!(question.vote_option_map, vote_option_map);
+        assert_eq!(question.max_vote_options, Uint256::from_u128(3u128));
+        assert_eq!(
+            contract.max_vote_options(&app).unwrap(),
+            Uint256::from_u128(5u128)
+        );
+
+        app.update_block(next_block);
+        let pubkey = PubKey {
+            x: uint256_from_decimal_string(
+                "3557592161792765812904087712812111121909518311142005886657252371904276697771",
+            ),
+            y: uint256_from_decimal_string(
+                "4363822302427519764561660537570341277214758164895027920046745209970137856681",
+            ),
+        };
+        let message = MessageData {
+            data: [Uint256::from_u128(1u128); 7],
+        };
+        contract.sign_up(&mut app, user1(), pubkey.clone()).unwrap();
+
+        contract
+            .publish_message(&mut app, user1(), message.clone(), pubkey.clone())
+            .unwrap();
+        // the same ciphertext may only be published for one question
+        let question_message = MessageData {
+            data: [Uint256::from_u128(2u128); 7],
+        };
+        for _ in 0..2 {
+            contract
+                .publish_question_message(
+                    &mut app,
+                    user1(),
+                    question_message.clone(),
+                    pubkey.clone(),
+                    1,
+                )
+                .unwrap();
+        }
+        let err = contract
+            .publish_question_message(&mut app, user1(), message, pubkey, 2)
+            .unwrap_err();
+        assert_eq!(
+            ContractError::QuestionNotFound { question_id: 2 },
+            err.downcast().unwrap()
+        );
+
+        assert_eq!(
+            contract.msg_length(&app).unwrap(),
+            Uint256::from_u128(1u128)
+        );
+        assert_eq!(
+            contract.get_question(&app, 1).unwrap().msg_chain_length,
+            Uint256::from_u128(2u128)
+        );
+    }
+
+    #[test]
+    fn messages_cannot_be_replayed_into_another_question() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let contract = code_id
+            .instantiate_with_questions(
+                &mut app,
+                owner(),
+                user1(),
+                user2(),
+                vec![QuestionConfig {
+                    max_vote_options: Uint256::from_u128(5u128),
+                }],
+                "Group",
+            )
+            .unwrap();
+        app.update_block(next_block);
+
+        let coordinator = PubKey {
+            x: uint256_from_decimal_string(
+                "3557592161792765812904087712812111121909518311142005886657252371904276697771",
+            ),
+            y: uint256_from_decimal_string(
+                "4363822302427519764561660537570341277214758164895027920046745209970137856681",
+            ),
+        };
+        let voter = Keypair::new(Uint256::from_u128(123456789u128)).unwrap();
+        contract
+            .sign_up(&mut app, user1(), voter.pub_key.clone())
+            .unwrap();
+
+        // A vote for question 1, copied from the chain by someone else into question 0
+        let command = Command::vote(0, 2, 4, 1, voter.pub_key.clone());
+        let vote = build_message(&voter, &coordinator, &command).unwrap();
+        contract
+            .publish_question_message(
+                &mut app,
+                user1(),
+                vote.message.clone(),
+                vote.enc_pub_key.clone(),
+                1,
+            )
+            .unwrap();
+        let err = contract
+            .publish_message(&mut app, user2(), vote.message, vote.enc_pub_key)
+            .unwrap_err();
+        assert_eq!(
+            ContractError::MessageReplayed { question_id: 1 },
+            err.downcast().unwrap()
+        );
+        assert_eq!(contract.msg_length(&app).unwrap(), Uint256::zero());
+
+        // The voter votes on question 0 with a message of its own
+        let vote = build_message(&voter, &coordinator, &command).unwrap();
+        contract
+            .publish_message(&mut app, user1(), vote.message, vote.enc_pub_key)
+            .unwrap();
+        assert_eq!(contract.msg_length(&app).unwrap(), Uint256::one());
+    }
+
+    #[test]
+    fn round_ends_after_every_question_is_tallied() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let label = "Group";
+        let contract = code_id
+            .instantiate_with_questions(
+                &mut app,
+                owner(),
+                user1(),
+                user2(),
+                vec![QuestionConfig {
+                    max_vote_options: Uint256::from_u128(3u128),
+                }],
+                label,
+            )
+            .unwrap();
+
+        app.update_block(next_block_11_min);
+        app.update_block(next_block);
+        contract.start_process(&mut app, owner()).unwrap();
+        contract.stop_processing(&mut app, owner()).unwrap();
+
+        contract
+            .stop_tallying(
+                &mut app,
+                owner(),
+                vec![Uint256::from_u128(4u128), Uint256::from_u128(1u128)],
+                Uint256::from_u128(0u128),
+            )
+            .unwrap();
+        assert_eq!(
+            contract.get_period(&app).unwrap(),
+            Period {
+                status: PeriodStatus::Tallying
+            }
+        );
+
+        let err = contract
+            .stop_tallying(&mut app, owner(), vec![], Uint256::from_u128(0u128))
+            .unwrap_err();
+        assert_eq!(
+            ContractError::QuestionAlreadyTallied { question_id: 0 },
+            err.downcast().unwrap()
+        );
+
+        contract
+            .stop_tallying_question(
+                &mut app,
+                owner(),
+                vec![Uint256::from_u128(0u128), Uint256::from_u128(7u128)],
+                Uint256::from_u128(0u128),
+                1,
+            )
+            .unwrap();
+        assert_eq!(
+            contract.get_period(&app).unwrap(),
+            Period {
+                status: PeriodStatus::Ended
+            }
+        );
+
+        assert_eq!(
+            contract.get_all_result(&app).unwrap(),
+            Uint256::from_u128(5u128)
+        );
+        assert_eq!(
+            contract.get_question(&app, 1).unwrap().total_result,
+            Some(Uint256::from_u128(7u128))
+        );
+        assert_eq!(
+            contract
+                .get_question_result(&app, 0, Uint256::from_u128(1u128))
+                .unwrap(),
+            Uint256::from_u128(1u128)
+        );
+        assert_eq!(
+            contract
+                .get_question_result(&app, 1, Uint256::from_u128(1u128))
+                .unwrap(),
+            Uint256::from_u128(7u128)
+        );
+    }
+
+    #[test]
+    fn migrate_backfills_question_count() {
+        let mut deps = mock_dependencies();
+        cw2::set_contract_version(deps.as_mut().storage, "crates.io:cw-amaci", "0.1.0").unwrap();
+
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert!(res.attributes.contains(&attr("from_version", "0.1.0")));
+        assert_eq!(QUESTION_COUNT.load(deps.as_ref().storage).unwrap(), 1u64);
+        assert_eq!(
+            cw2::get_contract_version(deps.as_ref().storage)
+                .unwrap()
+                .version,
+            env!("CARGO_PKG_VERSION")
+        );
+
+        // A round can not be migrated back to an older code version
+        cw2::set_contract_version(deps.as_mut().storage, "crates.io:cw-amaci", "9.9.9").unwrap();
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+    }
+
+    #[test]
+    fn result_and_voice_credit_balance_are_independent() {
+        let mut deps = mock_dependencies();
+        let index = Uint256::from_u128(0u128);
+        VOICECREDITBALANCE
+            .save(
+                deps.as_mut().storage,
+                index.to_be_bytes().to_vec(),
+                &Uint256::from_u128(100u128),
+            )
+            .unwrap();
+        RESULT
+            .save(
+                deps.as_mut().storage,
+                index.to_be_bytes().to_vec(),
+                &Uint256::from_u128(7u128),
+            )
+            .unwrap();
+
+        let result: Uint256 =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::GetResult { index }).unwrap())
+                .unwrap();
+        let balance: Uint256 = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetVoiceCreditBalance { index },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(result, Uint256::from_u128(7u128));
+        assert_eq!(balance, Uint256::from_u128(100u128));
+    }
+
+    #[test]
+    fn migrate_relocates_results() {
+        let mut deps = mock_dependencies();
+        cw2::set_contract_version(deps.as_mut().storage, "crates.io:cw-amaci", "0.1.1").unwrap();
+        // Tallied results [0, 15, 4, 0, 0] written over the shared namespace of older rounds
+        let results = [0u128, 15, 4, 0, 0].map(Uint256::from_u128);
+        for (i, result) in results.iter().enumerate() {
+            VOICECREDITBALANCE
+                .save(
+                    deps.as_mut().storage,
+                    Uint256::from_u128(i as u128).to_be_bytes().to_vec(),
+                    result,
+                )
+                .unwrap();
+        }
+        TOTAL_RESULT
+            .save(deps.as_mut().storage, &Uint256::from_u128(19u128))
+            .unwrap();
+        MAX_VOTE_OPTIONS
+            .save(deps.as_mut().storage, &Uint256::from_u128(5u128))
+            .unwrap();
+
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert!(res.attributes.contains(&attr("relocated_results", "5")));
+
+        for (i, result) in results.iter().enumerate() {
+            let key = Uint256::from_u128(i as u128).to_be_bytes().to_vec();
+            assert_eq!(RESULT.load(deps.as_ref().storage, key).unwrap(), *result);
+        }
+        // Voice credit balances were never stored, so nothing is left in the old namespace
+        for i in 0..results.len() {
+            let key = Uint256::from_u128(i as u128).to_be_bytes().to_vec();
+            assert!(!VOICECREDITBALANCE.has(deps.as_ref().storage, key));
+        }
+    }
+
+    const LEGACY_NODES: Map<Vec<u8>, Uint256> = Map::new("nodes");
+
+    // Sign up before v0.1.3: store the leaf and rewrite every node on its path to the root
+    fn legacy_enqueue(
+        storage: &mut dyn Storage,
+        depth: u32,
+        zeros: &[Uint256],
+        index: u128,
+        leaf: Uint256,
+    ) {
+        let mut idx = (5u128.pow(depth) - 1) / 4 + index;
+        let key = |idx: u128| Uint256::from_u128(idx).to_be_bytes().to_vec();
+        LEGACY_NODES.save(storage, key(idx), &leaf).unwrap();
+
+        let mut height = 0;
+        while idx > 0 {
+            let parent = (idx - 1) / 5;
+            let mut inputs = [zeros[height]; 5];
+            for (i, input) in inputs.iter_mut().enumerate() {
+                if let Some(node) = LEGACY_NODES
+                    .may_load(storage, key(parent * 5 + 1 + i as u128))
+                    .unwrap()
+                {
+                    *input = node;
+                }
+            }
+            LEGACY_NODES
+                .save(storage, key(parent), &hash5(inputs))
+                .unwrap();
+            height += 1;
+            idx = parent;
+        }
+    }
+
+    fn legacy_root(storage: &dyn Storage) -> Uint256 {
+        LEGACY_NODES
+            .load(storage, Uint256::zero().to_be_bytes().to_vec())
+            .unwrap()
+    }
+
+    #[test]
+    fn migrate_rebuilds_the_state_tree_from_legacy_nodes() {
+        let mut deps = mock_dependencies();
+        cw2::set_contract_version(deps.as_mut().storage, "crates.io:cw-amaci", "0.1.2").unwrap();
+        let depth = 2u32;
+        let zeros: [Uint256; 7] = zero_hashes(Uint256::from_u128(5u128), 6)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        MACIPARAMETERS
+            .save(
+                deps.as_mut().storage,
+                &MaciParameters {
+                    state_tree_depth: Uint256::from_u128(2u128),
+                    int_state_tree_depth: Uint256::from_u128(1u128),
+                    message_batch_size: Uint256::from_u128(5u128),
+                    vote_option_tree_depth: Uint256::from_u128(1u128),
+                },
+            )
+            .unwrap();
+        ZEROS_H10.save(deps.as_mut().storage, &zeros).unwrap();
+
+        let leaves: Vec<Uint256> = (1..=25u128).map(Uint256::from_u128).collect();
+        for (i, leaf) in leaves.iter().enumerate().take(13) {
+            legacy_enqueue(deps.as_mut().storage, depth, &zeros, i as u128, *leaf);
+        }
+        NUMSIGNUPS
+            .save(deps.as_mut().storage, &Uint256::from_u128(13u128))
+            .unwrap();
+
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert!(res.attributes.contains(&attr("state_tree_leaves", "13")));
+
+        let mut tree = STATE_TREE.load(deps.as_ref().storage).unwrap();
+        assert_eq!(tree.root(), Ok(legacy_root(deps.as_ref().storage)));
+
+        // Sign ups after the upgrade keep the root the old path updates produced
+        for (i, leaf) in leaves.iter().enumerate().skip(13) {
+            legacy_enqueue(deps.as_mut().storage, depth, &zeros, i as u128, *leaf);
+            assert_eq!(tree.push(*leaf), Ok(i as u64));
+            assert_eq!(tree.root(), Ok(legacy_root(deps.as_ref().storage)));
+        }
+    }
+
+    #[test]
+    fn sign_up_and_publish_emit_structured_events() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let contract = code_id
+            .instantiate_with_voting_time(&mut app, owner(), user1(), user2(), "Events")
+            .unwrap();
+        app.update_block(next_block);
+
+        let pubkey = PubKey {
+            x: uint256_from_decimal_string(
+                "3557592161792765812904087712812111121909518311142005886657252371904276697771",
+            ),
+            y: uint256_from_decimal_string(
+                "4363822302427519764561660537570341277214758164895027920046745209970137856681",
+            ),
+        };
+        let res = contract.sign_up(&mut app, user1(), pubkey.clone()).unwrap();
+        let sign_up = res
+            .events
+            .iter()
+            .find(|e| e.ty == format!("wasm-{}", maci_events::SIGN_UP_EVENT))
+            .expect("sign up event");
+        assert!(sign_up
+            .attributes
+            .contains(&attr("schema_version", maci_events::SCHEMA_VERSION)));
+        assert!(sign_up.attributes.contains(&attr("state_idx", "0")));
+        assert!(sign_up
+            .attributes
+            .contains(&attr("pubkey_x", pubkey.x.to_string())));
+
+        let message = MessageData {
+            data: [Uint256::from_u128(1u128); 7],
+        };
+        let res = contract
+            .publish_message(&mut app, user1(), message, pubkey)
+            .unwrap();
+        let publish = res
+            .events
+            .iter()
+            .find(|e| e.ty == format!("wasm-{}", maci_events::PUBLISH_MESSAGE_EVENT))
+            .expect("publish message event");
+        let encoded = &publish
+            .attributes
+            .iter()
+            .find(|a| a.key == "message")
+            .unwrap()
+            .value;
+        let message: Vec<String> = serde_json::from_str(encoded).unwrap();
+        assert_eq!(message, vec!["1"; 7]);
+        assert!(publish.attributes.contains(&attr("question_id", "0")));
+    }
+
+    // Run with `cargo test -p cw-amaci bench_ -- --ignored --nocapture`. Multitest does not meter
+    // the pairing e(alpha, beta) the prepared key saves, so this reports the gas the SDK charges
+    // one verification to read its key from the round's store, at the default KV costs.
+    #[test]
+    #[ignore]
+    fn bench_groth16_verification_with_prepared_vkey() {
+        const READ_COST_FLAT: u64 = 1000;
+        const READ_COST_PER_BYTE: u64 = 3;
+        let read_gas = |key: &[u8], value: &[u8]| {
+            READ_COST_FLAT + READ_COST_PER_BYTE * (key.len() + value.len()) as u64
+        };
+
+        let mut app = create_app();
+        let contract = MaciCodeId::store_code(&mut app)
+            .instantiate_with_voting_time(&mut app, owner(), user1(), user2(), "Group")
+            .unwrap();
+        let stored_vkey = app
+            .wrap()
+            .query_wasm_raw(contract.addr(), GROTH16_PROCESS_VKEYS.as_slice())
+            .unwrap()
+            .unwrap();
+        let vkey: Groth16VkeyStr = from_json(&stored_vkey).unwrap();
+        let proof = parse_groth16_proof::<Bn256>(Groth16ProofStr {
+            pi_a: hex::decode("27fb48285bc59bc74c9197857856cf5f3dcce55f22b83589e399240b8469e45725c5495e3ebcdd3bc04620fd13fed113c31d19a685f7f037daf02dde02d26e4f").unwrap(),
+            pi_b: hex::decode("0d1bd72809defb6e85ea48de4c28e9ec9dcd2bc5111acdb66b5cdb38ccf6d4e32bdeac48a806c2fd6cef8e09bfde1983961693c8d4a513777ba26b07f2abacba1efb7600f04e786d93f321c6df732eb0043548cfe12fa8a5aea848a500ef5b9728dbc747fc76993c16dadf2c8ef68f3d757afa6d4caf9a767c424ec0d7ff4932").unwrap(),
+            pi_c: hex::decode("2062c6bee5dad15af1ebcb0e623b27f7d29775774cc92b2a7554d1801af818940309fa215204181d3a1fef15d162aa779b8900e2b84d8b8fa22a20b65652eb46").unwrap(),
+        })
+        .unwrap();
+        // the pairings are the same whether or not the input matches the proof
+        let input = [Fr::from_str("1").unwrap()];
+
+        // before: every verification reads the key and computes e(alpha, beta)
+        let pvk = prepare_verifying_key(&parse_groth16_vkey::<Bn256>(vkey.clone()).unwrap());
+        let expected = verify_proof(&pvk, &proof, &input).unwrap();
+        let uncached = read_gas(GROTH16_PROCESS_VKEYS.as_slice(), &stored_vkey);
+
+        // after: the prepared key, as the contract stores it on its first use, is read instead
+        let stored_pvk = to_json_vec(&prepare_groth16_vkey(
+            &parse_groth16_vkey::<Bn256>(vkey).unwrap(),
+        ))
+        .unwrap();
+        let pvk: Groth16PreparedVkeyStr = from_json(&stored_pvk).unwrap();
+        assert_eq!(
+            verify_groth16_prepared(&pvk, &proof, &input).unwrap(),
+            expected
+        );
+        let cached = read_gas(GROTH16_PROCESS_PREPARED_VKEYS.as_slice(), &stored_pvk);
+
+        println!(
+            "groth16 verification: {} store gas without the prepared key, {} with it",
+            uncached, cached
+        );
+    }
+
+    #[test]
+    fn process_message_accepts_snarkjs_proof() {
+        let hex_proof = Groth16ProofType {
+            a: "27fb48285bc59bc74c9197857856cf5f3dcce55f22b83589e399240b8469e45725c5495e3ebcdd3bc04620fd13fed113c31d19a685f7f037daf02dde02d26e4f".to_string(),
+            b: "0d1bd72809defb6e85ea48de4c28e9ec9dcd2bc5111acdb66b5cdb38ccf6d4e32bdeac48a806c2fd6cef8e09bfde1983961693c8d4a513777ba26b07f2abacba1efb7600f04e786d93f321c6df732eb0043548cfe12fa8a5aea848a500ef5b9728dbc747fc76993c16dadf2c8ef68f3d757afa6d4caf9a767c424ec0d7ff4932".to_string(),
+            c: "2062c6bee5dad15af1ebcb0e623b27f7d29775774cc92b2a7554d1801af818940309fa215204181d3a1fef15d162aa779b8900e2b84d8b8fa22a20b65652eb46".to_string(),
+        };
+
+        // The same proof as snarkjs writes it to proof.json
+        let msg: ExecuteMsg = from_json(
+            r#"{
+                "process_message": {
+                    "new_state_commitment": "1",
+                    "groth16_proof": {
+                        "pi_a": [
+                            "18084177721806309187607675391141355414187478666321496301613428455112383784023",
+                            "17084150637667556812073496166504684163366858755866301199872395346920288185935",
+                            "1"
+                        ],
+                        "pi_b": [
+                            [
+                                "19842881596644310626949946488147867718536008378204485813950848448624583224506",
+                                "5929256857233446246154085410432506346938350733267844843581580272689493759203"
+                            ],
+                            [
+                                "18480828838783832516625498054333577134745242080277255885508697646053392861490",
+                                "14013678502133518264170655266449753514257834825042452375553798621048358263703"
+                            ],
+                            ["1", "0"]
+                        ],
+                        "pi_c": [
+                            "14648533859385868593892904452616170292936195080249016877701103961338505861268",
+                            "1374566504235034452357431652743167964433836478256198273150337944389921467206",
+                            "1"
+                        ],
+                        "protocol": "groth16",
+                        "curve": "bn128"
+                    }
+                }
+            }"#
+            .as_bytes(),
+        )
+        .unwrap();
+        let ExecuteMsg::ProcessMessage { groth16_proof, .. } = msg else {
+            panic!("expected process_message");
+        };
+        assert!(matches!(groth16_proof, Groth16ProofInput::Snarkjs(_)));
+        assert_eq!(groth16_proof.into_uncompressed().unwrap(), hex_proof);
+
+        // Messages with the uncompressed hex points keep their layout
+        let msg: ExecuteMsg = from_json(
+            to_json_vec(&ExecuteMsg::ProcessMessage {
+                new_state_commitment: Uint256::from_u128(1u128),
+                groth16_proof: hex_proof.clone().into(),
+                question_id: None,
+            })
+            .unwrap(),
+        )
+        .unwrap();
+        let ExecuteMsg::ProcessMessage { groth16_proof, .. } = msg else {
+            panic!("expected process_message");
+        };
+        assert_eq!(groth16_proof, Groth16ProofInput::Uncompressed(hex_proof));
+    }
+
+    #[test]
+    fn client_built_messages_extend_the_message_chains() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let contract = code_id
+            .instantiate_with_voting_time(&mut app, owner(), user1(), user2(), "Client")
+            .unwrap();
+        app.update_block(next_block);
+
+        let coordinator = PubKey {
+            x: uint256_from_decimal_string(
+                "3557592161792765812904087712812111121909518311142005886657252371904276697771",
+            ),
+            y: uint256_from_decimal_string(
+                "4363822302427519764561660537570341277214758164895027920046745209970137856681",
+            ),
+        };
+        let voter = Keypair::new(Uint256::from_u128(123456789u128)).unwrap();
+        contract
+            .sign_up(&mut app, user1(), voter.pub_key.clone())
+            .unwrap();
+
+        let mut prev_hash = Uint256::zero();
+        for (nonce, option) in [(1, 2), (2, 0)] {
+            let command = Command::vote(0, option, 4, nonce, voter.pub_key.clone());
+            let message = build_message(&voter, &coordinator, &command).unwrap();
+            contract
+                .publish_message(
+                    &mut app,
+                    user1(),
+                    message.message.clone(),
+                    message.enc_pub_key.clone(),
+                )
+                .unwrap();
+
+            let expected = hash_message_and_enc_pub_key(
+                message.message.clone(),
+                message.enc_pub_key.clone(),
+                prev_hash,
+            )
+            .unwrap();
+            assert_eq!(message_hash(&message, prev_hash).unwrap(), expected);
+            let stored = MSG_HASHES
+                .query(
+                    &app.wrap(),
+                    contract.addr(),
+                    Uint256::from(nonce).to_be_bytes().to_vec(),
+                )
+                .unwrap();
+            assert_eq!(stored, Some(expected));
+            prev_hash = expected;
+        }
+        assert_eq!(
+            contract.msg_length(&app).unwrap(),
+            Uint256::from_u128(2u128)
+        );
+
+        let deactivate = build_message(&voter, &coordinator, &Command::deactivate(0)).unwrap();
+        contract
+            .publish_deactivate_message(
+                &mut app,
+                user1(),
+                deactivate.message.clone(),
+                deactivate.enc_pub_key.clone(),
+            )
+            .unwrap();
+        let stored = DMSG_HASHES
+            .query(
+                &app.wrap(),
+                contract.addr(),
+                Uint256::one().to_be_bytes().to_vec(),
+            )
+            .unwrap();
+        assert_eq!(
+            stored,
+            Some(message_hash(&deactivate, Uint256::zero()).unwrap())
+        );
+    }
+
+    fn empty_round_dump() -> RoundDump {
+        RoundDump {
+            question_id: 0,
+            parameters: MaciParameters {
+                state_tree_depth: Uint256::from_u128(2u128),
+                int_state_tree_depth: Uint256::from_u128(1u128),
+                vote_option_tree_depth: Uint256::from_u128(1u128),
+                message_batch_size: Uint256::from_u128(5u128),
+            },
+            coordinator: PubKey {
+                x: uint256_from_decimal_string(
+                    "3557592161792765812904087712812111121909518311142005886657252371904276697771",
+                ),
+                y: uint256_from_decimal_string(
+                    "4363822302427519764561660537570341277214758164895027920046745209970137856681",
+                ),
+            },
+            circuit_type: Uint256::zero(),
+            num_sign_ups: Uint256::zero(),
+            max_vote_options: Uint256::from_u128(5u128),
+            state_root: Uint256::zero(),
+            deactivate_messages: vec![],
+            deactivate_batches: vec![],
+            add_new_keys: vec![],
+            messages: vec![],
+            process_batches: vec![],
+            tally_batches: vec![],
+            results: vec![],
+            salt: Uint256::zero(),
+        }
+    }
+
+    #[test]
+    fn audit_replays_the_batches_of_a_round() {
+        let report = audit(&empty_round_dump()).unwrap();
+        assert_eq!(report.process_batches, 0);
+        assert_eq!(report.tally_commitment, Uint256::zero());
+        assert_eq!(report.unverified.len(), 1);
+
+        let message = PublishedMessage {
+            message: MessageData {
+                data: [Uint256::from_u128(1u128); 7],
+            },
+            enc_pub_key: empty_round_dump().coordinator,
+        };
+        let proof = Groth16ProofType {
+            a: "27fb48285bc59bc74c9197857856cf5f3dcce55f22b83589e399240b8469e45725c5495e3ebcdd3bc04620fd13fed113c31d19a685f7f037daf02dde02d26e4f".to_string(),
+            b: "0d1bd72809defb6e85ea48de4c28e9ec9dcd2bc5111acdb66b5cdb38ccf6d4e32bdeac48a806c2fd6cef8e09bfde1983961693c8d4a513777ba26b07f2abacba1efb7600f04e786d93f321c6df732eb0043548cfe12fa8a5aea848a500ef5b9728dbc747fc76993c16dadf2c8ef68f3d757afa6d4caf9a767c424ec0d7ff4932".to_string(),
+            c: "2062c6bee5dad15af1ebcb0e623b27f7d29775774cc92b2a7554d1801af818940309fa215204181d3a1fef15d162aa779b8900e2b84d8b8fa22a20b65652eb46".to_string(),
+        };
+
+        let mut dump = empty_round_dump();
+        dump.messages = vec![message.clone()];
+        assert_eq!(
+            audit(&dump).unwrap_err(),
+            AuditError::Unprocessed {
+                step: String::from("process"),
+                left: Uint256::one(),
+            }
+        );
+
+        // The proof does not match the input hash of this round
+        dump.process_batches = vec![ProcessBatch {
+            question_id: 0,
+            new_state_commitment: Uint256::one(),
+            proof: proof.clone(),
+        }];
+        assert_eq!(
+            audit(&dump).unwrap_err(),
+            AuditError::InvalidProof {
+                step: String::from("process"),
+                batch: 0,
+            }
+        );
+
+        // A batch of another question does not belong to this message chain
+        dump.process_batches[0].question_id = 1;
+        assert_eq!(
+            audit(&dump).unwrap_err(),
+            AuditError::QuestionMismatch {
+                step: String::from("process"),
+                batch: 0,
+                expected: 0,
+                found: 1,
+            }
+        );
+
+        // The contract never accepts deactivate batches above the batch size
+        let mut dump = empty_round_dump();
+        dump.deactivate_messages = vec![message.clone()];
+        dump.deactivate_batches = vec![DeactivateBatch {
+            size: Uint256::from_u128(6u128),
+            new_deactivate_commitment: Uint256::one(),
+            new_deactivate_root: Uint256::one(),
+            state_root: Uint256::zero(),
+            proof: proof.clone(),
+        }];
+        assert_eq!(
+            audit(&dump).unwrap_err(),
+            AuditError::BatchTooLarge {
+                batch: 0,
+                size: Uint256::from_u128(6u128),
+                batch_size: Uint256::from_u128(5u128),
+            }
+        );
+
+        // New keys are verified against the deactivate root of the batch before them
+        let mut dump = empty_round_dump();
+        dump.add_new_keys = vec![AddNewKeyCall {
+            pubkey: empty_round_dump().coordinator,
+            nullifier: Uint256::one(),
+            d: [Uint256::one(); 4],
+            deactivate_batches: 0,
+            proof: proof.clone(),
+        }];
+        assert_eq!(
+            audit(&dump).unwrap_err(),
+            AuditError::InvalidProof {
+                step: String::from("add_new_key"),
+                batch: 0,
+            }
+        );
+        dump.add_new_keys[0].deactivate_batches = 1;
+        assert_eq!(
+            audit(&dump).unwrap_err(),
+            AuditError::UnknownDeactivateBatch {
+                call: 0,
+                deactivate_batches: 1,
+                count: 0,
+            }
+        );
+
+        let mut dump = empty_round_dump();
+        dump.tally_batches = vec![TallyBatch {
+            question_id: 0,
+            new_tally_commitment: Uint256::one(),
+            proof,
+        }];
+        assert_eq!(
+            audit(&dump).unwrap_err(),
+            AuditError::ExtraBatch {
+                step: String::from("tally"),
+                batch: 0,
+            }
+        );
+
+        let mut dump = empty_round_dump();
+        dump.results = vec![Uint256::zero(); 6];
+        assert_eq!(
+            audit(&dump).unwrap_err(),
+            AuditError::TooManyResults {
+                count: 6,
+                max_vote_options: Uint256::from_u128(5u128),
+            }
+        );
+    }
+
+    #[test]
+    fn a_failed_performance_report_does_not_block_the_claim() {
+        let fee = coins(100u128, "peaka");
+        let mut app = create_app();
+        app.init_modules(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &owner(), fee.clone())
+                .unwrap()
+        });
+        let code_id = MaciCodeId::store_code(&mut app);
+        // No contract lives at the registry address, so the report fails
+        let contract = code_id
+            .instantiate_with_registry(
+                &mut app,
+                owner(),
+                Addr::unchecked("registry"),
+                &fee,
+                "Group",
+            )
+            .unwrap();
+
+        // The tally timed out, the admin gets the funds back
+        app.update_block(|block| {
+            block.time = Timestamp::from_nanos(1571797424879000000)
+                .plus_minutes(11)
+                .plus_days(5)
+        });
+        let resp = app
+            .execute_contract(owner(), contract.addr(), &ExecuteMsg::Claim {}, &[])
+            .unwrap();
+        assert!(resp.events.iter().any(|event| event
+            .attributes
+            .contains(&attr("action", "report_performance"))));
+        assert_eq!(
+            app.wrap().query_balance(owner(), "peaka").unwrap().amount,
+            Uint128::from(100u128)
+        );
+        assert_eq!(
+            app.wrap()
+                .query_balance(contract.addr(), "peaka")
+                .unwrap()
+                .amount,
+            Uint128::zero()
+        );
+    }
 }
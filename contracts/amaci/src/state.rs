@@ -1,7 +1,10 @@
-use crate::utils::{hash2, hash5, uint256_from_hex_string};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Timestamp, Uint128, Uint256};
 use cw_storage_plus::{Item, Map};
+pub use maci_core::{
+    Groth16PreparedVkeyStr, Groth16ProofStr, Groth16VkeyStr, IncrementalQuinaryTree, MessageData,
+    PlonkProofStr, PubKey, QuinaryTreeRoot, StateLeaf,
+};
 
 #[cw_serde]
 pub struct RoundInfo {
@@ -20,6 +23,15 @@ pub struct VotingTime {
 
 pub const VOTINGTIME: Item<VotingTime> = Item::new("voting_time");
 
+/// Optional registration window. When it is not set, sign up follows `VotingTime`.
+#[cw_serde]
+pub struct SignUpTime {
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
+}
+
+pub const SIGNUPTIME: Item<SignUpTime> = Item::new("sign_up_time");
+
 pub const VOTEOPTIONMAP: Item<Vec<String>> = Item::new("vote_option_map");
 
 #[cw_serde]
@@ -68,68 +80,16 @@ pub const NUMSIGNUPS: Item<Uint256> = Item::new("num_sign_ups");
 // key is state_key, value is sender balance
 pub const VOICECREDITBALANCE: Map<Vec<u8>, Uint256> = Map::new("voice_credit_balance");
 
-pub const NODES: Map<Vec<u8>, Uint256> = Map::new("nodes");
+// Frontier of the state tree, the nodes of rounds before v0.1.3 were kept under "nodes"
+pub const STATE_TREE: Item<IncrementalQuinaryTree> = Item::new("state_tree");
 
 pub const MAX_VOTE_OPTIONS: Item<Uint256> = Item::new("max_vote_options");
 pub const CURRENT_STATE_COMMITMENT: Item<Uint256> = Item::new("current_state_commitment");
 pub const CURRENT_TALLY_COMMITMENT: Item<Uint256> = Item::new("current_tally_commitment");
 
-pub const RESULT: Map<Vec<u8>, Uint256> = Map::new("voice_credit_balance");
+pub const RESULT: Map<Vec<u8>, Uint256> = Map::new("result");
 pub const TOTAL_RESULT: Item<Uint256> = Item::new("total_result");
 
-#[cw_serde]
-pub struct PubKey {
-    pub x: Uint256,
-    pub y: Uint256,
-}
-
-#[cw_serde]
-pub struct StateLeaf {
-    pub pub_key: PubKey,
-    pub voice_credit_balance: Uint256,
-    pub vote_option_tree_root: Uint256,
-    pub nonce: Uint256,
-}
-
-impl StateLeaf {
-    pub fn hash_state_leaf(&self) -> Uint256 {
-        let mut plaintext: [Uint256; 5] = [Uint256::from_u128(0); 5];
-
-        plaintext[0] = self.pub_key.x;
-        plaintext[1] = self.pub_key.y;
-        plaintext[2] = self.voice_credit_balance;
-        plaintext[3] = self.vote_option_tree_root;
-        plaintext[4] = self.nonce;
-        return hash5(plaintext);
-    }
-
-    pub fn hash_decativate_state_leaf(&self) -> Uint256 {
-        let mut plaintext: [Uint256; 5] = [Uint256::from_u128(0); 5];
-
-        plaintext[0] = self.pub_key.x;
-        plaintext[1] = self.pub_key.y;
-        plaintext[2] = self.voice_credit_balance;
-        return hash2([
-            hash5(plaintext),
-            uint256_from_hex_string(
-                "2066be41bebe6caf7e079360abe14fbf9118c62eabc42e2fe75e342b160a95bc",
-            ),
-        ]);
-    }
-
-    pub fn hash_new_key_state_leaf(&self, d: [Uint256; 4]) -> Uint256 {
-        let mut plaintext: [Uint256; 5] = [Uint256::from_u128(0); 5];
-
-        plaintext[0] = self.pub_key.x;
-        plaintext[1] = self.pub_key.y;
-        plaintext[2] = self.voice_credit_balance;
-        return hash2([
-            hash5(plaintext),
-            hash5([d[0], d[1], d[2], d[3], Uint256::from_u128(0u128)]),
-        ]);
-    }
-}
-
 // Init Data
 pub const MAX_LEAVES_COUNT: Item<Uint256> = Item::new("max_leaves_count");
 pub const LEAF_IDX_0: Item<Uint256> = Item::new("leaf_idx_0");
@@ -137,16 +97,41 @@ pub const COORDINATORHASH: Item<Uint256> = Item::new("coordinator_hash");
 pub const ZEROS: Item<[Uint256; 8]> = Item::new("zeros");
 pub const ZEROS_H10: Item<[Uint256; 7]> = Item::new("zeros_h10");
 
-#[cw_serde]
-pub struct MessageData {
-    pub data: [Uint256; 7],
-}
-
 pub const MSG_HASHES: Map<Vec<u8>, Uint256> = Map::new("msg_hashes");
 pub const MSG_CHAIN_LENGTH: Item<Uint256> = Item::new("msg_chain_length");
 pub const PROCESSED_MSG_COUNT: Item<Uint256> = Item::new("processed_msg_count");
 pub const PROCESSED_USER_COUNT: Item<Uint256> = Item::new("processed_user_count");
 
+/// A ballot of the round. Every question shares the voter state tree, but keeps its own
+/// message chain, vote options and tally. Question 0 is stored in the single ballot items
+/// above, additional questions are stored in `QUESTIONS`.
+#[cw_serde]
+pub struct Question {
+    pub vote_option_map: Vec<String>,
+    pub max_vote_options: Uint256,
+    pub msg_chain_length: Uint256,
+    pub processed_msg_count: Uint256,
+    pub state_commitment: Uint256,
+    pub tally_commitment: Uint256,
+    pub processed_user_count: Uint256,
+    pub total_result: Option<Uint256>,
+}
+
+#[cw_serde]
+pub struct QuestionConfig {
+    pub max_vote_options: Uint256,
+}
+
+// number of questions in the round, including question 0
+pub const QUESTION_COUNT: Item<u64> = Item::new("question_count");
+pub const QUESTIONS: Map<u64, Question> = Map::new("questions");
+pub const QUESTION_MSG_HASHES: Map<(u64, Vec<u8>), Uint256> = Map::new("question_msg_hashes");
+pub const QUESTION_RESULT: Map<(u64, Vec<u8>), Uint256> = Map::new("question_result");
+// the question each message of a multi-question round was published for, keyed by the hash of
+// the message and its key. Commands do not name their question, so the same ciphertext must not
+// be counted by two questions
+pub const PUBLISHED_MESSAGES: Map<Vec<u8>, u64> = Map::new("published_messages");
+
 pub const DMSG_CHAIN_LENGTH: Item<Uint256> = Item::new("dmsg_chain_length");
 pub const DMSG_HASHES: Map<Vec<u8>, Uint256> = Map::new("dmsg_hashes");
 pub const STATE_ROOT_BY_DMSG: Map<Vec<u8>, Uint256> = Map::new("state_root_by_dmsg");
@@ -160,79 +145,6 @@ pub const PRE_DEACTIVATE_ROOT: Item<Uint256> = Item::new("pre_deactivate_root");
 
 pub const DEACTIVATE_COUNT: Item<u128> = Item::new("deactivate_count");
 
-#[cw_serde]
-pub struct Groth16ProofStr {
-    pub pi_a: Vec<u8>,
-    pub pi_b: Vec<u8>,
-    pub pi_c: Vec<u8>,
-}
-
-#[cw_serde]
-pub struct QuinaryTreeRoot {
-    pub zeros: [Uint256; 9],
-}
-
-impl QuinaryTreeRoot {
-    const DEGREE: u32 = 5;
-
-    pub fn root_of(&self, depth: Uint256, nodes: Vec<Uint256>) -> Uint256 {
-        let _depth = depth.to_string().parse().unwrap();
-        let capacity = Self::DEGREE.pow(_depth);
-        let length = nodes.len() as u32;
-
-        assert!(capacity >= length, "overflow");
-
-        let mut c = capacity / Self::DEGREE;
-        let mut pl = (length - 1) / Self::DEGREE + 1;
-        let mut _nodes = nodes;
-
-        for i in 0.._depth {
-            let zero = self.get_zero(i);
-            // number of non-zero parent nodes
-            for j in 0..c {
-                if j >= length {
-                    continue;
-                }
-                let mut h = Uint256::zero();
-                if j < pl {
-                    let mut inputs = [Uint256::zero(); 5];
-                    let mut s = Uint256::zero();
-                    for k in 0..5 {
-                        let node = if j * 5 + k < length {
-                            _nodes[(j * 5 + k) as usize]
-                        } else {
-                            Uint256::zero()
-                        };
-                        s += node;
-                        let mut input = node;
-                        if node == Uint256::zero() {
-                            input = zero;
-                        }
-                        inputs[k as usize] = input;
-                    }
-                    if s > Uint256::zero() {
-                        h = hash5(inputs);
-                    }
-                }
-                _nodes[j as usize] = h;
-            }
-
-            pl = (pl - 1) / Self::DEGREE + 1;
-            c = c / Self::DEGREE;
-        }
-
-        let mut result = _nodes[0];
-        if result == Uint256::zero() {
-            result = self.get_zero(_depth);
-        }
-        result
-    }
-
-    fn get_zero(&self, height: u32) -> Uint256 {
-        self.zeros[height as usize]
-    }
-}
-
 pub const QTR_LIB: Item<QuinaryTreeRoot> = Item::new("qtr_lib");
 
 #[cw_serde]
@@ -272,39 +184,21 @@ pub const CIRCUITTYPE: Item<Uint256> = Item::new("circuit_type"); // <0: 1p1v |
 
 pub const CERTSYSTEM: Item<Uint256> = Item::new("certification_system"); // <0: groth16 | 1: plonk>
 
-#[cw_serde]
-pub struct PlonkProofStr {
-    pub num_inputs: usize,
-    pub n: usize,
-    pub input_values: Vec<String>,
-    pub wire_commitments: Vec<Vec<u8>>,
-    pub grand_product_commitment: Vec<u8>,
-    pub quotient_poly_commitments: Vec<Vec<u8>>,
-    pub wire_values_at_z: Vec<String>,
-    pub wire_values_at_z_omega: Vec<String>,
-    pub grand_product_at_z_omega: String,
-    pub quotient_polynomial_at_z: String,
-    pub linearization_polynomial_at_z: String,
-    pub permutation_polynomials_at_z: Vec<String>,
-    pub opening_at_z_proof: Vec<u8>,
-    pub opening_at_z_omega_proof: Vec<u8>,
-}
-
-#[cw_serde]
-pub struct Groth16VkeyStr {
-    pub alpha_1: Vec<u8>,
-    pub beta_2: Vec<u8>,
-    pub gamma_2: Vec<u8>,
-    pub delta_2: Vec<u8>,
-    pub ic0: Vec<u8>,
-    pub ic1: Vec<u8>,
-}
-
 pub const GROTH16_PROCESS_VKEYS: Item<Groth16VkeyStr> = Item::new("groth16_process_vkeys");
 pub const GROTH16_TALLY_VKEYS: Item<Groth16VkeyStr> = Item::new("groth16_tally_vkeys");
 pub const GROTH16_DEACTIVATE_VKEYS: Item<Groth16VkeyStr> = Item::new("groth16_deactivate_vkeys");
 pub const GROTH16_NEWKEY_VKEYS: Item<Groth16VkeyStr> = Item::new("groth16_newkey_vkeys");
 
+// prepared on the first verification with each key
+pub const GROTH16_PROCESS_PREPARED_VKEYS: Item<Groth16PreparedVkeyStr> =
+    Item::new("groth16_process_prepared_vkeys");
+pub const GROTH16_TALLY_PREPARED_VKEYS: Item<Groth16PreparedVkeyStr> =
+    Item::new("groth16_tally_prepared_vkeys");
+pub const GROTH16_DEACTIVATE_PREPARED_VKEYS: Item<Groth16PreparedVkeyStr> =
+    Item::new("groth16_deactivate_prepared_vkeys");
+pub const GROTH16_NEWKEY_PREPARED_VKEYS: Item<Groth16PreparedVkeyStr> =
+    Item::new("groth16_newkey_prepared_vkeys");
+
 // registry operator data
 pub const MACI_DEACTIVATE_MESSAGE: Map<&Addr, Vec<Vec<String>>> =
     // contract_address - [["", "", "", "", ""]]
@@ -324,6 +218,9 @@ pub const FIRST_DMSG_TIMESTAMP: Item<Timestamp> = Item::new("first_dmsg_timestam
 
 pub const FEE_RECIPIENT: Item<Addr> = Item::new("fee_recipient");
 
+// the registry that created the round, which `Claim` reports the operator to
+pub const REGISTRY: Item<Addr> = Item::new("registry");
+
 #[cw_serde]
 pub enum DelayType {
     DeactivateDelay = 0,
@@ -346,6 +243,36 @@ pub struct DelayRecords {
 
 pub const DELAY_RECORDS: Item<DelayRecords> = Item::new("delay_records");
 
+#[cw_serde]
+pub enum BatchStep {
+    ProcessDeactivateMessage,
+    ProcessMessage,
+    ProcessTally,
+}
+
+/// A batch whose proof was accepted. The index range is over the deactivate message chain,
+/// the message chain of `question_id` or the state leaves, depending on the step.
+#[cw_serde]
+pub struct BatchRecord {
+    pub id: u64,
+    pub step: BatchStep,
+    /// not set for deactivate batches, which are shared by every question
+    pub question_id: Option<u64>,
+    pub start_index: Uint256,
+    pub end_index: Uint256,
+    pub old_commitment: Uint256,
+    pub new_commitment: Uint256,
+    pub input_hash: Uint256,
+    pub certification_system: String,
+    /// the proof as JSON, the same as in the process events
+    pub proof: String,
+    pub block_time: Timestamp,
+    pub submitter: Addr,
+}
+
+pub const BATCH_RECORDS: Map<u64, BatchRecord> = Map::new("batch_records");
+pub const BATCH_RECORD_COUNT: Item<u64> = Item::new("batch_record_count");
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,116 @@
+//! Converts a verifying key into the JSON the round contracts take in their instantiate message.
+//!
+//! cargo run --bin vkey -- groth16 verification_key.json [--rust <name>]
+//! cargo run --bin vkey -- plonk vk.bin [--rust <name>]
+//!
+//! A groth16 key is the `verification_key.json` written by `snarkjs zkey export
+//! verificationkey`. A plonk key is the binary key exported for the bellman_ce width-4 verifier
+//! the contracts use. snarkjs plonk keys cannot be converted: they describe a width-3 circuit, the
+//! fourth permutation commitment needs the `[x]_1` point of the setup which the snarkjs key does
+//! not carry, and snarkjs plonk proofs use another transcript, so they would not verify against
+//! any converted key. Such circuits need a bellman_ce setup. The key is checked the same way
+//! instantiate checks it. `--rust` prints a `Groth16VKeyType` or
+//! `PlonkVKeyType` binding for `circuit_params.rs` instead of JSON.
+
+use std::error::Error;
+use std::fs;
+
+use bellman_ce::plonk::better_cs::cs::PlonkCsWidth4WithNextStepParams;
+use bellman_ce::plonk::better_cs::keys::VerificationKey;
+use cosmwasm_std::{from_json, to_json_string};
+use cw_maci::msg::{Groth16VKeyInput, Groth16VKeyType, PlonkVKeyType};
+use cw_maci::vkey::{format_groth16_vkey, format_plonk_vkey, groth16_vkey_type, plonk_vkey_type};
+use maci_core::SnarkjsVkey;
+use pairing_ce::bn256::Bn256;
+use serde::Deserialize;
+
+const USAGE: &str = "usage: vkey <groth16|plonk> <key file> [--rust <name>]";
+
+#[derive(Deserialize)]
+struct Protocol {
+    protocol: Option<String>,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (system, path, rust_name) = match args.as_slice() {
+        [system, path] => (system, path, None),
+        [system, path, flag, name] if flag == "--rust" => (system, path, Some(name)),
+        _ => return Err(USAGE.into()),
+    };
+    let bytes = fs::read(path)?;
+
+    let output = match system.as_str() {
+        "groth16" => {
+            let protocol: Protocol = from_json(&bytes)?;
+            if let Some(protocol) = protocol.protocol.filter(|p| p != "groth16") {
+                return Err(format!("{path} is a {protocol} key, not a groth16 key").into());
+            }
+            let vkey: SnarkjsVkey = from_json(&bytes)?;
+            let vkey = groth16_vkey_type(&format_groth16_vkey(Groth16VKeyInput::Snarkjs(vkey))?);
+            match rust_name {
+                Some(name) => groth16_rust(name, &vkey),
+                None => to_json_string(&vkey)?,
+            }
+        }
+        "plonk" => {
+            if from_json::<Protocol>(&bytes).is_ok() {
+                return Err(format!(
+                    "{path} is a snarkjs key, snarkjs plonk proofs do not verify with the \
+                     bellman_ce plonk verifier of the contracts; export the key of a bellman_ce \
+                     setup of the circuit"
+                )
+                .into());
+            }
+            let vk = VerificationKey::<Bn256, PlonkCsWidth4WithNextStepParams>::read(&bytes[..])?;
+            let vkey = plonk_vkey_type(&vk);
+            format_plonk_vkey(vkey.clone())?;
+            match rust_name {
+                Some(name) => plonk_rust(name, &vkey),
+                None => to_json_string(&vkey)?,
+            }
+        }
+        _ => return Err(USAGE.into()),
+    };
+
+    println!("{output}");
+    Ok(())
+}
+
+fn groth16_rust(name: &str, vkey: &Groth16VKeyType) -> String {
+    let fields = [
+        ("vk_alpha1", &vkey.vk_alpha1),
+        ("vk_beta_2", &vkey.vk_beta_2),
+        ("vk_gamma_2", &vkey.vk_gamma_2),
+        ("vk_delta_2", &vkey.vk_delta_2),
+        ("vk_ic0", &vkey.vk_ic0),
+        ("vk_ic1", &vkey.vk_ic1),
+    ];
+    let mut out = format!("let {name} = Groth16VKeyType {{\n");
+    for (field, value) in fields {
+        out += &format!("    {field}: \"{value}\".to_string(),\n");
+    }
+    out + "};"
+}
+
+fn plonk_rust(name: &str, vkey: &PlonkVKeyType) -> String {
+    let list = |values: &[String]| {
+        let items: Vec<String> = values
+            .iter()
+            .map(|value| format!("        \"{value}\".to_string(),\n"))
+            .collect();
+        format!("vec![\n{}    ]", items.concat())
+    };
+    format!(
+        "let {name} = PlonkVKeyType {{\n    n: {},\n    num_inputs: {},\n    \
+         selector_commitments: {},\n    next_step_selector_commitments: {},\n    \
+         permutation_commitments: {},\n    non_residues: {},\n    g2_elements: {},\n}};",
+        vkey.n,
+        vkey.num_inputs,
+        list(&vkey.selector_commitments),
+        list(&vkey.next_step_selector_commitments),
+        list(&vkey.permutation_commitments),
+        list(&vkey.non_residues),
+        list(&vkey.g2_elements),
+    )
+}
@@ -1,10 +1,9 @@
 pub mod contract;
 mod error;
-pub mod groth16_parser;
+mod migrates;
 pub mod msg;
-pub mod plonk_parser;
 pub mod state;
-pub mod utils;
+pub mod vkey;
 
 #[cfg(test)]
 pub mod multitest;
@@ -0,0 +1,73 @@
+use crate::error::ContractError;
+use crate::state::{MAX_VOTE_OPTIONS, NUMSIGNUPS, RESULT, TOTAL_RESULT, VOICECREDITBALANCE};
+use cosmwasm_std::{Attribute, DepsMut, StdResult, Storage, Uint256};
+use cw_storage_plus::Map;
+
+// Before v0.1.2 `RESULT` shared the "voice_credit_balance" namespace
+const LEGACY_RESULT: Map<Vec<u8>, Uint256> = Map::new("voice_credit_balance");
+
+pub fn migrate_v0_1_2(
+    deps: DepsMut,
+    voice_credit_balances: Option<Vec<Uint256>>,
+) -> Result<Vec<Attribute>, ContractError> {
+    let relocated = relocate_results(deps.storage)?;
+
+    // Tallying overwrote the balances of the signed up state indices it reached. The
+    // whitelist zeroes a balance on sign up, so the originals have to be supplied by the
+    // migrator (they are emitted as the `balance` attribute of every sign_up event).
+    let mut restored = 0u128;
+    if relocated > 0 {
+        let num_sign_ups = NUMSIGNUPS.load(deps.storage)?;
+        for i in 0..relocated {
+            let key = Uint256::from_u128(i).to_be_bytes().to_vec();
+            if Uint256::from_u128(i) >= num_sign_ups {
+                VOICECREDITBALANCE.remove(deps.storage, key);
+                continue;
+            }
+
+            let balance = voice_credit_balances
+                .as_ref()
+                .and_then(|balances| balances.get(i as usize))
+                .ok_or(ContractError::MissingVoiceCreditBalances {
+                    count: Uint256::from_u128(relocated).min(num_sign_ups),
+                })?;
+            VOICECREDITBALANCE.save(deps.storage, key, balance)?;
+            restored += 1;
+        }
+    }
+
+    let attributes: Vec<Attribute> = vec![
+        Attribute::new("version", "0.1.2"),
+        Attribute::new("relocated_results", relocated.to_string()),
+        Attribute::new("restored_balances", restored.to_string()),
+    ];
+
+    Ok(attributes)
+}
+
+// Copies the tallied results into their own namespace and returns how many leading
+// indices held a result. Results are a contiguous run starting at index 0 whose sum is
+// the stored total, followed only by zero-valued options. Sign up rejects a zero
+// balance, so a non-zero value past the total is an untouched balance.
+fn relocate_results(storage: &mut dyn Storage) -> StdResult<u128> {
+    let total_result = match TOTAL_RESULT.may_load(storage)? {
+        Some(total_result) => total_result,
+        None => return Ok(0),
+    };
+    let max_vote_options = MAX_VOTE_OPTIONS.load(storage)?;
+
+    let mut sum = Uint256::zero();
+    let mut count = 0u128;
+    while Uint256::from_u128(count) < max_vote_options {
+        let key = Uint256::from_u128(count).to_be_bytes().to_vec();
+        let result = match LEGACY_RESULT.may_load(storage, key.clone())? {
+            Some(result) if sum < total_result || result.is_zero() => result,
+            _ => break,
+        };
+        RESULT.save(storage, key, &result)?;
+        sum += result;
+        count += 1;
+    }
+
+    Ok(count)
+}
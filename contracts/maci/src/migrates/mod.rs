@@ -0,0 +1,3 @@
+pub mod migrate_v0_1_1;
+pub mod migrate_v0_1_2;
+pub mod migrate_v0_1_3;
@@ -1,26 +1,31 @@
 use crate::state::{
-    MaciParameters, MessageData, PeriodStatus, PubKey, QuinaryTreeRoot, RoundInfo, VotingTime,
-    Whitelist,
+    BatchRecord, MaciParameters, MessageData, PeriodStatus, PubKey, QuinaryTreeRoot, RoundInfo,
+    SignUpTime, VotingTime, Whitelist,
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Uint128, Uint256};
+use cosmwasm_std::{Addr, Timestamp, Uint128, Uint256};
+use maci_core::groth16_parser::parse_snarkjs_proof;
+use maci_core::{CoreError, SnarkjsProof, SnarkjsVkey};
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub parameters: MaciParameters,
     pub coordinator: PubKey,
     pub qtr_lib: QuinaryTreeRoot,
-    pub groth16_process_vkey: Option<Groth16VKeyType>,
-    pub groth16_tally_vkey: Option<Groth16VKeyType>,
+    pub groth16_process_vkey: Option<Groth16VKeyInput>,
+    pub groth16_tally_vkey: Option<Groth16VKeyInput>,
     pub plonk_process_vkey: Option<PlonkVKeyType>,
     pub plonk_tally_vkey: Option<PlonkVKeyType>,
     pub max_vote_options: Uint256,
 
     pub round_info: RoundInfo,
     pub voting_time: Option<VotingTime>,
+    pub sign_up_time: Option<SignUpTime>,
     pub whitelist: Option<Whitelist>,
     pub circuit_type: Uint256,         // <0: 1p1v | 1: pv>
     pub certification_system: Uint256, // <0: groth16 | 1: plonk>
+    /// the admin and operator of the round, the sender if not set
+    pub admin: Option<Addr>,
 }
 
 #[cw_serde]
@@ -33,6 +38,21 @@ pub struct Groth16VKeyType {
     pub vk_ic1: String,
 }
 
+/// A groth16 verifying key, either as uncompressed hex points or as snarkjs writes it to
+/// `verification_key.json`
+#[cw_serde]
+#[serde(untagged)]
+pub enum Groth16VKeyInput {
+    Uncompressed(Groth16VKeyType),
+    Snarkjs(SnarkjsVkey),
+}
+
+impl From<Groth16VKeyType> for Groth16VKeyInput {
+    fn from(vkey: Groth16VKeyType) -> Self {
+        Groth16VKeyInput::Uncompressed(vkey)
+    }
+}
+
 #[cw_serde]
 pub struct Groth16ProofType {
     pub a: String,
@@ -40,6 +60,37 @@ pub struct Groth16ProofType {
     pub c: String,
 }
 
+/// A groth16 proof, either as uncompressed hex points or as snarkjs writes it to `proof.json`
+#[cw_serde]
+#[serde(untagged)]
+pub enum Groth16ProofInput {
+    Uncompressed(Groth16ProofType),
+    Snarkjs(SnarkjsProof),
+}
+
+impl Groth16ProofInput {
+    /// normalize to the uncompressed hex points that are verified and emitted in events
+    pub fn into_uncompressed(self) -> Result<Groth16ProofType, CoreError> {
+        match self {
+            Groth16ProofInput::Uncompressed(proof) => Ok(proof),
+            Groth16ProofInput::Snarkjs(proof) => {
+                let proof = parse_snarkjs_proof(&proof)?;
+                Ok(Groth16ProofType {
+                    a: hex::encode(proof.pi_a),
+                    b: hex::encode(proof.pi_b),
+                    c: hex::encode(proof.pi_c),
+                })
+            }
+        }
+    }
+}
+
+impl From<Groth16ProofType> for Groth16ProofInput {
+    fn from(proof: Groth16ProofType) -> Self {
+        Groth16ProofInput::Uncompressed(proof)
+    }
+}
+
 #[cw_serde]
 pub struct PlonkVKeyType {
     pub n: usize,
@@ -98,13 +149,13 @@ pub enum ExecuteMsg {
     },
     ProcessMessage {
         new_state_commitment: Uint256,
-        groth16_proof: Option<Groth16ProofType>,
+        groth16_proof: Option<Groth16ProofInput>,
         plonk_proof: Option<PlonkProofType>,
     },
     StopProcessingPeriod {},
     ProcessTally {
         new_tally_commitment: Uint256,
-        groth16_proof: Option<Groth16ProofType>,
+        groth16_proof: Option<Groth16ProofInput>,
         plonk_proof: Option<PlonkProofType>,
     },
     StopTallyingPeriod {
@@ -126,13 +177,20 @@ pub struct Period {
     pub status: PeriodStatus,
 }
 
+#[cw_serde]
+pub struct MigrateMsg {
+    /// Original voice credit balances by state index, required when migrating an ended
+    /// round from before v0.1.2 whose results overwrote them
+    pub voice_credit_balances: Option<Vec<Uint256>>,
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
     #[returns(RoundInfo)]
     GetRoundInfo {},
 
-    #[returns(VotingTime)]
+    #[returns(VotingTimeResponse)]
     GetVotingTime {},
 
     #[returns(Period)]
@@ -187,4 +245,18 @@ pub enum QueryMsg {
 
     #[returns(Uint256)]
     QueryCertSystem {},
+
+    /// Accepted process and tally batches, in the order they were accepted
+    #[returns(Vec<BatchRecord>)]
+    BatchRecords {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct VotingTimeResponse {
+    pub start_time: Option<Timestamp>,
+    pub end_time: Option<Timestamp>,
+    pub sign_up_time: Option<SignUpTime>,
 }
@@ -5,14 +5,14 @@ use anyhow::Result as AnyResult;
 
 use crate::msg::Groth16VKeyType;
 use crate::state::{
-    MaciParameters, MessageData, Period, PubKey, QuinaryTreeRoot, RoundInfo, VotingTime, Whitelist,
-    WhitelistConfig,
+    BatchRecord, MaciParameters, MessageData, Period, PubKey, QuinaryTreeRoot, RoundInfo,
+    SignUpTime, VotingTime, Whitelist, WhitelistConfig,
 };
-use crate::utils::uint256_from_hex_string;
 use crate::{
     contract::{execute, instantiate, query},
     msg::*,
 };
+use maci_core::utils::uint256_from_hex_string;
 
 use cosmwasm_std::testing::{MockApi, MockStorage};
 use cosmwasm_std::{Addr, Coin, Empty, StdResult, Timestamp, Uint128, Uint256};
@@ -142,6 +142,52 @@ impl MaciCodeId {
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn instantiate_with_sign_up_time(
+        self,
+        app: &mut App,
+        sender: Addr,
+        user1: Addr,
+        user2: Addr,
+        sign_up_time: SignUpTime,
+        label: &str,
+    ) -> AnyResult<MaciContract> {
+        let round_info = RoundInfo {
+            title: String::from("HackWasm Berlin"),
+            description: String::from("Hack In Brelin"),
+            link: String::from("https://baidu.com"),
+        };
+        let whitelist = Some(Whitelist {
+            users: vec![
+                WhitelistConfig {
+                    addr: user1.to_string(),
+                    balance: Uint256::from_u128(100u128),
+                },
+                WhitelistConfig {
+                    addr: user2.to_string(),
+                    balance: Uint256::from_u128(80u128),
+                },
+            ],
+        });
+        let start_time = Timestamp::from_nanos(1571797424879000000).plus_minutes(6);
+        let voting_time = Some(VotingTime {
+            start_time: Some(start_time),
+            end_time: Some(start_time.plus_minutes(11)),
+        });
+        let circuit_type = Uint256::from_u128(0u128);
+        MaciContract::instantiate_with_sign_up_time(
+            app,
+            self,
+            sender,
+            round_info,
+            whitelist,
+            voting_time,
+            Some(sign_up_time),
+            circuit_type,
+            label,
+        )
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn instantiate_with_voting_time_plonk(
         self,
@@ -417,13 +463,65 @@ impl MaciContract {
         circuit_type: Uint256,
         label: &str,
     ) -> AnyResult<Self> {
+        Self::instantiate_with_sign_up_time(
+            app,
+            code_id,
+            sender,
+            round_info,
+            whitelist,
+            voting_time,
+            None,
+            circuit_type,
+            label,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[track_caller]
+    pub fn instantiate_with_sign_up_time(
+        app: &mut App,
+        code_id: MaciCodeId,
+        sender: Addr,
+        round_info: RoundInfo,
+        whitelist: Option<Whitelist>,
+        voting_time: Option<VotingTime>,
+        sign_up_time: Option<SignUpTime>,
+        circuit_type: Uint256,
+        label: &str,
+    ) -> AnyResult<Self> {
+        let init_msg = Self::instantiate_msg(
+            round_info,
+            whitelist,
+            voting_time,
+            sign_up_time,
+            circuit_type,
+        );
+        app.instantiate_contract(
+            code_id.0,
+            Addr::unchecked(sender),
+            &init_msg,
+            &[],
+            label,
+            None,
+        )
+        .map(Self::from)
+    }
+
+    // groth16 round with the 2-1-1-5 circuit keys
+    pub fn instantiate_msg(
+        round_info: RoundInfo,
+        whitelist: Option<Whitelist>,
+        voting_time: Option<VotingTime>,
+        sign_up_time: Option<SignUpTime>,
+        circuit_type: Uint256,
+    ) -> InstantiateMsg {
         let parameters = MaciParameters {
             state_tree_depth: Uint256::from_u128(2u128),
             int_state_tree_depth: Uint256::from_u128(1u128),
             message_batch_size: Uint256::from_u128(5u128),
             vote_option_tree_depth: Uint256::from_u128(1u128),
         };
-        let init_msg = InstantiateMsg {
+        InstantiateMsg {
                             parameters,
                             coordinator: PubKey {
                                 x: uint256_from_decimal_string("3557592161792765812904087712812111121909518311142005886657252371904276697771"),
@@ -466,7 +564,7 @@ impl MaciContract {
                                 vk_delta_2: "2178a9c3805dd82071b2b28bb4c0ffc8178cad913c8c990b98b4863284dc3a5d175c0be554fc060c27c551e5e32effef015b918a0f5a2dc1b92909b8272719301c521d5f6542db5ea4775a42d32159c356a696599c1a3df011ec00559ae1c2b60d860f7e6513a7d20feaeaca401863e35a0f691dd7d30ce06d07946840de1ec8".to_string(),
                                 vk_ic0: "19126a54a9b6d0d415f892c246485cb2889487cf9c4a8cd88dab5e1140e1d0630d1d76ef4652df8887c9dc557aa57f25e221db7e5b2e4cf618a362bece107f5c".to_string(),
                                 vk_ic1: "0632e625fefc7172e8aec1070c4d32b90b6c482f6f3806773a4c55a03877c2d716cfd935eb3e3883f580c93f56adbf3a253ce3c208c52fb784f9d8fec139c617".to_string(),
-                            }),
+                            }.into()),
                             groth16_tally_vkey: Some(Groth16VKeyType {
                                 vk_alpha1: "2d4d9aa7e302d9df41749d5507949d05dbea33fbb16c643b22f599a2be6df2e214bedd503c37ceb061d8ec60209fe345ce89830a19230301f076caff004d1926".to_string(),
                                 vk_beta_2: "0967032fcbf776d1afc985f88877f182d38480a653f2decaa9794cbc3bf3060c0e187847ad4c798374d0d6732bf501847dd68bc0e071241e0213bc7fc13db7ab304cfbd1e08a704a99f5e847d93f8c3caafddec46b7a0d379da69a4d112346a71739c1b1a457a8c7313123d24d2f9192f896b7c63eea05a9d57f06547ad0cec8".to_string(),
@@ -474,26 +572,18 @@ impl MaciContract {
                                 vk_delta_2: "2e9fad39728c543c5213599111e1a44b01720c999a6785e8136c3e3b3bf8e07e248e1933d477969ca6e27cb7a74bca18cac7e3bbdf9371be5c54fe151f6376a30955609ec69b89329322a2f435b706ca248d1312c7513853a50ef37ed0f7826c25a5c57bf07789d89e538bc24017cf2722811f21480b0bb8030ed0028ecb7cd8".to_string(),
                                 vk_ic0: "1bc1a1a3444256469c07cd6f4d1cfd9f7c9ddce596a306e0af077ca9e9c0fe9602db2a9aecef76a9dc4c19bf88c0099b04fc75410cc9004f0966440825e3790a".to_string(),
                                 vk_ic1: "05b8b475f2bfedba4fa04ab1972006da9764c2c3e6fb65d6dd0aac938fd298112a560e13770b06a3f709a49fddf016331ea205fa125026993f6666eff69f4def".to_string()
-                            }),
+                            }.into()),
                             plonk_process_vkey: None,
                             plonk_tally_vkey: None,
                             certification_system: Uint256::from_u128(0u128),
+                            admin: None,
                             max_vote_options: Uint256::from_u128(5u128),
                             round_info,
                             whitelist,
                             voting_time,
+                            sign_up_time,
                             circuit_type
-        };
-
-        app.instantiate_contract(
-            code_id.0,
-            Addr::unchecked(sender),
-            &init_msg,
-            &[],
-            label,
-            None,
-        )
-        .map(Self::from)
+        }
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -611,10 +701,12 @@ impl MaciContract {
                                       ].to_vec()
                                 }),
                                 certification_system: Uint256::from_u128(1u128), // plonk system
+                                admin: None,
                                 max_vote_options: Uint256::from_u128(5u128),
                                 round_info,
                                 whitelist,
                                 voting_time,
+                                sign_up_time: None,
                                 circuit_type
             };
 
@@ -761,7 +853,7 @@ impl MaciContract {
             self.addr(),
             &ExecuteMsg::ProcessMessage {
                 new_state_commitment,
-                groth16_proof: Some(proof),
+                groth16_proof: Some(proof.into()),
                 plonk_proof: None,
             },
             &[],
@@ -811,7 +903,7 @@ impl MaciContract {
             self.addr(),
             &ExecuteMsg::ProcessTally {
                 new_tally_commitment,
-                groth16_proof: Some(proof),
+                groth16_proof: Some(proof.into()),
                 plonk_proof: None,
             },
             &[],
@@ -910,7 +1002,7 @@ impl MaciContract {
             .query_wasm_smart(self.addr(), &QueryMsg::GetAllResult {})
     }
 
-    pub fn get_voting_time(&self, app: &App) -> StdResult<VotingTime> {
+    pub fn get_voting_time(&self, app: &App) -> StdResult<VotingTimeResponse> {
         app.wrap()
             .query_wasm_smart(self.addr(), &QueryMsg::GetVotingTime {})
     }
@@ -929,6 +1021,16 @@ impl MaciContract {
         app.wrap()
             .query_wasm_smart(self.addr(), &QueryMsg::QueryTotalFeeGrant {})
     }
+
+    pub fn batch_records(
+        &self,
+        app: &App,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<BatchRecord>> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::BatchRecords { start_after, limit })
+    }
 }
 
 impl From<Addr> for MaciContract {
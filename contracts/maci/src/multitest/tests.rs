@@ -1,13 +1,26 @@
 #[cfg(test)]
 mod test {
+    use crate::contract::{instantiate, migrate, query};
     use crate::error::ContractError;
-    use crate::msg::{Groth16ProofType, PlonkProofType};
+    use crate::msg::{Groth16ProofType, Groth16VKeyInput, MigrateMsg, PlonkProofType, QueryMsg};
     use crate::multitest::{
-        create_app, owner, uint256_from_decimal_string, user1, user2, MaciCodeId,
+        create_app, owner, uint256_from_decimal_string, user1, user2, MaciCodeId, MaciContract,
     };
-    use crate::state::{MessageData, Period, PeriodStatus, PubKey, RoundInfo};
-    use cosmwasm_std::{coins, Addr, Uint128, Uint256};
+    use crate::state::{
+        BatchStep, MessageData, Period, PeriodStatus, PubKey, RoundInfo, SignUpTime,
+        GROTH16_PROCESS_VKEYS, MAX_VOTE_OPTIONS, NUMSIGNUPS, RESULT, TOTAL_RESULT,
+        VOICECREDITBALANCE,
+    };
+    use crate::vkey::{format_groth16_vkey, format_plonk_vkey, groth16_vkey_type, plonk_vkey_type};
+    use bellman_ce::plonk::better_cs::cs::PlonkCsWidth4WithNextStepParams;
+    use bellman_ce::plonk::better_cs::keys::VerificationKey;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{attr, coins, from_json, Addr, Timestamp, Uint128, Uint256};
     use cw_multi_test::{next_block, AppBuilder, StargateAccepting};
+    use ff_ce::PrimeField;
+    use maci_core::plonk_parser::parse_plonk_vkey;
+    use pairing_ce::bn256::{Bn256, Fr, G1Affine, G2Affine};
+    use pairing_ce::CurveAffine;
     use serde::{Deserialize, Serialize};
     use serde_json;
     use std::fs;
@@ -582,6 +595,28 @@ mod test {
             .process_tally(&mut app, owner(), new_tally_commitment, tally_proof)
             .unwrap();
 
+        let records = contract.batch_records(&app, None, None).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].step, BatchStep::ProcessMessage);
+        assert_eq!(records[0].start_index, Uint256::zero());
+        assert_eq!(records[0].end_index, contract.msg_length(&app).unwrap());
+        assert_eq!(records[0].new_commitment, new_state_commitment);
+        assert_eq!(records[0].certification_system, "groth16");
+        assert_eq!(records[0].submitter, owner());
+        assert_eq!(records[1].id, 1);
+        assert_eq!(records[1].step, BatchStep::ProcessTally);
+        assert_eq!(records[1].old_commitment, Uint256::zero());
+        assert_eq!(records[1].new_commitment, new_tally_commitment);
+        assert_eq!(records[1].end_index, contract.num_sign_up(&app).unwrap());
+        assert_eq!(
+            contract.batch_records(&app, Some(0), None).unwrap(),
+            vec![records[1].clone()]
+        );
+        assert_eq!(
+            contract.batch_records(&app, None, Some(1)).unwrap(),
+            vec![records[0].clone()]
+        );
+
         let results: Vec<Uint256> = result_data
             .results
             .iter()
@@ -1931,4 +1966,417 @@ mod test {
             contract.get_period(&app).unwrap()
         );
     }
+
+    #[test]
+    fn sign_up_window_closes_before_voting_opens() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let label = "Group";
+        let sign_up_start = Timestamp::from_nanos(1571797424879000000);
+        let sign_up_time = SignUpTime {
+            start_time: sign_up_start,
+            end_time: sign_up_start.plus_minutes(5),
+        };
+        let contract = code_id
+            .instantiate_with_sign_up_time(
+                &mut app,
+                owner(),
+                user1(),
+                user2(),
+                sign_up_time.clone(),
+                label,
+            )
+            .unwrap();
+
+        let voting_time = contract.get_voting_time(&app).unwrap();
+        assert_eq!(voting_time.start_time, Some(sign_up_start.plus_minutes(6)));
+        assert_eq!(voting_time.sign_up_time, Some(sign_up_time));
+
+        let pubkey = PubKey {
+            x: uint256_from_decimal_string(
+                "3557592161792765812904087712812111121909518311142005886657252371904276697771",
+            ),
+            y: uint256_from_decimal_string(
+                "4363822302427519764561660537570341277214758164895027920046745209970137856681",
+            ),
+        };
+
+        app.update_block(next_block); // Start sign up
+        contract.sign_up(&mut app, user1(), pubkey.clone()).unwrap();
+        assert_eq!(
+            contract.num_sign_up(&app).unwrap(),
+            Uint256::from_u128(1u128)
+        );
+
+        let publish_error = contract
+            .publish_message(
+                &mut app,
+                user1(),
+                MessageData {
+                    data: [Uint256::from_u128(1u128); 7],
+                },
+                pubkey.clone(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::PeriodError {},
+            publish_error.downcast().unwrap()
+        ); // Cannot vote before voting phase
+
+        app.update_block(|block| block.time = block.time.plus_minutes(11)); // Start Voting
+
+        let sign_up_error = contract.sign_up(&mut app, user2(), pubkey).unwrap_err();
+        assert_eq!(
+            ContractError::PeriodError {},
+            sign_up_error.downcast().unwrap()
+        ); // Cannot signup after sign up phase
+    }
+
+    #[test]
+    fn values_outside_the_snark_field_are_rejected() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let sign_up_start = Timestamp::from_nanos(1571797424879000000);
+        let contract = code_id
+            .instantiate_with_sign_up_time(
+                &mut app,
+                owner(),
+                user1(),
+                user2(),
+                SignUpTime {
+                    start_time: sign_up_start,
+                    end_time: sign_up_start.plus_minutes(5),
+                },
+                "Group",
+            )
+            .unwrap();
+        let snark_scalar_field = uint256_from_decimal_string(
+            "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+        );
+        let pubkey = PubKey {
+            x: uint256_from_decimal_string(
+                "3557592161792765812904087712812111121909518311142005886657252371904276697771",
+            ),
+            y: uint256_from_decimal_string(
+                "4363822302427519764561660537570341277214758164895027920046745209970137856681",
+            ),
+        };
+
+        app.update_block(next_block); // Start sign up
+        let sign_up_error = contract
+            .sign_up(
+                &mut app,
+                user1(),
+                PubKey {
+                    x: snark_scalar_field,
+                    y: pubkey.y,
+                },
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidPrimeField {},
+            sign_up_error.downcast().unwrap()
+        );
+        contract.sign_up(&mut app, user1(), pubkey.clone()).unwrap();
+
+        app.update_block(|block| block.time = block.time.plus_minutes(11)); // Start Voting
+        let mut data = [Uint256::from_u128(1u128); 7];
+        data[6] = snark_scalar_field;
+        let publish_error = contract
+            .publish_message(&mut app, user1(), MessageData { data }, pubkey.clone())
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidPrimeField {},
+            publish_error.downcast().unwrap()
+        );
+        assert_eq!(contract.msg_length(&app).unwrap(), Uint256::zero());
+
+        contract
+            .publish_message(
+                &mut app,
+                user1(),
+                MessageData {
+                    data: [Uint256::from_u128(1u128); 7],
+                },
+                pubkey,
+            )
+            .unwrap();
+        assert_eq!(contract.msg_length(&app).unwrap(), Uint256::one());
+    }
+
+    #[test]
+    fn migrate_round_without_contract_version() {
+        let mut deps = mock_dependencies();
+
+        // Rounds instantiated before versioning have no cw2 info and migrate as v0.1.0
+        let res = migrate(
+            deps.as_mut(),
+            mock_env(),
+            MigrateMsg {
+                voice_credit_balances: None,
+            },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("from_version", "0.1.0")));
+        assert!(res.attributes.contains(&attr("version", "0.1.1")));
+        // One action for the whole migration, one version for each step it applied
+        let keys: Vec<&str> = res.attributes.iter().map(|a| a.key.as_str()).collect();
+        assert_eq!(keys.iter().filter(|key| **key == "action").count(), 1);
+        assert_eq!(keys.iter().filter(|key| **key == "version").count(), 3);
+
+        let version = cw2::get_contract_version(deps.as_ref().storage).unwrap();
+        assert_eq!(version.contract, "crates.io:cw-maci");
+        assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn result_and_voice_credit_balance_are_independent() {
+        let mut deps = mock_dependencies();
+        let index = Uint256::from_u128(0u128);
+        VOICECREDITBALANCE
+            .save(
+                deps.as_mut().storage,
+                index.to_be_bytes().to_vec(),
+                &Uint256::from_u128(100u128),
+            )
+            .unwrap();
+        RESULT
+            .save(
+                deps.as_mut().storage,
+                index.to_be_bytes().to_vec(),
+                &Uint256::from_u128(7u128),
+            )
+            .unwrap();
+
+        let result: Uint256 =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::GetResult { index }).unwrap())
+                .unwrap();
+        let balance: Uint256 = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetVoiceCreditBalance { index },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(result, Uint256::from_u128(7u128));
+        assert_eq!(balance, Uint256::from_u128(100u128));
+    }
+
+    #[test]
+    fn migrate_relocates_results_and_restores_balances() {
+        let mut deps = mock_dependencies();
+        cw2::set_contract_version(deps.as_mut().storage, "crates.io:cw-maci", "0.1.1").unwrap();
+        NUMSIGNUPS
+            .save(deps.as_mut().storage, &Uint256::from_u128(2u128))
+            .unwrap();
+        // Tallied results [0, 15, 4, 0, 0] written over the shared namespace of older rounds
+        let results = [0u128, 15, 4, 0, 0].map(Uint256::from_u128);
+        for (i, result) in results.iter().enumerate() {
+            VOICECREDITBALANCE
+                .save(
+                    deps.as_mut().storage,
+                    Uint256::from_u128(i as u128).to_be_bytes().to_vec(),
+                    result,
+                )
+                .unwrap();
+        }
+        TOTAL_RESULT
+            .save(deps.as_mut().storage, &Uint256::from_u128(19u128))
+            .unwrap();
+        MAX_VOTE_OPTIONS
+            .save(deps.as_mut().storage, &Uint256::from_u128(5u128))
+            .unwrap();
+
+        // The overwritten balances can only come from the migrator
+        let err = migrate(
+            deps.as_mut(),
+            mock_env(),
+            MigrateMsg {
+                voice_credit_balances: None,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::MissingVoiceCreditBalances {
+                count: Uint256::from_u128(2u128)
+            }
+        );
+
+        cw2::set_contract_version(deps.as_mut().storage, "crates.io:cw-maci", "0.1.1").unwrap();
+        let res = migrate(
+            deps.as_mut(),
+            mock_env(),
+            MigrateMsg {
+                voice_credit_balances: Some(vec![
+                    Uint256::from_u128(100u128),
+                    Uint256::from_u128(80u128),
+                ]),
+            },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("relocated_results", "5")));
+        assert!(res.attributes.contains(&attr("restored_balances", "2")));
+
+        for (i, result) in results.iter().enumerate() {
+            let key = Uint256::from_u128(i as u128).to_be_bytes().to_vec();
+            assert_eq!(RESULT.load(deps.as_ref().storage, key).unwrap(), *result);
+        }
+        let balance = |i: u128| {
+            VOICECREDITBALANCE
+                .may_load(
+                    deps.as_ref().storage,
+                    Uint256::from_u128(i).to_be_bytes().to_vec(),
+                )
+                .unwrap()
+        };
+        assert_eq!(balance(0), Some(Uint256::from_u128(100u128)));
+        assert_eq!(balance(1), Some(Uint256::from_u128(80u128)));
+        assert_eq!(balance(2), None);
+    }
+
+    // The process key of `MaciContract::instantiate_msg` as snarkjs writes it to
+    // verification_key.json
+    const SNARKJS_PROCESS_VKEY: &str = r#"{
+        "protocol": "groth16",
+        "curve": "bn128",
+        "nPublic": 1,
+        "vk_alpha_1": [
+            "20491192805390485299153009773594534940189261866228447918068658471970481763042",
+            "9383485363053290200918347156157836566562967994039712273449902621266178545958",
+            "1"
+        ],
+        "vk_beta_2": [
+            [
+                "6375614351688725206403948262868962793625744043794305715222011528459656738731",
+                "4252822878758300859123897981450591353533073413197771768651442665752259397132"
+            ],
+            [
+                "10505242626370262277552901082094356697409835680220590971873171140371331206856",
+                "21847035105528745403288232691147584728191162732299865338377159692350059136679"
+            ],
+            ["1", "0"]
+        ],
+        "vk_gamma_2": [
+            [
+                "10857046999023057135944570762232829481370756359578518086990519993285655852781",
+                "11559732032986387107991004021392285783925812861821192530917403151452391805634"
+            ],
+            [
+                "8495653923123431417604973247489272438418190587263600148770280649306958101930",
+                "4082367875863433681332203403145435568316851327593401208105741076214120093531"
+            ],
+            ["1", "0"]
+        ],
+        "vk_delta_2": [
+            [
+                "10565827549362248769804996567892334081937384235985943310853103882658969491760",
+                "15139517316861983252330250584925824629888716629386768859865484837313836759645"
+            ],
+            [
+                "6116931472055875750861408387199061525010963021153223825877771024756004232904",
+                "12809843942146321492371554588555667195435872298105846152941716716411552907958"
+            ],
+            ["1", "0"]
+        ],
+        "IC": [
+            [
+                "11340358329368933791422594486370669685006627364785949555745511303878948278371",
+                "5932126453363163394364276276943289375207159056244954336384395943020967526236",
+                "1"
+            ],
+            [
+                "2803807870769477237182614291490570266173045192496849055766968983345769726679",
+                "10318119143849397691362597753105476860699776036279875462512286754904854873623",
+                "1"
+            ]
+        ]
+    }"#;
+
+    #[test]
+    fn instantiate_with_snarkjs_verification_key() {
+        let round_info = RoundInfo {
+            title: String::from("HackWasm Berlin"),
+            description: String::from("Hack In Brelin"),
+            link: String::from("https://baidu.com"),
+        };
+        let hex_msg = MaciContract::instantiate_msg(round_info, None, None, None, Uint256::zero());
+
+        let mut snarkjs_msg = hex_msg.clone();
+        snarkjs_msg.groth16_process_vkey =
+            Some(from_json(SNARKJS_PROCESS_VKEY.as_bytes()).unwrap());
+        assert!(matches!(
+            snarkjs_msg.groth16_process_vkey,
+            Some(Groth16VKeyInput::Snarkjs(_))
+        ));
+
+        let mut hex_deps = mock_dependencies();
+        instantiate(
+            hex_deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            hex_msg,
+        )
+        .unwrap();
+        let mut snarkjs_deps = mock_dependencies();
+        instantiate(
+            snarkjs_deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            snarkjs_msg,
+        )
+        .unwrap();
+
+        assert_eq!(
+            GROTH16_PROCESS_VKEYS
+                .load(snarkjs_deps.as_ref().storage)
+                .unwrap(),
+            GROTH16_PROCESS_VKEYS
+                .load(hex_deps.as_ref().storage)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn snarkjs_vkey_converts_to_the_instantiate_hex_key() {
+        let round_info = RoundInfo {
+            title: String::from("HackWasm Berlin"),
+            description: String::from("Hack In Brelin"),
+            link: String::from("https://baidu.com"),
+        };
+        let hex_msg = MaciContract::instantiate_msg(round_info, None, None, None, Uint256::zero());
+
+        let snarkjs =
+            Groth16VKeyInput::Snarkjs(from_json(SNARKJS_PROCESS_VKEY.as_bytes()).unwrap());
+        let converted = groth16_vkey_type(&format_groth16_vkey(snarkjs).unwrap());
+        assert_eq!(
+            hex_msg.groth16_process_vkey,
+            Some(Groth16VKeyInput::Uncompressed(converted))
+        );
+    }
+
+    #[test]
+    fn plonk_vkey_conversion_round_trips() {
+        let fr = |n: &str| Fr::from_str(n).unwrap();
+        let vk = VerificationKey::<Bn256, PlonkCsWidth4WithNextStepParams> {
+            n: 1023,
+            num_inputs: 1,
+            selector_commitments: vec![G1Affine::one(); 6],
+            next_step_selector_commitments: vec![G1Affine::one()],
+            permutation_commitments: vec![G1Affine::one(); 4],
+            non_residues: vec![fr("5"), fr("7"), fr("10")],
+            g2_elements: [G2Affine::one(); 2],
+            _marker: std::marker::PhantomData,
+        };
+
+        let vkey = plonk_vkey_type(&vk);
+        assert_eq!(vkey.non_residues[2], format!("{:0>64}", "a"));
+        let parsed = parse_plonk_vkey::<Bn256, PlonkCsWidth4WithNextStepParams>(
+            format_plonk_vkey(vkey.clone()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(plonk_vkey_type(&parsed), vkey);
+    }
 }
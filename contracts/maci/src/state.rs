@@ -1,7 +1,10 @@
-use crate::utils::hash5;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Timestamp, Uint128, Uint256};
 use cw_storage_plus::{Item, Map};
+pub use maci_core::{
+    Groth16PreparedVkeyStr, Groth16ProofStr, Groth16VkeyStr, IncrementalQuinaryTree, MessageData,
+    PlonkProofStr, PlonkVkeyStr, PubKey, QuinaryTreeRoot, StateLeaf,
+};
 
 #[cw_serde]
 pub struct RoundInfo {
@@ -20,6 +23,15 @@ pub struct VotingTime {
 
 pub const VOTINGTIME: Item<VotingTime> = Item::new("voting_time");
 
+/// Optional registration window. When it is not set, sign up follows `VotingTime`.
+#[cw_serde]
+pub struct SignUpTime {
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
+}
+
+pub const SIGNUPTIME: Item<SignUpTime> = Item::new("sign_up_time");
+
 pub const VOTEOPTIONMAP: Item<Vec<String>> = Item::new("vote_option_map");
 
 #[cw_serde]
@@ -69,132 +81,28 @@ pub const NUMSIGNUPS: Item<Uint256> = Item::new("num_sign_ups");
 // key is state_key, value is sender balance
 pub const VOICECREDITBALANCE: Map<Vec<u8>, Uint256> = Map::new("voice_credit_balance");
 
-pub const NODES: Map<Vec<u8>, Uint256> = Map::new("nodes");
+// Frontier of the state tree, the nodes of rounds before v0.1.3 were kept under "nodes"
+pub const STATE_TREE: Item<IncrementalQuinaryTree> = Item::new("state_tree");
 
 pub const MAX_VOTE_OPTIONS: Item<Uint256> = Item::new("max_vote_options");
 pub const CURRENT_STATE_COMMITMENT: Item<Uint256> = Item::new("current_state_commitment");
 pub const CURRENT_TALLY_COMMITMENT: Item<Uint256> = Item::new("current_tally_commitment");
 
-pub const RESULT: Map<Vec<u8>, Uint256> = Map::new("voice_credit_balance");
+pub const RESULT: Map<Vec<u8>, Uint256> = Map::new("result");
 pub const TOTAL_RESULT: Item<Uint256> = Item::new("total_result");
 
-#[cw_serde]
-pub struct PubKey {
-    pub x: Uint256,
-    pub y: Uint256,
-}
-
-#[cw_serde]
-pub struct StateLeaf {
-    pub pub_key: PubKey,
-    pub voice_credit_balance: Uint256,
-    pub vote_option_tree_root: Uint256,
-    pub nonce: Uint256,
-}
-
-impl StateLeaf {
-    pub fn hash_state_leaf(&self) -> Uint256 {
-        let mut plaintext: [Uint256; 5] = [Uint256::from_u128(0); 5];
-
-        plaintext[0] = self.pub_key.x;
-        plaintext[1] = self.pub_key.y;
-        plaintext[2] = self.voice_credit_balance;
-        plaintext[3] = self.vote_option_tree_root;
-        plaintext[4] = self.nonce;
-        return hash5(plaintext);
-    }
-}
-
 // Init Data
 pub const MAX_LEAVES_COUNT: Item<Uint256> = Item::new("max_leaves_count");
 pub const LEAF_IDX_0: Item<Uint256> = Item::new("leaf_idx_0");
 pub const COORDINATORHASH: Item<Uint256> = Item::new("coordinator_hash");
 pub const ZEROS: Item<[Uint256; 8]> = Item::new("zeros");
 
-#[cw_serde]
-pub struct MessageData {
-    pub data: [Uint256; 7],
-}
-
 pub const MSG_HASHES: Map<Vec<u8>, Uint256> = Map::new("msg_hashes");
 // pub const MSG_HASHES: Item<Vec<Uint256>> = Item::new("msg_hashes");
 pub const MSG_CHAIN_LENGTH: Item<Uint256> = Item::new("msg_chain_length");
 pub const PROCESSED_MSG_COUNT: Item<Uint256> = Item::new("processed_msg_count");
 pub const PROCESSED_USER_COUNT: Item<Uint256> = Item::new("processed_user_count");
 
-#[cw_serde]
-pub struct Groth16ProofStr {
-    pub pi_a: Vec<u8>,
-    pub pi_b: Vec<u8>,
-    pub pi_c: Vec<u8>,
-}
-
-#[cw_serde]
-pub struct QuinaryTreeRoot {
-    pub zeros: [Uint256; 9],
-}
-
-impl QuinaryTreeRoot {
-    const DEGREE: u32 = 5;
-
-    pub fn root_of(&self, depth: Uint256, nodes: Vec<Uint256>) -> Uint256 {
-        let _depth = depth.to_string().parse().unwrap();
-        let capacity = Self::DEGREE.pow(_depth);
-        let length = nodes.len() as u32;
-
-        assert!(capacity >= length, "overflow");
-
-        let mut c = capacity / Self::DEGREE;
-        let mut pl = (length - 1) / Self::DEGREE + 1;
-        let mut _nodes = nodes;
-
-        for i in 0.._depth {
-            let zero = self.get_zero(i);
-            // number of non-zero parent nodes
-            for j in 0..c {
-                if j >= length {
-                    continue;
-                }
-                let mut h = Uint256::zero();
-                if j < pl {
-                    let mut inputs = [Uint256::zero(); 5];
-                    let mut s = Uint256::zero();
-                    for k in 0..5 {
-                        let node = if j * 5 + k < length {
-                            _nodes[(j * 5 + k) as usize]
-                        } else {
-                            Uint256::zero()
-                        };
-                        s += node;
-                        let mut input = node;
-                        if node == Uint256::zero() {
-                            input = zero;
-                        }
-                        inputs[k as usize] = input;
-                    }
-                    if s > Uint256::zero() {
-                        h = hash5(inputs);
-                    }
-                }
-                _nodes[j as usize] = h;
-            }
-
-            pl = (pl - 1) / Self::DEGREE + 1;
-            c = c / Self::DEGREE;
-        }
-
-        let mut result = _nodes[0];
-        if result == Uint256::zero() {
-            result = self.get_zero(_depth);
-        }
-        result
-    }
-
-    fn get_zero(&self, height: u32) -> Uint256 {
-        self.zeros[height as usize]
-    }
-}
-
 pub const QTR_LIB: Item<QuinaryTreeRoot> = Item::new("qtr_lib");
 
 #[cw_serde]
@@ -248,50 +156,44 @@ pub const CIRCUITTYPE: Item<Uint256> = Item::new("circuit_type"); // <0: 1p1v |
 
 pub const CERTSYSTEM: Item<Uint256> = Item::new("certification_system"); // <0: groth16 | 1: plonk>
 
-#[cw_serde]
-pub struct PlonkProofStr {
-    pub num_inputs: usize,
-    pub n: usize,
-    pub input_values: Vec<String>,
-    pub wire_commitments: Vec<Vec<u8>>,
-    pub grand_product_commitment: Vec<u8>,
-    pub quotient_poly_commitments: Vec<Vec<u8>>,
-    pub wire_values_at_z: Vec<String>,
-    pub wire_values_at_z_omega: Vec<String>,
-    pub grand_product_at_z_omega: String,
-    pub quotient_polynomial_at_z: String,
-    pub linearization_polynomial_at_z: String,
-    pub permutation_polynomials_at_z: Vec<String>,
-    pub opening_at_z_proof: Vec<u8>,
-    pub opening_at_z_omega_proof: Vec<u8>,
-}
+pub const GROTH16_PROCESS_VKEYS: Item<Groth16VkeyStr> = Item::new("groth16_process_vkeys");
+pub const GROTH16_TALLY_VKEYS: Item<Groth16VkeyStr> = Item::new("groth16_tally_vkeys");
+
+// prepared on the first verification with each key
+pub const GROTH16_PROCESS_PREPARED_VKEYS: Item<Groth16PreparedVkeyStr> =
+    Item::new("groth16_process_prepared_vkeys");
+pub const GROTH16_TALLY_PREPARED_VKEYS: Item<Groth16PreparedVkeyStr> =
+    Item::new("groth16_tally_prepared_vkeys");
+
+pub const PLONK_PROCESS_VKEYS: Item<PlonkVkeyStr> = Item::new("plonk_process_vkeys");
+pub const PLONK_TALLY_VKEYS: Item<PlonkVkeyStr> = Item::new("plonk_tally_vkeys");
 
 #[cw_serde]
-pub struct Groth16VkeyStr {
-    pub alpha_1: Vec<u8>,
-    pub beta_2: Vec<u8>,
-    pub gamma_2: Vec<u8>,
-    pub delta_2: Vec<u8>,
-    pub ic0: Vec<u8>,
-    pub ic1: Vec<u8>,
+pub enum BatchStep {
+    ProcessMessage,
+    ProcessTally,
 }
 
-pub const GROTH16_PROCESS_VKEYS: Item<Groth16VkeyStr> = Item::new("groth16_process_vkeys");
-pub const GROTH16_TALLY_VKEYS: Item<Groth16VkeyStr> = Item::new("groth16_tally_vkeys");
-
+/// A batch whose proof was accepted. The index range is over the message chain for
+/// `ProcessMessage` and over the state leaves for `ProcessTally`.
 #[cw_serde]
-pub struct PlonkVkeyStr {
-    pub n: usize,
-    pub num_inputs: usize,
-    pub selector_commitments: Vec<Vec<u8>>,
-    pub next_step_selector_commitments: Vec<Vec<u8>>,
-    pub permutation_commitments: Vec<Vec<u8>>,
-    pub non_residues: Vec<String>,
-    pub g2_elements: Vec<Vec<u8>>,
+pub struct BatchRecord {
+    pub id: u64,
+    pub step: BatchStep,
+    pub start_index: Uint256,
+    pub end_index: Uint256,
+    pub old_commitment: Uint256,
+    pub new_commitment: Uint256,
+    pub input_hash: Uint256,
+    pub certification_system: String,
+    /// the proof as JSON, the same as in the `process_message` and `process_tally` events
+    pub proof: String,
+    pub block_time: Timestamp,
+    pub submitter: Addr,
 }
 
-pub const PLONK_PROCESS_VKEYS: Item<PlonkVkeyStr> = Item::new("plonk_process_vkeys");
-pub const PLONK_TALLY_VKEYS: Item<PlonkVkeyStr> = Item::new("plonk_tally_vkeys");
+pub const BATCH_RECORDS: Map<u64, BatchRecord> = Map::new("batch_records");
+pub const BATCH_RECORD_COUNT: Item<u64> = Item::new("batch_record_count");
 
 #[cfg(test)]
 mod tests {
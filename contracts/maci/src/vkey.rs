@@ -0,0 +1,89 @@
+use crate::error::ContractError;
+use crate::msg::{Groth16VKeyInput, Groth16VKeyType, PlonkVKeyType};
+use crate::state::{Groth16VkeyStr, PlonkVkeyStr};
+use bellman_ce::plonk::better_cs::cs::PlonkCsWidth4WithNextStepParams;
+use bellman_ce::plonk::better_cs::keys::VerificationKey;
+use ff_ce::to_hex;
+use maci_core::groth16_parser::{parse_groth16_vkey, parse_snarkjs_vkey};
+use maci_core::plonk_parser::parse_plonk_vkey;
+use pairing_ce::bn256::{Bn256, G1Affine};
+use pairing_ce::CurveAffine;
+
+/// Decode a groth16 key from the instantiate message and check that it is a valid key
+pub fn format_groth16_vkey(vkey: Groth16VKeyInput) -> Result<Groth16VkeyStr, ContractError> {
+    let vkey_str = match vkey {
+        Groth16VKeyInput::Uncompressed(vkey) => Groth16VkeyStr {
+            alpha_1: decode(&vkey.vk_alpha1)?,
+            beta_2: decode(&vkey.vk_beta_2)?,
+            gamma_2: decode(&vkey.vk_gamma_2)?,
+            delta_2: decode(&vkey.vk_delta_2)?,
+            ic0: decode(&vkey.vk_ic0)?,
+            ic1: decode(&vkey.vk_ic1)?,
+        },
+        Groth16VKeyInput::Snarkjs(vkey) => parse_snarkjs_vkey(&vkey)?,
+    };
+    let _ = parse_groth16_vkey::<Bn256>(vkey_str.clone())?;
+    Ok(vkey_str)
+}
+
+/// Decode a plonk key from the instantiate message and check that it is a valid key
+pub fn format_plonk_vkey(vkey: PlonkVKeyType) -> Result<PlonkVkeyStr, ContractError> {
+    let decode_all = |points: Vec<String>| {
+        points
+            .iter()
+            .map(|point| decode(point))
+            .collect::<Result<Vec<Vec<u8>>, ContractError>>()
+    };
+    let vkey_str = PlonkVkeyStr {
+        n: vkey.n,
+        num_inputs: vkey.num_inputs,
+        selector_commitments: decode_all(vkey.selector_commitments)?,
+        next_step_selector_commitments: decode_all(vkey.next_step_selector_commitments)?,
+        permutation_commitments: decode_all(vkey.permutation_commitments)?,
+        non_residues: vkey.non_residues,
+        g2_elements: decode_all(vkey.g2_elements)?,
+    };
+    let _ = parse_plonk_vkey::<Bn256, PlonkCsWidth4WithNextStepParams>(vkey_str.clone())?;
+    Ok(vkey_str)
+}
+
+/// The hex form of a decoded groth16 key, as the instantiate message takes it
+pub fn groth16_vkey_type(vkey: &Groth16VkeyStr) -> Groth16VKeyType {
+    Groth16VKeyType {
+        vk_alpha1: hex::encode(&vkey.alpha_1),
+        vk_beta_2: hex::encode(&vkey.beta_2),
+        vk_gamma_2: hex::encode(&vkey.gamma_2),
+        vk_delta_2: hex::encode(&vkey.delta_2),
+        vk_ic0: hex::encode(&vkey.ic0),
+        vk_ic1: hex::encode(&vkey.ic1),
+    }
+}
+
+/// The hex form of a bellman plonk key, with uncompressed points
+pub fn plonk_vkey_type(
+    vk: &VerificationKey<Bn256, PlonkCsWidth4WithNextStepParams>,
+) -> PlonkVKeyType {
+    let g1_all = |points: &[G1Affine]| -> Vec<String> {
+        points
+            .iter()
+            .map(|point| hex::encode(point.into_uncompressed().as_ref()))
+            .collect()
+    };
+    PlonkVKeyType {
+        n: vk.n,
+        num_inputs: vk.num_inputs,
+        selector_commitments: g1_all(&vk.selector_commitments),
+        next_step_selector_commitments: g1_all(&vk.next_step_selector_commitments),
+        permutation_commitments: g1_all(&vk.permutation_commitments),
+        non_residues: vk.non_residues.iter().map(to_hex).collect(),
+        g2_elements: vk
+            .g2_elements
+            .iter()
+            .map(|point| hex::encode(point.into_uncompressed().as_ref()))
+            .collect(),
+    }
+}
+
+fn decode(point: &str) -> Result<Vec<u8>, ContractError> {
+    hex::decode(point).map_err(|_| ContractError::HexDecodingError {})
+}
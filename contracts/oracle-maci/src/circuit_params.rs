@@ -1,10 +1,10 @@
 use crate::error::ContractError;
-use crate::groth16_parser::parse_groth16_vkey;
 use crate::{
     msg::Groth16VKeyType,
     state::{Groth16VkeyStr, MaciParameters},
 };
 use cosmwasm_std::Uint256;
+use maci_core::groth16_parser::parse_groth16_vkey;
 use pairing_ce::bn256::Bn256;
 
 pub struct OracleVkeyParams {
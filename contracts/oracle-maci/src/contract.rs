@@ -1,21 +1,29 @@
 use crate::circuit_params::{calculate_circuit_params, match_oracle_vkeys};
 use crate::error::ContractError;
-use crate::groth16_parser::{parse_groth16_proof, parse_groth16_vkey};
+use crate::migrates::migrate_v0_1_1::migrate_v0_1_1;
+use crate::migrates::migrate_v0_1_2::migrate_v0_1_2;
+use crate::migrates::migrate_v0_1_3::migrate_v0_1_3;
 use crate::msg::{
-    ExecuteMsg, Groth16ProofType, InstantiateMsg, InstantiationData, PlonkProofType, QueryMsg,
+    ExecuteMsg, Groth16ProofInput, Groth16ProofType, InstantiateMsg, InstantiationData, MigrateMsg,
+    PlonkProofType, QueryMsg, VotingTimeResponse,
 };
-use crate::plonk_parser::{parse_plonk_proof, parse_plonk_vkey};
 use crate::state::{
-    Admin, FeeGrantOperator, GrantConfig, Groth16ProofStr, MessageData, OracleWhitelistConfig,
+    Admin, BatchRecord, BatchStep, FeeGrantOperator, GrantConfig, Groth16PreparedVkeyStr,
+    Groth16ProofStr, Groth16VkeyStr, IncrementalQuinaryTree, MessageData, OracleWhitelistConfig,
     Period, PeriodStatus, PlonkProofStr, PubKey, QuinaryTreeRoot, RoundInfo, StateLeaf,
-    VotingPowerConfig, VotingPowerMode, VotingTime, WhitelistConfig, ADMIN, CERTSYSTEM,
-    CIRCUITTYPE, COORDINATORHASH, CURRENT_STATE_COMMITMENT, CURRENT_TALLY_COMMITMENT,
-    FEEGRANTOPERATOR, FEEGRANTS, GRANTLIST, GROTH16_PROCESS_VKEYS, GROTH16_TALLY_VKEYS, LEAF_IDX_0,
-    MACIPARAMETERS, MAX_LEAVES_COUNT, MAX_VOTE_OPTIONS, MAX_WHITELIST_NUM, MSG_CHAIN_LENGTH,
-    MSG_HASHES, NODES, NUMSIGNUPS, ORACLE_WHITELIST_CONFIG, PERIOD, PLONK_PROCESS_VKEYS,
-    PLONK_TALLY_VKEYS, PROCESSED_MSG_COUNT, PROCESSED_USER_COUNT, QTR_LIB, RESULT, ROUNDINFO,
-    STATEIDXINC, TOTAL_RESULT, VOICECREDITBALANCE, VOTEOPTIONMAP, VOTINGTIME, WHITELIST, ZEROS,
+    VotingPowerConfig, VotingPowerMode, VotingTime, WhitelistConfig, ADMIN, BATCH_RECORDS,
+    BATCH_RECORD_COUNT, CERTSYSTEM, CIRCUITTYPE, COORDINATORHASH, CURRENT_STATE_COMMITMENT,
+    CURRENT_TALLY_COMMITMENT, FEEGRANTOPERATOR, FEEGRANTS, GRANTLIST,
+    GROTH16_PROCESS_PREPARED_VKEYS, GROTH16_PROCESS_VKEYS, GROTH16_TALLY_PREPARED_VKEYS,
+    GROTH16_TALLY_VKEYS, LEAF_IDX_0, MACIPARAMETERS, MAX_LEAVES_COUNT, MAX_VOTE_OPTIONS,
+    MAX_WHITELIST_NUM, MSG_CHAIN_LENGTH, MSG_HASHES, NUMSIGNUPS, ORACLE_WHITELIST_CONFIG, PERIOD,
+    PLONK_PROCESS_VKEYS, PLONK_TALLY_VKEYS, PROCESSED_MSG_COUNT, PROCESSED_USER_COUNT, QTR_LIB,
+    RESULT, ROUNDINFO, SIGNUPTIME, STATEIDXINC, STATE_TREE, TOTAL_RESULT, VOICECREDITBALANCE,
+    VOTEOPTIONMAP, VOTINGTIME, WHITELIST, ZEROS,
 };
+use maci_core::groth16_parser::{parse_groth16_proof, parse_groth16_vkey, parse_public_input};
+use maci_core::groth16_verifier::{prepare_groth16_vkey, verify_groth16_prepared};
+use maci_core::plonk_parser::{parse_plonk_proof, parse_plonk_vkey};
 use sha2::{Digest as ShaDigest, Sha256};
 
 use pairing_ce::bn256::Bn256;
@@ -25,6 +33,8 @@ use bellman_ce::plonk::better_cs::cs::PlonkCsWidth4WithNextStepParams;
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
+use cw2::set_contract_version;
+use cw_storage_plus::{Bound, Item};
 
 use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as SdkCoin;
 use cosmos_sdk_proto::cosmos::feegrant::v1beta1::{
@@ -36,22 +46,25 @@ use cosmos_sdk_proto::Any;
 use prost_types::Timestamp as SdkTimestamp;
 
 use cosmwasm_std::{
-    attr, coins, to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-    Reply, Response, StdResult, Uint128, Uint256,
+    attr, coins, to_json_binary, to_json_string, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut,
+    Env, Event, MessageInfo, Order, Reply, Response, StdResult, Storage, Uint128, Uint256,
 };
 
-use crate::utils::{hash2, hash5, hash_256_uint256_list, uint256_from_hex_string};
+use maci_core::utils::{
+    hash2, hash_256_uint256_list, try_hash2, try_hash5, uint256_from_hex_string,
+};
 
 use bellman_ce::plonk::better_cs::verifier::verify as plonk_verify;
 use bellman_ce::plonk::commitments::transcript::keccak_transcript::RollingKeccakTranscript;
-use bellman_ce_verifier::{prepare_verifying_key, verify_proof as groth16_verify};
-
-use ff_ce::PrimeField as Fr;
 
 use hex;
 
 use serde_json;
 
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw-oracle-maci";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 // Pre-computed constants to avoid repeated calculations
 const SNARK_SCALAR_FIELD_HEX: &str =
     "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
@@ -102,6 +115,7 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     // Clone necessary data for InstantiationData at the beginning
     let caller = info.sender.clone();
     let coordinator = msg.coordinator.clone();
@@ -109,6 +123,7 @@ pub fn instantiate(
     let vote_option_map = msg.vote_option_map.clone();
     let round_info = msg.round_info.clone();
     let voting_time = msg.voting_time.clone();
+    let sign_up_time = msg.sign_up_time.clone();
     let circuit_type = msg.circuit_type;
     let certification_system = msg.certification_system;
     let whitelist_backend_pubkey = msg.whitelist_backend_pubkey.clone();
@@ -125,9 +140,9 @@ pub fn instantiate(
         .map(|coin| coin.amount)
         .unwrap_or_else(|| Uint128::zero());
 
-    // Create an admin with the sender address
+    // Create an admin with the given address, or the sender address
     let admin = Admin {
-        admin: info.sender.clone(),
+        admin: msg.admin.clone().unwrap_or_else(|| info.sender.clone()),
     };
     ADMIN.save(deps.storage, &admin)?;
 
@@ -194,7 +209,7 @@ pub fn instantiate(
     CERTSYSTEM.save(deps.storage, &msg.certification_system)?;
 
     // Compute the coordinator hash from the coordinator values in the message
-    let coordinator_hash = hash2([msg.coordinator.x, msg.coordinator.y]);
+    let coordinator_hash = try_hash2([msg.coordinator.x, msg.coordinator.y])?;
     COORDINATORHASH.save(deps.storage, &coordinator_hash)?;
 
     // Compute the maximum number of leaves based on the state tree depth (optimization: use pre-computed values directly)
@@ -205,12 +220,6 @@ pub fn instantiate(
     let leaf_idx0 = (max_leaves_count - Uint256::from_u128(1u128)) / Uint256::from_u128(4u128);
     LEAF_IDX_0.save(deps.storage, &leaf_idx0)?;
 
-    NODES.save(
-        deps.storage,
-        Uint256::from_u128(0u128).to_be_bytes().to_vec(),
-        &Uint256::from_u128(0u128),
-    )?;
-
     // Define an array of zero values
     let zeros: [Uint256; 10] = [
         uint256_from_hex_string("2066be41bebe6caf7e079360abe14fbf9118c62eabc42e2fe75e342b160a95bc"),
@@ -236,6 +245,13 @@ pub fn instantiate(
     ];
     ZEROS.save(deps.storage, &zeros)?;
 
+    // Sign ups append to the state tree, its root is only hashed when a period needs it
+    let state_tree_depth = state_tree_depth(parameters.state_tree_depth)?;
+    STATE_TREE.save(
+        deps.storage,
+        &IncrementalQuinaryTree::new(state_tree_depth, &zeros)?,
+    )?;
+
     // Save initial values for message hash, message chain length, processed message count, current tally commitment,
     // processed user count, and number of signups to storage
     MSG_HASHES.save(
@@ -281,6 +297,16 @@ pub fn instantiate(
     }
 
     VOTINGTIME.save(deps.storage, &msg.voting_time)?;
+
+    if let Some(sign_up_time) = &msg.sign_up_time {
+        if sign_up_time.start_time >= sign_up_time.end_time
+            || sign_up_time.end_time > msg.voting_time.end_time
+        {
+            return Err(ContractError::WrongSignUpTimeSet {});
+        }
+        SIGNUPTIME.save(deps.storage, sign_up_time)?;
+    }
+
     let whitelist_backend_pubkey_binary = Binary::from_base64(&msg.whitelist_backend_pubkey)
         .map_err(|_| ContractError::InvalidBase64 {})?;
 
@@ -317,6 +343,7 @@ pub fn instantiate(
         vote_option_map,
         round_info,
         voting_time,
+        sign_up_time,
         circuit_type: if circuit_type == Uint256::from_u128(0u128) {
             "0".to_string() // 1p1v
         } else {
@@ -386,7 +413,9 @@ pub fn execute(
             env,
             info,
             new_state_commitment,
-            groth16_proof,
+            groth16_proof
+                .map(Groth16ProofInput::into_uncompressed)
+                .transpose()?,
             plonk_proof,
         ),
         ExecuteMsg::StopProcessingPeriod {} => execute_stop_processing_period(deps, env, info),
@@ -399,7 +428,9 @@ pub fn execute(
             env,
             info,
             new_tally_commitment,
-            groth16_proof,
+            groth16_proof
+                .map(Groth16ProofInput::into_uncompressed)
+                .transpose()?,
             plonk_proof,
         ),
         ExecuteMsg::StopTallyingPeriod { results, salt } => {
@@ -503,8 +534,7 @@ pub fn execute_sign_up(
     amount: Uint256,
     certificate: String,
 ) -> Result<Response, ContractError> {
-    let voting_time = VOTINGTIME.load(deps.storage)?;
-    check_voting_time(env.clone(), voting_time)?;
+    check_sign_up_time(deps.as_ref(), env.clone())?;
 
     if amount == Uint256::from_u128(0u128) {
         return Err(ContractError::AmountIsZero {});
@@ -575,10 +605,9 @@ pub fn execute_sign_up(
     // Check if the number of sign-ups is less than the maximum number of leaves
     assert!(num_sign_ups < max_leaves_count, "full");
     // Check if the pubkey values are within the allowed range
-    assert!(
-        pubkey.x < snark_scalar_field && pubkey.y < snark_scalar_field,
-        "MACI: pubkey values should be less than the snark scalar field"
-    );
+    if pubkey.x >= snark_scalar_field || pubkey.y >= snark_scalar_field {
+        return Err(ContractError::InvalidPrimeField {});
+    }
 
     // Create a state leaf with the provided pubkey and amount (optimization: avoid unnecessary cloning)
     let state_leaf = StateLeaf {
@@ -616,7 +645,12 @@ pub fn execute_sign_up(
             "pubkey",
             format!("{:?},{:?}", pubkey.x.to_string(), pubkey.y.to_string()),
         )
-        .add_attribute("balance", voting_power.to_string()))
+        .add_attribute("balance", voting_power.to_string())
+        .add_event(Event::from(maci_events::SignUp {
+            state_idx: state_index,
+            pubkey: [pubkey.x, pubkey.y],
+            balance: voting_power,
+        })))
 }
 
 // in voting
@@ -645,7 +679,7 @@ pub fn execute_publish_message(
 
         // Compute the new message hash using the provided message, encrypted public key, and previous hash
         let new_hash =
-            hash_message_and_enc_pub_key(message.clone(), enc_pub_key.clone(), old_msg_hashes);
+            hash_message_and_enc_pub_key(message.clone(), enc_pub_key.clone(), old_msg_hashes)?;
         MSG_HASHES.save(
             deps.storage,
             (msg_chain_length + Uint256::from_u128(1u128))
@@ -670,7 +704,13 @@ pub fn execute_publish_message(
                     enc_pub_key.x.to_string(),
                     enc_pub_key.y.to_string()
                 ),
-            ))
+            )
+            .add_event(Event::from(maci_events::PublishMessage {
+                msg_chain_length: old_chain_length,
+                message: message.data.to_vec(),
+                enc_pub_key: [enc_pub_key.x, enc_pub_key.y],
+                question_id: None,
+            })))
     } else {
         // Return an error response for invalid user or encrypted public key
         Ok(Response::new()
@@ -680,7 +720,7 @@ pub fn execute_publish_message(
 }
 
 pub fn execute_start_process_period(
-    mut deps: DepsMut,
+    deps: DepsMut,
     env: Env,
     _info: MessageInfo,
 ) -> Result<Response, ContractError> {
@@ -703,21 +743,13 @@ pub fn execute_start_process_period(
     //     return Err(ContractError::PeriodError {});
     // }
 
-    let leaf_idx_0 = LEAF_IDX_0.load(deps.storage)?;
-    let num_sign_ups = NUMSIGNUPS.load(deps.storage)?;
-    let _ = state_update_at(
-        &mut deps,
-        leaf_idx_0 + num_sign_ups - Uint256::from_u128(1u128),
-        true,
-    );
-
     // Update the period status to Processing
     let period = Period {
         status: PeriodStatus::Processing,
     };
     PERIOD.save(deps.storage, &period)?;
     // Compute the state root
-    let state_root = state_root(deps.as_ref());
+    let state_root = state_root(deps.as_ref())?;
 
     // Compute the current state commitment as the hash of the state root and 0
     CURRENT_STATE_COMMITMENT.save(
@@ -731,8 +763,8 @@ pub fn execute_start_process_period(
 
 pub fn execute_process_message(
     deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
+    env: Env,
+    info: MessageInfo,
     new_state_commitment: Uint256,
     groth16_proof: Option<Groth16ProofType>,
     plonk_proof: Option<PlonkProofType>,
@@ -802,11 +834,10 @@ pub fn execute_process_message(
     let input_hash = uint256_from_hex_string(&hash_256_uint256_list(&input)) % snark_scalar_field; // input hash
 
     let mut attributes = vec![];
+    let mut events = vec![];
+    let mut accepted_proof = None;
 
     if let Some(groth16_proof_data) = groth16_proof {
-        // Load the process verification keys
-        let process_vkeys_str = GROTH16_PROCESS_VKEYS.load(deps.storage)?;
-
         // Parse the SNARK proof (optimization: avoid unnecessary cloning)
         let proof_str = Groth16ProofStr {
             pi_a: hex::decode(&groth16_proof_data.a)
@@ -817,20 +848,18 @@ pub fn execute_process_message(
                 .map_err(|_| ContractError::HexDecodingError {})?,
         };
 
-        // Parse the verification key and prepare for verification
-        let vkey = parse_groth16_vkey::<Bn256>(process_vkeys_str)?;
-        let pvk = prepare_verifying_key(&vkey);
+        // Load the verification key, prepared on its first use
+        let pvk = load_prepared_vkey(
+            deps.storage,
+            &GROTH16_PROCESS_VKEYS,
+            &GROTH16_PROCESS_PREPARED_VKEYS,
+        )?;
 
         // Parse the proof and prepare for verification
         let pof = parse_groth16_proof::<Bn256>(proof_str.clone())?;
 
         // Verify the SNARK proof using the input hash
-        let is_passed = groth16_verify(
-            &pvk,
-            &pof,
-            &[Fr::from_str(&input_hash.to_string()).unwrap()],
-        )
-        .unwrap();
+        let is_passed = verify_groth16_prepared(&pvk, &pof, &[parse_public_input(input_hash)?])?;
 
         // If the proof verification fails, return an error
         if !is_passed {
@@ -845,6 +874,14 @@ pub fn execute_process_message(
             attr("proof", format!("{:?}", groth16_proof_data)),
             attr("certification_system", "groth16"),
         ];
+        accepted_proof = Some(("groth16", to_json_string(&groth16_proof_data)?));
+        events.push(Event::from(maci_events::ProcessMessage {
+            processed_msg_count,
+            new_state_commitment,
+            certification_system: String::from("groth16"),
+            proof: to_json_string(&groth16_proof_data)?,
+            question_id: None,
+        }));
     }
 
     if let Some(plonk_proof_data) = plonk_proof {
@@ -907,9 +944,37 @@ pub fn execute_process_message(
             attr("proof", format!("{:?}", plonk_proof_data)),
             attr("certification_system", "plonk"),
         ];
+        accepted_proof = Some(("plonk", to_json_string(&plonk_proof_data)?));
+        events.push(Event::from(maci_events::ProcessMessage {
+            processed_msg_count,
+            new_state_commitment,
+            certification_system: String::from("plonk"),
+            proof: to_json_string(&plonk_proof_data)?,
+            question_id: None,
+        }));
     }
 
     // Proof verify success
+    if let Some((certification_system, proof)) = accepted_proof {
+        let id = save_batch_record(
+            deps.storage,
+            BatchRecord {
+                id: 0,
+                step: BatchStep::ProcessMessage,
+                start_index: batch_start_index,
+                end_index: batch_end_index,
+                old_commitment: current_state_commitment,
+                new_commitment: new_state_commitment,
+                input_hash,
+                certification_system: certification_system.to_string(),
+                proof,
+                block_time: env.block.time,
+                submitter: info.sender,
+            },
+        )?;
+        attributes.push(attr("batch_record_id", id.to_string()));
+    }
+
     // Update the current state commitment
     CURRENT_STATE_COMMITMENT.save(deps.storage, &new_state_commitment)?;
 
@@ -918,7 +983,8 @@ pub fn execute_process_message(
     PROCESSED_MSG_COUNT.save(deps.storage, &processed_msg_count)?;
     Ok(Response::new()
         .add_attribute("action", "process_message")
-        .add_attributes(attributes))
+        .add_attributes(attributes)
+        .add_events(events))
 }
 
 pub fn execute_stop_processing_period(
@@ -952,8 +1018,8 @@ pub fn execute_stop_processing_period(
 
 pub fn execute_process_tally(
     deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
+    env: Env,
+    info: MessageInfo,
     new_tally_commitment: Uint256,
     groth16_proof: Option<Groth16ProofType>,
     plonk_proof: Option<PlonkProofType>,
@@ -1006,11 +1072,10 @@ pub fn execute_process_tally(
     let input_hash = uint256_from_hex_string(&hash_256_uint256_list(&input)) % snark_scalar_field;
 
     let mut attributes = vec![];
+    let mut events = vec![];
+    let mut accepted_proof = None;
     let is_passed;
     if let Some(groth16_proof_data) = groth16_proof {
-        // Load the tally verification keys
-        let tally_vkeys_str = GROTH16_TALLY_VKEYS.load(deps.storage)?;
-
         // Parse the SNARK proof (optimization: avoid unnecessary cloning)
         let proof_str = Groth16ProofStr {
             pi_a: hex::decode(&groth16_proof_data.a)
@@ -1021,20 +1086,18 @@ pub fn execute_process_tally(
                 .map_err(|_| ContractError::HexDecodingError {})?,
         };
 
-        // Parse the verification key and prepare for verification
-        let vkey = parse_groth16_vkey::<Bn256>(tally_vkeys_str)?;
-        let pvk = prepare_verifying_key(&vkey);
+        // Load the verification key, prepared on its first use
+        let pvk = load_prepared_vkey(
+            deps.storage,
+            &GROTH16_TALLY_VKEYS,
+            &GROTH16_TALLY_PREPARED_VKEYS,
+        )?;
 
         // Parse the proof and prepare for verification
         let pof = parse_groth16_proof::<Bn256>(proof_str.clone())?;
 
         // Verify the SNARK proof using the input hash
-        let is_passed = groth16_verify(
-            &pvk,
-            &pof,
-            &[Fr::from_str(&input_hash.to_string()).unwrap()],
-        )
-        .unwrap();
+        let is_passed = verify_groth16_prepared(&pvk, &pof, &[parse_public_input(input_hash)?])?;
 
         // If the proof verification fails, return an error
         if !is_passed {
@@ -1049,6 +1112,14 @@ pub fn execute_process_tally(
             attr("proof", format!("{:?}", groth16_proof_data)),
             attr("certification_system", "groth16"),
         ];
+        accepted_proof = Some(("groth16", to_json_string(&groth16_proof_data)?));
+        events.push(Event::from(maci_events::ProcessTally {
+            processed_user_count,
+            new_tally_commitment,
+            certification_system: String::from("groth16"),
+            proof: to_json_string(&groth16_proof_data)?,
+            question_id: None,
+        }));
     }
 
     if let Some(plonk_proof_data) = plonk_proof {
@@ -1111,9 +1182,37 @@ pub fn execute_process_tally(
             attr("proof", format!("{:?}", plonk_proof_data)),
             attr("certification_system", "plonk"),
         ];
+        accepted_proof = Some(("plonk", to_json_string(&plonk_proof_data)?));
+        events.push(Event::from(maci_events::ProcessTally {
+            processed_user_count,
+            new_tally_commitment,
+            certification_system: String::from("plonk"),
+            proof: to_json_string(&plonk_proof_data)?,
+            question_id: None,
+        }));
     }
 
     // Proof verify success
+    if let Some((certification_system, proof)) = accepted_proof {
+        let id = save_batch_record(
+            deps.storage,
+            BatchRecord {
+                id: 0,
+                step: BatchStep::ProcessTally,
+                start_index: processed_user_count,
+                end_index: (processed_user_count + batch_size).min(num_sign_ups),
+                old_commitment: current_tally_commitment,
+                new_commitment: new_tally_commitment,
+                input_hash,
+                certification_system: certification_system.to_string(),
+                proof,
+                block_time: env.block.time,
+                submitter: info.sender,
+            },
+        )?;
+        attributes.push(attr("batch_record_id", id.to_string()));
+    }
+
     // Update the current tally commitment
     CURRENT_TALLY_COMMITMENT
         .save(deps.storage, &new_tally_commitment)
@@ -1128,7 +1227,8 @@ pub fn execute_process_tally(
 
     Ok(Response::new()
         .add_attribute("action", "process_tally")
-        .add_attributes(attributes))
+        .add_attributes(attributes)
+        .add_events(events))
 }
 
 fn execute_stop_tallying_period(
@@ -1159,10 +1259,10 @@ fn execute_stop_tallying_period(
     let parameters = MACIPARAMETERS.load(deps.storage)?;
 
     // Calculate the results root
-    let results_root = qtr_lib.root_of(parameters.vote_option_tree_depth, results.clone());
+    let results_root = qtr_lib.try_root_of(parameters.vote_option_tree_depth, results.clone())?;
 
     // Calculate the tally commitment
-    let tally_commitment = hash2([results_root, salt]);
+    let tally_commitment = try_hash2([results_root, salt])?;
 
     // Load the current tally commitment
     let current_tally_commitment = CURRENT_TALLY_COMMITMENT.load(deps.storage)?;
@@ -1200,7 +1300,12 @@ fn execute_stop_tallying_period(
                         .collect::<Vec<String>>()
                 ),
             )
-            .add_attribute("all_result", sum.to_string()));
+            .add_attribute("all_result", sum.to_string())
+            .add_event(Event::from(maci_events::Tally {
+                results,
+                total_result: sum,
+                question_id: None,
+            })));
     }
     // Check that the tally commitment matches the current tally commitment
     assert_eq!(tally_commitment, current_tally_commitment);
@@ -1238,7 +1343,12 @@ fn execute_stop_tallying_period(
                     .collect::<Vec<String>>()
             ),
         )
-        .add_attribute("all_result", sum.to_string()))
+        .add_attribute("all_result", sum.to_string())
+        .add_event(Event::from(maci_events::Tally {
+            results,
+            total_result: sum,
+            question_id: None,
+        })))
 }
 
 fn execute_grant(
@@ -1528,91 +1638,22 @@ fn user_balance_of(
     Ok(Uint256::zero())
 }
 
-// Load the root node of the state tree
-fn state_root(deps: Deps) -> Uint256 {
-    let root = NODES
-        .load(
-            deps.storage,
-            Uint256::from_u128(0u128).to_be_bytes().to_vec(),
-        )
-        .unwrap();
-    root
+// Compute the root of the state tree
+fn state_root(deps: Deps) -> Result<Uint256, ContractError> {
+    let tree = STATE_TREE.load(deps.storage)?;
+    // The root node of older rounds was stored as 0 until the first sign up
+    if tree.leaf_count == 0 {
+        return Ok(Uint256::zero());
+    }
+    Ok(tree.root()?)
 }
 
 // Enqueues the state leaf into the tree
-fn state_enqueue(deps: &mut DepsMut, leaf: Uint256) -> Result<bool, ContractError> {
-    let leaf_idx0 = LEAF_IDX_0.load(deps.storage).unwrap();
-    let num_sign_ups = NUMSIGNUPS.load(deps.storage).unwrap();
-
-    let leaf_idx = leaf_idx0 + num_sign_ups;
-    NODES.save(deps.storage, leaf_idx.to_be_bytes().to_vec(), &leaf)?;
-    state_update_at(deps, leaf_idx, false)
-}
-
-// Updates the state at the given index in the tree
-fn state_update_at(deps: &mut DepsMut, index: Uint256, full: bool) -> Result<bool, ContractError> {
-    let leaf_idx0 = LEAF_IDX_0.load(deps.storage).unwrap();
-    if index < leaf_idx0 {
-        return Err(ContractError::MustUpdate {});
-    }
-
-    let mut idx = index.clone();
-
-    let mut height = 0;
-
-    let zeros = ZEROS.load(deps.storage).unwrap();
-
-    while idx > Uint256::from_u128(0u128)
-        && (full || idx % Uint256::from_u128(5u128) == Uint256::from_u128(0u128))
-    {
-        let parent_idx = (idx - Uint256::one()) / Uint256::from(5u8);
-        let children_idx0 = parent_idx * Uint256::from(5u8) + Uint256::one();
-
-        let zero = zeros[height];
-
-        let mut inputs: [Uint256; 5] = [Uint256::zero(); 5];
-
-        for i in 0..5 {
-            let node_value = NODES
-                .may_load(
-                    deps.storage,
-                    (children_idx0 + Uint256::from_u128(i as u128))
-                        .to_be_bytes()
-                        .to_vec(),
-                )
-                .unwrap();
-
-            let child = match node_value {
-                Some(value) => value,
-                None => zero,
-            };
-
-            inputs[i] = child;
-        }
-
-        if NODES.has(deps.storage, parent_idx.to_be_bytes().to_vec()) {
-            NODES
-                .update(
-                    deps.storage,
-                    parent_idx.to_be_bytes().to_vec(),
-                    |_c: Option<Uint256>| -> StdResult<_> { Ok(hash5(inputs)) },
-                )
-                .unwrap();
-        } else {
-            NODES
-                .save(
-                    deps.storage,
-                    parent_idx.to_be_bytes().to_vec(),
-                    &hash5(inputs),
-                )
-                .unwrap();
-        }
-
-        height += 1;
-        idx = parent_idx;
-    }
-
-    Ok(true)
+fn state_enqueue(deps: &mut DepsMut, leaf: Uint256) -> Result<(), ContractError> {
+    let mut tree = STATE_TREE.load(deps.storage)?;
+    tree.push(leaf)?;
+    STATE_TREE.save(deps.storage, &tree)?;
+    Ok(())
 }
 
 fn calculate_voting_power(amount: Uint256, config: &VotingPowerConfig) -> Uint256 {
@@ -1639,11 +1680,34 @@ fn check_voting_time(env: Env, voting_time: VotingTime) -> Result<(), ContractEr
     Ok(())
 }
 
+// Sign up uses its own window when one is configured, otherwise the voting window.
+fn check_sign_up_time(deps: Deps, env: Env) -> Result<(), ContractError> {
+    match SIGNUPTIME.may_load(deps.storage)? {
+        Some(sign_up_time) => {
+            if env.block.time < sign_up_time.start_time {
+                return Err(ContractError::PeriodError {});
+            }
+            if env.block.time >= sign_up_time.end_time {
+                return Err(ContractError::PeriodError {});
+            }
+            Ok(())
+        }
+        None => check_voting_time(env, VOTINGTIME.load(deps.storage)?),
+    }
+}
+
+pub(crate) fn state_tree_depth(depth: Uint256) -> Result<u32, ContractError> {
+    depth
+        .to_string()
+        .parse()
+        .map_err(|_| ContractError::InvalidStateTreeDepth { depth })
+}
+
 pub fn hash_message_and_enc_pub_key(
     message: MessageData,
     enc_pub_key: PubKey,
     prev_hash: Uint256,
-) -> Uint256 {
+) -> Result<Uint256, ContractError> {
     let mut m: [Uint256; 5] = [Uint256::zero(); 5];
     m[0] = message.data[0];
     m[1] = message.data[1];
@@ -1658,11 +1722,35 @@ pub fn hash_message_and_enc_pub_key(
     n[3] = enc_pub_key.y;
     n[4] = prev_hash;
 
-    let m_hash = hash5(m);
+    let m_hash = try_hash5(m)?;
+
+    let n_hash = try_hash5(n)?;
+    let m_n_hash = try_hash2([m_hash, n_hash])?;
+    Ok(m_n_hash)
+}
 
-    let n_hash = hash5(n);
-    let m_n_hash = hash2([m_hash, n_hash]);
-    return m_n_hash;
+// Verification keys are prepared once and kept next to the raw key, rounds created before
+// the prepared keys existed prepare them on their first verification
+fn load_prepared_vkey(
+    storage: &mut dyn Storage,
+    vkeys: &Item<Groth16VkeyStr>,
+    prepared_vkeys: &Item<Groth16PreparedVkeyStr>,
+) -> Result<Groth16PreparedVkeyStr, ContractError> {
+    if let Some(prepared) = prepared_vkeys.may_load(storage)? {
+        return Ok(prepared);
+    }
+    let vkey = parse_groth16_vkey::<Bn256>(vkeys.load(storage)?)?;
+    let prepared = prepare_groth16_vkey(&vkey);
+    prepared_vkeys.save(storage, &prepared)?;
+    Ok(prepared)
+}
+
+// Batch records are numbered from 0 in the order they were accepted
+fn save_batch_record(storage: &mut dyn Storage, record: BatchRecord) -> StdResult<u64> {
+    let id = BATCH_RECORD_COUNT.may_load(storage)?.unwrap_or_default();
+    BATCH_RECORDS.save(storage, id, &BatchRecord { id, ..record })?;
+    BATCH_RECORD_COUNT.save(storage, &(id + 1))?;
+    Ok(id)
 }
 
 // Only admin can execute
@@ -1691,7 +1779,12 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             to_json_binary::<RoundInfo>(&ROUNDINFO.load(deps.storage).unwrap())
         }
         QueryMsg::GetVotingTime {} => {
-            to_json_binary::<VotingTime>(&VOTINGTIME.load(deps.storage).unwrap())
+            let voting_time = VOTINGTIME.load(deps.storage)?;
+            to_json_binary::<VotingTimeResponse>(&VotingTimeResponse {
+                start_time: voting_time.start_time,
+                end_time: voting_time.end_time,
+                sign_up_time: SIGNUPTIME.may_load(deps.storage)?,
+            })
         }
         QueryMsg::GetPeriod {} => to_json_binary::<Period>(&PERIOD.load(deps.storage).unwrap()),
         QueryMsg::GetNumSignUp {} => {
@@ -1777,6 +1870,9 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::QueryOracleWhitelistConfig {} => {
             to_json_binary::<OracleWhitelistConfig>(&ORACLE_WHITELIST_CONFIG.load(deps.storage)?)
         }
+        QueryMsg::BatchRecords { start_after, limit } => {
+            to_json_binary::<Vec<BatchRecord>>(&query_batch_records(deps, start_after, limit)?)
+        }
     }
 }
 
@@ -1800,6 +1896,20 @@ pub fn query_user_balance_of(
     Ok(user_balance_of(deps, env, &sender, amount, certificate)?)
 }
 
+pub fn query_batch_records(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<BatchRecord>> {
+    let limit = limit.unwrap_or(30).min(100) as usize;
+    let start = start_after.map(Bound::exclusive);
+    BATCH_RECORDS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, record)| record))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {}
 
@@ -1808,3 +1918,34 @@ pub fn reply(_deps: DepsMut, _env: Env, _msg: Reply) -> Result<Response, Contrac
     // Oracle MACI合约本身不需要处理任何reply，但需要这个函数来支持multitest
     Ok(Response::default())
 }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Rounds instantiated before versioning was added have no cw2 info
+    if cw2::get_contract_version(deps.storage).is_err() {
+        set_contract_version(deps.storage, CONTRACT_NAME, "0.1.0")?;
+    }
+
+    let stored_version =
+        cw2::ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Apply every storage upgrade newer than the stored version, oldest first
+    let stored = (
+        stored_version.major,
+        stored_version.minor,
+        stored_version.patch,
+    );
+    let mut attributes = vec![attr("action", "migrate")];
+    if stored < (0, 1, 1) {
+        attributes.extend(migrate_v0_1_1(deps.branch())?);
+    }
+    if stored < (0, 1, 2) {
+        attributes.extend(migrate_v0_1_2(deps.branch())?);
+    }
+    if stored < (0, 1, 3) {
+        attributes.extend(migrate_v0_1_3(deps.branch())?);
+    }
+
+    attributes.push(attr("from_version", stored_version.to_string()));
+    Ok(Response::new().add_attributes(attributes))
+}
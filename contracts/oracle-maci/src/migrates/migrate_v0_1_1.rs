@@ -0,0 +1,10 @@
+use crate::error::ContractError;
+use cosmwasm_std::{Attribute, DepsMut};
+
+pub fn migrate_v0_1_1(_deps: DepsMut) -> Result<Vec<Attribute>, ContractError> {
+    // The optional sign up window needs no backfill, rounds without it keep following
+    // the voting time.
+    let attributes: Vec<Attribute> = vec![Attribute::new("version", "0.1.1")];
+
+    Ok(attributes)
+}
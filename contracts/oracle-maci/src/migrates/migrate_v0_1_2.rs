@@ -0,0 +1,76 @@
+use crate::error::ContractError;
+use crate::state::{
+    MAX_VOTE_OPTIONS, NUMSIGNUPS, RESULT, STATEIDXINC, TOTAL_RESULT, VOICECREDITBALANCE, WHITELIST,
+};
+use cosmwasm_std::{Addr, Attribute, DepsMut, Order, StdResult, Storage, Uint256};
+use cw_storage_plus::Map;
+
+// Before v0.1.2 `RESULT` shared the "voice_credit_balance" namespace
+const LEGACY_RESULT: Map<Vec<u8>, Uint256> = Map::new("voice_credit_balance");
+
+pub fn migrate_v0_1_2(deps: DepsMut) -> Result<Vec<Attribute>, ContractError> {
+    let relocated = relocate_results(deps.storage)?;
+
+    let mut restored = 0u128;
+    if relocated > 0 {
+        // Keys past the signed up state indices only ever held a result
+        let num_sign_ups = NUMSIGNUPS.load(deps.storage)?;
+        for i in 0..relocated {
+            if Uint256::from_u128(i) >= num_sign_ups {
+                VOICECREDITBALANCE
+                    .remove(deps.storage, Uint256::from_u128(i).to_be_bytes().to_vec());
+            }
+        }
+
+        // Tallying overwrote the balances of the signed up state indices it reached; the
+        // whitelist still records each voter's voting power next to their state index
+        let voters = STATEIDXINC
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<(Addr, Uint256)>>>()?;
+        for (voter, state_idx_inc) in voters {
+            let state_index = state_idx_inc - Uint256::from_u128(1u128);
+            if state_index >= Uint256::from_u128(relocated) {
+                continue;
+            }
+
+            let balance = WHITELIST.load(deps.storage, &voter)?.balance_of();
+            VOICECREDITBALANCE.save(deps.storage, state_index.to_be_bytes().to_vec(), &balance)?;
+            restored += 1;
+        }
+    }
+
+    let attributes: Vec<Attribute> = vec![
+        Attribute::new("version", "0.1.2"),
+        Attribute::new("relocated_results", relocated.to_string()),
+        Attribute::new("restored_balances", restored.to_string()),
+    ];
+
+    Ok(attributes)
+}
+
+// Copies the tallied results into their own namespace and returns how many leading
+// indices held a result. Results are a contiguous run starting at index 0 whose sum is
+// the stored total, followed only by zero-valued options. Sign up rejects a zero
+// voting power, so a non-zero value past the total is an untouched balance.
+fn relocate_results(storage: &mut dyn Storage) -> StdResult<u128> {
+    let total_result = match TOTAL_RESULT.may_load(storage)? {
+        Some(total_result) => total_result,
+        None => return Ok(0),
+    };
+    let max_vote_options = MAX_VOTE_OPTIONS.load(storage)?;
+
+    let mut sum = Uint256::zero();
+    let mut count = 0u128;
+    while Uint256::from_u128(count) < max_vote_options {
+        let key = Uint256::from_u128(count).to_be_bytes().to_vec();
+        let result = match LEGACY_RESULT.may_load(storage, key.clone())? {
+            Some(result) if sum < total_result || result.is_zero() => result,
+            _ => break,
+        };
+        RESULT.save(storage, key, &result)?;
+        sum += result;
+        count += 1;
+    }
+
+    Ok(count)
+}
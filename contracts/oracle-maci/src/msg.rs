@@ -1,9 +1,11 @@
 use crate::state::{
-    GrantConfig, MaciParameters, MessageData, OracleWhitelistConfig, PeriodStatus, PubKey,
-    RoundInfo, VotingPowerMode, VotingTime, WhitelistConfig,
+    BatchRecord, GrantConfig, MaciParameters, MessageData, OracleWhitelistConfig, PeriodStatus,
+    PubKey, RoundInfo, SignUpTime, VotingPowerMode, VotingTime, WhitelistConfig,
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Uint128, Uint256};
+use cosmwasm_std::{Addr, Timestamp, Uint128, Uint256};
+use maci_core::groth16_parser::parse_snarkjs_proof;
+use maci_core::{CoreError, SnarkjsProof};
 
 #[cw_serde]
 pub struct WhitelistBase {
@@ -33,6 +35,7 @@ pub struct InstantiateMsg {
 
     pub round_info: RoundInfo,
     pub voting_time: VotingTime,
+    pub sign_up_time: Option<SignUpTime>,
     pub circuit_type: Uint256,         // <0: 1p1v | 1: pv>
     pub certification_system: Uint256, // <0: groth16 | 1: plonk>
 
@@ -42,6 +45,8 @@ pub struct InstantiateMsg {
     pub whitelist_voting_power_args: VotingPowerArgs,
 
     pub feegrant_operator: Addr,
+    /// the admin of the round, the sender if not set
+    pub admin: Option<Addr>,
 }
 
 #[cw_serde]
@@ -61,6 +66,37 @@ pub struct Groth16ProofType {
     pub c: String,
 }
 
+/// A groth16 proof, either as uncompressed hex points or as snarkjs writes it to `proof.json`
+#[cw_serde]
+#[serde(untagged)]
+pub enum Groth16ProofInput {
+    Uncompressed(Groth16ProofType),
+    Snarkjs(SnarkjsProof),
+}
+
+impl Groth16ProofInput {
+    /// normalize to the uncompressed hex points that are verified and emitted in events
+    pub fn into_uncompressed(self) -> Result<Groth16ProofType, CoreError> {
+        match self {
+            Groth16ProofInput::Uncompressed(proof) => Ok(proof),
+            Groth16ProofInput::Snarkjs(proof) => {
+                let proof = parse_snarkjs_proof(&proof)?;
+                Ok(Groth16ProofType {
+                    a: hex::encode(proof.pi_a),
+                    b: hex::encode(proof.pi_b),
+                    c: hex::encode(proof.pi_c),
+                })
+            }
+        }
+    }
+}
+
+impl From<Groth16ProofType> for Groth16ProofInput {
+    fn from(proof: Groth16ProofType) -> Self {
+        Groth16ProofInput::Uncompressed(proof)
+    }
+}
+
 #[cw_serde]
 pub struct PlonkVKeyType {
     pub n: usize,
@@ -113,13 +149,13 @@ pub enum ExecuteMsg {
     },
     ProcessMessage {
         new_state_commitment: Uint256,
-        groth16_proof: Option<Groth16ProofType>,
+        groth16_proof: Option<Groth16ProofInput>,
         plonk_proof: Option<PlonkProofType>,
     },
     StopProcessingPeriod {},
     ProcessTally {
         new_tally_commitment: Uint256,
-        groth16_proof: Option<Groth16ProofType>,
+        groth16_proof: Option<Groth16ProofInput>,
         plonk_proof: Option<PlonkProofType>,
     },
     StopTallyingPeriod {
@@ -144,13 +180,16 @@ pub struct Period {
     pub status: PeriodStatus,
 }
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
     #[returns(RoundInfo)]
     GetRoundInfo {},
 
-    #[returns(VotingTime)]
+    #[returns(VotingTimeResponse)]
     GetVotingTime {},
 
     #[returns(Period)]
@@ -223,6 +262,20 @@ pub enum QueryMsg {
 
     #[returns(OracleWhitelistConfig)]
     QueryOracleWhitelistConfig {},
+
+    /// Accepted process and tally batches, in the order they were accepted
+    #[returns(Vec<BatchRecord>)]
+    BatchRecords {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct VotingTimeResponse {
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
+    pub sign_up_time: Option<SignUpTime>,
 }
 
 #[cw_serde]
@@ -234,6 +287,7 @@ pub struct InstantiationData {
     pub vote_option_map: Vec<String>,
     pub round_info: RoundInfo,
     pub voting_time: VotingTime,
+    pub sign_up_time: Option<SignUpTime>,
     pub circuit_type: String,
     pub certification_system: String,
     pub whitelist_backend_pubkey: String,
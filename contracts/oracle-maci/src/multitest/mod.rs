@@ -7,10 +7,10 @@ mod tests;
 use anyhow::Result as AnyResult;
 
 use crate::state::{
-    GrantConfig, MessageData, OracleWhitelistConfig, Period, PubKey, RoundInfo, VotingPowerMode,
-    VotingTime, WhitelistConfig,
+    BatchRecord, GrantConfig, MessageData, OracleWhitelistConfig, Period, PubKey, RoundInfo,
+    SignUpTime, VotingPowerMode, VotingTime, WhitelistConfig,
 };
-use crate::utils::uint256_from_hex_string;
+use maci_core::utils::uint256_from_hex_string;
 use crate::{
     contract::{execute, instantiate, query, reply},
     msg::*,
@@ -148,6 +148,38 @@ impl MaciCodeId {
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn instantiate_with_sign_up_time(
+        self,
+        app: &mut App,
+        sender: Addr,
+        sign_up_time: SignUpTime,
+        label: &str,
+    ) -> AnyResult<MaciContract> {
+        let round_info = RoundInfo {
+            title: String::from("HackWasm Berlin"),
+            description: String::from("Hack In Brelin"),
+            link: String::from("https://baidu.com"),
+        };
+
+        let start_time = Timestamp::from_nanos(1571797424879000000).plus_minutes(6);
+        let voting_time = VotingTime {
+            start_time,
+            end_time: start_time.plus_minutes(11),
+        };
+        let circuit_type = Uint256::from_u128(0u128);
+        MaciContract::instantiate_with_sign_up_time(
+            app,
+            self,
+            sender,
+            round_info,
+            voting_time,
+            Some(sign_up_time),
+            circuit_type,
+            label,
+        )
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn instantiate_with_voting_time_plonk(
         self,
@@ -377,6 +409,30 @@ impl MaciContract {
         voting_time: VotingTime,
         circuit_type: Uint256,
         label: &str,
+    ) -> AnyResult<Self> {
+        Self::instantiate_with_sign_up_time(
+            app,
+            code_id,
+            sender,
+            round_info,
+            voting_time,
+            None,
+            circuit_type,
+            label,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[track_caller]
+    pub fn instantiate_with_sign_up_time(
+        app: &mut App,
+        code_id: MaciCodeId,
+        sender: Addr,
+        round_info: RoundInfo,
+        voting_time: VotingTime,
+        sign_up_time: Option<SignUpTime>,
+        circuit_type: Uint256,
+        label: &str,
     ) -> AnyResult<Self> {
         let init_msg = InstantiateMsg {
             coordinator: PubKey {
@@ -397,6 +453,7 @@ impl MaciContract {
             ],
             round_info,
             voting_time,
+            sign_up_time,
             circuit_type,
             certification_system: Uint256::from_u128(0u128),
             whitelist_backend_pubkey: whitelist_pubkey(),
@@ -408,6 +465,7 @@ impl MaciContract {
                 threshold: whitelist_threshold(),
             },
             feegrant_operator: owner(),
+            admin: None,
         };
 
         app.instantiate_contract(
@@ -451,6 +509,7 @@ impl MaciContract {
             ],
             round_info,
             voting_time,
+            sign_up_time: None,
             circuit_type,
             certification_system: Uint256::from_u128(1u128), // plonk system
             whitelist_backend_pubkey: whitelist_pubkey(),
@@ -462,6 +521,7 @@ impl MaciContract {
                 threshold: whitelist_threshold(),
             },
             feegrant_operator: owner(),
+            admin: None,
         };
 
         app.instantiate_contract(
@@ -621,7 +681,7 @@ impl MaciContract {
             self.addr(),
             &ExecuteMsg::ProcessMessage {
                 new_state_commitment,
-                groth16_proof: Some(proof),
+                groth16_proof: Some(proof.into()),
                 plonk_proof: None,
             },
             &[],
@@ -671,7 +731,7 @@ impl MaciContract {
             self.addr(),
             &ExecuteMsg::ProcessTally {
                 new_tally_commitment,
-                groth16_proof: Some(proof),
+                groth16_proof: Some(proof.into()),
                 plonk_proof: None,
             },
             &[],
@@ -770,7 +830,7 @@ impl MaciContract {
             .query_wasm_smart(self.addr(), &QueryMsg::GetAllResult {})
     }
 
-    pub fn get_voting_time(&self, app: &App) -> StdResult<VotingTime> {
+    pub fn get_voting_time(&self, app: &App) -> StdResult<VotingTimeResponse> {
         app.wrap()
             .query_wasm_smart(self.addr(), &QueryMsg::GetVotingTime {})
     }
@@ -790,6 +850,16 @@ impl MaciContract {
             .query_wasm_smart(self.addr(), &QueryMsg::QueryTotalFeeGrant {})
     }
 
+    pub fn batch_records(
+        &self,
+        app: &App,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<BatchRecord>> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::BatchRecords { start_after, limit })
+    }
+
     pub fn query_is_whitelist(
         &self,
         app: &App,
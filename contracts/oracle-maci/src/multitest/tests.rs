@@ -1,13 +1,19 @@
 #[cfg(test)]
 mod test {
+    use crate::contract::{migrate, query};
     use crate::error::ContractError;
-    use crate::msg::Groth16ProofType;
+    use crate::msg::{Groth16ProofType, MigrateMsg, QueryMsg};
     use crate::multitest::{
         create_app, match_user_certificate, owner, uint256_from_decimal_string, user2,
         whitelist_slope, MaciCodeId,
     };
-    use crate::state::{MessageData, Period, PeriodStatus, PubKey};
-    use cosmwasm_std::{Addr, Uint256};
+    use crate::state::{
+        BatchStep, MessageData, Period, PeriodStatus, PubKey, SignUpTime, WhitelistConfig,
+        MAX_VOTE_OPTIONS, NUMSIGNUPS, RESULT, STATEIDXINC, TOTAL_RESULT, VOICECREDITBALANCE,
+        WHITELIST,
+    };
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cosmwasm_std::{attr, from_json, Addr, Timestamp, Uint128, Uint256};
     use cw_multi_test::next_block;
     use serde::{Deserialize, Serialize};
     use serde_json;
@@ -453,6 +459,21 @@ mod test {
             contract.msg_length(&app).unwrap(),
             Uint256::from_u128(2u128)
         );
+
+        let records = contract.batch_records(&app, None, None).unwrap();
+        let steps: Vec<BatchStep> = records.iter().map(|r| r.step.clone()).collect();
+        assert_eq!(
+            steps,
+            vec![BatchStep::ProcessMessage, BatchStep::ProcessTally]
+        );
+        assert_eq!(records[0].end_index, Uint256::from_u128(2u128));
+        assert_eq!(records[0].submitter, owner());
+        assert_eq!(records[1].start_index, Uint256::zero());
+        assert_eq!(records[1].end_index, Uint256::from_u128(2u128));
+        assert_eq!(
+            contract.batch_records(&app, Some(0), Some(10)).unwrap(),
+            vec![records[1].clone()]
+        );
     }
 
     // #[test]
@@ -651,4 +672,228 @@ mod test {
             contract.get_period(&app).unwrap()
         );
     }
+
+    #[test]
+    fn sign_up_window_closes_before_voting_opens() {
+        let pubkey_file_path = "./src/test/user_pubkey.json";
+
+        let mut pubkey_file = fs::File::open(pubkey_file_path).expect("Failed to open file");
+        let mut pubkey_content = String::new();
+
+        pubkey_file
+            .read_to_string(&mut pubkey_content)
+            .expect("Failed to read file");
+        let pubkey_data: UserPubkeyData =
+            serde_json::from_str(&pubkey_content).expect("Failed to parse JSON");
+
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let label = "Group";
+        let sign_up_start = Timestamp::from_nanos(1571797424879000000);
+        let sign_up_time = SignUpTime {
+            start_time: sign_up_start,
+            end_time: sign_up_start.plus_minutes(5),
+        };
+        let contract = code_id
+            .instantiate_with_sign_up_time(&mut app, owner(), sign_up_time.clone(), label)
+            .unwrap();
+
+        let voting_time = contract.get_voting_time(&app).unwrap();
+        assert_eq!(voting_time.start_time, sign_up_start.plus_minutes(6));
+        assert_eq!(voting_time.sign_up_time, Some(sign_up_time));
+
+        let pubkey0 = PubKey {
+            x: uint256_from_decimal_string(&pubkey_data.pubkeys[0][0]),
+            y: uint256_from_decimal_string(&pubkey_data.pubkeys[0][1]),
+        };
+        let pubkey1 = PubKey {
+            x: uint256_from_decimal_string(&pubkey_data.pubkeys[1][0]),
+            y: uint256_from_decimal_string(&pubkey_data.pubkeys[1][1]),
+        };
+
+        app.update_block(next_block); // Start sign up
+        contract
+            .sign_up(
+                &mut app,
+                Addr::unchecked("0"),
+                pubkey0.clone(),
+                match_user_certificate(0).amount,
+                match_user_certificate(0).certificate,
+            )
+            .unwrap();
+        assert_eq!(
+            contract.num_sign_up(&app).unwrap(),
+            Uint256::from_u128(1u128)
+        );
+
+        let publish_error = contract
+            .publish_message(
+                &mut app,
+                Addr::unchecked("0"),
+                MessageData {
+                    data: [Uint256::from_u128(1u128); 7],
+                },
+                pubkey0,
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::PeriodError {},
+            publish_error.downcast().unwrap()
+        ); // Cannot vote before voting phase
+
+        app.update_block(|block| block.time = block.time.plus_minutes(11)); // Start Voting
+
+        let sign_up_error = contract
+            .sign_up(
+                &mut app,
+                Addr::unchecked("1"),
+                pubkey1,
+                match_user_certificate(1).amount,
+                match_user_certificate(1).certificate,
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::PeriodError {},
+            sign_up_error.downcast().unwrap()
+        ); // Cannot signup after sign up phase
+    }
+
+    #[test]
+    fn instantiate_with_sign_up_after_voting_end_error() {
+        let mut app = create_app();
+        let code_id = MaciCodeId::store_code(&mut app);
+        let label = "Group";
+        let sign_up_start = Timestamp::from_nanos(1571797424879000000);
+        let sign_up_time = SignUpTime {
+            start_time: sign_up_start,
+            end_time: sign_up_start.plus_minutes(30),
+        };
+        let error = code_id
+            .instantiate_with_sign_up_time(&mut app, owner(), sign_up_time, label)
+            .unwrap_err();
+        assert_eq!(
+            ContractError::WrongSignUpTimeSet {},
+            error.downcast().unwrap()
+        );
+    }
+
+    #[test]
+    fn migrate_round_without_contract_version() {
+        let mut deps = mock_dependencies();
+
+        // Rounds instantiated before versioning have no cw2 info and migrate as v0.1.0
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert!(res.attributes.contains(&attr("from_version", "0.1.0")));
+        assert!(res.attributes.contains(&attr("version", "0.1.1")));
+
+        let version = cw2::get_contract_version(deps.as_ref().storage).unwrap();
+        assert_eq!(version.contract, "crates.io:cw-oracle-maci");
+        assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn result_and_voice_credit_balance_are_independent() {
+        let mut deps = mock_dependencies();
+        let index = Uint256::from_u128(0u128);
+        VOICECREDITBALANCE
+            .save(
+                deps.as_mut().storage,
+                index.to_be_bytes().to_vec(),
+                &Uint256::from_u128(100u128),
+            )
+            .unwrap();
+        RESULT
+            .save(
+                deps.as_mut().storage,
+                index.to_be_bytes().to_vec(),
+                &Uint256::from_u128(7u128),
+            )
+            .unwrap();
+
+        let result: Uint256 =
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::GetResult { index }).unwrap())
+                .unwrap();
+        let balance: Uint256 = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::GetVoiceCreditBalance { index },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(result, Uint256::from_u128(7u128));
+        assert_eq!(balance, Uint256::from_u128(100u128));
+    }
+
+    #[test]
+    fn migrate_relocates_results_and_restores_balances() {
+        let mut deps = mock_dependencies();
+        cw2::set_contract_version(deps.as_mut().storage, "crates.io:cw-oracle-maci", "0.1.1")
+            .unwrap();
+        NUMSIGNUPS
+            .save(deps.as_mut().storage, &Uint256::from_u128(2u128))
+            .unwrap();
+        // Sign up records the state index plus one next to the voter's voting power
+        for (voter, state_idx_inc, voting_power) in [("voter0", 1u128, 100u128), ("voter1", 2, 80)]
+        {
+            let voter = Addr::unchecked(voter);
+            STATEIDXINC
+                .save(
+                    deps.as_mut().storage,
+                    &voter,
+                    &Uint256::from_u128(state_idx_inc),
+                )
+                .unwrap();
+            WHITELIST
+                .save(
+                    deps.as_mut().storage,
+                    &voter,
+                    &WhitelistConfig {
+                        balance: Uint256::from_u128(voting_power),
+                        is_register: true,
+                        fee_amount: Uint128::from(0u128),
+                        fee_grant: false,
+                    },
+                )
+                .unwrap();
+        }
+        // Tallied results [0, 15, 4, 0, 0] written over the shared namespace of older rounds
+        let results = [0u128, 15, 4, 0, 0].map(Uint256::from_u128);
+        for (i, result) in results.iter().enumerate() {
+            VOICECREDITBALANCE
+                .save(
+                    deps.as_mut().storage,
+                    Uint256::from_u128(i as u128).to_be_bytes().to_vec(),
+                    result,
+                )
+                .unwrap();
+        }
+        TOTAL_RESULT
+            .save(deps.as_mut().storage, &Uint256::from_u128(19u128))
+            .unwrap();
+        MAX_VOTE_OPTIONS
+            .save(deps.as_mut().storage, &Uint256::from_u128(5u128))
+            .unwrap();
+
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert!(res.attributes.contains(&attr("relocated_results", "5")));
+        assert!(res.attributes.contains(&attr("restored_balances", "2")));
+
+        for (i, result) in results.iter().enumerate() {
+            let key = Uint256::from_u128(i as u128).to_be_bytes().to_vec();
+            assert_eq!(RESULT.load(deps.as_ref().storage, key).unwrap(), *result);
+        }
+        let balance = |i: u128| {
+            VOICECREDITBALANCE
+                .may_load(
+                    deps.as_ref().storage,
+                    Uint256::from_u128(i).to_be_bytes().to_vec(),
+                )
+                .unwrap()
+        };
+        assert_eq!(balance(0), Some(Uint256::from_u128(100u128)));
+        assert_eq!(balance(1), Some(Uint256::from_u128(80u128)));
+        assert_eq!(balance(2), None);
+    }
 }
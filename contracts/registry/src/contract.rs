@@ -1,32 +1,60 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, coins, from_json, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo,
-    Reply, Response, StdError, StdResult, SubMsg, SubMsgResponse, Uint128, Uint256, WasmMsg,
+    attr, coin, coins, from_json, to_json_binary, Addr, BankMsg, Binary, Coin, Deps, DepsMut, Env,
+    Event, MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, SubMsg,
+    SubMsgResponse, Timestamp, Uint128, Uint256, WasmMsg,
 };
 use bech32::{self};
 
 use crate::error::ContractError;
 use crate::migrates::migrate_v0_1_4::migrate_v0_1_4;
-use crate::msg::{ExecuteMsg, InstantiateMsg, InstantiationData, MigrateMsg, QueryMsg};
+use crate::migrates::migrate_v0_1_5::migrate_v0_1_5;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, InstantiationData, MigrateMsg, NewRound,
+    OperatorStatsResponse, QueryMsg, RoundFeeQuote, RoundOverrides,
+};
 use crate::state::{
-    Admin, CircuitChargeConfig, ValidatorSet, ADMIN, AMACI_CODE_ID, CIRCUIT_CHARGE_CONFIG,
-    COORDINATOR_PUBKEY_MAP, MACI_OPERATOR_IDENTITY, MACI_OPERATOR_PUBKEY, MACI_OPERATOR_SET,
-    MACI_VALIDATOR_LIST, MACI_VALIDATOR_OPERATOR_SET, OPERATOR,
+    default_fee_tiers, Admin, CircuitChargeConfig, Config, FeeTier, OperatorPubkey, OperatorStats,
+    PubkeyStatus, RoundRecord, RoundSettings, RoundTemplate, RoundType, Unbonding, ValidatorSet,
+    ValidatorSource, ADMIN, AMACI_CODE_ID, CIRCUIT_CHARGE_CONFIG, CONFIG, COORDINATOR_PUBKEY_MAP,
+    FEE_DISCOUNTS, FEE_TIERS, GLOBAL_ROUND_TEMPLATES, MACI_CODE_ID, MACI_OPERATOR_IDENTITY,
+    MACI_OPERATOR_PUBKEY, MACI_OPERATOR_PUBKEY_HISTORY, MACI_OPERATOR_SET, MACI_VALIDATOR_LIST,
+    MACI_VALIDATOR_OPERATOR_SET, OPERATOR, OPERATOR_STATS, ORACLE_MACI_CODE_ID, PENDING_ROUND,
+    REPORTED_ROUNDS, ROUNDS, ROUNDS_BY_CREATOR, ROUNDS_BY_OPERATOR, ROUNDS_BY_VOTING_START,
+    ROUND_COUNT, ROUND_ID_BY_ADDR, ROUND_TEMPLATES, UNBONDINGS, VALIDATOR_SOURCE,
 };
 use cosmwasm_std::Decimal;
 use cw2::set_contract_version;
 use cw_amaci::msg::{
     InstantiateMsg as AMaciInstantiateMsg, InstantiationData as AMaciInstantiationData,
-    WhitelistBase,
+    RoundPerformance, WhitelistBase,
+};
+use cw_amaci::state::{MaciParameters, QuestionConfig, RoundInfo, SignUpTime, VotingTime};
+use cw_maci::msg::{Groth16VKeyInput, InstantiateMsg as MaciInstantiateMsg, PlonkVKeyType};
+use cw_maci::state::{
+    MaciParameters as MaciRoundParameters, RoundInfo as MaciRoundInfo,
+    SignUpTime as MaciSignUpTime, VotingTime as MaciVotingTime, Whitelist as MaciWhitelist,
+};
+use cw_oracle_maci::circuit_params::calculate_circuit_params;
+use cw_oracle_maci::msg::{
+    InstantiateMsg as OracleMaciInstantiateMsg, InstantiationData as OracleMaciInstantiationData,
+    VotingPowerArgs,
+};
+use cw_oracle_maci::state::{
+    RoundInfo as OracleMaciRoundInfo, SignUpTime as OracleMaciSignUpTime,
+    VotingTime as OracleMaciVotingTime,
 };
-use cw_amaci::state::{MaciParameters, PubKey, RoundInfo, VotingTime};
-use cw_utils::parse_instantiate_response_data;
+use cw_storage_plus::{Bound, Map};
+use cw_utils::{must_pay, parse_instantiate_response_data, MsgInstantiateContractResponse};
+use maci_core::{PubKey, QuinaryTreeRoot};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw-amaci-registry";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const CREATED_GROTH16_ROUND_REPLY_ID: u64 = 1;
+pub const CREATED_MACI_ROUND_REPLY_ID: u64 = 2;
+pub const CREATED_ORACLE_MACI_ROUND_REPLY_ID: u64 = 3;
 
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
@@ -38,18 +66,40 @@ pub fn instantiate(
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    let admin = Admin { admin: msg.admin };
+    let admin = Admin {
+        admin: msg.admin.clone(),
+    };
 
     ADMIN.save(deps.storage, &admin)?;
     OPERATOR.save(deps.storage, &msg.operator)?;
 
     AMACI_CODE_ID.save(deps.storage, &msg.amaci_code_id)?;
 
+    let config = Config {
+        denom: msg.denom,
+        min_deposit_amount: msg.min_deposit_amount,
+        slash_amount: msg.slash_amount,
+        unbonding_period: msg.unbonding_period,
+        max_delay_records: msg.max_delay_records,
+        address_prefix: msg.address_prefix,
+    };
+    validate_config(&config)?;
+    CONFIG.save(deps.storage, &config)?;
+    validate_address(deps.as_ref(), &msg.admin)?;
+    validate_address(deps.as_ref(), &msg.operator)?;
+
     let circuit_charge_config = CircuitChargeConfig {
         fee_rate: Decimal::from_ratio(1u128, 10u128), // 10%
     };
 
     CIRCUIT_CHARGE_CONFIG.save(deps.storage, &circuit_charge_config)?;
+    for round_type in RoundType::all() {
+        FEE_TIERS.save(
+            deps.storage,
+            round_type.as_str(),
+            &default_fee_tiers(&round_type),
+        )?;
+    }
 
     Ok(Response::default())
 }
@@ -69,34 +119,136 @@ pub fn execute(
         ExecuteMsg::SetMaciOperatorPubkey { pubkey } => {
             execute_set_maci_operator_pubkey(deps, env, info, pubkey)
         }
+        ExecuteMsg::ReleaseMaciOperatorPubkey { pubkey } => {
+            execute_release_maci_operator_pubkey(deps, env, info, pubkey)
+        }
+        ExecuteMsg::BurnMaciOperatorPubkey { pubkey } => {
+            execute_burn_maci_operator_pubkey(deps, env, info, pubkey)
+        }
         ExecuteMsg::SetMaciOperatorIdentity { identity } => {
             execute_set_maci_operator_identity(deps, env, info, identity)
         }
-        ExecuteMsg::CreateRound {
-            operator,
-            max_voter,
-            max_option,
-            voice_credit_amount,
+        ExecuteMsg::CreateRound { round } => match round {
+            NewRound::Amaci {
+                operator,
+                max_voter,
+                max_option,
+                voice_credit_amount,
+                round_info,
+                voting_time,
+                sign_up_time,
+                whitelist,
+                pre_deactivate_root,
+                circuit_type,
+                certification_system,
+                extra_questions,
+            } => execute_create_round(
+                deps,
+                env,
+                info,
+                operator,
+                max_voter,
+                max_option,
+                voice_credit_amount,
+                round_info,
+                voting_time,
+                sign_up_time,
+                whitelist,
+                pre_deactivate_root,
+                circuit_type,
+                certification_system,
+                extra_questions,
+            ),
+            NewRound::Maci {
+                coordinator,
+                qtr_lib,
+                groth16_process_vkey,
+                groth16_tally_vkey,
+                plonk_process_vkey,
+                plonk_tally_vkey,
+                max_voter,
+                max_option,
+                round_info,
+                voting_time,
+                sign_up_time,
+                whitelist,
+                circuit_type,
+                certification_system,
+            } => execute_create_maci_round(
+                deps,
+                env,
+                info,
+                coordinator,
+                qtr_lib,
+                groth16_process_vkey,
+                groth16_tally_vkey,
+                plonk_process_vkey,
+                plonk_tally_vkey,
+                max_voter,
+                max_option,
+                round_info,
+                voting_time,
+                sign_up_time,
+                whitelist,
+                circuit_type,
+                certification_system,
+            ),
+            NewRound::OracleMaci {
+                coordinator,
+                max_voters,
+                vote_option_map,
+                round_info,
+                voting_time,
+                sign_up_time,
+                circuit_type,
+                certification_system,
+                whitelist_backend_pubkey,
+                whitelist_ecosystem,
+                whitelist_snapshot_height,
+                whitelist_voting_power_args,
+            } => execute_create_oracle_maci_round(
+                deps,
+                env,
+                info,
+                coordinator,
+                max_voters,
+                vote_option_map,
+                round_info,
+                voting_time,
+                sign_up_time,
+                circuit_type,
+                certification_system,
+                whitelist_backend_pubkey,
+                whitelist_ecosystem,
+                whitelist_snapshot_height,
+                whitelist_voting_power_args,
+            ),
+        },
+        ExecuteMsg::SetRoundTemplate {
+            name,
+            global,
+            settings,
+        } => execute_set_round_template(deps, env, info, name, global, settings),
+        ExecuteMsg::RemoveRoundTemplate { name, global } => {
+            execute_remove_round_template(deps, env, info, name, global)
+        }
+        ExecuteMsg::CreateRoundFromTemplate {
+            name,
+            global,
             round_info,
             voting_time,
-            whitelist,
-            pre_deactivate_root,
-            circuit_type,
-            certification_system,
-        } => execute_create_round(
+            sign_up_time,
+            overrides,
+        } => execute_create_round_from_template(
             deps,
             env,
             info,
-            operator,
-            max_voter,
-            max_option,
-            voice_credit_amount,
+            name,
+            global,
             round_info,
             voting_time,
-            whitelist,
-            pre_deactivate_root,
-            circuit_type,
-            certification_system,
+            sign_up_time,
+            overrides,
         ),
         ExecuteMsg::SetValidators { addresses } => {
             execute_set_validators(deps, env, info, addresses)
@@ -104,23 +256,45 @@ pub fn execute(
         ExecuteMsg::RemoveValidator { address } => {
             execute_remove_validator(deps, env, info, address)
         }
+        ExecuteMsg::SetValidatorSource { source } => {
+            execute_set_validator_source(deps, env, info, source)
+        }
+        ExecuteMsg::PruneValidators {} => execute_prune_validators(deps, env, info),
         ExecuteMsg::UpdateAmaciCodeId { amaci_code_id } => {
             execute_update_amaci_code_id(deps, env, info, amaci_code_id)
         }
+        ExecuteMsg::UpdateRoundCodeId {
+            round_type,
+            code_id,
+        } => execute_update_round_code_id(deps, env, info, round_type, code_id),
         ExecuteMsg::ChangeOperator { address } => execute_change_operator(deps, env, info, address),
         ExecuteMsg::ChangeChargeConfig { config } => {
             execute_change_charge_config(deps, env, info, config)
         }
+        ExecuteMsg::UpdateConfig { config } => execute_update_config(deps, env, info, config),
+        ExecuteMsg::SetFeeTiers { round_type, tiers } => {
+            execute_set_fee_tiers(deps, env, info, round_type, tiers)
+        }
+        ExecuteMsg::SetFeeDiscount { creator, discount } => {
+            execute_set_fee_discount(deps, env, info, creator, discount)
+        }
+        ExecuteMsg::Bond {} => execute_bond(deps, env, info),
+        ExecuteMsg::Unbond { amount } => execute_unbond(deps, env, info, amount),
+        ExecuteMsg::WithdrawUnbonded {} => execute_withdraw_unbonded(deps, env, info),
+        ExecuteMsg::ReportRoundPerformance { performance } => {
+            execute_report_round_performance(deps, env, info, performance)
+        }
     }
 }
 
-// validate address is valid dora prefix cosmos address
-pub fn validate_dora_address(address: &str) -> Result<(), ContractError> {
-    match bech32::decode(address) {
+// validate address is a cosmos address with the configured prefix
+pub fn validate_address(deps: Deps, address: &Addr) -> Result<(), ContractError> {
+    let expected = CONFIG.load(deps.storage)?.address_prefix;
+    match bech32::decode(address.as_str()) {
         Ok((prefix, _data, _variant)) => {
-            if prefix != "dora" {
+            if prefix != expected {
                 return Err(ContractError::InvalidAddressPrefix {
-                    expected: "dora".to_string(),
+                    expected,
                     actual: prefix,
                 });
             }
@@ -142,71 +316,77 @@ pub fn execute_create_round(
     voice_credit_amount: Uint256,
     round_info: RoundInfo,
     voting_time: VotingTime,
+    sign_up_time: Option<SignUpTime>,
     whitelist: Option<WhitelistBase>,
     pre_deactivate_root: Uint256,
     circuit_type: Uint256,
     certification_system: Uint256,
+    extra_questions: Option<Vec<QuestionConfig>>,
 ) -> Result<Response, ContractError> {
-    validate_dora_address(operator.as_str())?;
-    
-    let maci_parameters: MaciParameters;
-    let required_fee: Uint128;
-
-    if max_voter <= Uint256::from_u128(25u128) && max_option <= Uint256::from_u128(5u128) {
-        // state_tree_depth: 2
-        // vote_option_tree_depth: 1
-        // price: 20 DORA
-        maci_parameters = MaciParameters {
-            state_tree_depth: Uint256::from_u128(2u128),
-            int_state_tree_depth: Uint256::from_u128(1u128),
-            vote_option_tree_depth: Uint256::from_u128(1u128),
-            message_batch_size: Uint256::from_u128(5u128),
-        };
-        required_fee = Uint128::from(20000000000000000000u128);
-        // required_fee = Uint128::from(50000000000000000000u128);
-    } else if max_voter <= Uint256::from_u128(625u128) && max_option <= Uint256::from_u128(25u128) {
-        // state_tree_depth: 4
-        // vote_option_tree_depth: 2
-        // price: 750 DORA
-        maci_parameters = MaciParameters {
-            state_tree_depth: Uint256::from_u128(4u128),
-            int_state_tree_depth: Uint256::from_u128(2u128),
-            vote_option_tree_depth: Uint256::from_u128(2u128),
-            message_batch_size: Uint256::from_u128(25u128),
-        };
-        required_fee = Uint128::from(750000000000000000000u128);
-        // required_fee = Uint128::from(100000000000000000000u128);
-    } else {
-        return Err(ContractError::NoMatchedSizeCircuit {});
+    validate_address(deps.as_ref(), &operator)?;
+    for user in whitelist.iter().flat_map(|whitelist| &whitelist.users) {
+        validate_address(deps.as_ref(), &user.addr)?;
     }
 
-    let denom = "peaka".to_string();
-    let mut amount: Uint128 = Uint128::new(0);
-    info.funds.iter().for_each(|fund| {
-        if fund.denom == denom {
-            amount = fund.amount;
-        }
-    });
-
-    // check user's payment
-    if amount < required_fee {
-        return Err(ContractError::InsufficientFee {
-            required: required_fee,
-            provided: amount,
+    // The circuit must fit the question with the most vote options
+    let circuit_max_option = extra_questions
+        .iter()
+        .flatten()
+        .fold(max_option, |max, question| {
+            max.max(question.max_vote_options)
         });
-    }
+
+    let quote = quote_round_fee(
+        deps.as_ref(),
+        &RoundType::Amaci,
+        max_voter,
+        circuit_max_option,
+        Some(&info.sender),
+    )?
+    .ok_or(ContractError::NoMatchedSizeCircuit {})?;
+    let maci_parameters = quote.parameters;
+    let required_fee = quote.fee;
+    let config = CONFIG.load(deps.storage)?;
+    let refund = round_fee_refund(&info, &config.denom, required_fee)?;
 
     if !MACI_OPERATOR_PUBKEY.has(deps.storage, &operator) {
         return Err(ContractError::NotSetOperatorPubkey {});
     }
     let operator_pubkey = MACI_OPERATOR_PUBKEY.load(deps.storage, &operator)?;
 
+    // Only operators with enough bond can be assigned rounds
+    let min_deposit_amount = config.min_deposit_amount;
+    let bond = MACI_OPERATOR_SET
+        .may_load(deps.storage, &operator)?
+        .unwrap_or_default();
+    if bond < min_deposit_amount {
+        return Err(ContractError::InsufficientDeposit { min_deposit_amount });
+    }
+
     let total_fee = required_fee;
     let admin = ADMIN.load(deps.storage)?.admin;
     
     // No longer send admin_fee directly to admin, instead send all fees to amaci contract
     // Add admin_fee information in the instantiate message for potential refunds in the future
 
+    PENDING_ROUND.save(
+        deps.storage,
+        &RoundRecord {
+            id: 0,
+            round_type: RoundType::Amaci,
+            round_addr: Addr::unchecked("pending"),
+            creator: info.sender.clone(),
+            operator: operator.clone(),
+            coordinator_pubkey: operator_pubkey.clone(),
+            parameters: maci_parameters.clone(),
+            circuit_type: circuit_type.to_string(),
+            certification_system: certification_system.to_string(),
+            fee: total_fee,
+            voting_time: voting_time.clone(),
+            created_at: env.block.time,
+        },
+    )?;
+
     let init_msg = AMaciInstantiateMsg {
         parameters: maci_parameters,
         coordinator: operator_pubkey,
@@ -217,18 +397,27 @@ pub fn execute_create_round(
         voice_credit_amount,
         round_info,
         voting_time,
+        sign_up_time,
         whitelist,
         pre_deactivate_root,
         circuit_type,
         certification_system,
+        extra_questions,
+        registry: Some(env.contract.address.clone()),
     };
     let amaci_code_id = AMACI_CODE_ID.load(deps.storage)?;
+    // Send all fees, including admin_fee; a fully discounted round gets no funds
+    let funds = if total_fee.is_zero() {
+        vec![]
+    } else {
+        coins(total_fee.u128(), &config.denom)
+    };
     let instantiate_msg = SubMsg::reply_on_success(
         WasmMsg::Instantiate {
             admin: Some(env.contract.address.to_string()),
             code_id: amaci_code_id,
             msg: to_json_binary(&init_msg)?,
-            funds: coins(total_fee.u128(), "peaka"), // Send all fees, including admin_fee
+            funds,
             label: "AMACI".to_string(),
         },
         CREATED_GROTH16_ROUND_REPLY_ID,
@@ -237,22 +426,417 @@ pub fn execute_create_round(
     let resp = Response::new()
         .add_submessage(instantiate_msg)
         .add_attribute("action", "create_round")
+        .add_attribute("round_type", RoundType::Amaci.as_str())
         .add_attribute("amaci_code_id", &amaci_code_id.to_string())
         .add_attribute("total_fee", total_fee.to_string())
         .add_attribute("fee_recipient", admin.to_string());
 
-    Ok(resp)
+    Ok(add_refund(resp, &info.sender, refund))
+}
+
+pub fn execute_set_round_template(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    global: bool,
+    settings: RoundSettings,
+) -> Result<Response, ContractError> {
+    validate_address(deps.as_ref(), &settings.operator)?;
+    if let Some(whitelist) = &settings.whitelist {
+        for user in &whitelist.users {
+            validate_address(deps.as_ref(), &user.addr)?;
+        }
+    }
+
+    let template = RoundTemplate {
+        name: name.clone(),
+        owner: (!global).then(|| info.sender.clone()),
+        settings,
+        updated_at: env.block.time,
+    };
+    if global {
+        if !is_admin(deps.as_ref(), info.sender.as_ref())? {
+            return Err(ContractError::Unauthorized {});
+        }
+        GLOBAL_ROUND_TEMPLATES.save(deps.storage, &name, &template)?;
+    } else {
+        ROUND_TEMPLATES.save(deps.storage, (&info.sender, &name), &template)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_round_template")
+        .add_attribute("name", name)
+        .add_attribute("owner", template_owner(&template)))
+}
+
+pub fn execute_remove_round_template(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    name: String,
+    global: bool,
+) -> Result<Response, ContractError> {
+    let template = load_round_template(deps.as_ref(), &info.sender, &name, global)?;
+    if global {
+        if !is_admin(deps.as_ref(), info.sender.as_ref())? {
+            return Err(ContractError::Unauthorized {});
+        }
+        GLOBAL_ROUND_TEMPLATES.remove(deps.storage, &name);
+    } else {
+        ROUND_TEMPLATES.remove(deps.storage, (&info.sender, &name));
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_round_template")
+        .add_attribute("name", name)
+        .add_attribute("owner", template_owner(&template)))
+}
+
+pub fn execute_create_round_from_template(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    global: bool,
+    round_info: RoundInfo,
+    voting_time: VotingTime,
+    sign_up_time: Option<SignUpTime>,
+    overrides: RoundOverrides,
+) -> Result<Response, ContractError> {
+    let template = load_round_template(deps.as_ref(), &info.sender, &name, global)?;
+    let owner = template_owner(&template);
+    let settings = template.settings;
+
+    let resp = execute_create_round(
+        deps,
+        env,
+        info,
+        overrides.operator.unwrap_or(settings.operator),
+        overrides.max_voter.unwrap_or(settings.max_voter),
+        overrides.max_option.unwrap_or(settings.max_option),
+        overrides
+            .voice_credit_amount
+            .unwrap_or(settings.voice_credit_amount),
+        round_info,
+        voting_time,
+        sign_up_time,
+        overrides.whitelist.or(settings.whitelist),
+        settings.pre_deactivate_root,
+        settings.circuit_type,
+        settings.certification_system,
+        overrides.extra_questions.or(settings.extra_questions),
+    )?;
+
+    Ok(resp
+        .add_attribute("template", name)
+        .add_attribute("template_owner", owner))
+}
+
+// a template of the sender, or a global one
+fn load_round_template(
+    deps: Deps,
+    sender: &Addr,
+    name: &str,
+    global: bool,
+) -> Result<RoundTemplate, ContractError> {
+    let template = if global {
+        GLOBAL_ROUND_TEMPLATES.may_load(deps.storage, name)?
+    } else {
+        ROUND_TEMPLATES.may_load(deps.storage, (sender, name))?
+    };
+    template.ok_or_else(|| ContractError::RoundTemplateNotFound {
+        name: name.to_string(),
+    })
+}
+
+fn template_owner(template: &RoundTemplate) -> String {
+    template
+        .owner
+        .as_ref()
+        .map_or_else(|| String::from("global"), |owner| owner.to_string())
+}
+
+pub fn execute_create_maci_round(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    coordinator: PubKey,
+    qtr_lib: QuinaryTreeRoot,
+    groth16_process_vkey: Option<Groth16VKeyInput>,
+    groth16_tally_vkey: Option<Groth16VKeyInput>,
+    plonk_process_vkey: Option<PlonkVKeyType>,
+    plonk_tally_vkey: Option<PlonkVKeyType>,
+    max_voter: Uint256,
+    max_option: Uint256,
+    round_info: RoundInfo,
+    voting_time: VotingTime,
+    sign_up_time: Option<SignUpTime>,
+    whitelist: Option<MaciWhitelist>,
+    circuit_type: Uint256,
+    certification_system: Uint256,
+) -> Result<Response, ContractError> {
+    for user in whitelist.iter().flat_map(|whitelist| &whitelist.users) {
+        validate_address(deps.as_ref(), &Addr::unchecked(&user.addr))?;
+    }
+
+    let quote = quote_round_fee(
+        deps.as_ref(),
+        &RoundType::Maci,
+        max_voter,
+        max_option,
+        Some(&info.sender),
+    )?
+    .ok_or(ContractError::NoMatchedSizeCircuit {})?;
+    let denom = CONFIG.load(deps.storage)?.denom;
+    let refund = round_fee_refund(&info, &denom, quote.fee)?;
+    let maci_code_id = round_code_id(deps.storage, &RoundType::Maci)?;
+    let admin = ADMIN.load(deps.storage)?.admin;
+
+    // The sender operates the round with its own coordinator key
+    PENDING_ROUND.save(
+        deps.storage,
+        &RoundRecord {
+            id: 0,
+            round_type: RoundType::Maci,
+            round_addr: Addr::unchecked("pending"),
+            creator: info.sender.clone(),
+            operator: info.sender.clone(),
+            coordinator_pubkey: coordinator.clone(),
+            parameters: quote.parameters.clone(),
+            circuit_type: circuit_type.to_string(),
+            certification_system: certification_system.to_string(),
+            fee: quote.fee,
+            voting_time: voting_time.clone(),
+            created_at: env.block.time,
+        },
+    )?;
+
+    let init_msg = MaciInstantiateMsg {
+        parameters: MaciRoundParameters {
+            state_tree_depth: quote.parameters.state_tree_depth,
+            int_state_tree_depth: quote.parameters.int_state_tree_depth,
+            vote_option_tree_depth: quote.parameters.vote_option_tree_depth,
+            message_batch_size: quote.parameters.message_batch_size,
+        },
+        coordinator,
+        qtr_lib,
+        groth16_process_vkey,
+        groth16_tally_vkey,
+        plonk_process_vkey,
+        plonk_tally_vkey,
+        max_vote_options: max_option,
+        round_info: MaciRoundInfo {
+            title: round_info.title,
+            description: round_info.description,
+            link: round_info.link,
+        },
+        voting_time: Some(MaciVotingTime {
+            start_time: Some(voting_time.start_time),
+            end_time: Some(voting_time.end_time),
+        }),
+        sign_up_time: sign_up_time.map(|sign_up_time| MaciSignUpTime {
+            start_time: sign_up_time.start_time,
+            end_time: sign_up_time.end_time,
+        }),
+        whitelist,
+        circuit_type,
+        certification_system,
+        admin: Some(info.sender.clone()),
+    };
+    let instantiate_msg = SubMsg::reply_on_success(
+        WasmMsg::Instantiate {
+            admin: Some(env.contract.address.to_string()),
+            code_id: maci_code_id,
+            msg: to_json_binary(&init_msg)?,
+            funds: vec![],
+            label: "MACI".to_string(),
+        },
+        CREATED_MACI_ROUND_REPLY_ID,
+    );
+
+    // maci rounds hold no funds, the fee goes straight to the admin
+    let mut resp = Response::new()
+        .add_submessage(instantiate_msg)
+        .add_attribute("action", "create_round")
+        .add_attribute("round_type", RoundType::Maci.as_str())
+        .add_attribute("maci_code_id", maci_code_id.to_string())
+        .add_attribute("total_fee", quote.fee.to_string())
+        .add_attribute("fee_recipient", admin.to_string());
+    if !quote.fee.is_zero() {
+        resp = resp.add_message(BankMsg::Send {
+            to_address: admin.to_string(),
+            amount: coins(quote.fee.u128(), &denom),
+        });
+    }
+
+    Ok(add_refund(resp, &info.sender, refund))
+}
+
+pub fn execute_create_oracle_maci_round(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    coordinator: PubKey,
+    max_voters: u128,
+    vote_option_map: Vec<String>,
+    round_info: RoundInfo,
+    voting_time: VotingTime,
+    sign_up_time: Option<SignUpTime>,
+    circuit_type: Uint256,
+    certification_system: Uint256,
+    whitelist_backend_pubkey: String,
+    whitelist_ecosystem: String,
+    whitelist_snapshot_height: Uint256,
+    whitelist_voting_power_args: VotingPowerArgs,
+) -> Result<Response, ContractError> {
+    // oracle-maci picks the circuit itself, the fee tiers only price it
+    let max_vote_options = vote_option_map.len() as u128;
+    let parameters = calculate_circuit_params(max_voters, max_vote_options)
+        .map_err(|_| ContractError::NoMatchedSizeCircuit {})?;
+    let quote = quote_round_fee(
+        deps.as_ref(),
+        &RoundType::OracleMaci,
+        Uint256::from_u128(max_voters),
+        Uint256::from_u128(max_vote_options),
+        Some(&info.sender),
+    )?
+    .ok_or(ContractError::NoMatchedSizeCircuit {})?;
+    let denom = CONFIG.load(deps.storage)?.denom;
+    let refund = round_fee_refund(&info, &denom, quote.fee)?;
+    let oracle_maci_code_id = round_code_id(deps.storage, &RoundType::OracleMaci)?;
+
+    PENDING_ROUND.save(
+        deps.storage,
+        &RoundRecord {
+            id: 0,
+            round_type: RoundType::OracleMaci,
+            round_addr: Addr::unchecked("pending"),
+            creator: info.sender.clone(),
+            operator: info.sender.clone(),
+            coordinator_pubkey: coordinator.clone(),
+            parameters: MaciParameters {
+                state_tree_depth: parameters.state_tree_depth,
+                int_state_tree_depth: parameters.int_state_tree_depth,
+                vote_option_tree_depth: parameters.vote_option_tree_depth,
+                message_batch_size: parameters.message_batch_size,
+            },
+            circuit_type: circuit_type.to_string(),
+            certification_system: certification_system.to_string(),
+            fee: quote.fee,
+            voting_time: voting_time.clone(),
+            created_at: env.block.time,
+        },
+    )?;
+
+    let init_msg = OracleMaciInstantiateMsg {
+        coordinator,
+        max_voters,
+        vote_option_map,
+        round_info: OracleMaciRoundInfo {
+            title: round_info.title,
+            description: round_info.description,
+            link: round_info.link,
+        },
+        voting_time: OracleMaciVotingTime {
+            start_time: voting_time.start_time,
+            end_time: voting_time.end_time,
+        },
+        sign_up_time: sign_up_time.map(|sign_up_time| OracleMaciSignUpTime {
+            start_time: sign_up_time.start_time,
+            end_time: sign_up_time.end_time,
+        }),
+        circuit_type,
+        certification_system,
+        whitelist_backend_pubkey,
+        whitelist_ecosystem,
+        whitelist_snapshot_height,
+        whitelist_voting_power_args,
+        feegrant_operator: info.sender.clone(),
+        admin: Some(info.sender.clone()),
+    };
+    // The fee pays the fee grants of the voters of the round
+    let funds = if quote.fee.is_zero() {
+        vec![]
+    } else {
+        coins(quote.fee.u128(), &denom)
+    };
+    let instantiate_msg = SubMsg::reply_on_success(
+        WasmMsg::Instantiate {
+            admin: Some(env.contract.address.to_string()),
+            code_id: oracle_maci_code_id,
+            msg: to_json_binary(&init_msg)?,
+            funds,
+            label: "ORACLE_MACI".to_string(),
+        },
+        CREATED_ORACLE_MACI_ROUND_REPLY_ID,
+    );
+
+    let resp = Response::new()
+        .add_submessage(instantiate_msg)
+        .add_attribute("action", "create_round")
+        .add_attribute("round_type", RoundType::OracleMaci.as_str())
+        .add_attribute("oracle_maci_code_id", oracle_maci_code_id.to_string())
+        .add_attribute("total_fee", quote.fee.to_string());
+
+    Ok(add_refund(resp, &info.sender, refund))
+}
+
+// check the sender paid the fee, everything sent beyond it goes back to the sender
+fn round_fee_refund(
+    info: &MessageInfo,
+    denom: &str,
+    required_fee: Uint128,
+) -> Result<Vec<Coin>, ContractError> {
+    let mut amount: Uint128 = Uint128::new(0);
+    info.funds.iter().for_each(|fund| {
+        if fund.denom == denom {
+            amount = fund.amount;
+        }
+    });
+
+    // check user's payment
+    if amount < required_fee {
+        return Err(ContractError::InsufficientFee {
+            required: required_fee,
+            provided: amount,
+        });
+    }
+
+    Ok(info
+        .funds
+        .iter()
+        .filter_map(|fund| {
+            let refund_amount = if fund.denom == denom {
+                fund.amount - required_fee
+            } else {
+                fund.amount
+            };
+            (!refund_amount.is_zero()).then(|| coin(refund_amount.u128(), &fund.denom))
+        })
+        .collect())
+}
+
+fn add_refund(resp: Response, sender: &Addr, refund: Vec<Coin>) -> Response {
+    if refund.is_empty() {
+        return resp;
+    }
+    resp.add_attribute("refund", format!("{:?}", refund))
+        .add_message(BankMsg::Send {
+            to_address: sender.to_string(),
+            amount: refund,
+        })
 }
 
 // validator
 pub fn execute_set_maci_operator(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     operator: Addr,
 ) -> Result<Response, ContractError> {
-    validate_dora_address(operator.as_str())?;
-    
+    validate_address(deps.as_ref(), &operator)?;
+
     if !is_validator(deps.as_ref(), &info.sender)? {
         return Err(ContractError::Unauthorized {});
     }
@@ -273,9 +857,10 @@ pub fn execute_set_maci_operator(
                 ),
             );
             MACI_OPERATOR_PUBKEY.remove(deps.storage, &old_operator);
+            deactivate_pubkey(deps.storage, &env, &old_operator, PubkeyStatus::Released)?;
         }
 
-        MACI_OPERATOR_SET.remove(deps.storage, &old_operator);
+        unbond_all(deps.storage, &env, &old_operator)?;
 
         MACI_VALIDATOR_OPERATOR_SET.save(deps.storage, &info.sender, &operator)?;
         MACI_OPERATOR_SET.save(deps.storage, &operator, &Uint128::from(0u128))?;
@@ -292,7 +877,7 @@ pub fn execute_set_maci_operator(
 // validator operator
 pub fn execute_set_maci_operator_pubkey(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     pubkey: PubKey,
 ) -> Result<Response, ContractError> {
@@ -309,6 +894,19 @@ pub fn execute_set_maci_operator_pubkey(
             return Err(ContractError::PubkeyExisted {});
         }
 
+        // The previous key stays reserved until the operator releases it
+        deactivate_pubkey(deps.storage, &env, &info.sender, PubkeyStatus::Retired)?;
+        let mut history = MACI_OPERATOR_PUBKEY_HISTORY
+            .may_load(deps.storage, &info.sender)?
+            .unwrap_or_default();
+        history.push(OperatorPubkey {
+            pubkey: pubkey.clone(),
+            status: PubkeyStatus::Active,
+            activated_at: Some(env.block.time),
+            deactivated_at: None,
+        });
+        MACI_OPERATOR_PUBKEY_HISTORY.save(deps.storage, &info.sender, &history)?;
+
         MACI_OPERATOR_PUBKEY.save(deps.storage, &info.sender, &pubkey)?;
         COORDINATOR_PUBKEY_MAP.save(
             deps.storage,
@@ -326,6 +924,84 @@ pub fn execute_set_maci_operator_pubkey(
     }
 }
 
+// validator operator, also after it was removed
+pub fn execute_release_maci_operator_pubkey(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    pubkey: PubKey,
+) -> Result<Response, ContractError> {
+    set_retired_pubkey_status(deps.storage, &info.sender, &pubkey, PubkeyStatus::Released)?;
+    COORDINATOR_PUBKEY_MAP.remove(
+        deps.storage,
+        &(
+            pubkey.x.to_be_bytes().to_vec(),
+            pubkey.y.to_be_bytes().to_vec(),
+        ),
+    );
+
+    Ok(Response::new()
+        .add_attribute("action", "release_maci_operator_pubkey")
+        .add_attribute("maci_operator", info.sender.to_string())
+        .add_attribute("coordinator_pubkey_x", pubkey.x.to_string())
+        .add_attribute("coordinator_pubkey_y", pubkey.y.to_string()))
+}
+
+// validator operator, also after it was removed
+pub fn execute_burn_maci_operator_pubkey(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    pubkey: PubKey,
+) -> Result<Response, ContractError> {
+    set_retired_pubkey_status(deps.storage, &info.sender, &pubkey, PubkeyStatus::Burned)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "burn_maci_operator_pubkey")
+        .add_attribute("maci_operator", info.sender.to_string())
+        .add_attribute("coordinator_pubkey_x", pubkey.x.to_string())
+        .add_attribute("coordinator_pubkey_y", pubkey.y.to_string()))
+}
+
+// End the active key of the operator in its history
+fn deactivate_pubkey(
+    storage: &mut dyn Storage,
+    env: &Env,
+    operator: &Addr,
+    status: PubkeyStatus,
+) -> StdResult<()> {
+    let mut history = MACI_OPERATOR_PUBKEY_HISTORY
+        .may_load(storage, operator)?
+        .unwrap_or_default();
+    if let Some(active) = history
+        .iter_mut()
+        .find(|key| key.status == PubkeyStatus::Active)
+    {
+        active.status = status;
+        active.deactivated_at = Some(env.block.time);
+        MACI_OPERATOR_PUBKEY_HISTORY.save(storage, operator, &history)?;
+    }
+    Ok(())
+}
+
+fn set_retired_pubkey_status(
+    storage: &mut dyn Storage,
+    operator: &Addr,
+    pubkey: &PubKey,
+    status: PubkeyStatus,
+) -> Result<(), ContractError> {
+    let mut history = MACI_OPERATOR_PUBKEY_HISTORY
+        .may_load(storage, operator)?
+        .unwrap_or_default();
+    let retired = history
+        .iter_mut()
+        .find(|key| key.pubkey == *pubkey && key.status == PubkeyStatus::Retired)
+        .ok_or(ContractError::PubkeyNotRetired {})?;
+    retired.status = status;
+    MACI_OPERATOR_PUBKEY_HISTORY.save(storage, operator, &history)?;
+    Ok(())
+}
+
 // validator operator
 pub fn execute_set_maci_operator_identity(
     deps: DepsMut,
@@ -353,6 +1029,9 @@ pub fn execute_set_validators(
     if !is_operator(deps.as_ref(), info.sender.as_ref())? {
         Err(ContractError::Unauthorized {})
     } else {
+        for address in &addresses.addresses {
+            validate_address(deps.as_ref(), address)?;
+        }
         MACI_VALIDATOR_LIST.save(deps.storage, &addresses)?;
 
         Ok(Response::new()
@@ -363,7 +1042,7 @@ pub fn execute_set_validators(
 
 pub fn execute_remove_validator(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     address: Addr,
 ) -> Result<Response, ContractError> {
@@ -374,24 +1053,7 @@ pub fn execute_remove_validator(
         maci_validator_set.remove_validator(&address);
         MACI_VALIDATOR_LIST.save(deps.storage, &maci_validator_set)?;
 
-        if MACI_VALIDATOR_OPERATOR_SET.has(deps.storage, &address) {
-            let old_operator = MACI_VALIDATOR_OPERATOR_SET.load(deps.storage, &address)?;
-
-            MACI_VALIDATOR_OPERATOR_SET.remove(deps.storage, &address);
-            MACI_OPERATOR_SET.remove(deps.storage, &old_operator);
-
-            if MACI_OPERATOR_PUBKEY.has(deps.storage, &old_operator) {
-                let old_operator_pubkey = MACI_OPERATOR_PUBKEY.load(deps.storage, &old_operator)?;
-                COORDINATOR_PUBKEY_MAP.remove(
-                    deps.storage,
-                    &(
-                        old_operator_pubkey.x.to_be_bytes().to_vec(),
-                        old_operator_pubkey.y.to_be_bytes().to_vec(),
-                    ),
-                );
-                MACI_OPERATOR_PUBKEY.remove(deps.storage, &old_operator);
-            }
-        }
+        remove_validator_operator(deps.storage, &env, &address)?;
 
         // pub const MACI_VALIDATOR_LIST: Item<ValidatorSet> = Item::new("maci_validator_list"); // ['val1', 'val2', 'val3']
         // pub const MACI_VALIDATOR_OPERATOR_SET: Map<&Addr, Addr> = Map::new("maci_validator_operator_set"); // { val1: op1, val2: op2, val3: op3 }
@@ -407,44 +1069,133 @@ pub fn execute_remove_validator(
     }
 }
 
-pub fn execute_update_amaci_code_id(
+pub fn execute_set_validator_source(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    amaci_code_id: u64,
+    source: ValidatorSource,
 ) -> Result<Response, ContractError> {
     if !is_operator(deps.as_ref(), info.sender.as_ref())? {
-        Err(ContractError::Unauthorized {})
-    } else {
-        AMACI_CODE_ID.save(deps.storage, &amaci_code_id)?;
-        Ok(Response::new()
-            .add_attribute("action", "update_amaci_code_id")
-            .add_attribute("amaci_code_id", &amaci_code_id.to_string()))
+        return Err(ContractError::Unauthorized {});
     }
+
+    VALIDATOR_SOURCE.save(deps.storage, &source)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_validator_source")
+        .add_attribute("source", format!("{:?}", source)))
 }
 
-pub fn execute_change_operator(
+// anyone
+pub fn execute_prune_validators(
     deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    address: Addr,
+    env: Env,
+    _info: MessageInfo,
 ) -> Result<Response, ContractError> {
-    validate_dora_address(address.as_str())?;
-    
-    if !is_admin(deps.as_ref(), info.sender.as_ref())? {
-        Err(ContractError::Unauthorized {})
-    } else {
-        OPERATOR.save(deps.storage, &address)?;
+    let validator_set = query_validators(deps.as_ref())?;
+    let validators = MACI_VALIDATOR_OPERATOR_SET
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
 
-        Ok(Response::new()
-            .add_attribute("action", "change_operator")
-            .add_attribute("address", address.to_string()))
+    let mut pruned = vec![];
+    for validator in validators {
+        if !validator_set.is_validator(&validator) {
+            remove_validator_operator(deps.storage, &env, &validator)?;
+            pruned.push(validator.to_string());
+        }
     }
+
+    Ok(Response::new()
+        .add_attribute("action", "prune_validators")
+        .add_attribute("pruned", format!("{:?}", pruned)))
 }
 
-pub fn execute_change_charge_config(
-    deps: DepsMut,
-    _env: Env,
+// The maci operator of a removed validator loses its bond and its coordinator key
+fn remove_validator_operator(
+    storage: &mut dyn Storage,
+    env: &Env,
+    validator: &Addr,
+) -> StdResult<()> {
+    if let Some(old_operator) = MACI_VALIDATOR_OPERATOR_SET.may_load(storage, validator)? {
+        MACI_VALIDATOR_OPERATOR_SET.remove(storage, validator);
+        unbond_all(storage, env, &old_operator)?;
+
+        if let Some(old_operator_pubkey) = MACI_OPERATOR_PUBKEY.may_load(storage, &old_operator)? {
+            COORDINATOR_PUBKEY_MAP.remove(
+                storage,
+                &(
+                    old_operator_pubkey.x.to_be_bytes().to_vec(),
+                    old_operator_pubkey.y.to_be_bytes().to_vec(),
+                ),
+            );
+            MACI_OPERATOR_PUBKEY.remove(storage, &old_operator);
+            deactivate_pubkey(storage, env, &old_operator, PubkeyStatus::Released)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn execute_update_amaci_code_id(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    amaci_code_id: u64,
+) -> Result<Response, ContractError> {
+    if !is_operator(deps.as_ref(), info.sender.as_ref())? {
+        Err(ContractError::Unauthorized {})
+    } else {
+        AMACI_CODE_ID.save(deps.storage, &amaci_code_id)?;
+        Ok(Response::new()
+            .add_attribute("action", "update_amaci_code_id")
+            .add_attribute("amaci_code_id", &amaci_code_id.to_string()))
+    }
+}
+
+pub fn execute_update_round_code_id(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    round_type: RoundType,
+    code_id: u64,
+) -> Result<Response, ContractError> {
+    if !is_operator(deps.as_ref(), info.sender.as_ref())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match round_type {
+        RoundType::Amaci => AMACI_CODE_ID.save(deps.storage, &code_id)?,
+        RoundType::Maci => MACI_CODE_ID.save(deps.storage, &code_id)?,
+        RoundType::OracleMaci => ORACLE_MACI_CODE_ID.save(deps.storage, &code_id)?,
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_round_code_id")
+        .add_attribute("round_type", round_type.as_str())
+        .add_attribute("code_id", code_id.to_string()))
+}
+
+pub fn execute_change_operator(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    validate_address(deps.as_ref(), &address)?;
+
+    if !is_admin(deps.as_ref(), info.sender.as_ref())? {
+        Err(ContractError::Unauthorized {})
+    } else {
+        OPERATOR.save(deps.storage, &address)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "change_operator")
+            .add_attribute("address", address.to_string()))
+    }
+}
+
+pub fn execute_change_charge_config(
+    deps: DepsMut,
+    _env: Env,
     info: MessageInfo,
     config: CircuitChargeConfig,
 ) -> Result<Response, ContractError> {
@@ -459,6 +1210,356 @@ pub fn execute_change_charge_config(
         .add_attribute("fee_rate", config.fee_rate.to_string()))
 }
 
+pub fn execute_update_config(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    config: Config,
+) -> Result<Response, ContractError> {
+    if !is_admin(deps.as_ref(), info.sender.as_ref())? {
+        return Err(ContractError::Unauthorized {});
+    }
+    // Bonds and unbondings are held in the stake denom
+    let denom = CONFIG.load(deps.storage)?.denom;
+    if config.denom != denom {
+        return Err(ContractError::DenomImmutable { denom });
+    }
+    validate_config(&config)?;
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("denom", config.denom)
+        .add_attribute("min_deposit_amount", config.min_deposit_amount.to_string())
+        .add_attribute("slash_amount", config.slash_amount.to_string())
+        .add_attribute(
+            "unbonding_period",
+            config.unbonding_period.seconds().to_string(),
+        )
+        .add_attribute("max_delay_records", config.max_delay_records.to_string())
+        .add_attribute("address_prefix", config.address_prefix))
+}
+
+fn validate_config(config: &Config) -> Result<(), ContractError> {
+    // A bech32 prefix is lowercase, a mixed case address is invalid
+    if config.address_prefix.is_empty()
+        || !config
+            .address_prefix
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    {
+        return Err(ContractError::InvalidPrefix {
+            prefix: config.address_prefix.clone(),
+        });
+    }
+    // Unbonded tokens must stay slashable for a while
+    if config.unbonding_period.seconds() == 0 {
+        return Err(ContractError::InvalidUnbondingPeriod {});
+    }
+    if config.slash_amount > config.min_deposit_amount {
+        return Err(ContractError::InvalidSlashAmount {
+            slash_amount: config.slash_amount,
+            min_deposit_amount: config.min_deposit_amount,
+        });
+    }
+    Ok(())
+}
+
+pub fn execute_set_fee_tiers(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    round_type: RoundType,
+    tiers: Vec<FeeTier>,
+) -> Result<Response, ContractError> {
+    if !is_admin(deps.as_ref(), info.sender.as_ref())? {
+        return Err(ContractError::Unauthorized {});
+    }
+    validate_fee_tiers(&tiers)?;
+
+    FEE_TIERS.save(deps.storage, round_type.as_str(), &tiers)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_fee_tiers")
+        .add_attribute("round_type", round_type.as_str())
+        .add_attribute("tiers", tiers.len().to_string()))
+}
+
+// A round gets the first tier it fits, so every tier must be larger than the one before it in
+// both dimensions, and its circuit must hold the rounds it admits
+fn validate_fee_tiers(tiers: &[FeeTier]) -> Result<(), ContractError> {
+    for (index, tier) in tiers.iter().enumerate() {
+        if index > 0 {
+            let prev = &tiers[index - 1];
+            let larger = tier.max_voter >= prev.max_voter
+                && tier.max_option >= prev.max_option
+                && (tier.max_voter, tier.max_option) != (prev.max_voter, prev.max_option);
+            if !larger {
+                return Err(ContractError::UnsortedFeeTiers { index });
+            }
+        }
+        if tier.max_voter > tree_capacity(tier.parameters.state_tree_depth)
+            || tier.max_option > tree_capacity(tier.parameters.vote_option_tree_depth)
+        {
+            return Err(ContractError::FeeTierCircuitTooSmall { index });
+        }
+    }
+    Ok(())
+}
+
+// The leaves of a quinary tree of the depth, saturating at Uint256::MAX
+fn tree_capacity(depth: Uint256) -> Uint256 {
+    depth
+        .to_string()
+        .parse::<u32>()
+        .ok()
+        .and_then(|depth| Uint256::from_u128(5u128).checked_pow(depth).ok())
+        .unwrap_or(Uint256::MAX)
+}
+
+pub fn execute_set_fee_discount(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    creator: Addr,
+    discount: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    if !is_admin(deps.as_ref(), info.sender.as_ref())? {
+        return Err(ContractError::Unauthorized {});
+    }
+    validate_address(deps.as_ref(), &creator)?;
+
+    match discount {
+        Some(discount) => {
+            if discount > Decimal::one() {
+                return Err(ContractError::InvalidFeeDiscount { discount });
+            }
+            FEE_DISCOUNTS.save(deps.storage, &creator, &discount)?;
+        }
+        None => FEE_DISCOUNTS.remove(deps.storage, &creator),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_fee_discount")
+        .add_attribute("creator", creator.to_string())
+        .add_attribute("discount", discount.unwrap_or_default().to_string()))
+}
+
+// maci operator
+pub fn execute_bond(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let bond = match MACI_OPERATOR_SET.may_load(deps.storage, &info.sender)? {
+        Some(bond) => bond,
+        None => return Err(ContractError::Unauthorized {}),
+    };
+    let config = CONFIG.load(deps.storage)?;
+    let amount = must_pay(&info, &config.denom)?;
+
+    let bond = bond.checked_add(amount)?;
+    MACI_OPERATOR_SET.save(deps.storage, &info.sender, &bond)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "bond")
+        .add_attribute("maci_operator", info.sender.to_string())
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("bond", bond.to_string()))
+}
+
+// maci operator
+pub fn execute_unbond(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let bonded = match MACI_OPERATOR_SET.may_load(deps.storage, &info.sender)? {
+        Some(bond) => bond,
+        None => return Err(ContractError::Unauthorized {}),
+    };
+    if amount.is_zero() || amount > bonded {
+        return Err(ContractError::InvalidUnbondAmount { amount, bonded });
+    }
+
+    let bond = bonded - amount;
+    MACI_OPERATOR_SET.save(deps.storage, &info.sender, &bond)?;
+    let release_at = start_unbonding(deps.storage, &env, &info.sender, amount)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "unbond")
+        .add_attribute("maci_operator", info.sender.to_string())
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("bond", bond.to_string())
+        .add_attribute("release_at", release_at.seconds().to_string()))
+}
+
+pub fn execute_withdraw_unbonded(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let unbondings = UNBONDINGS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    let (released, pending): (Vec<Unbonding>, Vec<Unbonding>) = unbondings
+        .into_iter()
+        .partition(|unbonding| unbonding.release_at <= env.block.time);
+    let amount: Uint128 = released.iter().map(|unbonding| unbonding.amount).sum();
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    if pending.is_empty() {
+        UNBONDINGS.remove(deps.storage, &info.sender);
+    } else {
+        UNBONDINGS.save(deps.storage, &info.sender, &pending)?;
+    }
+
+    let denom = CONFIG.load(deps.storage)?.denom;
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(amount.u128(), denom),
+        })
+        .add_attribute("action", "withdraw_unbonded")
+        .add_attribute("address", info.sender.to_string())
+        .add_attribute("amount", amount.to_string()))
+}
+
+// round created by the registry
+pub fn execute_report_round_performance(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    performance: RoundPerformance,
+) -> Result<Response, ContractError> {
+    let round_id = match ROUND_ID_BY_ADDR.may_load(deps.storage, &info.sender)? {
+        Some(round_id) => round_id,
+        None => return Err(ContractError::Unauthorized {}),
+    };
+    // Only amaci rounds are served by maci operators, the others are run by their creator
+    let round = ROUNDS.load(deps.storage, round_id)?;
+    if round.round_type != RoundType::Amaci {
+        return Err(ContractError::Unauthorized {});
+    }
+    let operator = round.operator;
+    let config = CONFIG.load(deps.storage)?;
+
+    let delay_records = performance.deactivate_delays + performance.tally_delays;
+    let should_slash = performance.tally_timeout || delay_records > config.max_delay_records;
+
+    // A round can be claimed again after someone sends it funds, it only counts once
+    let mut slashed = Uint128::zero();
+    if !REPORTED_ROUNDS.has(deps.storage, round_id) {
+        record_performance(deps.storage, &operator, &performance)?;
+        if should_slash {
+            slashed = slash(deps.storage, &operator, config.slash_amount)?;
+        }
+        REPORTED_ROUNDS.save(deps.storage, round_id, &true)?;
+    }
+
+    let mut resp = Response::new()
+        .add_attribute("action", "report_round_performance")
+        .add_attribute("round_id", round_id.to_string())
+        .add_attribute("round_addr", info.sender.to_string())
+        .add_attribute("maci_operator", operator.to_string())
+        .add_attribute("tally_timeout", performance.tally_timeout.to_string())
+        .add_attribute("delay_records", delay_records.to_string())
+        .add_attribute("miss_rate", performance.miss_rate.to_string())
+        .add_attribute("slash_amount", slashed.to_string());
+    if !slashed.is_zero() {
+        let admin = ADMIN.load(deps.storage)?.admin;
+        resp = resp.add_message(BankMsg::Send {
+            to_address: admin.to_string(),
+            amount: coins(slashed.u128(), config.denom),
+        });
+    }
+
+    Ok(resp)
+}
+
+fn record_performance(
+    storage: &mut dyn Storage,
+    operator: &Addr,
+    performance: &RoundPerformance,
+) -> Result<(), ContractError> {
+    // The miss rate is a percentage
+    let miss_rate = Uint128::try_from(performance.miss_rate)
+        .ok()
+        .and_then(|rate| u64::try_from(rate.u128()).ok())
+        .filter(|rate| *rate <= 100)
+        .ok_or(ContractError::InvalidMissRate {
+            miss_rate: performance.miss_rate,
+        })?;
+    let mut stats = OPERATOR_STATS
+        .may_load(storage, operator)?
+        .unwrap_or_default();
+    stats.rounds_served += 1;
+    stats.late_deactivate_batches += performance.deactivate_delays;
+    stats.late_tallies += performance.tally_delays;
+    stats.tally_timeouts += performance.tally_timeout as u64;
+    stats.miss_rate_sum += miss_rate;
+    OPERATOR_STATS.save(storage, operator, &stats)?;
+    Ok(())
+}
+
+// Move the tokens into the unbonding queue, returns when they can be withdrawn
+fn start_unbonding(
+    storage: &mut dyn Storage,
+    env: &Env,
+    address: &Addr,
+    amount: Uint128,
+) -> StdResult<Timestamp> {
+    let unbonding_period = CONFIG.load(storage)?.unbonding_period;
+    let release_at = env.block.time.plus_seconds(unbonding_period.seconds());
+    let mut unbondings = UNBONDINGS.may_load(storage, address)?.unwrap_or_default();
+    unbondings.push(Unbonding { amount, release_at });
+    UNBONDINGS.save(storage, address, &unbondings)?;
+    Ok(release_at)
+}
+
+// A removed operator keeps its bond, which starts unbonding
+fn unbond_all(storage: &mut dyn Storage, env: &Env, operator: &Addr) -> StdResult<()> {
+    let bond = MACI_OPERATOR_SET
+        .may_load(storage, operator)?
+        .unwrap_or_default();
+    if !bond.is_zero() {
+        start_unbonding(storage, env, operator, bond)?;
+    }
+    MACI_OPERATOR_SET.remove(storage, operator);
+    Ok(())
+}
+
+// Take up to `amount` from the bond, then from the newest unbonding tokens
+fn slash(storage: &mut dyn Storage, operator: &Addr, amount: Uint128) -> StdResult<Uint128> {
+    let mut left = amount;
+    if let Some(bond) = MACI_OPERATOR_SET.may_load(storage, operator)? {
+        let taken = bond.min(left);
+        MACI_OPERATOR_SET.save(storage, operator, &(bond - taken))?;
+        left -= taken;
+    }
+
+    if !left.is_zero() {
+        let mut unbondings = UNBONDINGS.may_load(storage, operator)?.unwrap_or_default();
+        for unbonding in unbondings.iter_mut().rev() {
+            let taken = unbonding.amount.min(left);
+            unbonding.amount -= taken;
+            left -= taken;
+        }
+        unbondings.retain(|unbonding| !unbonding.amount.is_zero());
+        if unbondings.is_empty() {
+            UNBONDINGS.remove(storage, operator);
+        } else {
+            UNBONDINGS.save(storage, operator, &unbondings)?;
+        }
+    }
+
+    Ok(amount - left)
+}
+
 // Only admin can execute
 fn is_admin(deps: Deps, sender: &str) -> StdResult<bool> {
     let cfg = ADMIN.load(deps.storage)?;
@@ -479,9 +1580,43 @@ fn is_operator(deps: Deps, sender: &str) -> StdResult<bool> {
 }
 
 fn is_validator(deps: Deps, sender: &Addr) -> StdResult<bool> {
-    let cfg = MACI_VALIDATOR_LIST.load(deps.storage)?;
-    let can = cfg.is_validator(sender);
-    Ok(can)
+    match VALIDATOR_SOURCE.may_load(deps.storage)?.unwrap_or_default() {
+        ValidatorSource::Manual => {
+            let cfg = MACI_VALIDATOR_LIST.load(deps.storage)?;
+            let can = cfg.is_validator(sender);
+            Ok(can)
+        }
+        ValidatorSource::Staking => {
+            let prefix = CONFIG.load(deps.storage)?.address_prefix;
+            match convert_bech32(sender.as_str(), &format!("{}valoper", prefix)) {
+                Some(valoper) => Ok(deps.querier.query_validator(valoper)?.is_some()),
+                None => Ok(false),
+            }
+        }
+    }
+}
+
+fn query_validators(deps: Deps) -> StdResult<ValidatorSet> {
+    match VALIDATOR_SOURCE.may_load(deps.storage)?.unwrap_or_default() {
+        ValidatorSource::Manual => MACI_VALIDATOR_LIST.load(deps.storage),
+        ValidatorSource::Staking => {
+            let prefix = CONFIG.load(deps.storage)?.address_prefix;
+            let addresses = deps
+                .querier
+                .query_all_validators()?
+                .into_iter()
+                .filter_map(|validator| convert_bech32(&validator.address, &prefix))
+                .map(Addr::unchecked)
+                .collect();
+            Ok(ValidatorSet { addresses })
+        }
+    }
+}
+
+// A validator and the account of its operator share the same bytes under different prefixes
+fn convert_bech32(address: &str, prefix: &str) -> Option<String> {
+    let (_prefix, data, variant) = bech32::decode(address).ok()?;
+    bech32::encode(prefix, data, variant).ok()
 }
 
 fn is_operator_set(deps: Deps, sender: &Addr) -> StdResult<bool> {
@@ -503,7 +1638,10 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             to_json_binary(&MACI_OPERATOR_SET.has(deps.storage, &address))
         }
         QueryMsg::IsValidator { address } => to_json_binary(&is_validator(deps, &address)?),
-        QueryMsg::GetValidators {} => to_json_binary(&MACI_VALIDATOR_LIST.load(deps.storage)?),
+        QueryMsg::GetValidators {} => to_json_binary(&query_validators(deps)?),
+        QueryMsg::GetValidatorSource {} => {
+            to_json_binary(&VALIDATOR_SOURCE.may_load(deps.storage)?.unwrap_or_default())
+        }
         QueryMsg::GetValidatorOperator { address } => to_json_binary(
             &MACI_VALIDATOR_OPERATOR_SET
                 .may_load(deps.storage, &address)
@@ -512,49 +1650,350 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetMaciOperatorPubkey { address } => {
             to_json_binary(&MACI_OPERATOR_PUBKEY.load(deps.storage, &address)?)
         }
+        QueryMsg::GetMaciOperatorPubkeyHistory { address } => to_json_binary(
+            &MACI_OPERATOR_PUBKEY_HISTORY
+                .may_load(deps.storage, &address)?
+                .unwrap_or_default(),
+        ),
         QueryMsg::GetMaciOperatorIdentity { address } => {
             to_json_binary(&MACI_OPERATOR_IDENTITY.load(deps.storage, &address)?)
         }
         QueryMsg::GetCircuitChargeConfig {} => {
             to_json_binary(&CIRCUIT_CHARGE_CONFIG.load(deps.storage)?)
         }
+        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::GetRoundCodeId { round_type } => {
+            to_json_binary(&query_round_code_id(deps.storage, &round_type)?)
+        }
+        QueryMsg::GetFeeTiers { round_type } => to_json_binary(
+            &FEE_TIERS
+                .may_load(deps.storage, round_type.as_str())?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::GetFeeDiscount { address } => to_json_binary(
+            &FEE_DISCOUNTS
+                .may_load(deps.storage, &address)?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::QuoteRoundFee {
+            round_type,
+            max_voter,
+            max_option,
+            creator,
+        } => to_json_binary(&quote_round_fee(
+            deps,
+            &round_type,
+            max_voter,
+            max_option,
+            creator.as_ref(),
+        )?),
+        QueryMsg::GetOperatorBond { address } => to_json_binary(
+            &MACI_OPERATOR_SET
+                .may_load(deps.storage, &address)?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::GetUnbondings { address } => to_json_binary(
+            &UNBONDINGS
+                .may_load(deps.storage, &address)?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::GetOperatorStats { address } => {
+            to_json_binary(&query_operator_stats(deps, address)?)
+        }
+        QueryMsg::GetAllOperatorStats { start_after, limit } => {
+            to_json_binary(&query_all_operator_stats(deps, start_after, limit)?)
+        }
+        QueryMsg::GetRoundTemplate { owner, name } => to_json_binary(&match owner {
+            Some(owner) => ROUND_TEMPLATES.may_load(deps.storage, (&owner, &name))?,
+            None => GLOBAL_ROUND_TEMPLATES.may_load(deps.storage, &name)?,
+        }),
+        QueryMsg::GetRoundTemplates {
+            owner,
+            start_after,
+            limit,
+        } => to_json_binary(&query_round_templates(deps, owner, start_after, limit)?),
+        QueryMsg::GetRound { round_addr } => to_json_binary(&query_round(deps, &round_addr)?),
+        QueryMsg::GetRounds { start_after, limit } => {
+            to_json_binary(&query_rounds(deps, start_after, limit)?)
+        }
+        QueryMsg::GetRoundsByCreator {
+            creator,
+            start_after,
+            limit,
+        } => to_json_binary(&query_indexed_rounds(
+            deps,
+            &ROUNDS_BY_CREATOR,
+            &creator,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::GetRoundsByOperator {
+            operator,
+            start_after,
+            limit,
+        } => to_json_binary(&query_indexed_rounds(
+            deps,
+            &ROUNDS_BY_OPERATOR,
+            &operator,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::GetRoundsByVotingStart {
+            start,
+            end,
+            start_after,
+            limit,
+        } => to_json_binary(&query_rounds_by_voting_start(
+            deps,
+            start,
+            end,
+            start_after,
+            limit,
+        )?),
+    }
+}
+
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        denom: config.denom,
+        min_deposit_amount: config.min_deposit_amount,
+        slash_amount: config.slash_amount,
+        unbonding_period: config.unbonding_period,
+        max_delay_records: config.max_delay_records,
+        address_prefix: config.address_prefix,
+    })
+}
+
+fn query_round_code_id(storage: &dyn Storage, round_type: &RoundType) -> StdResult<Option<u64>> {
+    match round_type {
+        RoundType::Amaci => AMACI_CODE_ID.may_load(storage),
+        RoundType::Maci => MACI_CODE_ID.may_load(storage),
+        RoundType::OracleMaci => ORACLE_MACI_CODE_ID.may_load(storage),
     }
 }
 
+fn round_code_id(storage: &dyn Storage, round_type: &RoundType) -> Result<u64, ContractError> {
+    query_round_code_id(storage, round_type)?.ok_or_else(|| ContractError::RoundCodeIdNotSet {
+        round_type: round_type.as_str().to_string(),
+    })
+}
+
+fn quote_round_fee(
+    deps: Deps,
+    round_type: &RoundType,
+    max_voter: Uint256,
+    max_option: Uint256,
+    creator: Option<&Addr>,
+) -> StdResult<Option<RoundFeeQuote>> {
+    let tiers = FEE_TIERS
+        .may_load(deps.storage, round_type.as_str())?
+        .unwrap_or_default();
+    let tier = match tiers
+        .into_iter()
+        .find(|tier| max_voter <= tier.max_voter && max_option <= tier.max_option)
+    {
+        Some(tier) => tier,
+        None => return Ok(None),
+    };
+
+    let voter_fee = Uint128::try_from(Uint256::from(tier.fee_per_voter).checked_mul(max_voter)?)?;
+    let fee = tier.base_fee.checked_add(voter_fee)?;
+    let discount = match creator {
+        Some(creator) => FEE_DISCOUNTS
+            .may_load(deps.storage, creator)?
+            .unwrap_or_default(),
+        None => Decimal::zero(),
+    };
+
+    Ok(Some(RoundFeeQuote {
+        parameters: tier.parameters,
+        fee: fee * (Decimal::one() - discount),
+    }))
+}
+
+fn operator_stats_response(operator: Addr, stats: OperatorStats) -> OperatorStatsResponse {
+    let average_miss_rate = if stats.rounds_served == 0 {
+        Decimal::zero()
+    } else {
+        Decimal::from_ratio(stats.miss_rate_sum, stats.rounds_served * 100)
+    };
+    OperatorStatsResponse {
+        operator,
+        rounds_served: stats.rounds_served,
+        late_deactivate_batches: stats.late_deactivate_batches,
+        late_tallies: stats.late_tallies,
+        tally_timeouts: stats.tally_timeouts,
+        average_miss_rate,
+    }
+}
+
+fn query_operator_stats(deps: Deps, operator: Addr) -> StdResult<OperatorStatsResponse> {
+    let stats = OPERATOR_STATS
+        .may_load(deps.storage, &operator)?
+        .unwrap_or_default();
+    Ok(operator_stats_response(operator, stats))
+}
+
+fn query_all_operator_stats(
+    deps: Deps,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<OperatorStatsResponse>> {
+    let limit = limit.unwrap_or(30).min(100) as usize;
+    OPERATOR_STATS
+        .range(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(operator, stats)| operator_stats_response(operator, stats)))
+        .collect()
+}
+
+fn query_round_templates(
+    deps: Deps,
+    owner: Option<Addr>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<RoundTemplate>> {
+    let limit = limit.unwrap_or(30).min(100) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    let templates = match &owner {
+        Some(owner) => {
+            ROUND_TEMPLATES
+                .prefix(owner)
+                .range(deps.storage, start, None, Order::Ascending)
+        }
+        None => GLOBAL_ROUND_TEMPLATES.range(deps.storage, start, None, Order::Ascending),
+    };
+    templates
+        .take(limit)
+        .map(|item| item.map(|(_, template)| template))
+        .collect()
+}
+
+fn query_round(deps: Deps, round_addr: &Addr) -> StdResult<Option<RoundRecord>> {
+    match ROUND_ID_BY_ADDR.may_load(deps.storage, round_addr)? {
+        Some(id) => ROUNDS.may_load(deps.storage, id),
+        None => Ok(None),
+    }
+}
+
+fn query_rounds(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<RoundRecord>> {
+    let limit = limit.unwrap_or(30).min(100) as usize;
+    ROUNDS
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(_, round)| round))
+        .collect()
+}
+
+fn query_indexed_rounds<'a>(
+    deps: Deps,
+    index: &Map<'a, (&'a Addr, u64), bool>,
+    addr: &'a Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<RoundRecord>> {
+    let limit = limit.unwrap_or(30).min(100) as usize;
+    index
+        .prefix(addr)
+        .keys(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|id| ROUNDS.load(deps.storage, id?))
+        .collect()
+}
+
+fn query_rounds_by_voting_start(
+    deps: Deps,
+    start: Timestamp,
+    end: Timestamp,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<RoundRecord>> {
+    let limit = limit.unwrap_or(30).min(100) as usize;
+    let mut min = Bound::inclusive((start.seconds(), 0u64));
+    if let Some(id) = start_after {
+        let round = ROUNDS.load(deps.storage, id)?;
+        let key = (round.voting_time.start_time.seconds(), id);
+        if key >= (start.seconds(), 0u64) {
+            min = Bound::exclusive(key);
+        }
+    }
+    ROUNDS_BY_VOTING_START
+        .keys(
+            deps.storage,
+            Some(min),
+            Some(Bound::inclusive((end.seconds(), u64::MAX))),
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|key| ROUNDS.load(deps.storage, key?.1))
+        .collect()
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, env: Env, reply: Reply) -> Result<Response, ContractError> {
     match reply.id {
         CREATED_GROTH16_ROUND_REPLY_ID => {
             reply_created_round(deps, env, reply.result.into_result())
         }
+        CREATED_MACI_ROUND_REPLY_ID => reply_created_maci_round(deps, reply.result.into_result()),
+        CREATED_ORACLE_MACI_ROUND_REPLY_ID => {
+            reply_created_oracle_maci_round(deps, reply.result.into_result())
+        }
         id => Err(ContractError::UnRecognizedReplyIdErr { id }),
     }
 }
 
+fn parse_created_round(
+    reply: Result<SubMsgResponse, String>,
+) -> Result<MsgInstantiateContractResponse, ContractError> {
+    let response = reply.map_err(StdError::generic_err)?;
+    let data = response.data.ok_or(ContractError::DataMissingErr {})?;
+    // let response = parse_instantiate_response_data(&data)?;
+    match parse_instantiate_response_data(&data) {
+        Ok(data) => Ok(data),
+        Err(err) => Err(ContractError::Std(cosmwasm_std::StdError::generic_err(
+            err.to_string(),
+        ))),
+    }
+}
+
 pub fn reply_created_round(
     deps: DepsMut,
     _env: Env,
     reply: Result<SubMsgResponse, String>,
 ) -> Result<Response, ContractError> {
-    let response = reply.map_err(StdError::generic_err)?;
-    let data = response.data.ok_or(ContractError::DataMissingErr {})?;
-    // let response = parse_instantiate_response_data(&data)?;
-    let response = match parse_instantiate_response_data(&data) {
-        Ok(data) => data,
-        Err(err) => {
-            return Err(ContractError::Std(cosmwasm_std::StdError::generic_err(
-                err.to_string(),
-            )))
-        }
-    };
+    let response = parse_created_round(reply)?;
     let amaci_code_id = AMACI_CODE_ID.load(deps.storage)?;
 
     let addr = Addr::unchecked(response.clone().contract_address);
     let data = InstantiationData { addr: addr.clone() };
     let amaci_return_data: AMaciInstantiationData = from_json(&response.data.unwrap())?;
 
+    let round_id = save_pending_round(deps.storage, &addr)?.id;
+
     let mut attributes = vec![
         attr("action", "created_round"),
+        attr("round_id", round_id.to_string()),
         attr("code_id", amaci_code_id.to_string()),
         attr("round_addr", addr.to_string()),
         attr("caller", &amaci_return_data.caller.to_string()),
@@ -630,6 +2069,17 @@ pub fn reply_created_round(
         ),
     ];
 
+    if let Some(sign_up_time) = &amaci_return_data.sign_up_time {
+        attributes.push(attr(
+            "sign_up_start",
+            sign_up_time.start_time.nanos().to_string(),
+        ));
+        attributes.push(attr(
+            "sign_up_end",
+            sign_up_time.end_time.nanos().to_string(),
+        ));
+    }
+
     if amaci_return_data.round_info.description != "" {
         attributes.push(attr(
             "round_description",
@@ -641,14 +2091,160 @@ pub fn reply_created_round(
         attributes.push(attr("round_link", &amaci_return_data.round_info.link));
     }
 
+    let create_round_event = Event::from(maci_events::CreateRound {
+        round_addr: addr,
+        round_type: String::from("amaci"),
+        code_id: Some(amaci_code_id),
+        caller: Some(amaci_return_data.caller),
+        admin: Some(amaci_return_data.admin),
+        operator: Some(amaci_return_data.operator),
+        round_title: amaci_return_data.round_info.title,
+        voting_start: Some(amaci_return_data.voting_time.start_time.nanos()),
+        voting_end: Some(amaci_return_data.voting_time.end_time.nanos()),
+        coordinator_pubkey: [
+            amaci_return_data.coordinator.x,
+            amaci_return_data.coordinator.y,
+        ],
+        max_vote_options: amaci_return_data.max_vote_options,
+        circuit_type: amaci_return_data.circuit_type,
+        certification_system: amaci_return_data.certification_system,
+    });
+
     Ok(Response::new()
         .add_attributes(attributes)
+        .add_event(create_round_event)
         .set_data(to_json_binary(&data)?))
 }
 
+pub fn reply_created_maci_round(
+    deps: DepsMut,
+    reply: Result<SubMsgResponse, String>,
+) -> Result<Response, ContractError> {
+    let response = parse_created_round(reply)?;
+    let maci_code_id = MACI_CODE_ID.load(deps.storage)?;
+
+    let addr = Addr::unchecked(response.contract_address);
+    let data = InstantiationData { addr: addr.clone() };
+    let round = save_pending_round(deps.storage, &addr)?;
+
+    // the round emits the `CreateRound` event itself
+    Ok(Response::new()
+        .add_attributes(created_round_attributes(&round, maci_code_id))
+        .set_data(to_json_binary(&data)?))
+}
+
+pub fn reply_created_oracle_maci_round(
+    deps: DepsMut,
+    reply: Result<SubMsgResponse, String>,
+) -> Result<Response, ContractError> {
+    let response = parse_created_round(reply)?;
+    let oracle_maci_code_id = ORACLE_MACI_CODE_ID.load(deps.storage)?;
+
+    let addr = Addr::unchecked(response.contract_address);
+    let data = InstantiationData { addr: addr.clone() };
+    let oracle_maci_return_data: OracleMaciInstantiationData =
+        from_json(&response.data.ok_or(ContractError::DataMissingErr {})?)?;
+    let round = save_pending_round(deps.storage, &addr)?;
+
+    let mut attributes = created_round_attributes(&round, oracle_maci_code_id);
+    attributes.extend(vec![
+        attr("round_title", &oracle_maci_return_data.round_info.title),
+        attr("max_voters", oracle_maci_return_data.max_voters.to_string()),
+        attr(
+            "fee_grant_amount",
+            oracle_maci_return_data.fee_grant_amount.to_string(),
+        ),
+    ]);
+
+    let create_round_event = Event::from(maci_events::CreateRound {
+        round_addr: addr,
+        round_type: String::from(RoundType::OracleMaci.as_str()),
+        code_id: Some(oracle_maci_code_id),
+        caller: Some(oracle_maci_return_data.caller),
+        admin: Some(round.creator.clone()),
+        operator: Some(round.operator),
+        round_title: oracle_maci_return_data.round_info.title,
+        voting_start: Some(round.voting_time.start_time.nanos()),
+        voting_end: Some(round.voting_time.end_time.nanos()),
+        coordinator_pubkey: [round.coordinator_pubkey.x, round.coordinator_pubkey.y],
+        max_vote_options: Uint256::from_u128(oracle_maci_return_data.vote_option_map.len() as u128),
+        circuit_type: oracle_maci_return_data.circuit_type,
+        certification_system: oracle_maci_return_data.certification_system,
+    });
+
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .add_event(create_round_event)
+        .set_data(to_json_binary(&data)?))
+}
+
+fn created_round_attributes(round: &RoundRecord, code_id: u64) -> Vec<cosmwasm_std::Attribute> {
+    vec![
+        attr("action", "created_round"),
+        attr("round_id", round.id.to_string()),
+        attr("round_type", round.round_type.as_str()),
+        attr("code_id", code_id.to_string()),
+        attr("round_addr", round.round_addr.to_string()),
+        attr("admin", round.creator.to_string()),
+        attr("operator", round.operator.to_string()),
+        attr(
+            "voting_start",
+            round.voting_time.start_time.nanos().to_string(),
+        ),
+        attr("voting_end", round.voting_time.end_time.nanos().to_string()),
+        attr("fee", round.fee.to_string()),
+    ]
+}
+
+// Index the round saved by the create message now that its address is known
+fn save_pending_round(storage: &mut dyn Storage, round_addr: &Addr) -> StdResult<RoundRecord> {
+    let mut round = PENDING_ROUND.load(storage)?;
+    round.id = ROUND_COUNT.may_load(storage)?.unwrap_or_default();
+    round.round_addr = round_addr.clone();
+    save_round(storage, &round)?;
+    PENDING_ROUND.remove(storage);
+    Ok(round)
+}
+
+fn save_round(storage: &mut dyn Storage, round: &RoundRecord) -> StdResult<()> {
+    ROUNDS.save(storage, round.id, round)?;
+    ROUND_ID_BY_ADDR.save(storage, &round.round_addr, &round.id)?;
+    ROUNDS_BY_CREATOR.save(storage, (&round.creator, round.id), &true)?;
+    ROUNDS_BY_OPERATOR.save(storage, (&round.operator, round.id), &true)?;
+    ROUNDS_BY_VOTING_START.save(
+        storage,
+        (round.voting_time.start_time.seconds(), round.id),
+        &true,
+    )?;
+    ROUND_COUNT.save(storage, &(round.id + 1))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    cw2::ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+pub fn migrate(mut deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored_version =
+        cw2::ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Apply every storage upgrade newer than the stored version, oldest first
+    let stored = (
+        stored_version.major,
+        stored_version.minor,
+        stored_version.patch,
+    );
+    let mut attributes = vec![attr("action", "migrate")];
+    if stored < (0, 1, 4) {
+        attributes.extend(migrate_v0_1_4(deps.branch())?);
+    }
+    if stored < (0, 1, 5) {
+        attributes.extend(migrate_v0_1_5(deps.branch())?);
+    }
+
+    if let Some(address_prefix) = msg.address_prefix {
+        let mut config = CONFIG.load(deps.storage)?;
+        config.address_prefix = address_prefix;
+        CONFIG.save(deps.storage, &config)?;
+        attributes.push(attr("address_prefix", config.address_prefix));
+    }
 
-    migrate_v0_1_4(deps)
+    attributes.push(attr("from_version", stored_version.to_string()));
+    Ok(Response::new().add_attributes(attributes))
 }
@@ -1,7 +1,8 @@
-use cosmwasm_std::{StdError, Uint128, OverflowError};
+use cosmwasm_std::{Decimal, OverflowError, StdError, Uint128, Uint256};
 use thiserror::Error;
 
 use cw_controllers::{AdminError, HookError};
+use cw_utils::PaymentError;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
@@ -20,6 +21,9 @@ pub enum ContractError {
     #[error("{0}")]
     Hook(#[from] HookError),
 
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -88,4 +92,43 @@ pub enum ContractError {
     
     #[error("Invalid address prefix. Expected: {expected}, Actual: {actual}")]
     InvalidAddressPrefix { expected: String, actual: String },
+
+    #[error("Cannot unbond {amount}, bonded: {bonded}")]
+    InvalidUnbondAmount { amount: Uint128, bonded: Uint128 },
+
+    #[error("Only a retired pubkey of the sender can be released or burned")]
+    PubkeyNotRetired {},
+
+    #[error("Fee discount {discount} is more than the whole fee")]
+    InvalidFeeDiscount { discount: Decimal },
+
+    #[error("Fee tier {index} is not larger than the tier before it")]
+    UnsortedFeeTiers { index: usize },
+
+    #[error("The circuit of fee tier {index} cannot hold its max_voter and max_option")]
+    FeeTierCircuitTooSmall { index: usize },
+
+    #[error("No code id is set for {round_type} rounds")]
+    RoundCodeIdNotSet { round_type: String },
+
+    #[error("Round template {name} not found")]
+    RoundTemplateNotFound { name: String },
+
+    #[error("The stake denom {denom} cannot be changed")]
+    DenomImmutable { denom: String },
+
+    #[error("Invalid address prefix {prefix}")]
+    InvalidPrefix { prefix: String },
+
+    #[error("Miss rate {miss_rate} is more than 100 percent")]
+    InvalidMissRate { miss_rate: Uint256 },
+
+    #[error("The unbonding period must not be zero")]
+    InvalidUnbondingPeriod {},
+
+    #[error("Slash amount {slash_amount} is more than the minimum deposit {min_deposit_amount}")]
+    InvalidSlashAmount {
+        slash_amount: Uint128,
+        min_deposit_amount: Uint128,
+    },
 }
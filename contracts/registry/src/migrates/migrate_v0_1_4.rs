@@ -1,11 +1,8 @@
 use crate::error::ContractError;
-use cosmwasm_std::{Attribute, DepsMut, Response};
+use cosmwasm_std::{Attribute, DepsMut};
 
-pub fn migrate_v0_1_4(_deps: DepsMut) -> Result<Response, ContractError> {
-    let attributes: Vec<Attribute> = vec![
-        Attribute::new("action", "migrate"),
-        Attribute::new("version", "0.1.4"),
-    ];
+pub fn migrate_v0_1_4(_deps: DepsMut) -> Result<Vec<Attribute>, ContractError> {
+    let attributes: Vec<Attribute> = vec![Attribute::new("version", "0.1.4")];
 
-    Ok(Response::new().add_attributes(attributes))
+    Ok(attributes)
 }
@@ -0,0 +1,53 @@
+use crate::error::ContractError;
+use crate::state::{
+    default_fee_tiers, Config, OperatorPubkey, PubkeyStatus, RoundType, CONFIG, FEE_TIERS,
+    MACI_OPERATOR_PUBKEY, MACI_OPERATOR_PUBKEY_HISTORY,
+};
+use cosmwasm_std::{Attribute, DepsMut, Order, StdResult, Timestamp, Uint128};
+
+pub fn migrate_v0_1_5(deps: DepsMut) -> Result<Vec<Attribute>, ContractError> {
+    let attributes: Vec<Attribute> = vec![Attribute::new("version", "0.1.5")];
+
+    // Existing operators have no bond, so nothing is required or slashed until the admin sets
+    // the amounts with `UpdateConfig`
+    if !CONFIG.exists(deps.storage) {
+        let config = Config {
+            denom: "peaka".to_string(),
+            min_deposit_amount: Uint128::zero(),
+            slash_amount: Uint128::zero(),
+            unbonding_period: Timestamp::from_seconds(7 * 24 * 60 * 60), // 7 days
+            max_delay_records: 3,
+            address_prefix: "dora".to_string(),
+        };
+        CONFIG.save(deps.storage, &config)?;
+    }
+
+    // Keep charging what `CreateRound` charged before the fee tiers were configurable
+    for round_type in RoundType::all() {
+        if !FEE_TIERS.has(deps.storage, round_type.as_str()) {
+            FEE_TIERS.save(
+                deps.storage,
+                round_type.as_str(),
+                &default_fee_tiers(&round_type),
+            )?;
+        }
+    }
+
+    // The current keys start the history, the keys they replaced are not known
+    let pubkeys = MACI_OPERATOR_PUBKEY
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (operator, pubkey) in pubkeys {
+        if !MACI_OPERATOR_PUBKEY_HISTORY.has(deps.storage, &operator) {
+            let active = OperatorPubkey {
+                pubkey,
+                status: PubkeyStatus::Active,
+                activated_at: None,
+                deactivated_at: None,
+            };
+            MACI_OPERATOR_PUBKEY_HISTORY.save(deps.storage, &operator, &vec![active])?;
+        }
+    }
+
+    Ok(attributes)
+}
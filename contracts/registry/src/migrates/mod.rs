@@ -1 +1,2 @@
 pub mod migrate_v0_1_4;
+pub mod migrate_v0_1_5;
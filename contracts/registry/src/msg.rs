@@ -1,21 +1,37 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Uint128, Uint256};
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128, Uint256};
 
 use cw_amaci::{
-    msg::WhitelistBase,
-    state::{PubKey, RoundInfo, VotingTime},
+    msg::{RoundPerformance, WhitelistBase},
+    state::{MaciParameters, QuestionConfig, RoundInfo, SignUpTime, VotingTime},
 };
+use cw_maci::{
+    msg::{Groth16VKeyInput, PlonkVKeyType},
+    state::Whitelist as MaciWhitelist,
+};
+use cw_oracle_maci::msg::VotingPowerArgs;
+use maci_core::{PubKey, QuinaryTreeRoot};
 
-use crate::state::{CircuitChargeConfig, ValidatorSet};
+use crate::state::{
+    CircuitChargeConfig, Config, FeeTier, OperatorPubkey, RoundRecord, RoundSettings,
+    RoundTemplate, RoundType, Unbonding, ValidatorSet, ValidatorSource,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
-    // /// denom of the token to stake
-    // pub denom: String,
+    /// denom of the token to stake
+    pub denom: String,
+
+    pub min_deposit_amount: Uint128,
+
+    pub slash_amount: Uint128,
+
+    pub unbonding_period: Timestamp,
 
-    // pub min_deposit_amount: Uint128,
+    pub max_delay_records: u64,
 
-    // pub slash_amount: Uint128,
+    /// bech32 prefix every address in the messages must have
+    pub address_prefix: String,
 
     // admin can only bond/withdraw token
     pub admin: Addr,
@@ -31,23 +47,46 @@ pub enum ExecuteMsg {
     SetMaciOperator {
         operator: Addr,
     },
+    /// Set a new coordinator key, the previous one is retired but stays reserved
     SetMaciOperatorPubkey {
         pubkey: PubKey,
     },
+    /// Free a retired key of the sender so any operator can set it
+    ReleaseMaciOperatorPubkey {
+        pubkey: PubKey,
+    },
+    /// Keep a retired key of the sender reserved forever
+    BurnMaciOperatorPubkey {
+        pubkey: PubKey,
+    },
     SetMaciOperatorIdentity {
         identity: String,
     },
+    /// Create a round of the type of `round`. Pays the fee quoted by `QuoteRoundFee` for that
+    /// type, any excess is refunded
     CreateRound {
-        operator: Addr,
-        max_voter: Uint256,
-        max_option: Uint256,
-        voice_credit_amount: Uint256,
+        round: NewRound,
+    },
+    /// Save a template of the sender, or a global one if the sender is the admin.
+    /// A template with the same name is replaced
+    SetRoundTemplate {
+        name: String,
+        global: bool,
+        settings: RoundSettings,
+    },
+    RemoveRoundTemplate {
+        name: String,
+        global: bool,
+    },
+    /// `CreateRound` of an amaci round with the settings of a template of the sender, or of a
+    /// global template
+    CreateRoundFromTemplate {
+        name: String,
+        global: bool,
         round_info: RoundInfo,
         voting_time: VotingTime,
-        whitelist: Option<WhitelistBase>,
-        pre_deactivate_root: Uint256,
-        circuit_type: Uint256,
-        certification_system: Uint256,
+        sign_up_time: Option<SignUpTime>,
+        overrides: RoundOverrides,
     },
     SetValidators {
         addresses: ValidatorSet,
@@ -55,19 +94,120 @@ pub enum ExecuteMsg {
     RemoveValidator {
         address: Addr,
     },
+    SetValidatorSource {
+        source: ValidatorSource,
+    },
+    /// Remove the maci operators of validators that are no longer in the validator set
+    PruneValidators {},
     UpdateAmaciCodeId {
         amaci_code_id: u64,
     },
+    UpdateRoundCodeId {
+        round_type: RoundType,
+        code_id: u64,
+    },
     ChangeOperator {
         address: Addr,
     },
     ChangeChargeConfig {
         config: CircuitChargeConfig,
     },
+    UpdateConfig {
+        config: Config,
+    },
+    SetFeeTiers {
+        round_type: RoundType,
+        tiers: Vec<FeeTier>,
+    },
+    /// Waive a share of the round fee of a creator, `None` removes the discount
+    SetFeeDiscount {
+        creator: Addr,
+        discount: Option<Decimal>,
+    },
+    /// Add the sent tokens to the bond of the sender, a maci operator
+    Bond {},
+    /// Start unbonding, the tokens can be withdrawn after the unbonding period
+    Unbond {
+        amount: Uint128,
+    },
+    WithdrawUnbonded {},
+    /// Sent by a round created through `CreateRound` when it is claimed
+    ReportRoundPerformance {
+        performance: RoundPerformance,
+    },
 }
 
+/// The settings of a round to create, keyed by its `RoundType`
 #[cw_serde]
-pub struct MigrateMsg {}
+pub enum NewRound {
+    /// An amaci round operated by a maci operator of the registry, which also keeps the fee
+    Amaci {
+        operator: Addr,
+        max_voter: Uint256,
+        max_option: Uint256,
+        voice_credit_amount: Uint256,
+        round_info: RoundInfo,
+        voting_time: VotingTime,
+        sign_up_time: Option<SignUpTime>,
+        whitelist: Option<WhitelistBase>,
+        pre_deactivate_root: Uint256,
+        circuit_type: Uint256,
+        certification_system: Uint256,
+        extra_questions: Option<Vec<QuestionConfig>>,
+    },
+    /// A maci round administered and operated by the sender, on the circuit of the maci fee
+    /// tier it fits. The fee goes to the admin of the registry
+    Maci {
+        coordinator: PubKey,
+        qtr_lib: QuinaryTreeRoot,
+        groth16_process_vkey: Option<Groth16VKeyInput>,
+        groth16_tally_vkey: Option<Groth16VKeyInput>,
+        plonk_process_vkey: Option<PlonkVKeyType>,
+        plonk_tally_vkey: Option<PlonkVKeyType>,
+        max_voter: Uint256,
+        max_option: Uint256,
+        round_info: RoundInfo,
+        voting_time: VotingTime,
+        sign_up_time: Option<SignUpTime>,
+        whitelist: Option<MaciWhitelist>,
+        circuit_type: Uint256,
+        certification_system: Uint256,
+    },
+    /// An oracle-maci round administered by the sender. The fee is sent to the round to pay the
+    /// fee grants of its voters
+    OracleMaci {
+        coordinator: PubKey,
+        max_voters: u128,
+        vote_option_map: Vec<String>,
+        round_info: RoundInfo,
+        voting_time: VotingTime,
+        sign_up_time: Option<SignUpTime>,
+        circuit_type: Uint256,
+        certification_system: Uint256,
+        whitelist_backend_pubkey: String,
+        whitelist_ecosystem: String,
+        whitelist_snapshot_height: Uint256,
+        whitelist_voting_power_args: VotingPowerArgs,
+    },
+}
+
+/// Settings of a template replaced for a single round
+#[cw_serde]
+#[derive(Default)]
+pub struct RoundOverrides {
+    pub operator: Option<Addr>,
+    pub max_voter: Option<Uint256>,
+    pub max_option: Option<Uint256>,
+    pub voice_credit_amount: Option<Uint256>,
+    pub whitelist: Option<WhitelistBase>,
+    pub extra_questions: Option<Vec<QuestionConfig>>,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {
+    /// replaces the bech32 prefix addresses are validated with
+    pub address_prefix: Option<String>,
+}
 
 #[cw_serde]
 #[derive(QueryResponses)]
@@ -87,17 +227,107 @@ pub enum QueryMsg {
     #[returns(ValidatorSet)]
     GetValidators {},
 
+    #[returns(ValidatorSource)]
+    GetValidatorSource {},
+
     #[returns(Addr)]
     GetValidatorOperator { address: Addr },
 
     #[returns(PubKey)]
     GetMaciOperatorPubkey { address: Addr },
 
+    #[returns(Vec<OperatorPubkey>)]
+    GetMaciOperatorPubkeyHistory { address: Addr },
+
     #[returns(String)]
     GetMaciOperatorIdentity { address: Addr },
 
     #[returns(CircuitChargeConfig)]
     GetCircuitChargeConfig {},
+
+    #[returns(ConfigResponse)]
+    Config {},
+
+    /// `None` if rounds of the type cannot be created yet
+    #[returns(Option<u64>)]
+    GetRoundCodeId { round_type: RoundType },
+
+    #[returns(Vec<FeeTier>)]
+    GetFeeTiers { round_type: RoundType },
+
+    #[returns(Decimal)]
+    GetFeeDiscount { address: Addr },
+
+    /// The circuit and fee of a round, `max_option` is the most options of any of its questions.
+    /// `None` if no circuit fits
+    #[returns(Option<RoundFeeQuote>)]
+    QuoteRoundFee {
+        round_type: RoundType,
+        max_voter: Uint256,
+        max_option: Uint256,
+        creator: Option<Addr>,
+    },
+
+    #[returns(Uint128)]
+    GetOperatorBond { address: Addr },
+
+    #[returns(Vec<Unbonding>)]
+    GetUnbondings { address: Addr },
+
+    #[returns(OperatorStatsResponse)]
+    GetOperatorStats { address: Addr },
+
+    /// The statistics of every operator that served a claimed round, ordered by address
+    #[returns(Vec<OperatorStatsResponse>)]
+    GetAllOperatorStats {
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+
+    /// A template of `owner`, or a global template if `owner` is not set
+    #[returns(Option<RoundTemplate>)]
+    GetRoundTemplate { owner: Option<Addr>, name: String },
+
+    /// The templates of `owner`, or the global templates if `owner` is not set, ordered by name
+    #[returns(Vec<RoundTemplate>)]
+    GetRoundTemplates {
+        owner: Option<Addr>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Option<RoundRecord>)]
+    GetRound { round_addr: Addr },
+
+    /// Created rounds, oldest first
+    #[returns(Vec<RoundRecord>)]
+    GetRounds {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Vec<RoundRecord>)]
+    GetRoundsByCreator {
+        creator: Addr,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Vec<RoundRecord>)]
+    GetRoundsByOperator {
+        operator: Addr,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Rounds whose voting starts between `start` and `end`, inclusive, ordered by start time
+    #[returns(Vec<RoundRecord>)]
+    GetRoundsByVotingStart {
+        start: Timestamp,
+        end: Timestamp,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -110,6 +340,28 @@ pub struct ConfigResponse {
     pub denom: String,
     pub min_deposit_amount: Uint128,
     pub slash_amount: Uint128,
+    pub unbonding_period: Timestamp,
+    pub max_delay_records: u64,
+    pub address_prefix: String,
+}
+
+#[cw_serde]
+pub struct RoundFeeQuote {
+    pub parameters: MaciParameters,
+    /// the fee after the discount of the creator
+    pub fee: Uint128,
+}
+
+#[cw_serde]
+pub struct OperatorStatsResponse {
+    pub operator: Addr,
+    pub rounds_served: u64,
+    pub late_deactivate_batches: u64,
+    pub late_tallies: u64,
+    pub tally_timeouts: u64,
+    /// the average share of the reward the operator lost to delays, a tally timeout loses all
+    /// of it. 0 before its first round
+    pub average_miss_rate: Decimal,
 }
 
 #[cw_serde]
@@ -6,18 +6,26 @@ use anyhow::Result as AnyResult;
 use crate::{
     contract::{execute, instantiate, migrate, query, reply},
     msg::*,
-    state::{CircuitChargeConfig, ValidatorSet},
+    state::{
+        CircuitChargeConfig, Config, FeeTier, OperatorPubkey, RoundRecord, RoundSettings,
+        RoundTemplate, RoundType, Unbonding, ValidatorSet, ValidatorSource,
+    },
 };
-use cosmwasm_std::{Addr, Coin, StdResult, Timestamp, Uint256};
+use cosmwasm_std::{Addr, Coin, Decimal, StdResult, Timestamp, Uint128, Uint256, Validator};
 use cw_amaci::msg::{WhitelistBase, WhitelistBaseConfig};
 
-use cw_amaci::state::{PubKey, RoundInfo, VotingTime};
+use cw_amaci::state::{RoundInfo, VotingTime};
 use cw_multi_test::{App, AppResponse, ContractWrapper, Executor};
+use cw_oracle_maci::{msg::VotingPowerArgs, state::VotingPowerMode};
+use maci_core::{PubKey, QuinaryTreeRoot};
 pub const MOCK_CONTRACT_ADDR: &str = "cosmos2contract";
 pub const DORA_DEMON: &str = "peaka";
 pub const DORA_DECIMALS: u8 = 18;
 pub const MIN_DEPOSIT_AMOUNT: u128 = 20u128;
 pub const SLASH_AMOUNT: u128 = 1u128; // only 1, 2 (admin amount is not enough)
+pub const UNBONDING_PERIOD: u64 = 7 * 24 * 60 * 60; // 7 days
+pub const MAX_DELAY_RECORDS: u64 = 3;
+pub const ADDRESS_PREFIX: &str = "dora";
 use num_bigint::BigUint;
 
 pub fn uint256_from_decimal_string(decimal_string: &str) -> Uint256 {
@@ -94,6 +102,25 @@ impl AmaciRegistryCodeId {
     }
 }
 
+pub fn store_maci_code(app: &mut App) -> u64 {
+    let contract = ContractWrapper::new(
+        cw_maci::contract::execute,
+        cw_maci::contract::instantiate,
+        cw_maci::contract::query,
+    );
+    app.store_code(Box::new(contract))
+}
+
+pub fn store_oracle_maci_code(app: &mut App) -> u64 {
+    let contract = ContractWrapper::new(
+        cw_oracle_maci::contract::execute,
+        cw_oracle_maci::contract::instantiate,
+        cw_oracle_maci::contract::query,
+    )
+    .with_reply(cw_oracle_maci::contract::reply);
+    app.store_code(Box::new(contract))
+}
+
 impl From<AmaciRegistryCodeId> for u64 {
     fn from(code_id: AmaciRegistryCodeId) -> Self {
         code_id.0
@@ -120,6 +147,12 @@ impl AmaciRegistryContract {
         label: &str,
     ) -> AnyResult<Self> {
         let init_msg = InstantiateMsg {
+            denom: DORA_DEMON.to_string(),
+            min_deposit_amount: Uint128::from(MIN_DEPOSIT_AMOUNT),
+            slash_amount: Uint128::from(SLASH_AMOUNT),
+            unbonding_period: Timestamp::from_seconds(UNBONDING_PERIOD),
+            max_delay_records: MAX_DELAY_RECORDS,
+            address_prefix: ADDRESS_PREFIX.to_string(),
             admin: admin().clone(),
             operator,
             amaci_code_id,
@@ -165,6 +198,124 @@ impl AmaciRegistryContract {
         )
     }
 
+    #[track_caller]
+    pub fn release_maci_operator_pubkey(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        pubkey: PubKey,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::ReleaseMaciOperatorPubkey { pubkey },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn burn_maci_operator_pubkey(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        pubkey: PubKey,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::BurnMaciOperatorPubkey { pubkey },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn bond(&self, app: &mut App, sender: Addr, send_funds: &[Coin]) -> AnyResult<AppResponse> {
+        app.execute_contract(sender, self.addr(), &ExecuteMsg::Bond {}, send_funds)
+    }
+
+    #[track_caller]
+    pub fn unbond(&self, app: &mut App, sender: Addr, amount: u128) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::Unbond {
+                amount: Uint128::from(amount),
+            },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn withdraw_unbonded(&self, app: &mut App, sender: Addr) -> AnyResult<AppResponse> {
+        app.execute_contract(sender, self.addr(), &ExecuteMsg::WithdrawUnbonded {}, &[])
+    }
+
+    #[track_caller]
+    pub fn update_config(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        config: Config,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::UpdateConfig { config },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn set_fee_tiers(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        round_type: RoundType,
+        tiers: Vec<FeeTier>,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::SetFeeTiers { round_type, tiers },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn update_round_code_id(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        round_type: RoundType,
+        code_id: u64,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::UpdateRoundCodeId {
+                round_type,
+                code_id,
+            },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn set_fee_discount(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        creator: Addr,
+        discount: Option<Decimal>,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::SetFeeDiscount { creator, discount },
+            &[],
+        )
+    }
+
     #[track_caller]
     pub fn create_round(
         &self,
@@ -185,19 +336,23 @@ impl AmaciRegistryContract {
         let end_time = start_time.plus_minutes(11);
 
         let msg = ExecuteMsg::CreateRound {
-            operator,
-            round_info,
-            max_voter: Uint256::from_u128(5u128),
-            max_option: Uint256::from_u128(5u128),
-            voice_credit_amount: Uint256::from_u128(30u128),
-            voting_time: VotingTime {
-                start_time,
-                end_time,
+            round: NewRound::Amaci {
+                operator,
+                round_info,
+                max_voter: Uint256::from_u128(5u128),
+                max_option: Uint256::from_u128(5u128),
+                voice_credit_amount: Uint256::from_u128(30u128),
+                voting_time: VotingTime {
+                    start_time,
+                    end_time,
+                },
+                sign_up_time: None,
+                whitelist: None,
+                pre_deactivate_root: Uint256::from_u128(0u128),
+                circuit_type,
+                certification_system,
+                extra_questions: None,
             },
-            whitelist: None,
-            pre_deactivate_root: Uint256::from_u128(0u128),
-            circuit_type,
-            certification_system,
         };
 
         app.execute_contract(sender, self.addr(), &msg, send_funds)
@@ -231,19 +386,173 @@ impl AmaciRegistryContract {
         });
 
         let msg = ExecuteMsg::CreateRound {
-            operator,
-            round_info,
-            max_voter: Uint256::from_u128(3u128),
-            max_option: Uint256::from_u128(5u128),
-            voice_credit_amount: Uint256::from_u128(100u128),
+            round: NewRound::Amaci {
+                operator,
+                round_info,
+                max_voter: Uint256::from_u128(3u128),
+                max_option: Uint256::from_u128(5u128),
+                voice_credit_amount: Uint256::from_u128(100u128),
+                voting_time: VotingTime {
+                    start_time,
+                    end_time,
+                },
+                sign_up_time: None,
+                whitelist,
+                pre_deactivate_root: Uint256::from_u128(0u128),
+                circuit_type,
+                certification_system,
+                extra_questions: None,
+            },
+        };
+
+        app.execute_contract(sender, self.addr(), &msg, send_funds)
+    }
+
+    #[track_caller]
+    pub fn set_round_template(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        name: &str,
+        global: bool,
+        settings: RoundSettings,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::SetRoundTemplate {
+                name: name.to_string(),
+                global,
+                settings,
+            },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn remove_round_template(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        name: &str,
+        global: bool,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::RemoveRoundTemplate {
+                name: name.to_string(),
+                global,
+            },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn create_round_from_template(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        name: &str,
+        global: bool,
+        overrides: RoundOverrides,
+        send_funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        let start_time = Timestamp::from_nanos(1571797424879000000);
+        let msg = ExecuteMsg::CreateRoundFromTemplate {
+            name: name.to_string(),
+            global,
+            round_info: RoundInfo {
+                title: String::from("Weekly Round"),
+                description: String::from(""),
+                link: String::from(""),
+            },
             voting_time: VotingTime {
                 start_time,
-                end_time,
+                end_time: start_time.plus_minutes(11),
+            },
+            sign_up_time: None,
+            overrides,
+        };
+
+        app.execute_contract(sender, self.addr(), &msg, send_funds)
+    }
+
+    #[track_caller]
+    pub fn create_maci_round(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        max_voter: u128,
+        send_funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        let start_time = Timestamp::from_nanos(1571797424879000000);
+        let msg = ExecuteMsg::CreateRound {
+            round: NewRound::Maci {
+                coordinator: operator_pubkey1(),
+                qtr_lib: qtr_lib(),
+                groth16_process_vkey: None,
+                groth16_tally_vkey: None,
+                plonk_process_vkey: None,
+                plonk_tally_vkey: None,
+                max_voter: Uint256::from_u128(max_voter),
+                max_option: Uint256::from_u128(5u128),
+                round_info: RoundInfo {
+                    title: String::from("HackWasm Berlin"),
+                    description: String::from("Hack In Brelin"),
+                    link: String::from("https://baidu.com"),
+                },
+                voting_time: VotingTime {
+                    start_time,
+                    end_time: start_time.plus_minutes(11),
+                },
+                sign_up_time: None,
+                whitelist: None,
+                circuit_type: Uint256::from_u128(0u128),
+                certification_system: Uint256::from_u128(0u128),
+            },
+        };
+
+        app.execute_contract(sender, self.addr(), &msg, send_funds)
+    }
+
+    #[track_caller]
+    pub fn create_oracle_maci_round(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        max_voters: u128,
+        send_funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        let start_time = Timestamp::from_nanos(1571797424879000000);
+        let msg = ExecuteMsg::CreateRound {
+            round: NewRound::OracleMaci {
+                coordinator: operator_pubkey2(),
+                max_voters,
+                vote_option_map: vec![String::from("yes"), String::from("no")],
+                round_info: RoundInfo {
+                    title: String::from("Oracle Round"),
+                    description: String::from(""),
+                    link: String::from(""),
+                },
+                voting_time: VotingTime {
+                    start_time,
+                    end_time: start_time.plus_minutes(11),
+                },
+                sign_up_time: None,
+                circuit_type: Uint256::from_u128(0u128),
+                certification_system: Uint256::from_u128(0u128),
+                whitelist_backend_pubkey: String::from(
+                    "AurWMPImmkDdmse2sMILI9g0fJlOfcnxjJ1QWyFRBdXr",
+                ),
+                whitelist_ecosystem: String::from("cosmoshub"),
+                whitelist_snapshot_height: Uint256::zero(),
+                whitelist_voting_power_args: VotingPowerArgs {
+                    mode: VotingPowerMode::Slope,
+                    slope: Uint256::one(),
+                    threshold: Uint256::one(),
+                },
             },
-            whitelist,
-            pre_deactivate_root: Uint256::from_u128(0u128),
-            circuit_type,
-            certification_system,
         };
 
         app.execute_contract(sender, self.addr(), &msg, send_funds)
@@ -311,6 +620,26 @@ impl AmaciRegistryContract {
         )
     }
 
+    #[track_caller]
+    pub fn set_validator_source(
+        &self,
+        app: &mut App,
+        sender: Addr,
+        source: ValidatorSource,
+    ) -> AnyResult<AppResponse> {
+        app.execute_contract(
+            sender,
+            self.addr(),
+            &ExecuteMsg::SetValidatorSource { source },
+            &[],
+        )
+    }
+
+    #[track_caller]
+    pub fn prune_validators(&self, app: &mut App, sender: Addr) -> AnyResult<AppResponse> {
+        app.execute_contract(sender, self.addr(), &ExecuteMsg::PruneValidators {}, &[])
+    }
+
     #[track_caller]
     pub fn change_operator(
         &self,
@@ -341,6 +670,69 @@ impl AmaciRegistryContract {
             .query_wasm_smart(self.addr(), &QueryMsg::GetCircuitChargeConfig {})
     }
 
+    pub fn get_config(&self, app: &App) -> StdResult<ConfigResponse> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::Config {})
+    }
+
+    pub fn get_round_code_id(&self, app: &App, round_type: RoundType) -> StdResult<Option<u64>> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetRoundCodeId { round_type })
+    }
+
+    pub fn get_fee_tiers(&self, app: &App, round_type: RoundType) -> StdResult<Vec<FeeTier>> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetFeeTiers { round_type })
+    }
+
+    pub fn quote_round_fee(
+        &self,
+        app: &App,
+        round_type: RoundType,
+        max_voter: u128,
+        max_option: u128,
+        creator: Option<Addr>,
+    ) -> StdResult<Option<RoundFeeQuote>> {
+        app.wrap().query_wasm_smart(
+            self.addr(),
+            &QueryMsg::QuoteRoundFee {
+                round_type,
+                max_voter: Uint256::from_u128(max_voter),
+                max_option: Uint256::from_u128(max_option),
+                creator,
+            },
+        )
+    }
+
+    pub fn get_operator_bond(&self, app: &App, address: Addr) -> StdResult<Uint128> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetOperatorBond { address })
+    }
+
+    pub fn get_unbondings(&self, app: &App, address: Addr) -> StdResult<Vec<Unbonding>> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetUnbondings { address })
+    }
+
+    pub fn get_operator_stats(&self, app: &App, address: Addr) -> StdResult<OperatorStatsResponse> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetOperatorStats { address })
+    }
+
+    pub fn get_all_operator_stats(
+        &self,
+        app: &App,
+        start_after: Option<Addr>,
+    ) -> StdResult<Vec<OperatorStatsResponse>> {
+        app.wrap().query_wasm_smart(
+            self.addr(),
+            &QueryMsg::GetAllOperatorStats {
+                start_after,
+                limit: None,
+            },
+        )
+    }
+
     pub fn balance_of(&self, app: &App, address: String, denom: String) -> StdResult<Coin> {
         app.wrap().query_balance(address, denom)
     }
@@ -355,6 +747,11 @@ impl AmaciRegistryContract {
             .query_wasm_smart(self.addr(), &QueryMsg::GetValidators {})
     }
 
+    pub fn get_validator_source(&self, app: &App) -> StdResult<ValidatorSource> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetValidatorSource {})
+    }
+
     pub fn get_validator_operator(&self, app: &App, address: Addr) -> StdResult<Addr> {
         app.wrap()
             .query_wasm_smart(self.addr(), &QueryMsg::GetValidatorOperator { address })
@@ -364,6 +761,113 @@ impl AmaciRegistryContract {
         app.wrap()
             .query_wasm_smart(self.addr(), &QueryMsg::GetMaciOperatorPubkey { address })
     }
+
+    pub fn get_operator_pubkey_history(
+        &self,
+        app: &App,
+        address: Addr,
+    ) -> StdResult<Vec<OperatorPubkey>> {
+        app.wrap().query_wasm_smart(
+            self.addr(),
+            &QueryMsg::GetMaciOperatorPubkeyHistory { address },
+        )
+    }
+
+    pub fn get_round_template(
+        &self,
+        app: &App,
+        owner: Option<Addr>,
+        name: &str,
+    ) -> StdResult<Option<RoundTemplate>> {
+        app.wrap().query_wasm_smart(
+            self.addr(),
+            &QueryMsg::GetRoundTemplate {
+                owner,
+                name: name.to_string(),
+            },
+        )
+    }
+
+    pub fn get_round_templates(
+        &self,
+        app: &App,
+        owner: Option<Addr>,
+        start_after: Option<String>,
+    ) -> StdResult<Vec<RoundTemplate>> {
+        app.wrap().query_wasm_smart(
+            self.addr(),
+            &QueryMsg::GetRoundTemplates {
+                owner,
+                start_after,
+                limit: None,
+            },
+        )
+    }
+
+    pub fn get_round(&self, app: &App, round_addr: Addr) -> StdResult<Option<RoundRecord>> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetRound { round_addr })
+    }
+
+    pub fn get_rounds(
+        &self,
+        app: &App,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<RoundRecord>> {
+        app.wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::GetRounds { start_after, limit })
+    }
+
+    pub fn get_rounds_by_creator(
+        &self,
+        app: &App,
+        creator: Addr,
+        start_after: Option<u64>,
+    ) -> StdResult<Vec<RoundRecord>> {
+        app.wrap().query_wasm_smart(
+            self.addr(),
+            &QueryMsg::GetRoundsByCreator {
+                creator,
+                start_after,
+                limit: None,
+            },
+        )
+    }
+
+    pub fn get_rounds_by_operator(
+        &self,
+        app: &App,
+        operator: Addr,
+        start_after: Option<u64>,
+    ) -> StdResult<Vec<RoundRecord>> {
+        app.wrap().query_wasm_smart(
+            self.addr(),
+            &QueryMsg::GetRoundsByOperator {
+                operator,
+                start_after,
+                limit: None,
+            },
+        )
+    }
+
+    pub fn get_rounds_by_voting_start(
+        &self,
+        app: &App,
+        start: Timestamp,
+        end: Timestamp,
+        start_after: Option<u64>,
+    ) -> StdResult<Vec<RoundRecord>> {
+        app.wrap().query_wasm_smart(
+            self.addr(),
+            &QueryMsg::GetRoundsByVotingStart {
+                start,
+                end,
+                start_after,
+                limit: None,
+            },
+        )
+    }
 }
 
 impl From<Addr> for AmaciRegistryContract {
@@ -373,27 +877,27 @@ impl From<Addr> for AmaciRegistryContract {
 }
 
 pub fn user1() -> Addr {
-    Addr::unchecked("0")
+    Addr::unchecked("dora1dxd5lekagfckq267pze6fl3u6jvx4z057efhqg")
 }
 
 pub fn user2() -> Addr {
-    Addr::unchecked("1")
+    Addr::unchecked("dora1ldxzmrvhnkly0dua6xx70yzfakm9cz2t9a9xtc")
 }
 
 pub fn user3() -> Addr {
-    Addr::unchecked("2")
+    Addr::unchecked("dora1zxlgv8p6ngrptd3gt3q6n6u9sc3x88232jhwsv")
 }
 
 pub fn user4() -> Addr {
-    Addr::unchecked("3")
+    Addr::unchecked("dora1rjqushngusdww00ewyhltdnvrnnd92lpakwvt4")
 }
 
 pub fn user5() -> Addr {
-    Addr::unchecked("4")
+    Addr::unchecked("dora1uxa0uyzv7whj3wqq20u3f7syfkd5m9rugv0qxp")
 }
 
 pub fn admin() -> Addr {
-    Addr::unchecked("admin")
+    Addr::unchecked("dora1dzf3hxry3uhzllppvczwrgqcawqh76q7wal8v0")
 }
 
 pub fn validator() -> Addr {
@@ -404,6 +908,41 @@ pub fn validator2() -> Addr {
     Addr::unchecked("validator2")
 }
 
+// the operator account of a validator of the staking module
+pub fn staking_validator1() -> Addr {
+    Addr::unchecked("dora1qcxce9c4thzxnfmpr2dqnnlqea9ey35yp5rerh")
+}
+
+pub fn staking_valoper1() -> String {
+    String::from("doravaloper1qcxce9c4thzxnfmpr2dqnnlqea9ey35yjzx04g")
+}
+
+pub fn staking_validator2() -> Addr {
+    Addr::unchecked("dora1xznhxqv7zqy3h5uqg6efxwdmjkhg7uh2a3tl0c")
+}
+
+pub fn staking_valoper2() -> String {
+    String::from("doravaloper1xznhxqv7zqy3h5uqg6efxwdmjkhg7uh2w8wfe8")
+}
+
+pub fn add_staking_validator(app: &mut App, address: String) {
+    let block = app.block_info();
+    app.init_modules(|router, api, storage| {
+        router.staking.add_validator(
+            api,
+            storage,
+            &block,
+            Validator {
+                address,
+                commission: Decimal::percent(10),
+                max_commission: Decimal::percent(20),
+                max_change_rate: Decimal::percent(1),
+            },
+        )
+    })
+    .unwrap();
+}
+
 pub fn operator() -> Addr {
     Addr::unchecked("dora1eu7mhp4ggxd6utnz8uzurw395natgs6jskl4ug")
 }
@@ -456,3 +995,35 @@ pub fn operator_pubkey3() -> PubKey {
         ),
     };
 }
+
+pub fn qtr_lib() -> QuinaryTreeRoot {
+    QuinaryTreeRoot {
+        zeros: [
+            uint256_from_decimal_string("0"),
+            uint256_from_decimal_string(
+                "14655542659562014735865511769057053982292279840403315552050801315682099828156",
+            ),
+            uint256_from_decimal_string(
+                "19261153649140605024552417994922546473530072875902678653210025980873274131905",
+            ),
+            uint256_from_decimal_string(
+                "21526503558325068664033192388586640128492121680588893182274749683522508994597",
+            ),
+            uint256_from_decimal_string(
+                "20017764101928005973906869479218555869286328459998999367935018992260318153770",
+            ),
+            uint256_from_decimal_string(
+                "16998355316577652097112514691750893516081130026395813155204269482715045879598",
+            ),
+            uint256_from_decimal_string(
+                "2612442706402737973181840577010736087708621987282725873936541279764292204086",
+            ),
+            uint256_from_decimal_string(
+                "17716535433480122581515618850811568065658392066947958324371350481921422579201",
+            ),
+            uint256_from_decimal_string(
+                "17437916409890180001398333108882255895598851862997171508841759030332444017770",
+            ),
+        ],
+    }
+}
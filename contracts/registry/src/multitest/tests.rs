@@ -1,24 +1,31 @@
-use cosmwasm_std::{coins, from_json, Addr, BlockInfo, Timestamp, Uint128, Uint256};
+use cosmwasm_std::{coins, from_json, Addr, BlockInfo, Decimal, Timestamp, Uint128, Uint256};
 use cw_multi_test::App;
 
-// use crate::error::ContractError;
+use crate::error::ContractError;
 // use crate::msg::ClaimsResponse;
 use crate::{
+    msg::RoundOverrides,
     multitest::{
-        operator, operator2, operator3, operator_pubkey1, operator_pubkey2, operator_pubkey3,
-        admin, creator, user1, user2, user3, user4, AmaciRegistryCodeId, InstantiationData, DORA_DEMON,
+        add_staking_validator, admin, creator, operator, operator2, operator3, operator_pubkey1,
+        operator_pubkey2, operator_pubkey3, staking_validator1, staking_validator2,
+        staking_valoper1, staking_valoper2, store_maci_code, store_oracle_maci_code, user1, user2,
+        user3, user4, AmaciRegistryCodeId, InstantiationData, DORA_DEMON, MIN_DEPOSIT_AMOUNT,
+        SLASH_AMOUNT,
+    },
+    state::{
+        Config, FeeTier, PubkeyStatus, RoundSettings, RoundType, ValidatorSet, ValidatorSource,
     },
-    state::ValidatorSet,
 };
 use cw_amaci::multitest::{fee_recipient, owner, MaciCodeId, MaciContract};
 use cw_amaci::ContractError as AmaciContractError;
 
-use cw_amaci::msg::Groth16ProofType;
-use cw_amaci::multitest::uint256_from_decimal_string;
-use cw_amaci::state::{
-    DelayRecord, DelayRecords, DelayType, MessageData, Period, PeriodStatus, PubKey,
+use cw_amaci::msg::{
+    Groth16ProofType, QueryMsg as AmaciQueryMsg, WhitelistBase, WhitelistBaseConfig,
 };
+use cw_amaci::multitest::uint256_from_decimal_string;
+use cw_amaci::state::{DelayRecord, DelayRecords, DelayType, MaciParameters, Period, PeriodStatus};
 use cw_multi_test::next_block;
+use maci_core::{MessageData, PubKey};
 use serde::{Deserialize, Serialize};
 use serde_json;
 use std::fs;
@@ -187,6 +194,11 @@ pub fn next_block_4_days(block: &mut BlockInfo) {
     block.height += 1;
 }
 
+pub fn next_block_8_days(block: &mut BlockInfo) {
+    block.time = block.time.plus_days(8);
+    block.height += 1;
+}
+
 // // #[test]
 // fn instantiate_should_works() {
 //     let user1_coin_amount = 30u128;
@@ -423,6 +435,10 @@ fn create_round_with_reward_should_works() {
             .bank
             .init_balance(storage, &creator(), coins(creator_coin_amount, DORA_DEMON))
             .unwrap();
+        router
+            .bank
+            .init_balance(storage, &operator(), coins(MIN_DEPOSIT_AMOUNT, DORA_DEMON))
+            .unwrap();
     });
 
     let register_code_id = AmaciRegistryCodeId::store_code(&mut app);
@@ -452,6 +468,9 @@ fn create_round_with_reward_should_works() {
     assert_eq!(true, user1_check_operator);
 
     _ = contract.set_maci_operator_pubkey(&mut app, operator(), operator_pubkey1());
+    contract
+        .bond(&mut app, operator(), &coins(MIN_DEPOSIT_AMOUNT, DORA_DEMON))
+        .unwrap();
 
     let user1_operator_pubkey = contract.get_operator_pubkey(&app, operator()).unwrap();
     assert_eq!(operator_pubkey1(), user1_operator_pubkey);
@@ -607,6 +626,10 @@ fn create_round_with_voting_time_qv_amaci_should_works() {
             .bank
             .init_balance(storage, &creator(), coins(creator_coin_amount, DORA_DEMON))
             .unwrap();
+        router
+            .bank
+            .init_balance(storage, &operator(), coins(MIN_DEPOSIT_AMOUNT, DORA_DEMON))
+            .unwrap();
     });
 
     let register_code_id = AmaciRegistryCodeId::store_code(&mut app);
@@ -636,6 +659,9 @@ fn create_round_with_voting_time_qv_amaci_should_works() {
     assert_eq!(true, user1_check_operator);
 
     _ = contract.set_maci_operator_pubkey(&mut app, operator(), operator_pubkey1());
+    contract
+        .bond(&mut app, operator(), &coins(MIN_DEPOSIT_AMOUNT, DORA_DEMON))
+        .unwrap();
 
     let user1_operator_pubkey = contract.get_operator_pubkey(&app, operator()).unwrap();
     assert_eq!(operator_pubkey1(), user1_operator_pubkey);
@@ -728,11 +754,7 @@ fn create_round_with_voting_time_qv_amaci_should_works() {
         y: uint256_from_decimal_string(&data.current_state_leaves[0][1]),
     };
     let sign_up_error = maci_contract
-        .amaci_sign_up(
-            &mut app,
-            Addr::unchecked(0.to_string()),
-            test_pubkey.clone(),
-        )
+        .amaci_sign_up(&mut app, user1(), test_pubkey.clone())
         .unwrap_err();
     assert_eq!(
         AmaciContractError::PeriodError {},
@@ -781,17 +803,17 @@ fn create_round_with_voting_time_qv_amaci_should_works() {
         y: uint256_from_decimal_string(&pubkey_data.pubkeys[1][1]),
     };
 
-    let _ = maci_contract.amaci_sign_up(&mut app, Addr::unchecked("0"), pubkey0.clone());
+    let _ = maci_contract.amaci_sign_up(&mut app, user1(), pubkey0.clone());
 
     let can_sign_up_error = maci_contract
-        .amaci_sign_up(&mut app, Addr::unchecked("0"), pubkey0.clone())
+        .amaci_sign_up(&mut app, user1(), pubkey0.clone())
         .unwrap_err();
     assert_eq!(
         AmaciContractError::UserAlreadyRegistered {},
         can_sign_up_error.downcast().unwrap()
     );
 
-    let _ = maci_contract.amaci_sign_up(&mut app, Addr::unchecked("1"), pubkey1.clone());
+    let _ = maci_contract.amaci_sign_up(&mut app, user2(), pubkey1.clone());
 
     assert_eq!(
         maci_contract.amaci_num_sign_up(&app).unwrap(),
@@ -924,11 +946,7 @@ fn create_round_with_voting_time_qv_amaci_should_works() {
                 app.update_block(next_block_11_minutes);
 
                 let sign_up_after_voting_end_error = maci_contract
-                    .amaci_sign_up(
-                        &mut app,
-                        Addr::unchecked(3.to_string()),
-                        test_pubkey.clone(),
-                    )
+                    .amaci_sign_up(&mut app, user4(), test_pubkey.clone())
                     .unwrap_err();
                 assert_eq!(
                     AmaciContractError::PeriodError {},
@@ -1155,6 +1173,17 @@ fn create_round_with_voting_time_qv_amaci_should_works() {
         admin_balance.amount,
         admin_fee
     );
+    // Two delay records are not enough to slash the operator
+    assert_eq!(
+        contract.get_operator_bond(&app, operator()).unwrap(),
+        Uint128::from(MIN_DEPOSIT_AMOUNT)
+    );
+    let stats = contract.get_operator_stats(&app, operator()).unwrap();
+    assert_eq!(stats.rounds_served, 1);
+    assert_eq!(stats.late_deactivate_batches, 1);
+    assert_eq!(stats.late_tallies, 1);
+    assert_eq!(stats.tally_timeouts, 0);
+    assert_eq!(stats.average_miss_rate, Decimal::percent(60));
     
     assert_eq!(
         creator_balance.amount,
@@ -1222,6 +1251,10 @@ fn create_round_with_voting_time_qv_amaci_after_4_days_with_no_operator_reward_s
             .bank
             .init_balance(storage, &creator(), coins(creator_coin_amount, DORA_DEMON))
             .unwrap();
+        router
+            .bank
+            .init_balance(storage, &operator(), coins(MIN_DEPOSIT_AMOUNT, DORA_DEMON))
+            .unwrap();
     });
 
     let register_code_id = AmaciRegistryCodeId::store_code(&mut app);
@@ -1251,6 +1284,9 @@ fn create_round_with_voting_time_qv_amaci_after_4_days_with_no_operator_reward_s
     assert_eq!(true, user1_check_operator);
 
     _ = contract.set_maci_operator_pubkey(&mut app, operator(), operator_pubkey1());
+    contract
+        .bond(&mut app, operator(), &coins(MIN_DEPOSIT_AMOUNT, DORA_DEMON))
+        .unwrap();
 
     let user1_operator_pubkey = contract.get_operator_pubkey(&app, operator()).unwrap();
     assert_eq!(operator_pubkey1(), user1_operator_pubkey);
@@ -1338,11 +1374,7 @@ fn create_round_with_voting_time_qv_amaci_after_4_days_with_no_operator_reward_s
         y: uint256_from_decimal_string(&data.current_state_leaves[0][1]),
     };
     let sign_up_error = maci_contract
-        .amaci_sign_up(
-            &mut app,
-            Addr::unchecked(0.to_string()),
-            test_pubkey.clone(),
-        )
+        .amaci_sign_up(&mut app, user1(), test_pubkey.clone())
         .unwrap_err();
     assert_eq!(
         AmaciContractError::PeriodError {},
@@ -1391,17 +1423,17 @@ fn create_round_with_voting_time_qv_amaci_after_4_days_with_no_operator_reward_s
         y: uint256_from_decimal_string(&pubkey_data.pubkeys[1][1]),
     };
 
-    let _ = maci_contract.amaci_sign_up(&mut app, Addr::unchecked("0"), pubkey0.clone());
+    let _ = maci_contract.amaci_sign_up(&mut app, user1(), pubkey0.clone());
 
     let can_sign_up_error = maci_contract
-        .amaci_sign_up(&mut app, Addr::unchecked("0"), pubkey0.clone())
+        .amaci_sign_up(&mut app, user1(), pubkey0.clone())
         .unwrap_err();
     assert_eq!(
         AmaciContractError::UserAlreadyRegistered {},
         can_sign_up_error.downcast().unwrap()
     );
 
-    let _ = maci_contract.amaci_sign_up(&mut app, Addr::unchecked("1"), pubkey1.clone());
+    let _ = maci_contract.amaci_sign_up(&mut app, user2(), pubkey1.clone());
 
     assert_eq!(
         maci_contract.amaci_num_sign_up(&app).unwrap(),
@@ -1534,11 +1566,7 @@ fn create_round_with_voting_time_qv_amaci_after_4_days_with_no_operator_reward_s
                 app.update_block(next_block_11_minutes);
 
                 let sign_up_after_voting_end_error = maci_contract
-                    .amaci_sign_up(
-                        &mut app,
-                        Addr::unchecked(3.to_string()),
-                        test_pubkey.clone(),
-                    )
+                    .amaci_sign_up(&mut app, user4(), test_pubkey.clone())
                     .unwrap_err();
                 assert_eq!(
                     AmaciContractError::PeriodError {},
@@ -1761,10 +1789,26 @@ fn create_round_with_voting_time_qv_amaci_after_4_days_with_no_operator_reward_s
         Uint128::from(0u128) // after 4 days, operator reward is 0, all funds are returned to creator
     );
     
+    // The tally timeout slashed the operator's bond to the admin
     assert_eq!(
         admin_balance.amount,
-        admin_fee
+        admin_fee + Uint128::from(SLASH_AMOUNT)
+    );
+    assert_eq!(
+        contract.get_operator_bond(&app, operator()).unwrap(),
+        Uint128::from(MIN_DEPOSIT_AMOUNT - SLASH_AMOUNT)
+    );
+    let stats = contract.get_operator_stats(&app, operator()).unwrap();
+    assert_eq!(stats.tally_timeouts, 1);
+    assert_eq!(stats.average_miss_rate, Decimal::one());
+    assert_eq!(
+        contract.get_all_operator_stats(&app, None).unwrap(),
+        vec![stats]
     );
+    assert!(contract
+        .get_all_operator_stats(&app, Some(operator()))
+        .unwrap()
+        .is_empty());
     
     assert_eq!(
         creator_balance.amount,
@@ -1787,3 +1831,1105 @@ fn create_round_with_voting_time_qv_amaci_after_4_days_with_no_operator_reward_s
     let tally_delay = maci_contract.amaci_query_tally_delay(&app).unwrap();
     println!("tally_delay: {:?}", tally_delay);
 }
+
+#[test]
+fn created_rounds_are_indexed() {
+    let creator_coin_amount = 1000000000000000000000u128; // 1000 DORA
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &creator(), coins(creator_coin_amount, DORA_DEMON))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &operator(), coins(MIN_DEPOSIT_AMOUNT, DORA_DEMON))
+            .unwrap();
+    });
+
+    let register_code_id = AmaciRegistryCodeId::store_code(&mut app);
+    let amaci_code_id = MaciCodeId::store_default_code(&mut app);
+    let contract = register_code_id
+        .instantiate(
+            &mut app,
+            creator(),
+            amaci_code_id.id(),
+            "Dora AMaci Registry",
+        )
+        .unwrap();
+
+    _ = contract.set_validators(&mut app, admin());
+    _ = contract.set_maci_operator(&mut app, user1(), operator());
+    _ = contract.set_maci_operator_pubkey(&mut app, operator(), operator_pubkey1());
+    contract
+        .bond(&mut app, operator(), &coins(MIN_DEPOSIT_AMOUNT, DORA_DEMON))
+        .unwrap();
+
+    let small_base_payamount = 20000000000000000000u128; // 20 DORA
+    let mut round_addrs = vec![];
+    for _ in 0..2 {
+        let resp = contract
+            .create_round(
+                &mut app,
+                creator(),
+                operator(),
+                Uint256::from_u128(0u128),
+                Uint256::from_u128(0u128),
+                &coins(small_base_payamount, DORA_DEMON),
+            )
+            .unwrap();
+        let data: InstantiationData = from_json(&resp.data.unwrap()).unwrap();
+        round_addrs.push(data.addr);
+    }
+
+    let rounds = contract.get_rounds(&app, None, None).unwrap();
+    assert_eq!(rounds.len(), 2);
+    assert_eq!(rounds[1].id, 1);
+    assert_eq!(rounds[1].round_addr, round_addrs[1]);
+    assert_eq!(rounds[0].creator, creator());
+    assert_eq!(rounds[0].operator, operator());
+    assert_eq!(rounds[0].fee, Uint128::from(small_base_payamount));
+    assert_eq!(rounds[0].circuit_type, "0");
+    assert_eq!(rounds[0].coordinator_pubkey, operator_pubkey1());
+    assert_eq!(
+        contract.get_round(&app, round_addrs[0].clone()).unwrap(),
+        Some(rounds[0].clone())
+    );
+    assert_eq!(contract.get_round(&app, user1()).unwrap(), None);
+    assert_eq!(
+        contract.get_rounds(&app, Some(0), Some(1)).unwrap(),
+        vec![rounds[1].clone()]
+    );
+
+    assert_eq!(
+        contract
+            .get_rounds_by_creator(&app, creator(), None)
+            .unwrap(),
+        rounds
+    );
+    assert_eq!(
+        contract
+            .get_rounds_by_operator(&app, operator(), Some(0))
+            .unwrap(),
+        vec![rounds[1].clone()]
+    );
+    assert!(contract
+        .get_rounds_by_operator(&app, operator2(), None)
+        .unwrap()
+        .is_empty());
+
+    let start_time = rounds[0].voting_time.start_time;
+    assert_eq!(
+        contract
+            .get_rounds_by_voting_start(&app, start_time, start_time, None)
+            .unwrap(),
+        rounds
+    );
+    assert_eq!(
+        contract
+            .get_rounds_by_voting_start(&app, start_time, start_time, Some(0))
+            .unwrap(),
+        vec![rounds[1].clone()]
+    );
+    assert!(contract
+        .get_rounds_by_voting_start(
+            &app,
+            start_time.plus_seconds(1),
+            start_time.plus_days(1),
+            None
+        )
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn operators_bond_before_they_are_assigned_rounds() {
+    let creator_coin_amount = 1000000000000000000000u128; // 1000 DORA
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &creator(), coins(creator_coin_amount, DORA_DEMON))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &operator(), coins(MIN_DEPOSIT_AMOUNT, DORA_DEMON))
+            .unwrap();
+    });
+
+    let register_code_id = AmaciRegistryCodeId::store_code(&mut app);
+    let amaci_code_id = MaciCodeId::store_default_code(&mut app);
+    let contract = register_code_id
+        .instantiate(
+            &mut app,
+            creator(),
+            amaci_code_id.id(),
+            "Dora AMaci Registry",
+        )
+        .unwrap();
+
+    let config = contract.get_config(&app).unwrap();
+    assert_eq!(config.min_deposit_amount, Uint128::from(MIN_DEPOSIT_AMOUNT));
+
+    // Only maci operators can bond
+    let err = contract
+        .bond(&mut app, operator(), &coins(MIN_DEPOSIT_AMOUNT, DORA_DEMON))
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    _ = contract.set_validators(&mut app, admin());
+    _ = contract.set_maci_operator(&mut app, user1(), operator());
+    _ = contract.set_maci_operator_pubkey(&mut app, operator(), operator_pubkey1());
+
+    let small_base_payamount = 20000000000000000000u128; // 20 DORA
+    let err = contract
+        .create_round(
+            &mut app,
+            creator(),
+            operator(),
+            Uint256::from_u128(0u128),
+            Uint256::from_u128(0u128),
+            &coins(small_base_payamount, DORA_DEMON),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InsufficientDeposit {
+            min_deposit_amount: Uint128::from(MIN_DEPOSIT_AMOUNT)
+        },
+        err.downcast().unwrap()
+    );
+
+    contract
+        .bond(&mut app, operator(), &coins(MIN_DEPOSIT_AMOUNT, DORA_DEMON))
+        .unwrap();
+    assert_eq!(
+        contract.get_operator_bond(&app, operator()).unwrap(),
+        Uint128::from(MIN_DEPOSIT_AMOUNT)
+    );
+    contract
+        .create_round(
+            &mut app,
+            creator(),
+            operator(),
+            Uint256::from_u128(0u128),
+            Uint256::from_u128(0u128),
+            &coins(small_base_payamount, DORA_DEMON),
+        )
+        .unwrap();
+
+    let err = contract
+        .unbond(&mut app, operator(), MIN_DEPOSIT_AMOUNT + 1)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidUnbondAmount {
+            amount: Uint128::from(MIN_DEPOSIT_AMOUNT + 1),
+            bonded: Uint128::from(MIN_DEPOSIT_AMOUNT)
+        },
+        err.downcast().unwrap()
+    );
+    contract.unbond(&mut app, operator(), 5).unwrap();
+    assert_eq!(
+        contract.get_operator_bond(&app, operator()).unwrap(),
+        Uint128::from(MIN_DEPOSIT_AMOUNT - 5)
+    );
+
+    // The operator is no longer bonded enough for new rounds
+    let err = contract
+        .create_round(
+            &mut app,
+            creator(),
+            operator(),
+            Uint256::from_u128(0u128),
+            Uint256::from_u128(0u128),
+            &coins(small_base_payamount, DORA_DEMON),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InsufficientDeposit {
+            min_deposit_amount: Uint128::from(MIN_DEPOSIT_AMOUNT)
+        },
+        err.downcast().unwrap()
+    );
+
+    let err = contract
+        .withdraw_unbonded(&mut app, operator())
+        .unwrap_err();
+    assert_eq!(ContractError::NothingToClaim {}, err.downcast().unwrap());
+
+    // Removing the validator moves the rest of the bond into unbonding
+    _ = contract.remove_validator(&mut app, admin(), user1());
+    assert_eq!(
+        contract.get_operator_bond(&app, operator()).unwrap(),
+        Uint128::zero()
+    );
+    let unbondings = contract.get_unbondings(&app, operator()).unwrap();
+    assert_eq!(unbondings.len(), 2);
+    assert_eq!(unbondings[1].amount, Uint128::from(MIN_DEPOSIT_AMOUNT - 5));
+
+    app.update_block(next_block_8_days);
+    contract.withdraw_unbonded(&mut app, operator()).unwrap();
+    assert_eq!(
+        contract
+            .balance_of(&app, operator().to_string(), DORA_DEMON.to_string())
+            .unwrap()
+            .amount,
+        Uint128::from(MIN_DEPOSIT_AMOUNT)
+    );
+    assert!(contract
+        .get_unbondings(&app, operator())
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn coordinator_pubkeys_rotate_and_release() {
+    let mut app = App::default();
+
+    let register_code_id = AmaciRegistryCodeId::store_code(&mut app);
+    let amaci_code_id = MaciCodeId::store_default_code(&mut app);
+    let contract = register_code_id
+        .instantiate(
+            &mut app,
+            creator(),
+            amaci_code_id.id(),
+            "Dora AMaci Registry",
+        )
+        .unwrap();
+
+    _ = contract.set_validators(&mut app, admin());
+    _ = contract.set_maci_operator(&mut app, user1(), operator());
+    _ = contract.set_maci_operator(&mut app, user2(), operator2());
+    contract
+        .set_maci_operator_pubkey(&mut app, operator(), operator_pubkey1())
+        .unwrap();
+    contract
+        .set_maci_operator_pubkey(&mut app, operator(), operator_pubkey2())
+        .unwrap();
+    assert_eq!(
+        contract.get_operator_pubkey(&app, operator()).unwrap(),
+        operator_pubkey2()
+    );
+
+    let history = contract
+        .get_operator_pubkey_history(&app, operator())
+        .unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].pubkey, operator_pubkey1());
+    assert_eq!(history[0].status, PubkeyStatus::Retired);
+    assert_eq!(history[0].deactivated_at, Some(app.block_info().time));
+    assert_eq!(history[1].status, PubkeyStatus::Active);
+
+    // The retired key stays reserved until it is released
+    let err = contract
+        .set_maci_operator_pubkey(&mut app, operator2(), operator_pubkey1())
+        .unwrap_err();
+    assert_eq!(ContractError::PubkeyExisted {}, err.downcast().unwrap());
+
+    let err = contract
+        .release_maci_operator_pubkey(&mut app, operator2(), operator_pubkey1())
+        .unwrap_err();
+    assert_eq!(ContractError::PubkeyNotRetired {}, err.downcast().unwrap());
+    let err = contract
+        .release_maci_operator_pubkey(&mut app, operator(), operator_pubkey2())
+        .unwrap_err();
+    assert_eq!(ContractError::PubkeyNotRetired {}, err.downcast().unwrap());
+
+    contract
+        .release_maci_operator_pubkey(&mut app, operator(), operator_pubkey1())
+        .unwrap();
+    contract
+        .set_maci_operator_pubkey(&mut app, operator2(), operator_pubkey1())
+        .unwrap();
+
+    // A burned key is never released
+    contract
+        .set_maci_operator_pubkey(&mut app, operator(), operator_pubkey3())
+        .unwrap();
+    contract
+        .burn_maci_operator_pubkey(&mut app, operator(), operator_pubkey2())
+        .unwrap();
+    let err = contract
+        .release_maci_operator_pubkey(&mut app, operator(), operator_pubkey2())
+        .unwrap_err();
+    assert_eq!(ContractError::PubkeyNotRetired {}, err.downcast().unwrap());
+
+    let statuses: Vec<PubkeyStatus> = contract
+        .get_operator_pubkey_history(&app, operator())
+        .unwrap()
+        .into_iter()
+        .map(|key| key.status)
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            PubkeyStatus::Released,
+            PubkeyStatus::Burned,
+            PubkeyStatus::Active
+        ]
+    );
+}
+
+#[test]
+fn round_fees_follow_the_fee_tiers() {
+    let creator_coin_amount = 1000000000000000000000u128; // 1000 DORA
+    let dora = 1000000000000000000u128; // 1 DORA
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &creator(), coins(creator_coin_amount, DORA_DEMON))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &operator(), coins(MIN_DEPOSIT_AMOUNT, DORA_DEMON))
+            .unwrap();
+    });
+
+    let register_code_id = AmaciRegistryCodeId::store_code(&mut app);
+    let amaci_code_id = MaciCodeId::store_default_code(&mut app);
+    let contract = register_code_id
+        .instantiate(
+            &mut app,
+            creator(),
+            amaci_code_id.id(),
+            "Dora AMaci Registry",
+        )
+        .unwrap();
+
+    _ = contract.set_validators(&mut app, admin());
+    _ = contract.set_maci_operator(&mut app, user1(), operator());
+    _ = contract.set_maci_operator_pubkey(&mut app, operator(), operator_pubkey1());
+    contract
+        .bond(&mut app, operator(), &coins(MIN_DEPOSIT_AMOUNT, DORA_DEMON))
+        .unwrap();
+
+    // The default tiers charge what rounds always cost
+    let quote = contract
+        .quote_round_fee(&app, RoundType::Amaci, 5, 5, None)
+        .unwrap()
+        .unwrap();
+    assert_eq!(quote.fee, Uint128::from(20 * dora));
+    assert_eq!(quote.parameters.state_tree_depth, Uint256::from_u128(2u128));
+    let quote = contract
+        .quote_round_fee(&app, RoundType::Amaci, 26, 5, None)
+        .unwrap()
+        .unwrap();
+    assert_eq!(quote.fee, Uint128::from(750 * dora));
+    assert_eq!(
+        contract
+            .quote_round_fee(&app, RoundType::Amaci, 626, 5, None)
+            .unwrap(),
+        None
+    );
+
+    let parameters = MaciParameters {
+        state_tree_depth: Uint256::from_u128(2u128),
+        int_state_tree_depth: Uint256::from_u128(1u128),
+        vote_option_tree_depth: Uint256::from_u128(1u128),
+        message_batch_size: Uint256::from_u128(5u128),
+    };
+    let tiers = vec![FeeTier {
+        max_voter: Uint256::from_u128(25u128),
+        max_option: Uint256::from_u128(5u128),
+        parameters,
+        base_fee: Uint128::from(10 * dora),
+        fee_per_voter: Uint128::from(dora),
+    }];
+    let err = contract
+        .set_fee_tiers(&mut app, creator(), RoundType::Amaci, tiers.clone())
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    // A round gets the first tier it fits, smaller tiers must come first
+    let default_tiers = contract.get_fee_tiers(&app, RoundType::Amaci).unwrap();
+    let unsorted = vec![default_tiers[1].clone(), default_tiers[0].clone()];
+    let err = contract
+        .set_fee_tiers(&mut app, admin(), RoundType::Amaci, unsorted)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::UnsortedFeeTiers { index: 1 },
+        err.downcast().unwrap()
+    );
+    // A state tree of depth 2 holds 25 voters
+    let mut too_small = tiers.clone();
+    too_small[0].max_voter = Uint256::from_u128(26u128);
+    let err = contract
+        .set_fee_tiers(&mut app, admin(), RoundType::Amaci, too_small)
+        .unwrap_err();
+    assert_eq!(
+        ContractError::FeeTierCircuitTooSmall { index: 0 },
+        err.downcast().unwrap()
+    );
+
+    contract
+        .set_fee_tiers(&mut app, admin(), RoundType::Amaci, tiers.clone())
+        .unwrap();
+    assert_eq!(
+        contract.get_fee_tiers(&app, RoundType::Amaci).unwrap(),
+        tiers
+    );
+
+    // 10 DORA and 1 DORA for each of the 5 voters
+    let quote = contract
+        .quote_round_fee(&app, RoundType::Amaci, 5, 5, None)
+        .unwrap()
+        .unwrap();
+    assert_eq!(quote.fee, Uint128::from(15 * dora));
+    assert_eq!(
+        contract
+            .quote_round_fee(&app, RoundType::Amaci, 26, 5, None)
+            .unwrap(),
+        None
+    );
+
+    let err = contract
+        .set_fee_discount(&mut app, admin(), creator(), Some(Decimal::percent(150)))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidFeeDiscount {
+            discount: Decimal::percent(150)
+        },
+        err.downcast().unwrap()
+    );
+    contract
+        .set_fee_discount(&mut app, admin(), creator(), Some(Decimal::percent(20)))
+        .unwrap();
+    let quote = contract
+        .quote_round_fee(&app, RoundType::Amaci, 5, 5, Some(creator()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(quote.fee, Uint128::from(12 * dora));
+
+    let err = contract
+        .create_round(
+            &mut app,
+            creator(),
+            operator(),
+            Uint256::from_u128(0u128),
+            Uint256::from_u128(0u128),
+            &coins(11 * dora, DORA_DEMON),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InsufficientFee {
+            required: Uint128::from(12 * dora),
+            provided: Uint128::from(11 * dora),
+        },
+        err.downcast().unwrap()
+    );
+
+    // Paying too much refunds the excess
+    let resp = contract
+        .create_round(
+            &mut app,
+            creator(),
+            operator(),
+            Uint256::from_u128(0u128),
+            Uint256::from_u128(0u128),
+            &coins(20 * dora, DORA_DEMON),
+        )
+        .unwrap();
+    let round: InstantiationData = from_json(&resp.data.unwrap()).unwrap();
+
+    let creator_balance = contract
+        .balance_of(&app, creator().to_string(), DORA_DEMON.to_string())
+        .unwrap();
+    assert_eq!(
+        creator_balance.amount,
+        Uint128::from(creator_coin_amount - 12 * dora)
+    );
+    let round_balance = contract
+        .balance_of(&app, round.addr.to_string(), DORA_DEMON.to_string())
+        .unwrap();
+    assert_eq!(round_balance.amount, Uint128::from(12 * dora));
+    let record = contract.get_round(&app, round.addr).unwrap().unwrap();
+    assert_eq!(record.fee, Uint128::from(12 * dora));
+
+    // A full discount creates the round without any funds
+    contract
+        .set_fee_discount(&mut app, admin(), creator(), Some(Decimal::one()))
+        .unwrap();
+    let resp = contract
+        .create_round(
+            &mut app,
+            creator(),
+            operator(),
+            Uint256::from_u128(0u128),
+            Uint256::from_u128(0u128),
+            &[],
+        )
+        .unwrap();
+    let round: InstantiationData = from_json(&resp.data.unwrap()).unwrap();
+    let round_balance = contract
+        .balance_of(&app, round.addr.to_string(), DORA_DEMON.to_string())
+        .unwrap();
+    assert_eq!(round_balance.amount, Uint128::zero());
+    let record = contract.get_round(&app, round.addr).unwrap().unwrap();
+    assert_eq!(record.fee, Uint128::zero());
+
+    // Without the discount the creator pays the full fee again
+    contract
+        .set_fee_discount(&mut app, admin(), creator(), None)
+        .unwrap();
+    let quote = contract
+        .quote_round_fee(&app, RoundType::Amaci, 5, 5, Some(creator()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(quote.fee, Uint128::from(15 * dora));
+}
+
+#[test]
+fn validators_come_from_the_staking_module() {
+    let mut app = App::default();
+
+    let register_code_id = AmaciRegistryCodeId::store_code(&mut app);
+    let amaci_code_id = MaciCodeId::store_default_code(&mut app);
+    let contract = register_code_id
+        .instantiate(
+            &mut app,
+            creator(),
+            amaci_code_id.id(),
+            "Dora AMaci Registry",
+        )
+        .unwrap();
+
+    // Manually listed validators keep their operators until they are pruned
+    _ = contract.set_validators(&mut app, admin());
+    contract
+        .set_maci_operator(&mut app, user1(), operator())
+        .unwrap();
+    contract
+        .set_maci_operator_pubkey(&mut app, operator(), operator_pubkey1())
+        .unwrap();
+    assert_eq!(
+        contract.get_validator_source(&app).unwrap(),
+        ValidatorSource::Manual
+    );
+
+    add_staking_validator(&mut app, staking_valoper1());
+
+    let err = contract
+        .set_validator_source(&mut app, creator(), ValidatorSource::Staking)
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    contract
+        .set_validator_source(&mut app, operator(), ValidatorSource::Staking)
+        .unwrap();
+
+    assert_eq!(
+        contract.get_validators(&app).unwrap(),
+        ValidatorSet {
+            addresses: vec![staking_validator1()]
+        }
+    );
+    assert!(contract.is_validator(&app, staking_validator1()).unwrap());
+    assert!(!contract.is_validator(&app, staking_validator2()).unwrap());
+    assert!(!contract.is_validator(&app, user1()).unwrap());
+
+    let err = contract
+        .set_maci_operator(&mut app, staking_validator2(), operator3())
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    contract
+        .set_maci_operator(&mut app, staking_validator1(), operator2())
+        .unwrap();
+    contract
+        .set_maci_operator_pubkey(&mut app, operator2(), operator_pubkey2())
+        .unwrap();
+
+    // user1 is not a bonded validator, so its operator loses its coordinator key
+    contract.prune_validators(&mut app, creator()).unwrap();
+    assert!(contract.get_operator_pubkey(&app, operator()).is_err());
+    let history = contract
+        .get_operator_pubkey_history(&app, operator())
+        .unwrap();
+    assert_eq!(history[0].status, PubkeyStatus::Released);
+    assert_eq!(
+        contract.get_operator_pubkey(&app, operator2()).unwrap(),
+        operator_pubkey2()
+    );
+    assert_eq!(
+        contract
+            .get_validator_operator(&app, staking_validator1())
+            .unwrap(),
+        operator2()
+    );
+
+    // A validator joining the staking module can assign an operator without `SetValidators`
+    add_staking_validator(&mut app, staking_valoper2());
+    contract
+        .set_maci_operator(&mut app, staking_validator2(), operator3())
+        .unwrap();
+    contract.prune_validators(&mut app, creator()).unwrap();
+    assert_eq!(
+        contract
+            .get_validator_operator(&app, staking_validator2())
+            .unwrap(),
+        operator3()
+    );
+}
+
+#[test]
+fn addresses_must_have_the_configured_prefix() {
+    let mut app = App::default();
+
+    let register_code_id = AmaciRegistryCodeId::store_code(&mut app);
+    let amaci_code_id = MaciCodeId::store_default_code(&mut app);
+    let contract = register_code_id
+        .instantiate(
+            &mut app,
+            creator(),
+            amaci_code_id.id(),
+            "Dora AMaci Registry",
+        )
+        .unwrap();
+    contract.set_validators(&mut app, admin()).unwrap();
+
+    let osmo_operator = Addr::unchecked("osmo194c3vs4hy6cygqtz0j5lhtpj7hy9xra3w0ha3p");
+    let err = contract
+        .set_maci_operator(&mut app, user1(), osmo_operator.clone())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidAddressPrefix {
+            expected: String::from("dora"),
+            actual: String::from("osmo"),
+        },
+        err.downcast().unwrap()
+    );
+
+    let err = contract
+        .set_fee_discount(&mut app, admin(), creator(), Some(Decimal::percent(10)))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidAddress {
+            address: creator().to_string(),
+        },
+        err.downcast().unwrap()
+    );
+
+    // A chain with another prefix accepts its own addresses instead
+    let config = contract.get_config(&app).unwrap();
+    assert_eq!(config.address_prefix, "dora");
+    contract
+        .update_config(
+            &mut app,
+            admin(),
+            Config {
+                denom: config.denom,
+                min_deposit_amount: config.min_deposit_amount,
+                slash_amount: config.slash_amount,
+                unbonding_period: config.unbonding_period,
+                max_delay_records: config.max_delay_records,
+                address_prefix: String::from("osmo"),
+            },
+        )
+        .unwrap();
+    contract
+        .set_maci_operator(&mut app, user1(), osmo_operator.clone())
+        .unwrap();
+    assert_eq!(
+        contract.get_validator_operator(&app, user1()).unwrap(),
+        osmo_operator
+    );
+    let err = contract
+        .set_maci_operator(&mut app, user2(), operator())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidAddressPrefix {
+            expected: String::from("osmo"),
+            actual: String::from("dora"),
+        },
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn update_config_is_validated() {
+    let mut app = App::default();
+
+    let register_code_id = AmaciRegistryCodeId::store_code(&mut app);
+    let amaci_code_id = MaciCodeId::store_default_code(&mut app);
+    let contract = register_code_id
+        .instantiate(
+            &mut app,
+            creator(),
+            amaci_code_id.id(),
+            "Dora AMaci Registry",
+        )
+        .unwrap();
+
+    let current = contract.get_config(&app).unwrap();
+    let config = Config {
+        denom: current.denom,
+        min_deposit_amount: current.min_deposit_amount,
+        slash_amount: current.slash_amount,
+        unbonding_period: current.unbonding_period,
+        max_delay_records: current.max_delay_records,
+        address_prefix: current.address_prefix,
+    };
+
+    // Bonds and unbondings are held in the stake denom
+    let err = contract
+        .update_config(
+            &mut app,
+            admin(),
+            Config {
+                denom: String::from("uatom"),
+                ..config.clone()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::DenomImmutable {
+            denom: DORA_DEMON.to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    for prefix in ["", "Dora", "do-ra"] {
+        let err = contract
+            .update_config(
+                &mut app,
+                admin(),
+                Config {
+                    address_prefix: String::from(prefix),
+                    ..config.clone()
+                },
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidPrefix {
+                prefix: String::from(prefix)
+            },
+            err.downcast().unwrap()
+        );
+    }
+
+    let err = contract
+        .update_config(
+            &mut app,
+            admin(),
+            Config {
+                unbonding_period: Timestamp::from_seconds(0),
+                ..config.clone()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidUnbondingPeriod {},
+        err.downcast().unwrap()
+    );
+
+    let err = contract
+        .update_config(
+            &mut app,
+            admin(),
+            Config {
+                slash_amount: Uint128::from(MIN_DEPOSIT_AMOUNT + 1),
+                ..config.clone()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidSlashAmount {
+            slash_amount: Uint128::from(MIN_DEPOSIT_AMOUNT + 1),
+            min_deposit_amount: Uint128::from(MIN_DEPOSIT_AMOUNT),
+        },
+        err.downcast().unwrap()
+    );
+
+    // The whole minimum deposit can be slashed
+    contract
+        .update_config(
+            &mut app,
+            admin(),
+            Config {
+                slash_amount: Uint128::from(MIN_DEPOSIT_AMOUNT),
+                ..config
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        contract.get_config(&app).unwrap().slash_amount,
+        Uint128::from(MIN_DEPOSIT_AMOUNT)
+    );
+}
+
+#[test]
+fn rounds_of_every_type_are_created_and_indexed() {
+    let creator_coin_amount = 1000000000000000000000u128; // 1000 DORA
+    let dora = 1000000000000000000u128; // 1 DORA
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &creator(), coins(creator_coin_amount, DORA_DEMON))
+            .unwrap();
+    });
+
+    let register_code_id = AmaciRegistryCodeId::store_code(&mut app);
+    let amaci_code_id = MaciCodeId::store_default_code(&mut app);
+    let contract = register_code_id
+        .instantiate(
+            &mut app,
+            creator(),
+            amaci_code_id.id(),
+            "Dora AMaci Registry",
+        )
+        .unwrap();
+
+    let err = contract
+        .create_maci_round(&mut app, creator(), 5, &coins(20 * dora, DORA_DEMON))
+        .unwrap_err();
+    assert_eq!(
+        ContractError::RoundCodeIdNotSet {
+            round_type: String::from("maci")
+        },
+        err.downcast().unwrap()
+    );
+
+    let maci_code_id = store_maci_code(&mut app);
+    let oracle_maci_code_id = store_oracle_maci_code(&mut app);
+    let err = contract
+        .update_round_code_id(&mut app, creator(), RoundType::Maci, maci_code_id)
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    contract
+        .update_round_code_id(&mut app, operator(), RoundType::Maci, maci_code_id)
+        .unwrap();
+    contract
+        .update_round_code_id(
+            &mut app,
+            operator(),
+            RoundType::OracleMaci,
+            oracle_maci_code_id,
+        )
+        .unwrap();
+    assert_eq!(
+        contract.get_round_code_id(&app, RoundType::Amaci).unwrap(),
+        Some(amaci_code_id.id())
+    );
+    assert_eq!(
+        contract.get_round_code_id(&app, RoundType::Maci).unwrap(),
+        Some(maci_code_id)
+    );
+
+    // maci rounds cost what amaci rounds cost, the fee goes to the admin
+    let quote = contract
+        .quote_round_fee(&app, RoundType::Maci, 5, 5, None)
+        .unwrap()
+        .unwrap();
+    assert_eq!(quote.fee, Uint128::from(20 * dora));
+    let resp = contract
+        .create_maci_round(&mut app, creator(), 5, &coins(25 * dora, DORA_DEMON))
+        .unwrap();
+    let maci_round: InstantiationData = from_json(&resp.data.unwrap()).unwrap();
+    let admin_balance = contract
+        .balance_of(&app, admin().to_string(), DORA_DEMON.to_string())
+        .unwrap();
+    assert_eq!(admin_balance.amount, Uint128::from(20 * dora));
+
+    // oracle-maci rounds cost 10 DORA and 1 DORA per voter, the round keeps the fee
+    let quote = contract
+        .quote_round_fee(&app, RoundType::OracleMaci, 5, 2, None)
+        .unwrap()
+        .unwrap();
+    assert_eq!(quote.fee, Uint128::from(15 * dora));
+    let resp = contract
+        .create_oracle_maci_round(&mut app, creator(), 5, &coins(15 * dora, DORA_DEMON))
+        .unwrap();
+    let oracle_maci_round: InstantiationData = from_json(&resp.data.unwrap()).unwrap();
+    let round_balance = contract
+        .balance_of(
+            &app,
+            oracle_maci_round.addr.to_string(),
+            DORA_DEMON.to_string(),
+        )
+        .unwrap();
+    assert_eq!(round_balance.amount, Uint128::from(15 * dora));
+
+    let creator_balance = contract
+        .balance_of(&app, creator().to_string(), DORA_DEMON.to_string())
+        .unwrap();
+    assert_eq!(
+        creator_balance.amount,
+        Uint128::from(creator_coin_amount - 35 * dora)
+    );
+
+    // Both rounds are indexed like amaci rounds, run by their creator
+    let rounds = contract
+        .get_rounds_by_creator(&app, creator(), None)
+        .unwrap();
+    assert_eq!(rounds.len(), 2);
+    assert_eq!(rounds[0].round_type, RoundType::Maci);
+    assert_eq!(rounds[0].round_addr, maci_round.addr);
+    assert_eq!(rounds[0].operator, creator());
+    assert_eq!(rounds[0].coordinator_pubkey, operator_pubkey1());
+    assert_eq!(rounds[0].fee, Uint128::from(20 * dora));
+    assert_eq!(rounds[1].round_type, RoundType::OracleMaci);
+    assert_eq!(rounds[1].round_addr, oracle_maci_round.addr);
+    assert_eq!(rounds[1].id, 1);
+    assert_eq!(
+        rounds[1].parameters.state_tree_depth,
+        Uint256::from_u128(2u128)
+    );
+    assert_eq!(
+        contract.get_round(&app, oracle_maci_round.addr).unwrap(),
+        Some(rounds[1].clone())
+    );
+}
+
+#[test]
+fn rounds_are_created_from_templates() {
+    let creator_coin_amount = 1000000000000000000000u128; // 1000 DORA
+    let dora = 1000000000000000000u128; // 1 DORA
+
+    let mut app = App::new(|router, _api, storage| {
+        router
+            .bank
+            .init_balance(storage, &creator(), coins(creator_coin_amount, DORA_DEMON))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &operator(), coins(MIN_DEPOSIT_AMOUNT, DORA_DEMON))
+            .unwrap();
+    });
+
+    let register_code_id = AmaciRegistryCodeId::store_code(&mut app);
+    let amaci_code_id = MaciCodeId::store_default_code(&mut app);
+    let contract = register_code_id
+        .instantiate(
+            &mut app,
+            creator(),
+            amaci_code_id.id(),
+            "Dora AMaci Registry",
+        )
+        .unwrap();
+
+    _ = contract.set_validators(&mut app, admin());
+    _ = contract.set_maci_operator(&mut app, user1(), operator());
+    _ = contract.set_maci_operator_pubkey(&mut app, operator(), operator_pubkey1());
+    contract
+        .bond(&mut app, operator(), &coins(MIN_DEPOSIT_AMOUNT, DORA_DEMON))
+        .unwrap();
+
+    let settings = RoundSettings {
+        operator: operator(),
+        max_voter: Uint256::from_u128(5u128),
+        max_option: Uint256::from_u128(5u128),
+        voice_credit_amount: Uint256::from_u128(30u128),
+        whitelist: Some(WhitelistBase {
+            users: vec![
+                WhitelistBaseConfig { addr: user1() },
+                WhitelistBaseConfig { addr: user2() },
+            ],
+        }),
+        pre_deactivate_root: Uint256::zero(),
+        circuit_type: Uint256::zero(),
+        certification_system: Uint256::zero(),
+        extra_questions: None,
+    };
+
+    // Only the admin keeps global templates
+    let err = contract
+        .set_round_template(&mut app, creator(), "default", true, settings.clone())
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    contract
+        .set_round_template(&mut app, admin(), "default", true, settings.clone())
+        .unwrap();
+    contract
+        .set_round_template(&mut app, creator(), "weekly", false, settings.clone())
+        .unwrap();
+
+    let templates = contract
+        .get_round_templates(&app, Some(creator()), None)
+        .unwrap();
+    assert_eq!(templates.len(), 1);
+    assert_eq!(templates[0].name, "weekly");
+    assert_eq!(templates[0].owner, Some(creator()));
+    assert_eq!(templates[0].settings, settings);
+    let templates = contract.get_round_templates(&app, None, None).unwrap();
+    assert_eq!(templates.len(), 1);
+    assert_eq!(templates[0].owner, None);
+
+    let err = contract
+        .create_round_from_template(
+            &mut app,
+            creator(),
+            "monthly",
+            false,
+            RoundOverrides::default(),
+            &coins(20 * dora, DORA_DEMON),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::RoundTemplateNotFound {
+            name: String::from("monthly")
+        },
+        err.downcast().unwrap()
+    );
+
+    let resp = contract
+        .create_round_from_template(
+            &mut app,
+            creator(),
+            "weekly",
+            false,
+            RoundOverrides {
+                voice_credit_amount: Some(Uint256::from_u128(50u128)),
+                ..RoundOverrides::default()
+            },
+            &coins(20 * dora, DORA_DEMON),
+        )
+        .unwrap();
+    let round: InstantiationData = from_json(&resp.data.unwrap()).unwrap();
+    let maci_contract = MaciContract::new(round.addr.clone());
+    assert_eq!(
+        maci_contract.query_round_info(&app).unwrap().title,
+        "Weekly Round"
+    );
+    let voice_credit_amount: Uint256 = app
+        .wrap()
+        .query_wasm_smart(round.addr.clone(), &AmaciQueryMsg::GetVoiceCreditAmount {})
+        .unwrap();
+    assert_eq!(voice_credit_amount, Uint256::from_u128(50u128));
+    let record = contract.get_round(&app, round.addr).unwrap().unwrap();
+    assert_eq!(record.operator, operator());
+
+    // A bigger round needs the bigger circuit
+    let err = contract
+        .create_round_from_template(
+            &mut app,
+            creator(),
+            "default",
+            true,
+            RoundOverrides {
+                max_voter: Some(Uint256::from_u128(26u128)),
+                ..RoundOverrides::default()
+            },
+            &coins(20 * dora, DORA_DEMON),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InsufficientFee {
+            required: Uint128::from(750 * dora),
+            provided: Uint128::from(20 * dora),
+        },
+        err.downcast().unwrap()
+    );
+
+    contract
+        .remove_round_template(&mut app, creator(), "weekly", false)
+        .unwrap();
+    assert_eq!(
+        contract
+            .get_round_template(&app, Some(creator()), "weekly")
+            .unwrap(),
+        None
+    );
+    assert!(contract
+        .get_round_template(&app, None, "default")
+        .unwrap()
+        .is_some());
+}
@@ -1,14 +1,24 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Decimal, Uint128};
-use cw_amaci::state::PubKey;
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128, Uint256};
+use cw_amaci::msg::WhitelistBase;
+use cw_amaci::state::{MaciParameters, QuestionConfig, VotingTime};
 use cw_storage_plus::{Item, Map};
+use maci_core::PubKey;
 
 #[cw_serde]
 pub struct Config {
     /// denom of the token to stake
     pub denom: String,
+    /// the bond an operator needs to be assigned rounds
     pub min_deposit_amount: Uint128,
+    /// taken from the bond of an operator whose round timed out or had too many delays
     pub slash_amount: Uint128,
+    /// how long unbonded tokens stay slashable before they can be withdrawn
+    pub unbonding_period: Timestamp,
+    /// a round tallied with more delay records than this slashes its operator
+    pub max_delay_records: u64,
+    /// bech32 prefix of the accounts of the chain, e.g. `dora`
+    pub address_prefix: String,
 }
 
 #[cw_serde]
@@ -42,20 +52,82 @@ impl ValidatorSet {
     }
 }
 
+/// The round contracts the registry can create
+#[cw_serde]
+pub enum RoundType {
+    Amaci,
+    Maci,
+    OracleMaci,
+}
+
+impl RoundType {
+    pub fn all() -> [RoundType; 3] {
+        [RoundType::Amaci, RoundType::Maci, RoundType::OracleMaci]
+    }
+
+    /// the name of the round type in storage keys and events
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RoundType::Amaci => "amaci",
+            RoundType::Maci => "maci",
+            RoundType::OracleMaci => "oracle_maci",
+        }
+    }
+}
+
+/// Where the registry operator's validators come from
+#[cw_serde]
+#[derive(Default)]
+pub enum ValidatorSource {
+    /// `MACI_VALIDATOR_LIST`, maintained with `SetValidators`
+    #[default]
+    Manual,
+    /// the bonded validators of the staking module, by the account of their operator
+    Staking,
+}
+
 pub const ADMIN: Item<Admin> = Item::new("admin");
 pub const OPERATOR: Item<Addr> = Item::new("operator");
-// pub const CONFIG: Item<Config> = Item::new("config");
+pub const CONFIG: Item<Config> = Item::new("config");
 pub const AMACI_CODE_ID: Item<u64> = Item::new("amaci_code_id");
+pub const MACI_CODE_ID: Item<u64> = Item::new("maci_code_id");
+pub const ORACLE_MACI_CODE_ID: Item<u64> = Item::new("oracle_maci_code_id");
 // pub const TOTAL: Item<u128> = Item::new(TOTAL_KEY);
+pub const VALIDATOR_SOURCE: Item<ValidatorSource> = Item::new("validator_source");
 pub const MACI_VALIDATOR_LIST: Item<ValidatorSet> = Item::new("maci_validator_list"); // ['val1', 'val2', 'val3']
 pub const MACI_VALIDATOR_OPERATOR_SET: Map<&Addr, Addr> = Map::new("maci_validator_operator_set"); // { val1: op1, val2: op2, val3: op3 }
-pub const MACI_OPERATOR_SET: Map<&Addr, Uint128> = Map::new("maci_operator_set"); // { op1: pub1, op2: pub2, op3: pub3 }
+pub const MACI_OPERATOR_SET: Map<&Addr, Uint128> = Map::new("maci_operator_set"); // { op1: bond1, op2: bond2, op3: bond3 }
 
 pub const MACI_OPERATOR_PUBKEY: Map<&Addr, PubKey> = Map::new("maci_operator_pubkey"); // operator_address - coordinator_pubkey
 pub const COORDINATOR_PUBKEY_MAP: Map<&(Vec<u8>, Vec<u8>), u64> =
     Map::new("coordinator_pubkey_map"); //
 pub const MACI_OPERATOR_IDENTITY: Map<&Addr, String> = Map::new("maci_operator_identity"); // operator_address - identity
 
+#[cw_serde]
+pub enum PubkeyStatus {
+    /// the key new rounds of the operator are created with
+    Active,
+    /// replaced by a newer key but still reserved, rounds created with it may be live
+    Retired,
+    /// no longer reserved, any operator can set it
+    Released,
+    /// reserved forever
+    Burned,
+}
+
+#[cw_serde]
+pub struct OperatorPubkey {
+    pub pubkey: PubKey,
+    pub status: PubkeyStatus,
+    /// unknown for keys set before the history was kept
+    pub activated_at: Option<Timestamp>,
+    pub deactivated_at: Option<Timestamp>,
+}
+
+// every coordinator key an operator has set, oldest first
+pub const MACI_OPERATOR_PUBKEY_HISTORY: Map<&Addr, Vec<OperatorPubkey>> =
+    Map::new("maci_operator_pubkey_history");
+
 #[cw_serde]
 pub struct CircuitChargeConfig {
     // // small circuit fee (max_voter <= 25, max_option <= 5)
@@ -67,3 +139,182 @@ pub struct CircuitChargeConfig {
 }
 
 pub const CIRCUIT_CHARGE_CONFIG: Item<CircuitChargeConfig> = Item::new("circuit_charge_config");
+
+/// The circuit and the price of rounds with at most `max_voter` voters and `max_option` options
+#[cw_serde]
+pub struct FeeTier {
+    pub max_voter: Uint256,
+    pub max_option: Uint256,
+    pub parameters: MaciParameters,
+    pub base_fee: Uint128,
+    /// charged for every voter of the round's `max_voter`
+    pub fee_per_voter: Uint128,
+}
+
+// keyed by `RoundType::as_str`, a round gets the first tier it fits, so smaller circuits come first
+pub const FEE_TIERS: Map<&str, Vec<FeeTier>> = Map::new("round_fee_tiers");
+pub const FEE_DISCOUNTS: Map<&Addr, Decimal> = Map::new("fee_discounts"); // creator - share of the fee waived
+
+/// The circuits and prices rounds were created with before the fee tiers were configurable.
+/// oracle-maci rounds pick their own circuit, their tiers only set the price
+pub fn default_fee_tiers(round_type: &RoundType) -> Vec<FeeTier> {
+    if *round_type == RoundType::OracleMaci {
+        return default_oracle_maci_fee_tiers();
+    }
+    vec![
+        // price: 20 DORA
+        FeeTier {
+            max_voter: Uint256::from_u128(25u128),
+            max_option: Uint256::from_u128(5u128),
+            parameters: MaciParameters {
+                state_tree_depth: Uint256::from_u128(2u128),
+                int_state_tree_depth: Uint256::from_u128(1u128),
+                vote_option_tree_depth: Uint256::from_u128(1u128),
+                message_batch_size: Uint256::from_u128(5u128),
+            },
+            base_fee: Uint128::from(20000000000000000000u128),
+            fee_per_voter: Uint128::zero(),
+        },
+        // price: 750 DORA
+        FeeTier {
+            max_voter: Uint256::from_u128(625u128),
+            max_option: Uint256::from_u128(25u128),
+            parameters: MaciParameters {
+                state_tree_depth: Uint256::from_u128(4u128),
+                int_state_tree_depth: Uint256::from_u128(2u128),
+                vote_option_tree_depth: Uint256::from_u128(2u128),
+                message_batch_size: Uint256::from_u128(25u128),
+            },
+            base_fee: Uint128::from(750000000000000000000u128),
+            fee_per_voter: Uint128::zero(),
+        },
+    ]
+}
+
+// price: 10 DORA and 1 DORA per voter, all of it funds the fee grants of the round
+fn default_oracle_maci_fee_tiers() -> Vec<FeeTier> {
+    let base_fee = Uint128::from(10000000000000000000u128);
+    let fee_per_voter = Uint128::from(1000000000000000000u128);
+    vec![
+        FeeTier {
+            max_voter: Uint256::from_u128(25u128),
+            max_option: Uint256::from_u128(5u128),
+            parameters: MaciParameters {
+                state_tree_depth: Uint256::from_u128(2u128),
+                int_state_tree_depth: Uint256::from_u128(1u128),
+                vote_option_tree_depth: Uint256::from_u128(1u128),
+                message_batch_size: Uint256::from_u128(5u128),
+            },
+            base_fee,
+            fee_per_voter,
+        },
+        FeeTier {
+            max_voter: Uint256::from_u128(625u128),
+            max_option: Uint256::from_u128(25u128),
+            parameters: MaciParameters {
+                state_tree_depth: Uint256::from_u128(4u128),
+                int_state_tree_depth: Uint256::from_u128(2u128),
+                vote_option_tree_depth: Uint256::from_u128(2u128),
+                message_batch_size: Uint256::from_u128(25u128),
+            },
+            base_fee,
+            fee_per_voter,
+        },
+        FeeTier {
+            max_voter: Uint256::from_u128(15625u128),
+            max_option: Uint256::from_u128(125u128),
+            parameters: MaciParameters {
+                state_tree_depth: Uint256::from_u128(6u128),
+                int_state_tree_depth: Uint256::from_u128(3u128),
+                vote_option_tree_depth: Uint256::from_u128(3u128),
+                message_batch_size: Uint256::from_u128(125u128),
+            },
+            base_fee,
+            fee_per_voter,
+        },
+    ]
+}
+
+/// A round created by the registry. Rounds created before the index existed are not listed.
+#[cw_serde]
+pub struct RoundRecord {
+    pub id: u64,
+    pub round_type: RoundType,
+    pub round_addr: Addr,
+    /// the sender of the create message, who is the admin of the round
+    pub creator: Addr,
+    /// the registry operator of amaci rounds, the creator of the other rounds
+    pub operator: Addr,
+    /// the coordinator key of the operator when the round was created
+    pub coordinator_pubkey: PubKey,
+    pub parameters: MaciParameters,
+    pub circuit_type: String,
+    pub certification_system: String,
+    pub fee: Uint128,
+    pub voting_time: VotingTime,
+    pub created_at: Timestamp,
+}
+
+pub const ROUND_COUNT: Item<u64> = Item::new("round_count");
+pub const ROUNDS: Map<u64, RoundRecord> = Map::new("rounds");
+pub const ROUND_ID_BY_ADDR: Map<&Addr, u64> = Map::new("round_id_by_addr");
+pub const ROUNDS_BY_CREATOR: Map<(&Addr, u64), bool> = Map::new("rounds_by_creator");
+pub const ROUNDS_BY_OPERATOR: Map<(&Addr, u64), bool> = Map::new("rounds_by_operator");
+// keyed by the voting start time in seconds
+pub const ROUNDS_BY_VOTING_START: Map<(u64, u64), bool> = Map::new("rounds_by_voting_start");
+
+// the round being instantiated, its id and address are set when its address is known
+pub const PENDING_ROUND: Item<RoundRecord> = Item::new("pending_round");
+
+/// What `CreateRound` needs besides the title and time window of the round
+#[cw_serde]
+pub struct RoundSettings {
+    /// the maci operator serving the round, its bond backs the round
+    pub operator: Addr,
+    pub max_voter: Uint256,
+    pub max_option: Uint256,
+    pub voice_credit_amount: Uint256,
+    pub whitelist: Option<WhitelistBase>,
+    pub pre_deactivate_root: Uint256,
+    pub circuit_type: Uint256,
+    pub certification_system: Uint256,
+    pub extra_questions: Option<Vec<QuestionConfig>>,
+}
+
+/// Named settings amaci rounds are created from with `CreateRoundFromTemplate`
+#[cw_serde]
+pub struct RoundTemplate {
+    pub name: String,
+    /// `None` for the global templates of the admin, which anyone can use
+    pub owner: Option<Addr>,
+    pub settings: RoundSettings,
+    pub updated_at: Timestamp,
+}
+
+pub const GLOBAL_ROUND_TEMPLATES: Map<&str, RoundTemplate> = Map::new("global_round_templates");
+pub const ROUND_TEMPLATES: Map<(&Addr, &str), RoundTemplate> = Map::new("round_templates"); // (owner, name) - template
+
+#[cw_serde]
+pub struct Unbonding {
+    pub amount: Uint128,
+    pub release_at: Timestamp,
+}
+
+// unbonded tokens of operators, including removed ones, oldest first
+pub const UNBONDINGS: Map<&Addr, Vec<Unbonding>> = Map::new("unbondings");
+// rounds that already reported their operator, so a round cannot slash or count twice
+pub const REPORTED_ROUNDS: Map<u64, bool> = Map::new("reported_rounds");
+
+/// The performance of an operator over the claimed rounds it served
+#[cw_serde]
+#[derive(Default)]
+pub struct OperatorStats {
+    pub rounds_served: u64,
+    pub late_deactivate_batches: u64,
+    pub late_tallies: u64,
+    pub tally_timeouts: u64,
+    /// the sum of the miss rates of the rounds, each the percentage of the reward lost, 0 to 100
+    pub miss_rate_sum: u64,
+}
+
+pub const OPERATOR_STATS: Map<&Addr, OperatorStats> = Map::new("operator_stats");
@@ -7,7 +7,7 @@ use cosmwasm_std::{
 };
 use cw2::set_contract_version;
 use cw_storage_plus::Bound;
-use cw_utils::{may_pay, parse_instantiate_response_data};
+use cw_utils::{may_pay, parse_execute_response_data};
 
 use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as SdkCoin;
 use cosmos_sdk_proto::cosmos::feegrant::v1beta1::{
@@ -17,23 +17,22 @@ use cosmos_sdk_proto::Any;
 use prost::Message;
 
 // External contract types with aliases to avoid path conflicts
-use cw_amaci::state::RoundInfo;
-use cw_oracle_maci::msg::{
-    InstantiateMsg as OracleMaciInstantiateMsg, InstantiationData as OracleMaciInstantiationData,
-    VotingPowerArgs,
-};
-use cw_oracle_maci::state::{
-    PubKey as OracleMaciPubKey, RoundInfo as OracleMaciRoundInfo, VotingPowerMode,
-    VotingTime as OracleMaciVotingTime,
+use cw_amaci::state::{RoundInfo, SignUpTime as AmaciSignUpTime, VotingTime};
+use cw_amaci_registry::msg::{
+    ExecuteMsg as RegistryExecuteMsg, InstantiationData as RegistryInstantiationData, NewRound,
+    QueryMsg as RegistryQueryMsg, RoundFeeQuote,
 };
+use cw_amaci_registry::state::RoundType;
+use cw_oracle_maci::msg::VotingPowerArgs;
+use cw_oracle_maci::state::{SignUpTime, VotingPowerMode};
 
 use cosmos_sdk_proto::traits::TypeUrl;
 // Local contract types
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, InstantiationData, MigrateMsg, PubKey, QueryMsg};
 use crate::state::{
-    Config, MaciContractInfo, OperatorInfo, CONFIG, MACI_CONTRACTS, MACI_CONTRACT_COUNTER,
-    OPERATORS, ORACLE_MACI_CODE_ID, TOTAL_BALANCE, TREASURY_MANAGER,
+    Config, MaciContractInfo, OperatorInfo, CONFIG, LEGACY_CONFIG, MACI_CONTRACTS,
+    MACI_CONTRACT_COUNTER, OPERATORS, ORACLE_MACI_CODE_ID, TOTAL_BALANCE, TREASURY_MANAGER,
 };
 
 // Version info for migration
@@ -56,9 +55,12 @@ pub fn instantiate(
         admin: msg.admin,
         registry_contract: msg.registry_contract,
         denom: msg.denom,
+        address_prefix: msg.address_prefix,
     };
 
     CONFIG.save(deps.storage, &config)?;
+    validate_address(deps.as_ref(), &config.admin)?;
+    validate_address(deps.as_ref(), &msg.treasury_manager)?;
     // Store treasury manager separately for easier access
     TREASURY_MANAGER.save(deps.storage, &msg.treasury_manager)?;
     TOTAL_BALANCE.save(deps.storage, &Uint128::zero())?;
@@ -84,7 +86,8 @@ pub fn execute(
             admin,
             registry_contract,
             denom,
-        } => execute_update_config(deps, info, admin, registry_contract, denom),
+            address_prefix,
+        } => execute_update_config(deps, info, admin, registry_contract, denom, address_prefix),
         ExecuteMsg::AddOperator { operator } => execute_add_operator(deps, env, info, operator),
         ExecuteMsg::RemoveOperator { operator } => {
             execute_remove_operator(deps, env, info, operator)
@@ -104,6 +107,7 @@ pub fn execute(
             round_info,
             start_time,
             end_time,
+            sign_up_time,
             circuit_type,
             certification_system,
             whitelist_backend_pubkey,
@@ -117,6 +121,7 @@ pub fn execute(
             round_info,
             start_time,
             end_time,
+            sign_up_time,
             circuit_type,
             certification_system,
             whitelist_backend_pubkey,
@@ -143,6 +148,7 @@ pub fn execute_update_config(
     admin: Option<Addr>,
     registry_contract: Option<Addr>,
     denom: Option<String>,
+    address_prefix: Option<String>,
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
 
@@ -160,8 +166,13 @@ pub fn execute_update_config(
     if let Some(denom) = denom {
         config.denom = denom;
     }
+    if let Some(address_prefix) = address_prefix {
+        config.address_prefix = address_prefix;
+    }
 
     CONFIG.save(deps.storage, &config)?;
+    // The admin must match the prefix, including a new one
+    validate_address(deps.as_ref(), &config.admin)?;
 
     Ok(Response::new().add_attribute("action", "update_config"))
 }
@@ -179,6 +190,8 @@ pub fn execute_add_operator(
         return Err(ContractError::Unauthorized {});
     }
 
+    validate_address(deps.as_ref(), &operator)?;
+
     // Check if operator already exists
     if OPERATORS.has(deps.storage, &operator) {
         return Err(ContractError::OperatorAlreadyExists {});
@@ -345,6 +358,7 @@ pub fn execute_withdraw(
 
     // Send funds to recipient
     let recipient_addr = recipient.unwrap_or_else(|| info.sender.clone());
+    validate_address(deps.as_ref(), &recipient_addr)?;
     let msg = BankMsg::Send {
         to_address: recipient_addr.to_string(),
         amount: vec![Coin {
@@ -371,6 +385,7 @@ pub fn execute_create_oracle_maci_round(
     round_info: RoundInfo,
     start_time: Timestamp,
     end_time: Timestamp,
+    sign_up_time: Option<SignUpTime>,
     circuit_type: Uint256,
     certification_system: Uint256,
     whitelist_backend_pubkey: String,
@@ -380,14 +395,35 @@ pub fn execute_create_oracle_maci_round(
         return Err(ContractError::Unauthorized {});
     }
 
-    // Calculate deployment fee - base fee of 10 DORA for contract deployment
-    let base_fee = Uint128::from(10000000000000000000u128); // 10 DORA
-
-    // Calculate required token amount for Oracle MACI (max_voters * 1 DORA)
-    let token_amount = Uint128::from(max_voters as u128 * 1000000000000000000u128); // max_voters * 1 DORA
-
-    // Total required amount = deployment fee + token amount
-    let total_required = base_fee + token_amount;
+    // The registry creates the round and charges the fee of its oracle-maci fee tiers
+    let config = CONFIG.load(deps.storage)?;
+    let registry = config
+        .registry_contract
+        .ok_or(ContractError::NoRegistryContract {})?;
+    let quote: Option<RoundFeeQuote> = deps.querier.query_wasm_smart(
+        &registry,
+        &RegistryQueryMsg::QuoteRoundFee {
+            round_type: RoundType::OracleMaci,
+            max_voter: Uint256::from_u128(max_voters),
+            max_option: Uint256::from_u128(vote_option_map.len() as u128),
+            creator: Some(env.contract.address.clone()),
+        },
+    )?;
+    let total_required = quote
+        .ok_or(ContractError::InvalidOracleMaciParameters {
+            reason: String::from("no fee tier of the registry fits the round"),
+        })?
+        .fee;
+    let oracle_maci_code_id: Option<u64> = deps.querier.query_wasm_smart(
+        &registry,
+        &RegistryQueryMsg::GetRoundCodeId {
+            round_type: RoundType::OracleMaci,
+        },
+    )?;
+    let oracle_maci_code_id =
+        oracle_maci_code_id.ok_or(ContractError::InvalidOracleMaciParameters {
+            reason: String::from("the registry has no oracle-maci code id"),
+        })?;
 
     // Check if SaaS has sufficient balance
     let total_balance = TOTAL_BALANCE.load(deps.storage)?;
@@ -402,56 +438,44 @@ pub fn execute_create_oracle_maci_round(
     let new_balance = total_balance - total_required;
     TOTAL_BALANCE.save(deps.storage, &new_balance)?;
 
-    // Create Oracle MACI VotingTime using provided start_time and end_time
-    let oracle_voting_time = OracleMaciVotingTime {
-        start_time: start_time,
-        end_time: end_time,
-    };
-
-    // Create Oracle MACI InstantiateMsg using proper Oracle MACI types (like registry does with AMACI)
-    let oracle_maci_instantiate_msg = OracleMaciInstantiateMsg {
-        coordinator: OracleMaciPubKey {
-            x: coordinator.x,
-            y: coordinator.y,
-        },
-        max_voters,
-        vote_option_map: vote_option_map.clone(),
-        round_info: OracleMaciRoundInfo {
-            title: round_info.title.clone(),
-            description: round_info.description.clone(),
-            link: round_info.link.clone(),
-        },
-        voting_time: oracle_voting_time,
-        circuit_type,
-        certification_system,
-        whitelist_backend_pubkey: whitelist_backend_pubkey.clone(),
-        // Fixed default values - one person one vote system
-        whitelist_ecosystem: "doravota".to_string(),
-        whitelist_snapshot_height: Uint256::zero(),
-        whitelist_voting_power_args: VotingPowerArgs {
-            mode: VotingPowerMode::Slope,
-            slope: Uint256::one(),
-            threshold: Uint256::one(),
+    // The SaaS contract administers the round and grants the fees of its voters
+    let create_round_msg = RegistryExecuteMsg::CreateRound {
+        round: NewRound::OracleMaci {
+            coordinator,
+            max_voters,
+            vote_option_map,
+            round_info: round_info.clone(),
+            voting_time: VotingTime {
+                start_time,
+                end_time,
+            },
+            sign_up_time: sign_up_time.map(|sign_up_time| AmaciSignUpTime {
+                start_time: sign_up_time.start_time,
+                end_time: sign_up_time.end_time,
+            }),
+            circuit_type,
+            certification_system,
+            whitelist_backend_pubkey,
+            // Fixed default values - one person one vote system
+            whitelist_ecosystem: "doravota".to_string(),
+            whitelist_snapshot_height: Uint256::zero(),
+            whitelist_voting_power_args: VotingPowerArgs {
+                mode: VotingPowerMode::Slope,
+                slope: Uint256::one(),
+                threshold: Uint256::one(),
+            },
         },
-        feegrant_operator: env.contract.address.clone(),
     };
-
-    // Validate the message can be serialized properly
-    let serialized_msg = to_json_binary(&oracle_maci_instantiate_msg).map_err(|e| {
-        ContractError::SerializationError {
-            msg: format!("Failed to serialize Oracle MACI InstantiateMsg: {}", e),
-        }
-    })?;
-
-    let oracle_maci_code_id = ORACLE_MACI_CODE_ID.load(deps.storage)?;
-
-    // Prepare the instantiate message with SaaS contract as admin and token funds
-    let instantiate_msg = WasmMsg::Instantiate {
-        admin: Some(env.contract.address.to_string()), // SaaS contract as Oracle MACI admin
-        code_id: oracle_maci_code_id,
-        msg: serialized_msg,
-        funds: coins(total_required.u128(), "peaka"), // Send all fees, include user signup and vote fees
-        label: format!("Oracle Maci Round - {}", round_info.title),
+    // The registry sends the fee on to the round, where it pays the fee grants
+    let funds = if total_required.is_zero() {
+        vec![]
+    } else {
+        coins(total_required.u128(), &config.denom)
+    };
+    let create_round = WasmMsg::Execute {
+        contract_addr: registry.to_string(),
+        msg: to_json_binary(&create_round_msg)?,
+        funds,
     };
 
     // Get the next MACI contract counter
@@ -470,8 +494,8 @@ pub fn execute_create_oracle_maci_round(
     };
     MACI_CONTRACTS.save(deps.storage, maci_counter, &maci_contract_info)?;
 
-    // Create SubMsg with reply using registry pattern - this allows getting the real contract address
-    let submsg = SubMsg::reply_on_success(instantiate_msg, CREATED_ORACLE_MACI_ROUND_REPLY_ID);
+    // The reply gets the address of the round from the registry
+    let submsg = SubMsg::reply_on_success(create_round, CREATED_ORACLE_MACI_ROUND_REPLY_ID);
     Ok(Response::new()
         .add_submessage(submsg)
         .add_attribute("action", "create_oracle_maci_round")
@@ -574,6 +598,8 @@ pub fn execute_grant_to_voter(
         return Err(ContractError::Unauthorized {});
     }
 
+    validate_address(deps.as_ref(), &grantee)?;
+
     // Validate the contract address format
     let target_addr = deps.api.addr_validate(&contract_addr)?;
 
@@ -713,149 +739,83 @@ fn reply_created_oracle_maci_round(
     // Parse SubMsg response
     let response = result.map_err(StdError::generic_err)?;
 
-    // Parse response data using the same method as registry
+    // The registry answers `CreateRound` with the address of the round
     let data = response
         .data
         .ok_or(ContractError::Std(StdError::generic_err(
             "Data missing from response",
         )))?;
-    let parsed_response = match parse_instantiate_response_data(&data) {
+    let parsed_response = match parse_execute_response_data(&data) {
         Ok(data) => data,
         Err(err) => {
             return Err(ContractError::Std(StdError::generic_err(format!(
-                "Failed to parse instantiate response: {}",
+                "Failed to parse execute response: {}",
                 err
             ))))
         }
     };
-
-    let contract_address = Addr::unchecked(parsed_response.contract_address.clone());
-
-    let oracle_maci_return_data: OracleMaciInstantiationData =
-        from_json(&parsed_response.data.unwrap())?;
+    let round_data = parsed_response
+        .data
+        .ok_or(ContractError::Std(StdError::generic_err(
+            "Data missing from response",
+        )))?;
+    let round: RegistryInstantiationData = from_json(&round_data)?;
+    let contract_address = round.addr;
 
     // Get current MACI contract counter
     let maci_counter = MACI_CONTRACT_COUNTER.load(deps.storage)?;
 
-    let oracle_maci_code_id = ORACLE_MACI_CODE_ID.load(deps.storage)?;
     // Update contract address in MACI contract record (from temporary to real address)
     let mut maci_contract_info = MACI_CONTRACTS.load(deps.storage, maci_counter)?;
     maci_contract_info.contract_address = contract_address.clone();
     MACI_CONTRACTS.save(deps.storage, maci_counter, &maci_contract_info)?;
 
-    // Prepare return data - now contains complete oracle maci instantiation data
     let saas_instantiation_data = InstantiationData {
         addr: contract_address.clone(),
     };
 
-    let mut response_attrs = vec![
-        attr("action", "created_oracle_maci_round"),
-        attr("round_addr", &contract_address.to_string()),
-        attr("code_id", &oracle_maci_code_id.to_string()),
-        attr("caller", &oracle_maci_return_data.caller.to_string()),
-        attr("admin", &oracle_maci_return_data.caller.to_string()),
-        attr("operator", &oracle_maci_return_data.caller.to_string()),
-        attr("maci_counter", maci_counter.to_string()),
-    ];
-
-    // If successfully parsed Oracle MACI instantiation data, add more detailed information
-    // if let Some(ref oracle_maci_data) = oracle_maci_instantiation_data {
-    response_attrs.extend(vec![
-        attr(
-            "voting_start",
-            &oracle_maci_return_data
-                .voting_time
-                .start_time
-                .nanos()
-                .to_string(),
-        ),
-        attr(
-            "voting_end",
-            &oracle_maci_return_data
-                .voting_time
-                .end_time
-                .nanos()
-                .to_string(),
-        ),
-        attr(
-            "round_title",
-            &oracle_maci_return_data.round_info.title.to_string(),
-        ),
-        attr("max_voters", oracle_maci_return_data.max_voters.to_string()),
-        attr(
-            "vote_option_map",
-            format!("{:?}", oracle_maci_return_data.vote_option_map),
-        ),
-        attr("circuit_type", &oracle_maci_return_data.circuit_type),
-        attr(
-            "certification_system",
-            &oracle_maci_return_data.certification_system,
-        ),
-        attr(
-            "coordinator_pubkey_x",
-            oracle_maci_return_data.coordinator.x.to_string(),
-        ),
-        attr(
-            "coordinator_pubkey_y",
-            oracle_maci_return_data.coordinator.y.to_string(),
-        ),
-        attr(
-            "state_tree_depth",
-            oracle_maci_return_data
-                .parameters
-                .state_tree_depth
-                .to_string(),
-        ),
-        attr(
-            "int_state_tree_depth",
-            &oracle_maci_return_data
-                .parameters
-                .int_state_tree_depth
-                .to_string(),
-        ),
-        attr(
-            "vote_option_tree_depth",
-            oracle_maci_return_data
-                .parameters
-                .vote_option_tree_depth
-                .to_string(),
-        ),
-        attr(
-            "message_batch_size",
-            &oracle_maci_return_data
-                .parameters
-                .message_batch_size
-                .to_string(),
-        ),
-        attr(
-            "fee_grant_amount",
-            oracle_maci_return_data.fee_grant_amount.to_string(),
-        ),
-    ]);
-
-    if oracle_maci_return_data.round_info.description != "" {
-        response_attrs.push(attr(
-            "round_description",
-            &oracle_maci_return_data.round_info.description,
-        ));
-    }
-
-    if oracle_maci_return_data.round_info.link != "" {
-        response_attrs.push(attr("round_link", &oracle_maci_return_data.round_info.link));
-    }
-
+    // The registry emits the `CreateRound` event and the details of the round
     Ok(Response::new()
-        .add_attributes(response_attrs)
+        .add_attributes(vec![
+            attr("action", "created_oracle_maci_round"),
+            attr("round_addr", contract_address.to_string()),
+            attr("code_id", maci_contract_info.code_id.to_string()),
+            attr("maci_counter", maci_counter.to_string()),
+        ])
         .set_data(to_json_binary(&saas_instantiation_data)?))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    cw2::ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored_version =
+        cw2::ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Configs saved before v0.1.3 have no address prefix
+    let stored = (
+        stored_version.major,
+        stored_version.minor,
+        stored_version.patch,
+    );
+    let mut config = if stored < (0, 1, 3) {
+        let legacy = LEGACY_CONFIG.load(deps.storage)?;
+        Config {
+            admin: legacy.admin,
+            registry_contract: legacy.registry_contract,
+            denom: legacy.denom,
+            address_prefix: "dora".to_string(),
+        }
+    } else {
+        CONFIG.load(deps.storage)?
+    };
+    if let Some(address_prefix) = msg.address_prefix {
+        config.address_prefix = address_prefix;
+    }
+    CONFIG.save(deps.storage, &config)?;
 
     Ok(Response::new().add_attributes(vec![
         attr("action", "migrate"),
         attr("version", CONTRACT_VERSION),
+        attr("address_prefix", config.address_prefix),
     ]))
 }
 
@@ -889,12 +849,13 @@ fn is_treasury_manager(deps: Deps, sender: &str) -> StdResult<bool> {
     Ok(treasury_manager == sender_addr)
 }
 
-pub fn validate_dora_address(address: &str) -> Result<(), ContractError> {
-    match bech32::decode(address) {
+pub fn validate_address(deps: Deps, address: &Addr) -> Result<(), ContractError> {
+    let expected = CONFIG.load(deps.storage)?.address_prefix;
+    match bech32::decode(address.as_str()) {
         Ok((prefix, _data, _variant)) => {
-            if prefix != "dora" {
+            if prefix != expected {
                 return Err(ContractError::InvalidAddressPrefix {
-                    expected: "dora".to_string(),
+                    expected,
                     actual: prefix,
                 });
             }
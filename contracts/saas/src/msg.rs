@@ -1,14 +1,11 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Uint128, Uint256};
 use cw_amaci::state::RoundInfo;
+use cw_oracle_maci::state::SignUpTime;
 
 use crate::state::{Config, OperatorInfo};
 
-#[cw_serde]
-pub struct PubKey {
-    pub x: Uint256,
-    pub y: Uint256,
-}
+pub use maci_core::PubKey;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -16,6 +13,8 @@ pub struct InstantiateMsg {
     pub treasury_manager: Addr,
     pub registry_contract: Option<Addr>,
     pub denom: String,
+    // bech32 prefix every account address in the messages must have
+    pub address_prefix: String,
     pub oracle_maci_code_id: u64,
 }
 
@@ -26,8 +25,10 @@ pub enum ExecuteMsg {
         admin: Option<Addr>,
         registry_contract: Option<Addr>,
         denom: Option<String>,
+        address_prefix: Option<String>,
     },
 
+    /// Kept for compatibility, rounds are created by the registry with its own oracle-maci code id
     UpdateOracleMaciCodeId {
         code_id: u64,
     },
@@ -55,6 +56,7 @@ pub enum ExecuteMsg {
         round_info: RoundInfo,
         start_time: cosmwasm_std::Timestamp,
         end_time: cosmwasm_std::Timestamp,
+        sign_up_time: Option<SignUpTime>,
         circuit_type: Uint256,
         certification_system: Uint256,
         whitelist_backend_pubkey: String,
@@ -121,7 +123,10 @@ pub enum QueryMsg {
 }
 
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    // Replaces the bech32 prefix addresses are validated with, "dora" if the stored config has none
+    pub address_prefix: Option<String>,
+}
 
 #[cw_serde]
 pub struct InstantiationData {
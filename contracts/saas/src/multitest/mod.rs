@@ -6,6 +6,10 @@ use cosmwasm_schema::cw_serde;
 use cosmwasm_std::testing::{MockApi, MockStorage};
 use cosmwasm_std::{Addr, Coin, Empty, StdResult, Timestamp, Uint128};
 use cw_amaci::state::RoundInfo;
+use cw_amaci_registry::msg::{
+    ExecuteMsg as RegistryExecuteMsg, InstantiateMsg as RegistryInstantiateMsg,
+};
+use cw_amaci_registry::state::RoundType;
 use cw_multi_test::{
     no_init, AppBuilder, AppResponse, BankKeeper, ContractWrapper, DistributionKeeper, Executor,
     FailingModule, GovFailingModule, IbcFailingModule, StakeKeeper, StargateAccepting, WasmKeeper,
@@ -18,6 +22,7 @@ use crate::{
 };
 
 pub const DORA_DEMON: &str = "peaka";
+pub const ADDRESS_PREFIX: &str = "dora";
 
 // Mock feegrant allowance for testing purposes
 #[cw_serde]
@@ -84,6 +89,49 @@ impl SaasCodeId {
     }
 }
 
+/// A registry that creates the oracle-maci rounds of the SaaS contract with `oracle_maci_code_id`
+pub fn instantiate_registry(app: &mut App, oracle_maci_code_id: u64) -> Addr {
+    let contract = ContractWrapper::new(
+        cw_amaci_registry::contract::execute,
+        cw_amaci_registry::contract::instantiate,
+        cw_amaci_registry::contract::query,
+    )
+    .with_reply(cw_amaci_registry::contract::reply);
+    let code_id = app.store_code(Box::new(contract));
+    let amaci_contract = ContractWrapper::new(
+        cw_amaci::contract::execute,
+        cw_amaci::contract::instantiate,
+        cw_amaci::contract::query,
+    )
+    .with_reply(cw_amaci::contract::reply);
+    let amaci_code_id = app.store_code(Box::new(amaci_contract));
+    let init_msg = RegistryInstantiateMsg {
+        denom: DORA_DEMON.to_string(),
+        min_deposit_amount: Uint128::zero(),
+        slash_amount: Uint128::zero(),
+        unbonding_period: Timestamp::from_seconds(7 * 24 * 60 * 60), // 7 days
+        max_delay_records: 3,
+        address_prefix: ADDRESS_PREFIX.to_string(),
+        admin: admin(),
+        operator: admin(),
+        amaci_code_id,
+    };
+    let registry = app
+        .instantiate_contract(code_id, creator(), &init_msg, &[], "Registry", None)
+        .unwrap();
+    app.execute_contract(
+        admin(),
+        registry.clone(),
+        &RegistryExecuteMsg::UpdateRoundCodeId {
+            round_type: RoundType::OracleMaci,
+            code_id: oracle_maci_code_id,
+        },
+        &[],
+    )
+    .unwrap();
+    registry
+}
+
 impl From<SaasCodeId> for u64 {
     fn from(code_id: SaasCodeId) -> Self {
         code_id.0
@@ -115,6 +163,7 @@ impl SaasContract {
             treasury_manager,
             registry_contract,
             denom,
+            address_prefix: ADDRESS_PREFIX.to_string(),
             oracle_maci_code_id,
         };
 
@@ -130,6 +179,7 @@ impl SaasContract {
         admin: Option<Addr>,
         registry_contract: Option<Addr>,
         denom: Option<String>,
+        address_prefix: Option<String>,
     ) -> AnyResult<AppResponse> {
         app.execute_contract(
             sender,
@@ -138,6 +188,7 @@ impl SaasContract {
                 admin,
                 registry_contract,
                 denom,
+                address_prefix,
             },
             &[],
         )
@@ -234,6 +285,7 @@ impl SaasContract {
                 round_info,
                 start_time,
                 end_time,
+                sign_up_time: None,
                 circuit_type,
                 certification_system,
                 whitelist_backend_pubkey,
@@ -336,7 +388,7 @@ impl From<Addr> for SaasContract {
 
 // Helper functions for creating test addresses
 pub fn admin() -> Addr {
-    Addr::unchecked("admin")
+    Addr::unchecked("dora1awz56v6w0keztrxuv7sz5k2dzey6xq63q9vqcd")
 }
 
 pub fn creator() -> Addr {
@@ -344,27 +396,27 @@ pub fn creator() -> Addr {
 }
 
 pub fn operator1() -> Addr {
-    Addr::unchecked("operator1")
+    Addr::unchecked("dora1e3pm53rkt7qxkhnrd5cl6f0h3ag40sjegjrdd7")
 }
 
 pub fn operator2() -> Addr {
-    Addr::unchecked("operator2")
+    Addr::unchecked("dora14nhen60wczfkx82elr50mv73wc46r2wuaerwj9")
 }
 
 pub fn operator3() -> Addr {
-    Addr::unchecked("operator3")
+    Addr::unchecked("dora1au5nl4hcl89jmjesf5tlfyp7zlwr8fysk3rsga")
 }
 
 pub fn user1() -> Addr {
-    Addr::unchecked("user1")
+    Addr::unchecked("dora1e0npvcwyqg5napv25m0fqupdmznm62h3vydnh6")
 }
 
 pub fn user2() -> Addr {
-    Addr::unchecked("user2")
+    Addr::unchecked("dora1k9mhpjh64hvfza80x9fzzrr252wv5f6dc58q93")
 }
 
 pub fn user3() -> Addr {
-    Addr::unchecked("user3")
+    Addr::unchecked("dora1u9ksd5jgg3cda9fhgp97wd9qrljht9up3h9txf")
 }
 
 pub fn mock_registry_contract() -> Addr {
@@ -372,7 +424,7 @@ pub fn mock_registry_contract() -> Addr {
 }
 
 pub fn treasury_manager() -> Addr {
-    Addr::unchecked("treasury_manager")
+    Addr::unchecked("dora172kz8p0wx2hur9jvrcg8u99dhzl6k0htva0850")
 }
 
 // Helper function to create test round info
@@ -1,13 +1,15 @@
-use cosmwasm_std::{coins, Uint128, Uint256};
+use cosmwasm_std::{coins, Addr, Uint128, Uint256};
 use cw_multi_test::{AppBuilder, Contract, ContractWrapper, Executor, StargateAccepting};
 
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, PubKey};
 use crate::multitest::{
-    admin, create_app, creator, mock_registry_contract, operator1, operator2, treasury_manager,
-    user1, user2, SaasCodeId, DORA_DEMON,
+    admin, create_app, creator, instantiate_registry, mock_registry_contract, operator1, operator2,
+    treasury_manager, user1, user2, SaasCodeId, DORA_DEMON,
 };
 use cw_amaci::multitest::uint256_from_decimal_string;
+use cw_amaci_registry::msg::QueryMsg as RegistryQueryMsg;
+use cw_amaci_registry::state::{RoundRecord, RoundType};
 use cw_oracle_maci;
 use cw_oracle_maci::state::RoundInfo as OracleMaciRoundInfo;
 
@@ -76,7 +78,7 @@ fn test_update_config() {
 
     // Update config as admin
     contract
-        .update_config(&mut app, admin(), Some(new_admin.clone()), None, None)
+        .update_config(&mut app, admin(), Some(new_admin.clone()), None, None, None)
         .unwrap();
 
     // Verify config updated
@@ -85,11 +87,100 @@ fn test_update_config() {
 
     // Try to update as non-admin (should fail)
     let err = contract
-        .update_config(&mut app, user2(), Some(admin()), None, None)
+        .update_config(&mut app, user2(), Some(admin()), None, None, None)
         .unwrap_err();
     assert!(err.to_string().contains("Error executing WasmMsg"));
 }
 
+#[test]
+fn test_address_prefix_validation() {
+    let mut app = create_app();
+
+    let oracle_maci_code_id = app.store_code(oracle_maci_contract());
+    let code_id = SaasCodeId::store_code(&mut app);
+
+    // The admin must have the configured prefix
+    let err = code_id
+        .instantiate(
+            &mut app,
+            creator(),
+            creator(),
+            treasury_manager(),
+            Some(mock_registry_contract()),
+            DORA_DEMON.to_string(),
+            oracle_maci_code_id,
+            "SaaS Contract",
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidAddress {
+            address: creator().to_string(),
+        },
+        err.downcast().unwrap()
+    );
+
+    let contract = code_id
+        .instantiate(
+            &mut app,
+            creator(),
+            admin(),
+            treasury_manager(),
+            Some(mock_registry_contract()),
+            DORA_DEMON.to_string(),
+            oracle_maci_code_id,
+            "SaaS Contract",
+        )
+        .unwrap();
+    assert_eq!(contract.query_config(&app).unwrap().address_prefix, "dora");
+
+    let osmo_operator = Addr::unchecked("osmo194c3vs4hy6cygqtz0j5lhtpj7hy9xra3w0ha3p");
+    let err = contract
+        .add_operator(&mut app, admin(), osmo_operator.clone())
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidAddressPrefix {
+            expected: "dora".to_string(),
+            actual: "osmo".to_string(),
+        },
+        err.downcast().unwrap()
+    );
+
+    // Switching the prefix also checks the admin against it
+    let err = contract
+        .update_config(
+            &mut app,
+            admin(),
+            None,
+            None,
+            None,
+            Some("osmo".to_string()),
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidAddressPrefix {
+            expected: "osmo".to_string(),
+            actual: "dora".to_string(),
+        },
+        err.downcast().unwrap()
+    );
+
+    let osmo_admin = Addr::unchecked("osmo1mlw5g4zjes4nmtyxfyudynz3ef89zmrvvrcj9l");
+    contract
+        .update_config(
+            &mut app,
+            admin(),
+            Some(osmo_admin.clone()),
+            None,
+            None,
+            Some("osmo".to_string()),
+        )
+        .unwrap();
+    contract
+        .add_operator(&mut app, osmo_admin, osmo_operator.clone())
+        .unwrap();
+    assert!(contract.query_is_operator(&app, osmo_operator).unwrap());
+}
+
 #[test]
 fn test_operator_management() {
     let mut app = create_app();
@@ -243,6 +334,7 @@ fn test_create_oracle_maci_round_success() {
         });
 
     let oracle_maci_code_id = app.store_code(oracle_maci_contract());
+    let registry = instantiate_registry(&mut app, oracle_maci_code_id);
     let code_id = SaasCodeId::store_code(&mut app);
     let contract = code_id
         .instantiate(
@@ -250,7 +342,7 @@ fn test_create_oracle_maci_round_success() {
             creator(),
             admin(),
             treasury_manager(),
-            None,
+            Some(registry.clone()),
             DORA_DEMON.to_string(),
             oracle_maci_code_id,
             "SaaS Contract",
@@ -284,6 +376,7 @@ fn test_create_oracle_maci_round_success() {
         },
         start_time: cosmwasm_std::Timestamp::from_seconds(1640995200), // 2022-01-01
         end_time: cosmwasm_std::Timestamp::from_seconds(1641081600),   // 2022-01-02
+        sign_up_time: None,
         circuit_type: Uint256::zero(),
         certification_system: Uint256::zero(),
         whitelist_backend_pubkey: "dGVzdA==".to_string(),
@@ -341,6 +434,22 @@ fn test_create_oracle_maci_round_success() {
     assert_eq!(maci_contracts[0].round_title, "Test Round");
     assert_eq!(maci_contracts[0].creator_operator, operator1());
 
+    // The round is created through the registry, which records the saas contract as its creator
+    let rounds: Vec<RoundRecord> = app
+        .wrap()
+        .query_wasm_smart(
+            &registry,
+            &RegistryQueryMsg::GetRounds {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(rounds.len(), 1);
+    assert_eq!(rounds[0].round_type, RoundType::OracleMaci);
+    assert_eq!(rounds[0].creator, contract.addr());
+    assert_eq!(rounds[0].round_addr, maci_contracts[0].contract_address);
+
     // Method 2: Query Oracle MACI address through SAAS contract, then query detailed round info
     if let Some(first_maci) = maci_contracts.first() {
         println!("========= MACI Contract Info Queried through SAAS ==========");
@@ -413,6 +522,7 @@ fn test_create_oracle_maci_round_unauthorized() {
         },
         start_time: cosmwasm_std::Timestamp::from_seconds(1753920000), // 2022-01-01
         end_time: cosmwasm_std::Timestamp::from_seconds(1754006400),   // 2022-01-02
+        sign_up_time: None,
         circuit_type: Uint256::zero(),
         certification_system: Uint256::zero(),
         whitelist_backend_pubkey: "AoYo/zENN/JquagPdG0/NMbWBBYxOM8BVN677mBXJKJQ".to_string(),
@@ -446,6 +556,7 @@ fn test_create_oracle_maci_round_insufficient_funds() {
         });
 
     let oracle_maci_code_id = app.store_code(oracle_maci_contract());
+    let registry = instantiate_registry(&mut app, oracle_maci_code_id);
     let code_id = SaasCodeId::store_code(&mut app);
     let contract = code_id
         .instantiate(
@@ -453,7 +564,7 @@ fn test_create_oracle_maci_round_insufficient_funds() {
             creator(),
             admin(),
             treasury_manager(),
-            None,
+            Some(registry.clone()),
             DORA_DEMON.to_string(),
             oracle_maci_code_id,
             "SaaS Contract",
@@ -482,6 +593,7 @@ fn test_create_oracle_maci_round_insufficient_funds() {
         },
         start_time: cosmwasm_std::Timestamp::from_seconds(1640995200), // 2022-01-01
         end_time: cosmwasm_std::Timestamp::from_seconds(1641081600),   // 2022-01-02
+        sign_up_time: None,
         circuit_type: Uint256::zero(),
         certification_system: Uint256::zero(),
         whitelist_backend_pubkey: "dGVzdA==".to_string(),
@@ -515,6 +627,7 @@ fn test_oracle_maci_round_management() {
         });
 
     let oracle_maci_code_id = app.store_code(oracle_maci_contract());
+    let registry = instantiate_registry(&mut app, oracle_maci_code_id);
     let code_id = SaasCodeId::store_code(&mut app);
     let contract = code_id
         .instantiate(
@@ -522,7 +635,7 @@ fn test_oracle_maci_round_management() {
             creator(),
             admin(),
             treasury_manager(),
-            None,
+            Some(registry.clone()),
             DORA_DEMON.to_string(),
             oracle_maci_code_id,
             "SaaS Contract",
@@ -556,6 +669,7 @@ fn test_oracle_maci_round_management() {
         },
         start_time: cosmwasm_std::Timestamp::from_seconds(1753920000), // 2022-01-01
         end_time: cosmwasm_std::Timestamp::from_seconds(1754006400),   // 2022-01-02
+        sign_up_time: None,
         circuit_type: Uint256::zero(),
         certification_system: Uint256::zero(),
         whitelist_backend_pubkey: "AoYo/zENN/JquagPdG0/NMbWBBYxOM8BVN677mBXJKJQ".to_string(),
@@ -7,6 +7,16 @@ pub struct Config {
     pub admin: Addr,
     pub registry_contract: Option<Addr>,
     pub denom: String,
+    // bech32 prefix of the accounts of the chain, e.g. "dora"
+    pub address_prefix: String,
+}
+
+// Config as stored before the address prefix was added (v0.1.2 and earlier)
+#[cw_serde]
+pub struct LegacyConfig {
+    pub admin: Addr,
+    pub registry_contract: Option<Addr>,
+    pub denom: String,
 }
 
 impl Config {
@@ -40,6 +50,7 @@ pub struct MaciContractInfo {
 
 // Storage items
 pub const CONFIG: Item<Config> = Item::new("config");
+pub const LEGACY_CONFIG: Item<LegacyConfig> = Item::new("config");
 pub const OPERATORS: Map<&Addr, OperatorInfo> = Map::new("operators");
 pub const TOTAL_BALANCE: Item<Uint128> = Item::new("total_balance");
 
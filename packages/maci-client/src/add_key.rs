@@ -0,0 +1,161 @@
+use crate::error::ClientError;
+use crate::keys::{from_point, random_field_element, to_bigint, to_point, Keypair};
+use babyjubjub_rs::{Point, B8};
+use cosmwasm_std::Uint256;
+use maci_core::utils::{
+    hash_256_uint256_list, snark_scalar_field, try_hash2, uint256_from_hex_string,
+};
+use maci_core::PubKey;
+
+/// "NULLIFIER" in ascii, the domain of add new key nullifiers
+pub const NULLIFIER_DOMAIN: u128 = 1444992409218394441042;
+
+/// The public values of `AddNewKey` and the private values its proof needs
+#[derive(Clone, Debug, PartialEq)]
+pub struct AddNewKeyInput {
+    pub nullifier: Uint256,
+    /// the rerandomized deactivate leaf, `d1 ‖ d2`
+    pub d: [Uint256; 4],
+    pub deactivate_index: usize,
+    pub deactivate_leaf: Vec<Uint256>,
+    pub random_val: Uint256,
+}
+
+/// The last element of the deactivate leaf of `old_key`
+pub fn shared_key_hash(old_key: &Keypair, coordinator: &PubKey) -> Result<Uint256, ClientError> {
+    let shared_key = old_key.shared_key(coordinator)?;
+    Ok(try_hash2([shared_key.x, shared_key.y])?)
+}
+
+/// Marks `old_key` as used, a second `AddNewKey` with the same key is rejected
+pub fn nullifier(old_key: &Keypair) -> Result<Uint256, ClientError> {
+    Ok(try_hash2([
+        old_key.formatted_priv_key(),
+        Uint256::from_u128(NULLIFIER_DOMAIN),
+    ])?)
+}
+
+/// Find the deactivate leaf of `old_key` among the leaves the operator uploaded and rerandomize
+/// its ciphertext, so the new key cannot be linked to the old one
+pub fn add_new_key_input(
+    old_key: &Keypair,
+    coordinator: &PubKey,
+    deactivates: &[Vec<Uint256>],
+) -> Result<AddNewKeyInput, ClientError> {
+    add_new_key_input_with_random(old_key, coordinator, deactivates, random_field_element())
+}
+
+/// `add_new_key_input` with a given rerandomization value
+pub fn add_new_key_input_with_random(
+    old_key: &Keypair,
+    coordinator: &PubKey,
+    deactivates: &[Vec<Uint256>],
+    random_val: Uint256,
+) -> Result<AddNewKeyInput, ClientError> {
+    let shared_key_hash = shared_key_hash(old_key, coordinator)?;
+    let deactivate_index = deactivates
+        .iter()
+        .position(|leaf| leaf.len() == 5 && leaf[4] == shared_key_hash)
+        .ok_or(ClientError::DeactivateLeafNotFound {})?;
+    let leaf = &deactivates[deactivate_index];
+
+    let r = to_bigint(random_val);
+    let d1 = add(&B8.mul_scalar(&r), &to_point(&pub_key(leaf[0], leaf[1]))?);
+    let d2 = add(
+        &to_point(coordinator)?.mul_scalar(&r),
+        &to_point(&pub_key(leaf[2], leaf[3]))?,
+    );
+    Ok(AddNewKeyInput {
+        nullifier: nullifier(old_key)?,
+        d: [d1.x, d1.y, d2.x, d2.y],
+        deactivate_index,
+        deactivate_leaf: leaf.clone(),
+        random_val,
+    })
+}
+
+impl AddNewKeyInput {
+    /// The public input of the add new key proof, as `execute_add_new_key` computes it
+    pub fn input_hash(
+        &self,
+        deactivate_root: Uint256,
+        coordinator: &PubKey,
+    ) -> Result<Uint256, ClientError> {
+        let input = [
+            deactivate_root,
+            try_hash2([coordinator.x, coordinator.y])?,
+            self.nullifier,
+            self.d[0],
+            self.d[1],
+            self.d[2],
+            self.d[3],
+        ];
+        Ok(uint256_from_hex_string(&hash_256_uint256_list(&input)) % snark_scalar_field())
+    }
+}
+
+fn pub_key(x: Uint256, y: Uint256) -> PubKey {
+    PubKey { x, y }
+}
+
+fn add(p: &Point, q: &Point) -> PubKey {
+    from_point(&p.projective().add(&q.projective()).affine())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ff::Field;
+
+    fn mul(p: &PubKey, scalar: Uint256) -> PubKey {
+        from_point(&to_point(p).unwrap().mul_scalar(&to_bigint(scalar)))
+    }
+
+    fn sub(p: &PubKey, q: &PubKey) -> PubKey {
+        let mut q = to_point(q).unwrap();
+        q.x.negate();
+        add(&to_point(p).unwrap(), &q)
+    }
+
+    #[test]
+    fn rerandomized_leaf_decrypts_to_the_same_point() {
+        let coordinator = Keypair::random();
+        let old_key = Keypair::random();
+        let base8 = from_point(&B8);
+
+        // an ElGamal encryption of a point under the coordinator key
+        let k = Uint256::from_u128(987654321);
+        let plain = mul(&base8, Uint256::from_u128(7));
+        let c1 = mul(&base8, k);
+        let c2 = add(
+            &to_point(&plain).unwrap(),
+            &to_point(&mul(&coordinator.pub_key, k)).unwrap(),
+        );
+        let leaf = vec![
+            c1.x,
+            c1.y,
+            c2.x,
+            c2.y,
+            shared_key_hash(&old_key, &coordinator.pub_key).unwrap(),
+        ];
+        let other = vec![Uint256::one(); 5];
+
+        let input =
+            add_new_key_input(&old_key, &coordinator.pub_key, &[other, leaf.clone()]).unwrap();
+        assert_eq!(input.deactivate_index, 1);
+        assert_eq!(input.deactivate_leaf, leaf);
+        assert_eq!(input.nullifier, nullifier(&old_key).unwrap());
+        assert_ne!(input.d, [c1.x, c1.y, c2.x, c2.y]);
+
+        let sk = coordinator.formatted_priv_key();
+        let d1 = pub_key(input.d[0], input.d[1]);
+        let d2 = pub_key(input.d[2], input.d[3]);
+        assert_eq!(sub(&c2, &mul(&c1, sk)), plain);
+        assert_eq!(sub(&d2, &mul(&d1, sk)), plain);
+
+        assert_eq!(
+            add_new_key_input(&Keypair::random(), &coordinator.pub_key, &[leaf]).unwrap_err(),
+            ClientError::DeactivateLeafNotFound {}
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! Poseidon encryption, as decrypted by the circuits' `PoseidonDecrypt`: a duplex sponge over
+//! the width 4 Poseidon permutation keyed by an ECDH shared key.
+
+use crate::error::ClientError;
+use crate::keys::{from_field, to_field};
+use cosmwasm_std::Uint256;
+use ff::Field;
+use maci_core::utils::Fr;
+use maci_core::PubKey;
+use poseidon_rs::{load_constants, Constants};
+
+/// Encrypt `plaintext` with a shared key. The ciphertext has `3 * ceil(len / 3) + 1` elements
+pub fn encrypt(
+    plaintext: &[Uint256],
+    shared_key: &PubKey,
+    nonce: Uint256,
+) -> Result<Vec<Uint256>, ClientError> {
+    let constants = load_constants();
+    let message = plaintext
+        .iter()
+        .map(|value| to_field::<Fr>(*value))
+        .collect::<Result<Vec<Fr>, ClientError>>()?;
+    let mut state = initial_state(shared_key, nonce, plaintext.len())?;
+
+    let mut ciphertext = vec![];
+    for chunk in message.chunks(3) {
+        state = permute(&constants, state);
+        for (i, value) in chunk.iter().enumerate() {
+            state[i + 1].add_assign(value);
+        }
+        ciphertext.extend(state[1..].iter().map(from_field::<Fr>));
+    }
+    state = permute(&constants, state);
+    ciphertext.push(from_field(&state[1]));
+    Ok(ciphertext)
+}
+
+/// Decrypt `length` elements, failing when the ciphertext was not made with this key and nonce
+pub fn decrypt(
+    ciphertext: &[Uint256],
+    shared_key: &PubKey,
+    nonce: Uint256,
+    length: usize,
+) -> Result<Vec<Uint256>, ClientError> {
+    if ciphertext.len() != length.div_ceil(3) * 3 + 1 {
+        return Err(ClientError::DecryptionFailed {});
+    }
+    let constants = load_constants();
+    let ciphertext = ciphertext
+        .iter()
+        .map(|value| to_field::<Fr>(*value))
+        .collect::<Result<Vec<Fr>, ClientError>>()?;
+    let mut state = initial_state(shared_key, nonce, length)?;
+
+    let (last, chunks) = ciphertext.split_last().expect("at least one element");
+    let mut plaintext = vec![];
+    for chunk in chunks.chunks(3) {
+        state = permute(&constants, state);
+        for (i, value) in chunk.iter().enumerate() {
+            let mut decrypted = *value;
+            decrypted.sub_assign(&state[i + 1]);
+            plaintext.push(from_field(&decrypted));
+            state[i + 1] = *value;
+        }
+    }
+    state = permute(&constants, state);
+    if state[1] != *last || plaintext[length..].iter().any(|pad| !pad.is_zero()) {
+        return Err(ClientError::DecryptionFailed {});
+    }
+    plaintext.truncate(length);
+    Ok(plaintext)
+}
+
+fn initial_state(
+    shared_key: &PubKey,
+    nonce: Uint256,
+    length: usize,
+) -> Result<Vec<Fr>, ClientError> {
+    let two_128 = Uint256::one() << 128;
+    if nonce >= two_128 {
+        return Err(ClientError::FieldTooLarge {
+            field: "nonce".to_string(),
+            bits: 128,
+        });
+    }
+    Ok(vec![
+        Fr::zero(),
+        to_field(shared_key.x)?,
+        to_field(shared_key.y)?,
+        to_field(nonce + Uint256::from(length as u64) * two_128)?,
+    ])
+}
+
+/// The Poseidon permutation, returning the whole state. `Poseidon::hash` is its first element
+/// for a state starting with zero
+fn permute(constants: &Constants, mut state: Vec<Fr>) -> Vec<Fr> {
+    let t = state.len();
+    let n_rounds_f = constants.n_rounds_f;
+    let n_rounds_p = constants.n_rounds_p[t - 2];
+    let round_constants = &constants.c[t - 2];
+    let mds = &constants.m[t - 2];
+
+    for round in 0..(n_rounds_f + n_rounds_p) {
+        for (i, element) in state.iter_mut().enumerate() {
+            element.add_assign(&round_constants[round * t + i]);
+        }
+        let full_round = round < n_rounds_f / 2 || round >= n_rounds_f / 2 + n_rounds_p;
+        let sboxes = if full_round { t } else { 1 };
+        for element in state.iter_mut().take(sboxes) {
+            let x = *element;
+            element.square();
+            element.square();
+            element.mul_assign(&x);
+        }
+        state = mds
+            .iter()
+            .map(|row| {
+                let mut mixed = Fr::zero();
+                for (m, element) in row.iter().zip(state.iter()) {
+                    let mut term = *m;
+                    term.mul_assign(element);
+                    mixed.add_assign(&term);
+                }
+                mixed
+            })
+            .collect();
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ff::PrimeField;
+    use maci_core::utils::hash;
+
+    fn key() -> PubKey {
+        PubKey {
+            x: Uint256::from_u128(1234),
+            y: Uint256::from_u128(5678),
+        }
+    }
+
+    #[test]
+    fn permutation_matches_the_poseidon_hash() {
+        let inputs: Vec<Fr> = (1..=3)
+            .map(|i| Fr::from_str(&i.to_string()).unwrap())
+            .collect();
+        let mut state = vec![Fr::zero()];
+        state.extend(inputs.iter().cloned());
+        let state = permute(&load_constants(), state);
+        assert_eq!(from_field(&state[0]), hash(inputs));
+    }
+
+    #[test]
+    fn decrypt_reverses_encrypt() {
+        for length in 1..=7 {
+            let plaintext: Vec<Uint256> = (0..length as u128).map(Uint256::from_u128).collect();
+            let ciphertext = encrypt(&plaintext, &key(), Uint256::zero()).unwrap();
+            assert_eq!(ciphertext.len(), length.div_ceil(3) * 3 + 1);
+            assert_eq!(
+                decrypt(&ciphertext, &key(), Uint256::zero(), length).unwrap(),
+                plaintext
+            );
+        }
+    }
+
+    #[test]
+    fn decrypt_fails_with_another_key_or_nonce() {
+        let plaintext = vec![Uint256::from_u128(7); 6];
+        let ciphertext = encrypt(&plaintext, &key(), Uint256::zero()).unwrap();
+        let other = PubKey {
+            x: Uint256::from_u128(1),
+            y: Uint256::from_u128(2),
+        };
+        assert_eq!(
+            decrypt(&ciphertext, &other, Uint256::zero(), 6).unwrap_err(),
+            ClientError::DecryptionFailed {}
+        );
+        assert_eq!(
+            decrypt(&ciphertext, &key(), Uint256::one(), 6).unwrap_err(),
+            ClientError::DecryptionFailed {}
+        );
+    }
+}
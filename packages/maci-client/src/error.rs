@@ -0,0 +1,23 @@
+use maci_core::CoreError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ClientError {
+    #[error("{0}")]
+    Core(#[from] CoreError),
+
+    #[error("{field} does not fit in {bits} bits")]
+    FieldTooLarge { field: String, bits: u32 },
+
+    #[error("point is not on the baby jubjub curve")]
+    InvalidPoint {},
+
+    #[error("could not sign: {reason}")]
+    Signing { reason: String },
+
+    #[error("message was not encrypted for this key")]
+    DecryptionFailed {},
+
+    #[error("no deactivate leaf belongs to this key")]
+    DeactivateLeafNotFound {},
+}
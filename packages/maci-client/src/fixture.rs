@@ -0,0 +1,24 @@
+//! The inputs of the amaci process messages test, produced by the circuit tooling
+
+use cosmwasm_std::Uint256;
+use maci_core::utils::try_uint256_from_decimal_string;
+use maci_core::PubKey;
+use serde_json::Value;
+
+pub fn msg_test() -> Value {
+    serde_json::from_str(include_str!(
+        "../../../contracts/amaci/src/test/msg_test.json"
+    ))
+    .unwrap()
+}
+
+pub fn uint(value: &Value) -> Uint256 {
+    try_uint256_from_decimal_string(value.as_str().unwrap()).unwrap()
+}
+
+pub fn pub_key(value: &Value) -> PubKey {
+    PubKey {
+        x: uint(&value[0]),
+        y: uint(&value[1]),
+    }
+}
@@ -0,0 +1,223 @@
+use crate::error::ClientError;
+use babyjubjub_rs::{verify, Point, PrivateKey};
+use cosmwasm_std::Uint256;
+use ff::{Field, PrimeField, PrimeFieldRepr};
+use maci_core::utils::{ensure_in_snark_field, snark_scalar_field, try_fr_from_uint256, Fr};
+use maci_core::{CoreError, PubKey};
+use num_bigint::{BigInt, Sign};
+use rand::RngCore;
+
+/// a random value below the snark scalar field
+pub fn random_field_element() -> Uint256 {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    Uint256::from_be_bytes(bytes) % snark_scalar_field()
+}
+
+/// A MACI keypair. The private key is a number below the snark scalar field; the baby jubjub
+/// key is derived from its 32 big-endian bytes, as circomlib's `prv2pub` does.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Keypair {
+    pub priv_key: Uint256,
+    pub pub_key: PubKey,
+}
+
+/// An EdDSA-Poseidon signature
+#[derive(Clone, Debug, PartialEq)]
+pub struct Signature {
+    pub r8: [Uint256; 2],
+    pub s: Uint256,
+}
+
+impl Keypair {
+    pub fn new(priv_key: Uint256) -> Result<Self, ClientError> {
+        try_fr_from_uint256(priv_key)?;
+        let pub_key = from_point(&babyjub_key(priv_key).public());
+        Ok(Keypair { priv_key, pub_key })
+    }
+
+    pub fn random() -> Self {
+        Keypair::new(random_field_element()).expect("below the snark scalar field")
+    }
+
+    /// The scalar the public key is a multiple of, `formatPrivKeyForBabyJub` in the circuits
+    pub fn formatted_priv_key(&self) -> Uint256 {
+        from_bigint(&babyjub_key(self.priv_key).scalar_key())
+    }
+
+    /// The ECDH key shared with the owner of `pub_key`
+    pub fn shared_key(&self, pub_key: &PubKey) -> Result<PubKey, ClientError> {
+        let point = to_point(pub_key)?;
+        Ok(from_point(
+            &point.mul_scalar(&babyjub_key(self.priv_key).scalar_key()),
+        ))
+    }
+
+    pub fn sign(&self, hash: Uint256) -> Result<Signature, ClientError> {
+        try_fr_from_uint256(hash)?;
+        let signature = babyjub_key(self.priv_key)
+            .sign(to_bigint(hash))
+            .map_err(|reason| ClientError::Signing { reason })?;
+        Ok(Signature {
+            r8: [from_field(&signature.r_b8.x), from_field(&signature.r_b8.y)],
+            s: from_bigint(&signature.s),
+        })
+    }
+}
+
+/// Check an EdDSA-Poseidon signature of `hash`, invalid points and values fail the check
+pub fn verify_signature(pub_key: &PubKey, hash: Uint256, signature: &Signature) -> bool {
+    let points = to_point(pub_key).and_then(|pub_key| {
+        let r8 = to_point(&PubKey {
+            x: signature.r8[0],
+            y: signature.r8[1],
+        })?;
+        Ok((pub_key, r8))
+    });
+    match points {
+        Ok((pub_key, r_b8)) => verify(
+            pub_key,
+            babyjubjub_rs::Signature {
+                r_b8,
+                s: to_bigint(signature.s),
+            },
+            to_bigint(hash),
+        ),
+        Err(_) => false,
+    }
+}
+
+fn babyjub_key(priv_key: Uint256) -> PrivateKey {
+    PrivateKey::import(priv_key.to_be_bytes().to_vec()).expect("a 32 byte key")
+}
+
+/// A baby jubjub point, rejecting coordinates outside the field or off the curve
+pub(crate) fn to_point(pub_key: &PubKey) -> Result<Point, ClientError> {
+    let x: Fr = try_fr_from_uint256(pub_key.x)?;
+    let y: Fr = try_fr_from_uint256(pub_key.y)?;
+
+    // 168700 x^2 + y^2 = 1 + 168696 x^2 y^2
+    let (mut x2, mut y2) = (x, y);
+    x2.square();
+    y2.square();
+    let mut lhs = x2;
+    lhs.mul_assign(&Fr::from_str("168700").unwrap());
+    lhs.add_assign(&y2);
+    let mut rhs = x2;
+    rhs.mul_assign(&y2);
+    rhs.mul_assign(&Fr::from_str("168696").unwrap());
+    rhs.add_assign(&Fr::one());
+    if lhs != rhs {
+        return Err(ClientError::InvalidPoint {});
+    }
+
+    Ok(Point {
+        x: to_field(pub_key.x)?,
+        y: to_field(pub_key.y)?,
+    })
+}
+
+pub(crate) fn from_point(point: &Point) -> PubKey {
+    PubKey {
+        x: from_field(&point.x),
+        y: from_field(&point.y),
+    }
+}
+
+/// Move a value into a field type, the fields of `babyjubjub-rs` and `poseidon-rs` are
+/// distinct types of the same field. The snark scalar field is also the base field of baby
+/// jubjub, values at or above it are rejected
+pub(crate) fn to_field<F: PrimeField>(value: Uint256) -> Result<F, ClientError> {
+    let value = ensure_in_snark_field(value)?;
+    F::from_str(&value.to_string()).ok_or(ClientError::Core(CoreError::InvalidPrimeField {}))
+}
+
+pub(crate) fn from_field<F: PrimeField>(value: &F) -> Uint256 {
+    let mut bytes = Vec::with_capacity(32);
+    value
+        .into_repr()
+        .write_be(&mut bytes)
+        .expect("writing to a vec");
+    Uint256::from_be_bytes(bytes.try_into().expect("a 32 byte field element"))
+}
+
+pub(crate) fn to_bigint(value: Uint256) -> BigInt {
+    BigInt::from_bytes_be(Sign::Plus, &value.to_be_bytes())
+}
+
+pub(crate) fn from_bigint(value: &BigInt) -> Uint256 {
+    let (_, bytes) = value.to_bytes_be();
+    let mut out = [0u8; 32];
+    out[32 - bytes.len()..].copy_from_slice(&bytes);
+    Uint256::from_be_bytes(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coordinator_key_matches_the_circuit_fixture() {
+        let inputs = crate::fixture::msg_test();
+        let coordinator = Keypair::new(crate::fixture::uint(&inputs["coordPrivKey"])).unwrap();
+        assert_eq!(
+            coordinator.pub_key,
+            crate::fixture::pub_key(&inputs["coordPubKey"])
+        );
+    }
+
+    #[test]
+    fn shared_keys_agree() {
+        let voter = Keypair::new(Uint256::from_u128(12345)).unwrap();
+        let coordinator = Keypair::new(Uint256::from_u128(67890)).unwrap();
+        assert_eq!(
+            voter.shared_key(&coordinator.pub_key).unwrap(),
+            coordinator.shared_key(&voter.pub_key).unwrap()
+        );
+        assert_ne!(voter.pub_key, coordinator.pub_key);
+    }
+
+    #[test]
+    fn signatures_verify_only_for_the_signed_hash_and_key() {
+        let voter = Keypair::random();
+        let hash = Uint256::from_u128(42);
+        let signature = voter.sign(hash).unwrap();
+        assert!(verify_signature(&voter.pub_key, hash, &signature));
+        assert!(!verify_signature(
+            &voter.pub_key,
+            Uint256::from_u128(43),
+            &signature
+        ));
+        assert!(!verify_signature(
+            &Keypair::random().pub_key,
+            hash,
+            &signature
+        ));
+    }
+
+    #[test]
+    fn points_off_the_curve_are_rejected() {
+        let mut pub_key = Keypair::random().pub_key;
+        pub_key.y += Uint256::one();
+        assert_eq!(
+            Keypair::random().shared_key(&pub_key).unwrap_err(),
+            ClientError::InvalidPoint {}
+        );
+    }
+
+    #[test]
+    fn values_outside_the_field_are_rejected() {
+        let out_of_field = ClientError::Core(CoreError::InvalidPrimeField {});
+        assert_eq!(
+            Keypair::new(snark_scalar_field()).unwrap_err(),
+            out_of_field
+        );
+
+        let voter = Keypair::random();
+        assert_eq!(voter.sign(snark_scalar_field()).unwrap_err(), out_of_field);
+        // the same point, were the coordinates reduced
+        let mut pub_key = Keypair::random().pub_key;
+        pub_key.x += snark_scalar_field();
+        assert_eq!(voter.shared_key(&pub_key).unwrap_err(), out_of_field);
+    }
+}
@@ -0,0 +1,21 @@
+//! Voter side of a MACI round: keys, signed commands and the encrypted messages the round
+//! contracts take in `PublishMessage`, `PublishDeactivateMessage` and `AddNewKey`.
+//!
+//! A message is a packed [`Command`] with the voter's new key and an EdDSA-Poseidon signature,
+//! Poseidon encrypted with the ECDH key of a fresh ephemeral keypair and the coordinator key.
+//! The ephemeral public key is published as `enc_pub_key`.
+
+pub mod add_key;
+pub mod cipher;
+pub mod error;
+#[cfg(test)]
+mod fixture;
+pub mod keys;
+pub mod message;
+
+pub use crate::add_key::{add_new_key_input, AddNewKeyInput};
+pub use crate::error::ClientError;
+pub use crate::keys::{verify_signature, Keypair, Signature};
+pub use crate::message::{
+    build_message, decrypt_message, message_hash, Command, DecryptedMessage, EncryptedMessage,
+};
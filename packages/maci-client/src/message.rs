@@ -0,0 +1,301 @@
+use crate::cipher::{decrypt, encrypt};
+use crate::error::ClientError;
+use crate::keys::{to_field, verify_signature, Keypair, Signature};
+use cosmwasm_std::Uint256;
+use maci_core::utils::{hash, try_hash2, try_hash5};
+use maci_core::{MessageData, PubKey};
+use rand::Rng;
+
+/// A vote or key change, packed as the circuits unpack it:
+/// `nonce | state_index << 32 | vote_option_index << 64 | new_votes << 96 | salt << 192`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Command {
+    pub nonce: u32,
+    pub state_index: u32,
+    pub vote_option_index: u32,
+    /// at most 96 bits
+    pub new_votes: u128,
+    /// at most 56 bits
+    pub salt: u64,
+    /// the key of the voter after this command, `[0, 0]` for a deactivation
+    pub new_pub_key: PubKey,
+}
+
+/// A message ready for `PublishMessage` or `PublishDeactivateMessage`
+#[derive(Clone, Debug, PartialEq)]
+pub struct EncryptedMessage {
+    pub message: MessageData,
+    pub enc_pub_key: PubKey,
+}
+
+/// A message as the coordinator reads it
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecryptedMessage {
+    pub command: Command,
+    pub signature: Signature,
+}
+
+impl Command {
+    /// A vote with a random salt. Nonces start at 1 and the last one a voter sends counts
+    pub fn vote(
+        state_index: u32,
+        vote_option_index: u32,
+        new_votes: u128,
+        nonce: u32,
+        new_pub_key: PubKey,
+    ) -> Self {
+        Command {
+            nonce,
+            state_index,
+            vote_option_index,
+            new_votes,
+            salt: random_salt(),
+            new_pub_key,
+        }
+    }
+
+    /// The command of a deactivate message, which clears the key of `state_index`
+    pub fn deactivate(state_index: u32) -> Self {
+        Command {
+            nonce: 0,
+            state_index,
+            vote_option_index: 0,
+            new_votes: 0,
+            salt: random_salt(),
+            new_pub_key: PubKey {
+                x: Uint256::zero(),
+                y: Uint256::zero(),
+            },
+        }
+    }
+
+    pub fn pack(&self) -> Result<Uint256, ClientError> {
+        if self.new_votes >> 96 != 0 {
+            return Err(ClientError::FieldTooLarge {
+                field: "new_votes".to_string(),
+                bits: 96,
+            });
+        }
+        if self.salt >> 56 != 0 {
+            return Err(ClientError::FieldTooLarge {
+                field: "salt".to_string(),
+                bits: 56,
+            });
+        }
+        Ok(Uint256::from(self.nonce)
+            + (Uint256::from(self.state_index) << 32)
+            + (Uint256::from(self.vote_option_index) << 64)
+            + (Uint256::from(self.new_votes) << 96)
+            + (Uint256::from(self.salt) << 192))
+    }
+
+    pub fn unpack(packed: Uint256, new_pub_key: PubKey) -> Self {
+        let bits = |shift: u32, len: u32| -> String {
+            ((packed >> shift) % (Uint256::one() << len)).to_string()
+        };
+        Command {
+            nonce: bits(0, 32).parse().unwrap(),
+            state_index: bits(32, 32).parse().unwrap(),
+            vote_option_index: bits(64, 32).parse().unwrap(),
+            new_votes: bits(96, 96).parse().unwrap(),
+            salt: bits(192, 56).parse().unwrap(),
+            new_pub_key,
+        }
+    }
+
+    /// The value the voter signs, `poseidon([packed, new_pub_key.x, new_pub_key.y])`
+    pub fn hash(&self) -> Result<Uint256, ClientError> {
+        Ok(hash(vec![
+            to_field(self.pack()?)?,
+            to_field(self.new_pub_key.x)?,
+            to_field(self.new_pub_key.y)?,
+        ]))
+    }
+}
+
+impl DecryptedMessage {
+    /// Whether the command was signed by `pub_key`, the key of its state leaf
+    pub fn is_signed_by(&self, pub_key: &PubKey) -> Result<bool, ClientError> {
+        Ok(verify_signature(
+            pub_key,
+            self.command.hash()?,
+            &self.signature,
+        ))
+    }
+}
+
+/// Sign `command` and encrypt it for the coordinator with a fresh ephemeral key
+pub fn build_message(
+    voter: &Keypair,
+    coordinator: &PubKey,
+    command: &Command,
+) -> Result<EncryptedMessage, ClientError> {
+    build_message_with_key(voter, coordinator, command, &Keypair::random())
+}
+
+/// `build_message` with a given ephemeral key
+pub fn build_message_with_key(
+    voter: &Keypair,
+    coordinator: &PubKey,
+    command: &Command,
+    enc_key: &Keypair,
+) -> Result<EncryptedMessage, ClientError> {
+    let signature = voter.sign(command.hash()?)?;
+    let plaintext = [
+        command.pack()?,
+        command.new_pub_key.x,
+        command.new_pub_key.y,
+        signature.r8[0],
+        signature.r8[1],
+        signature.s,
+    ];
+    let ciphertext = encrypt(
+        &plaintext,
+        &enc_key.shared_key(coordinator)?,
+        Uint256::zero(),
+    )?;
+    Ok(EncryptedMessage {
+        message: MessageData {
+            data: ciphertext.try_into().expect("6 elements encrypt to 7"),
+        },
+        enc_pub_key: enc_key.pub_key.clone(),
+    })
+}
+
+/// Decrypt a published message with the coordinator key
+pub fn decrypt_message(
+    coordinator: &Keypair,
+    message: &EncryptedMessage,
+) -> Result<DecryptedMessage, ClientError> {
+    let plaintext = decrypt(
+        &message.message.data,
+        &coordinator.shared_key(&message.enc_pub_key)?,
+        Uint256::zero(),
+        6,
+    )?;
+    let new_pub_key = PubKey {
+        x: plaintext[1],
+        y: plaintext[2],
+    };
+    Ok(DecryptedMessage {
+        command: Command::unpack(plaintext[0], new_pub_key),
+        signature: Signature {
+            r8: [plaintext[3], plaintext[4]],
+            s: plaintext[5],
+        },
+    })
+}
+
+/// The next hash of the message chain, the same as the contracts' `hash_message_and_enc_pub_key`
+pub fn message_hash(
+    message: &EncryptedMessage,
+    prev_hash: Uint256,
+) -> Result<Uint256, ClientError> {
+    let data = message.message.data;
+    let m_hash = try_hash5([data[0], data[1], data[2], data[3], data[4]])?;
+    let n_hash = try_hash5([
+        data[5],
+        data[6],
+        message.enc_pub_key.x,
+        message.enc_pub_key.y,
+        prev_hash,
+    ])?;
+    Ok(try_hash2([m_hash, n_hash])?)
+}
+
+fn random_salt() -> u64 {
+    rand::thread_rng().gen_range(0..1u64 << 56)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture;
+
+    #[test]
+    fn commands_pack_and_unpack() {
+        let command = Command::vote(3, 2, (1u128 << 96) - 1, 7, Keypair::random().pub_key);
+        let packed = command.pack().unwrap();
+        assert_eq!(
+            Command::unpack(packed, command.new_pub_key.clone()),
+            command
+        );
+
+        let too_many_votes = Command {
+            new_votes: 1u128 << 96,
+            ..command
+        };
+        assert_eq!(
+            too_many_votes.pack().unwrap_err(),
+            ClientError::FieldTooLarge {
+                field: "new_votes".to_string(),
+                bits: 96,
+            }
+        );
+    }
+
+    #[test]
+    fn coordinator_reads_the_signed_command() {
+        let voter = Keypair::random();
+        let coordinator = Keypair::random();
+        let command = Command::vote(1, 4, 25, 1, voter.pub_key.clone());
+        let message = build_message(&voter, &coordinator.pub_key, &command).unwrap();
+
+        let decrypted = decrypt_message(&coordinator, &message).unwrap();
+        assert_eq!(decrypted.command, command);
+        assert!(decrypted.is_signed_by(&voter.pub_key).unwrap());
+        assert!(!decrypted.is_signed_by(&coordinator.pub_key).unwrap());
+
+        assert_eq!(
+            decrypt_message(&voter, &message).unwrap_err(),
+            ClientError::DecryptionFailed {}
+        );
+    }
+
+    #[test]
+    fn deactivate_clears_the_key() {
+        let voter = Keypair::random();
+        let coordinator = Keypair::random();
+        let message = build_message(&voter, &coordinator.pub_key, &Command::deactivate(5)).unwrap();
+        let decrypted = decrypt_message(&coordinator, &message).unwrap();
+        assert_eq!(decrypted.command.state_index, 5);
+        assert_eq!(decrypted.command.new_pub_key.x, Uint256::zero());
+        assert!(decrypted.is_signed_by(&voter.pub_key).unwrap());
+    }
+
+    #[test]
+    fn fixture_messages_decrypt_to_signed_commands() {
+        let inputs = fixture::msg_test();
+        let coordinator = Keypair::new(fixture::uint(&inputs["coordPrivKey"])).unwrap();
+        let state_leaves = inputs["currentStateLeaves"].as_array().unwrap();
+        let vote_options = inputs["currentVoteWeights"].as_array().unwrap().len() as u32;
+
+        let mut decrypted_count = 0;
+        let msgs = inputs["msgs"].as_array().unwrap();
+        let enc_pub_keys = inputs["encPubKeys"].as_array().unwrap();
+        for (msg, enc_pub_key) in msgs.iter().zip(enc_pub_keys) {
+            let enc_pub_key = fixture::pub_key(enc_pub_key);
+            // the batch is padded with empty messages
+            if enc_pub_key.x.is_zero() && enc_pub_key.y.is_zero() {
+                continue;
+            }
+            let data: Vec<Uint256> = msg.as_array().unwrap().iter().map(fixture::uint).collect();
+            let message = EncryptedMessage {
+                message: MessageData {
+                    data: data.try_into().unwrap(),
+                },
+                enc_pub_key,
+            };
+
+            let decrypted = decrypt_message(&coordinator, &message).unwrap();
+            let command = &decrypted.command;
+            let state_leaf = &state_leaves[command.state_index as usize];
+            assert!(command.vote_option_index < vote_options);
+            assert!(decrypted
+                .is_signed_by(&fixture::pub_key(state_leaf))
+                .unwrap());
+            decrypted_count += 1;
+        }
+        assert_eq!(decrypted_count, 3);
+    }
+}
@@ -0,0 +1,82 @@
+use pairing_ce::bn256::{
+    G1Affine, G1Compressed, G1Uncompressed, G2Affine, G2Compressed, G2Uncompressed,
+};
+use pairing_ce::EncodedPoint;
+
+pub const G1_UNCOMPRESSED_BYTES: usize = 64;
+pub const G1_COMPRESSED_BYTES: usize = 32;
+pub const G2_UNCOMPRESSED_BYTES: usize = 128;
+pub const G2_COMPRESSED_BYTES: usize = 64;
+
+/// decode a G1 point from its uncompressed (x || y) or compressed (flagged x) encoding,
+/// `into_affine` rejects points that are off the curve or outside the prime order subgroup
+pub fn decode_g1(bytes: &[u8]) -> Option<G1Affine> {
+    match bytes.len() {
+        G1_UNCOMPRESSED_BYTES => {
+            let mut arr = [0u8; G1_UNCOMPRESSED_BYTES];
+            arr.copy_from_slice(bytes);
+            G1Uncompressed::from_fixed_bytes(arr).into_affine().ok()
+        }
+        G1_COMPRESSED_BYTES => {
+            let mut arr = [0u8; G1_COMPRESSED_BYTES];
+            arr.copy_from_slice(bytes);
+            G1Compressed::from_fixed_bytes(arr).into_affine().ok()
+        }
+        _ => None,
+    }
+}
+
+/// decode a G2 point from its uncompressed (x.c1 || x.c0 || y.c1 || y.c0) or compressed
+/// (flagged x.c1 || x.c0) encoding, the twist has a large cofactor so the subgroup check matters
+pub fn decode_g2(bytes: &[u8]) -> Option<G2Affine> {
+    match bytes.len() {
+        G2_UNCOMPRESSED_BYTES => {
+            let mut arr = [0u8; G2_UNCOMPRESSED_BYTES];
+            arr.copy_from_slice(bytes);
+            G2Uncompressed::from_fixed_bytes(arr).into_affine().ok()
+        }
+        G2_COMPRESSED_BYTES => {
+            let mut arr = [0u8; G2_COMPRESSED_BYTES];
+            arr.copy_from_slice(bytes);
+            G2Compressed::from_fixed_bytes(arr).into_affine().ok()
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pairing_ce::CurveAffine;
+
+    const POINT_A: &str = "27fb48285bc59bc74c9197857856cf5f3dcce55f22b83589e399240b8469e45725c5495e3ebcdd3bc04620fd13fed113c31d19a685f7f037daf02dde02d26e4f";
+    const POINT_B: &str = "0d1bd72809defb6e85ea48de4c28e9ec9dcd2bc5111acdb66b5cdb38ccf6d4e32bdeac48a806c2fd6cef8e09bfde1983961693c8d4a513777ba26b07f2abacba1efb7600f04e786d93f321c6df732eb0043548cfe12fa8a5aea848a500ef5b9728dbc747fc76993c16dadf2c8ef68f3d757afa6d4caf9a767c424ec0d7ff4932";
+
+    #[test]
+    fn compressed_points_decode_to_the_uncompressed_ones() {
+        let a = decode_g1(&hex::decode(POINT_A).unwrap()).unwrap();
+        let b = decode_g2(&hex::decode(POINT_B).unwrap()).unwrap();
+
+        assert_eq!(decode_g1(a.into_compressed().as_ref()), Some(a));
+        assert_eq!(decode_g2(b.into_compressed().as_ref()), Some(b));
+        assert_eq!(
+            decode_g1(G1Affine::zero().into_compressed().as_ref()),
+            Some(G1Affine::zero())
+        );
+    }
+
+    #[test]
+    fn g2_point_outside_the_subgroup_is_rejected() {
+        // x = 1 is on the twist y^2 = x^3 + 3 / (9 + u), but not in the prime order subgroup
+        let mut x = [0u8; G2_COMPRESSED_BYTES];
+        x[G2_COMPRESSED_BYTES - 1] = 1;
+        assert_eq!(decode_g2(&x), None);
+    }
+
+    #[test]
+    fn other_lengths_are_rejected() {
+        let a = hex::decode(POINT_A).unwrap();
+        assert_eq!(decode_g1(&a[..63]), None);
+        assert_eq!(decode_g2(&a[..32]), None);
+    }
+}
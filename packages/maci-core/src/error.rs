@@ -0,0 +1,28 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum CoreError {
+    #[error("Error verification")]
+    ErrorVerificationKey {},
+
+    #[error("Error proof")]
+    ErrorProof {},
+
+    #[error("could not convert into prime field")]
+    InvalidPrimeField {},
+
+    #[error("invalid hex format")]
+    InvalidHex {},
+
+    #[error("hex value of {len} digits does not fit in 32 bytes")]
+    HexTooLong { len: usize },
+
+    #[error("invalid decimal number")]
+    InvalidDecimal {},
+
+    #[error("{leaves} leaves do not fit in a quinary tree of depth {depth}")]
+    TreeOverflow { depth: u32, leaves: u64 },
+
+    #[error("leaf {index} is outside a quinary tree of depth {depth}")]
+    LeafIndexOutOfRange { depth: u32, index: u64 },
+}
@@ -0,0 +1,350 @@
+use crate::curve::{decode_g1, decode_g2};
+use crate::error::CoreError;
+use crate::types::{Groth16ProofStr, Groth16VkeyStr, SnarkjsProof, SnarkjsVkey};
+use crate::utils::ensure_in_snark_field;
+use bellman_ce_verifier::{Proof, VerifyingKey};
+use cosmwasm_std::{ensure, Uint256};
+use ff_ce::PrimeField;
+use pairing_ce::bn256::{Fr, G1Affine, G2Affine};
+use pairing_ce::{CurveAffine, Engine};
+use std::str::FromStr;
+
+/// convert the proof into the affine type, which will be used to verify. The points may use
+/// the uncompressed (64/128 bytes) or compressed (32/64 bytes) encoding
+pub fn parse_groth16_proof<E>(pof: Groth16ProofStr) -> Result<Proof<E>, CoreError>
+where
+    E: Engine<G1Affine = G1Affine, G2Affine = G2Affine>,
+{
+    Ok(Proof {
+        a: decode_g1(&pof.pi_a).ok_or(CoreError::ErrorProof {})?,
+        b: decode_g2(&pof.pi_b).ok_or(CoreError::ErrorProof {})?,
+        c: decode_g1(&pof.pi_c).ok_or(CoreError::ErrorProof {})?,
+    })
+}
+
+/// convert the verification key into the affine type, which will be used in verification.
+/// The points may use the uncompressed or compressed encoding
+pub fn parse_groth16_vkey<E>(vk: Groth16VkeyStr) -> Result<VerifyingKey<E>, CoreError>
+where
+    E: Engine<G1Affine = G1Affine, G2Affine = G2Affine>,
+{
+    let g1 = |bytes: &[u8]| decode_g1(bytes).ok_or(CoreError::ErrorVerificationKey {});
+    let g2 = |bytes: &[u8]| decode_g2(bytes).ok_or(CoreError::ErrorVerificationKey {});
+
+    // return verification key
+    Ok(VerifyingKey {
+        alpha_g1: g1(&vk.alpha_1)?,
+        beta_g1: G1Affine::zero(),
+        beta_g2: g2(&vk.beta_2)?,
+        gamma_g2: g2(&vk.gamma_2)?,
+        delta_g1: G1Affine::zero(),
+        delta_g2: g2(&vk.delta_2)?,
+        ic: vec![g1(&vk.ic0)?, g1(&vk.ic1)?],
+    })
+}
+
+/// convert a public signal into the scalar field of the proof, values at or above the field
+/// modulus are rejected
+pub fn parse_public_input(input: Uint256) -> Result<Fr, CoreError> {
+    let input = ensure_in_snark_field(input)?;
+    <Fr as PrimeField>::from_str(&input.to_string()).ok_or(CoreError::InvalidPrimeField {})
+}
+
+/// convert a snarkjs `proof.json` into the uncompressed points read by `parse_groth16_proof`
+pub fn parse_snarkjs_proof(proof: &SnarkjsProof) -> Result<Groth16ProofStr, CoreError> {
+    ensure!(
+        is_groth16_bn128(&proof.protocol, &proof.curve),
+        CoreError::ErrorProof {}
+    );
+
+    Ok(Groth16ProofStr {
+        pi_a: snarkjs_g1(&proof.pi_a).ok_or(CoreError::ErrorProof {})?,
+        pi_b: snarkjs_g2(&proof.pi_b).ok_or(CoreError::ErrorProof {})?,
+        pi_c: snarkjs_g1(&proof.pi_c).ok_or(CoreError::ErrorProof {})?,
+    })
+}
+
+/// convert a snarkjs `verification_key.json` into the uncompressed points read by
+/// `parse_groth16_vkey`, the circuits have a single public input
+pub fn parse_snarkjs_vkey(vk: &SnarkjsVkey) -> Result<Groth16VkeyStr, CoreError> {
+    ensure!(
+        is_groth16_bn128(&vk.protocol, &vk.curve),
+        CoreError::ErrorVerificationKey {}
+    );
+    ensure!(
+        vk.ic.len() == 2 && vk.n_public.unwrap_or(1) == 1,
+        CoreError::ErrorVerificationKey {}
+    );
+
+    let g1 = |point: &[String]| snarkjs_g1(point).ok_or(CoreError::ErrorVerificationKey {});
+    let g2 = |point: &[Vec<String>]| snarkjs_g2(point).ok_or(CoreError::ErrorVerificationKey {});
+    Ok(Groth16VkeyStr {
+        alpha_1: g1(&vk.vk_alpha_1)?,
+        beta_2: g2(&vk.vk_beta_2)?,
+        gamma_2: g2(&vk.vk_gamma_2)?,
+        delta_2: g2(&vk.vk_delta_2)?,
+        ic0: g1(&vk.ic[0])?,
+        ic1: g1(&vk.ic[1])?,
+    })
+}
+
+fn is_groth16_bn128(protocol: &Option<String>, curve: &Option<String>) -> bool {
+    protocol.as_deref().unwrap_or("groth16") == "groth16"
+        && curve.as_deref().unwrap_or("bn128") == "bn128"
+}
+
+fn decimal_to_bytes(value: &str) -> Option<[u8; 32]> {
+    Uint256::from_str(value).ok().map(|v| v.to_be_bytes())
+}
+
+// [x, y, "1"] -> x || y
+fn snarkjs_g1(point: &[String]) -> Option<Vec<u8>> {
+    if point.len() != 3 || point[2] != "1" {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(64);
+    for coordinate in &point[..2] {
+        bytes.extend_from_slice(&decimal_to_bytes(coordinate)?);
+    }
+    Some(bytes)
+}
+
+// [[x.c0, x.c1], [y.c0, y.c1], ["1", "0"]] -> x.c1 || x.c0 || y.c1 || y.c0
+fn snarkjs_g2(point: &[Vec<String>]) -> Option<Vec<u8>> {
+    if point.len() != 3 || point[2] != ["1", "0"] {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(128);
+    for coordinate in &point[..2] {
+        if coordinate.len() != 2 {
+            return None;
+        }
+        bytes.extend_from_slice(&decimal_to_bytes(&coordinate[1])?);
+        bytes.extend_from_slice(&decimal_to_bytes(&coordinate[0])?);
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pairing_ce::bn256::Bn256;
+
+    #[test]
+    fn short_proof_is_rejected() {
+        let proof = Groth16ProofStr {
+            pi_a: vec![0; 63],
+            pi_b: vec![0; 128],
+            pi_c: vec![0; 64],
+        };
+        assert_eq!(
+            parse_groth16_proof::<Bn256>(proof).err(),
+            Some(CoreError::ErrorProof {})
+        );
+    }
+
+    #[test]
+    fn short_vkey_is_rejected() {
+        let vkey = Groth16VkeyStr {
+            alpha_1: vec![0; 64],
+            beta_2: vec![0; 128],
+            gamma_2: vec![0; 128],
+            delta_2: vec![0; 128],
+            ic0: vec![0; 64],
+            ic1: vec![0; 10],
+        };
+        assert_eq!(
+            parse_groth16_vkey::<Bn256>(vkey).err(),
+            Some(CoreError::ErrorVerificationKey {})
+        );
+    }
+
+    const PROOF_A: &str = "27fb48285bc59bc74c9197857856cf5f3dcce55f22b83589e399240b8469e45725c5495e3ebcdd3bc04620fd13fed113c31d19a685f7f037daf02dde02d26e4f";
+    const PROOF_B: &str = "0d1bd72809defb6e85ea48de4c28e9ec9dcd2bc5111acdb66b5cdb38ccf6d4e32bdeac48a806c2fd6cef8e09bfde1983961693c8d4a513777ba26b07f2abacba1efb7600f04e786d93f321c6df732eb0043548cfe12fa8a5aea848a500ef5b9728dbc747fc76993c16dadf2c8ef68f3d757afa6d4caf9a767c424ec0d7ff4932";
+    const PROOF_C: &str = "2062c6bee5dad15af1ebcb0e623b27f7d29775774cc92b2a7554d1801af818940309fa215204181d3a1fef15d162aa779b8900e2b84d8b8fa22a20b65652eb46";
+
+    fn snarkjs_proof() -> SnarkjsProof {
+        cosmwasm_std::from_json(
+            r#"{
+                "pi_a": [
+                    "18084177721806309187607675391141355414187478666321496301613428455112383784023",
+                    "17084150637667556812073496166504684163366858755866301199872395346920288185935",
+                    "1"
+                ],
+                "pi_b": [
+                    [
+                        "19842881596644310626949946488147867718536008378204485813950848448624583224506",
+                        "5929256857233446246154085410432506346938350733267844843581580272689493759203"
+                    ],
+                    [
+                        "18480828838783832516625498054333577134745242080277255885508697646053392861490",
+                        "14013678502133518264170655266449753514257834825042452375553798621048358263703"
+                    ],
+                    ["1", "0"]
+                ],
+                "pi_c": [
+                    "14648533859385868593892904452616170292936195080249016877701103961338505861268",
+                    "1374566504235034452357431652743167964433836478256198273150337944389921467206",
+                    "1"
+                ],
+                "protocol": "groth16",
+                "curve": "bn128"
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn public_input_must_be_in_the_scalar_field() {
+        let modulus = Uint256::from_str(
+            "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+        )
+        .unwrap();
+        assert!(parse_public_input(modulus - Uint256::one()).is_ok());
+        assert_eq!(
+            parse_public_input(modulus).err(),
+            Some(CoreError::InvalidPrimeField {})
+        );
+    }
+
+    #[test]
+    fn compressed_proof_matches_uncompressed() {
+        let uncompressed = parse_groth16_proof::<Bn256>(Groth16ProofStr {
+            pi_a: hex::decode(PROOF_A).unwrap(),
+            pi_b: hex::decode(PROOF_B).unwrap(),
+            pi_c: hex::decode(PROOF_C).unwrap(),
+        })
+        .unwrap();
+        let compressed = Groth16ProofStr {
+            pi_a: uncompressed.a.into_compressed().as_ref().to_vec(),
+            pi_b: uncompressed.b.into_compressed().as_ref().to_vec(),
+            pi_c: uncompressed.c.into_compressed().as_ref().to_vec(),
+        };
+        assert_eq!(
+            (
+                compressed.pi_a.len(),
+                compressed.pi_b.len(),
+                compressed.pi_c.len()
+            ),
+            (32, 64, 32)
+        );
+
+        let proof = parse_groth16_proof::<Bn256>(compressed).unwrap();
+        assert_eq!(proof.a, uncompressed.a);
+        assert_eq!(proof.b, uncompressed.b);
+        assert_eq!(proof.c, uncompressed.c);
+    }
+
+    #[test]
+    fn snarkjs_proof_matches_uncompressed_hex() {
+        let proof = parse_snarkjs_proof(&snarkjs_proof()).unwrap();
+        assert_eq!(
+            proof,
+            Groth16ProofStr {
+                pi_a: hex::decode(PROOF_A).unwrap(),
+                pi_b: hex::decode(PROOF_B).unwrap(),
+                pi_c: hex::decode(PROOF_C).unwrap(),
+            }
+        );
+        assert!(parse_groth16_proof::<Bn256>(proof).is_ok());
+    }
+
+    #[test]
+    fn snarkjs_proof_must_be_affine_groth16() {
+        let mut proof = snarkjs_proof();
+        proof.pi_a[2] = "2".to_string();
+        assert_eq!(parse_snarkjs_proof(&proof), Err(CoreError::ErrorProof {}));
+
+        let mut proof = snarkjs_proof();
+        proof.protocol = Some("plonk".to_string());
+        assert_eq!(parse_snarkjs_proof(&proof), Err(CoreError::ErrorProof {}));
+
+        let mut proof = snarkjs_proof();
+        proof.pi_c[0] = "0x1".to_string();
+        assert_eq!(parse_snarkjs_proof(&proof), Err(CoreError::ErrorProof {}));
+    }
+
+    #[test]
+    fn snarkjs_vkey_matches_uncompressed_hex() {
+        let vkey: SnarkjsVkey = cosmwasm_std::from_json(
+            r#"{
+                "protocol": "groth16",
+                "curve": "bn128",
+                "nPublic": 1,
+                "vk_alpha_1": [
+                    "20491192805390485299153009773594534940189261866228447918068658471970481763042",
+                    "9383485363053290200918347156157836566562967994039712273449902621266178545958",
+                    "1"
+                ],
+                "vk_beta_2": [
+                    [
+                        "6375614351688725206403948262868962793625744043794305715222011528459656738731",
+                        "4252822878758300859123897981450591353533073413197771768651442665752259397132"
+                    ],
+                    [
+                        "10505242626370262277552901082094356697409835680220590971873171140371331206856",
+                        "21847035105528745403288232691147584728191162732299865338377159692350059136679"
+                    ],
+                    ["1", "0"]
+                ],
+                "vk_gamma_2": [
+                    [
+                        "10857046999023057135944570762232829481370756359578518086990519993285655852781",
+                        "11559732032986387107991004021392285783925812861821192530917403151452391805634"
+                    ],
+                    [
+                        "8495653923123431417604973247489272438418190587263600148770280649306958101930",
+                        "4082367875863433681332203403145435568316851327593401208105741076214120093531"
+                    ],
+                    ["1", "0"]
+                ],
+                "vk_delta_2": [
+                    [
+                        "10857046999023057135944570762232829481370756359578518086990519993285655852781",
+                        "11559732032986387107991004021392285783925812861821192530917403151452391805634"
+                    ],
+                    [
+                        "8495653923123431417604973247489272438418190587263600148770280649306958101930",
+                        "4082367875863433681332203403145435568316851327593401208105741076214120093531"
+                    ],
+                    ["1", "0"]
+                ],
+                "IC": [
+                    [
+                        "7052121773672646096389440442176521233740406138724651911130743631240396062473",
+                        "17917920539070359635822458078016316223428522402380763492482327906005517915720",
+                        "1"
+                    ],
+                    [
+                        "6181190977919478072750002902826451507542848441774366861251191614121364303178",
+                        "943373884031677733447048670245703708313144273638184542656590682244831707827",
+                        "1"
+                    ]
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let generator = "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c21800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa";
+        let parsed = parse_snarkjs_vkey(&vkey).unwrap();
+        assert_eq!(
+            parsed,
+            Groth16VkeyStr {
+                alpha_1: hex::decode("2d4d9aa7e302d9df41749d5507949d05dbea33fbb16c643b22f599a2be6df2e214bedd503c37ceb061d8ec60209fe345ce89830a19230301f076caff004d1926").unwrap(),
+                beta_2: hex::decode("0967032fcbf776d1afc985f88877f182d38480a653f2decaa9794cbc3bf3060c0e187847ad4c798374d0d6732bf501847dd68bc0e071241e0213bc7fc13db7ab304cfbd1e08a704a99f5e847d93f8c3caafddec46b7a0d379da69a4d112346a71739c1b1a457a8c7313123d24d2f9192f896b7c63eea05a9d57f06547ad0cec8").unwrap(),
+                gamma_2: hex::decode(generator).unwrap(),
+                delta_2: hex::decode(generator).unwrap(),
+                ic0: hex::decode("0f975c069541a0ec7e3ff282873f24fad44b0afd2061ef661da90a759ed8bf09279d2f02b5e22f35123aab12df022240bd22bba246a4c6f85b2d02731364d648").unwrap(),
+                ic1: hex::decode("0daa6e1bf0504c4eae7b692bb9632cae53ece0539542e94927f97193f30a2d4a0215ee422167418c64f01262acc75a7e61bb135e1a132700ad4f5b3db15302b3").unwrap(),
+            }
+        );
+        assert!(parse_groth16_vkey::<Bn256>(parsed).is_ok());
+
+        let mut vkey = vkey;
+        vkey.ic.pop();
+        assert_eq!(
+            parse_snarkjs_vkey(&vkey),
+            Err(CoreError::ErrorVerificationKey {})
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use crate::error::CoreError;
+use crate::types::Groth16PreparedVkeyStr;
+use bellman_ce_verifier::{Proof, VerifyingKey};
+use cosmwasm_std::ensure;
+use ff_ce::{PrimeField, PrimeFieldRepr};
+use pairing_ce::bn256::{
+    Bn256, Fq, Fq12, Fq2, Fq6, FqRepr, Fr, G1Affine, G1Uncompressed, G2Affine, G2Uncompressed,
+};
+use pairing_ce::{CurveAffine, CurveProjective, EncodedPoint, Engine};
+
+const FQ_BYTES: usize = 32;
+const FQ12_BYTES: usize = 12 * FQ_BYTES;
+
+/// precompute everything of the verification that only depends on the verifying key
+pub fn prepare_groth16_vkey(vk: &VerifyingKey<Bn256>) -> Groth16PreparedVkeyStr {
+    let mut neg_gamma_g2 = vk.gamma_g2;
+    neg_gamma_g2.negate();
+    let mut neg_delta_g2 = vk.delta_g2;
+    neg_delta_g2.negate();
+
+    Groth16PreparedVkeyStr {
+        alpha_g1_beta_g2: fq12_to_bytes(&Bn256::pairing(vk.alpha_g1, vk.beta_g2)),
+        neg_gamma_g2: neg_gamma_g2.into_uncompressed().as_ref().to_vec(),
+        neg_delta_g2: neg_delta_g2.into_uncompressed().as_ref().to_vec(),
+        ic: vk
+            .ic
+            .iter()
+            .map(|ic| ic.into_uncompressed().as_ref().to_vec())
+            .collect(),
+    }
+}
+
+/// same check as `bellman_ce_verifier::verify_proof`, against a key from `prepare_groth16_vkey`
+pub fn verify_groth16_prepared(
+    pvk: &Groth16PreparedVkeyStr,
+    proof: &Proof<Bn256>,
+    public_inputs: &[Fr],
+) -> Result<bool, CoreError> {
+    ensure!(
+        public_inputs.len() + 1 == pvk.ic.len(),
+        CoreError::ErrorVerificationKey {}
+    );
+
+    // the points were validated when the key was prepared, only decode them here
+    let ic = pvk
+        .ic
+        .iter()
+        .map(|ic| g1_from_bytes(ic))
+        .collect::<Result<Vec<G1Affine>, CoreError>>()?;
+    let neg_gamma_g2 = g2_from_bytes(&pvk.neg_gamma_g2)?;
+    let neg_delta_g2 = g2_from_bytes(&pvk.neg_delta_g2)?;
+    let alpha_g1_beta_g2 = fq12_from_bytes(&pvk.alpha_g1_beta_g2)?;
+
+    let mut acc = ic[0].into_projective();
+    for (input, base) in public_inputs.iter().zip(ic.iter().skip(1)) {
+        acc.add_assign(&base.mul(input.into_repr()));
+    }
+
+    let result = Bn256::final_exponentiation(&Bn256::miller_loop(
+        [
+            (&proof.a.prepare(), &proof.b.prepare()),
+            (&acc.into_affine().prepare(), &neg_gamma_g2.prepare()),
+            (&proof.c.prepare(), &neg_delta_g2.prepare()),
+        ]
+        .iter(),
+    ))
+    .ok_or(CoreError::ErrorProof {})?;
+
+    Ok(result == alpha_g1_beta_g2)
+}
+
+fn g1_from_bytes(bytes: &[u8]) -> Result<G1Affine, CoreError> {
+    ensure!(bytes.len() == 64, CoreError::ErrorVerificationKey {});
+    let mut arr: [u8; 64] = [0; 64];
+    arr.copy_from_slice(bytes);
+    G1Uncompressed::from_fixed_bytes(arr)
+        .into_affine_unchecked()
+        .map_err(|_| CoreError::ErrorVerificationKey {})
+}
+
+fn g2_from_bytes(bytes: &[u8]) -> Result<G2Affine, CoreError> {
+    ensure!(bytes.len() == 128, CoreError::ErrorVerificationKey {});
+    let mut arr: [u8; 128] = [0; 128];
+    arr.copy_from_slice(bytes);
+    G2Uncompressed::from_fixed_bytes(arr)
+        .into_affine_unchecked()
+        .map_err(|_| CoreError::ErrorVerificationKey {})
+}
+
+fn fq12_to_bytes(f: &Fq12) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(FQ12_BYTES);
+    for fq6 in [f.c0, f.c1] {
+        for fq2 in [fq6.c0, fq6.c1, fq6.c2] {
+            for fq in [fq2.c0, fq2.c1] {
+                fq.into_repr()
+                    .write_be(&mut bytes)
+                    .expect("writing into a vec never fails");
+            }
+        }
+    }
+    bytes
+}
+
+fn fq12_from_bytes(bytes: &[u8]) -> Result<Fq12, CoreError> {
+    ensure!(
+        bytes.len() == FQ12_BYTES,
+        CoreError::ErrorVerificationKey {}
+    );
+    let fqs = bytes
+        .chunks(FQ_BYTES)
+        .map(|chunk| {
+            let mut repr = FqRepr::default();
+            repr.read_be(chunk)
+                .map_err(|_| CoreError::ErrorVerificationKey {})?;
+            Fq::from_repr(repr).map_err(|_| CoreError::ErrorVerificationKey {})
+        })
+        .collect::<Result<Vec<Fq>, CoreError>>()?;
+
+    let fq2 = |i: usize| Fq2 {
+        c0: fqs[i],
+        c1: fqs[i + 1],
+    };
+    let fq6 = |i: usize| Fq6 {
+        c0: fq2(i),
+        c1: fq2(i + 2),
+        c2: fq2(i + 4),
+    };
+    Ok(Fq12 {
+        c0: fq6(0),
+        c1: fq6(6),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bellman_ce_verifier::{prepare_verifying_key, verify_proof};
+    use ff_ce::Field;
+
+    fn fr(n: u64) -> Fr {
+        Fr::from_str(&n.to_string()).unwrap()
+    }
+
+    fn g1(s: Fr) -> G1Affine {
+        G1Affine::one().mul(s.into_repr()).into_affine()
+    }
+
+    fn g2(s: Fr) -> G2Affine {
+        G2Affine::one().mul(s.into_repr()).into_affine()
+    }
+
+    // vkey built from known scalars, so a valid proof for `input` can be made without a circuit:
+    // a = alpha * beta + (ic0 + input * ic1) * gamma + c * delta, b = 1
+    fn vkey_and_proof(input: Fr) -> (VerifyingKey<Bn256>, Proof<Bn256>) {
+        let (alpha, beta, gamma, delta) = (fr(3), fr(5), fr(7), fr(11));
+        let (ic0, ic1, c) = (fr(13), fr(17), fr(19));
+
+        let mut acc = ic1;
+        acc.mul_assign(&input);
+        acc.add_assign(&ic0);
+        acc.mul_assign(&gamma);
+        let mut a = alpha;
+        a.mul_assign(&beta);
+        a.add_assign(&acc);
+        let mut c_delta = c;
+        c_delta.mul_assign(&delta);
+        a.add_assign(&c_delta);
+
+        let vk = VerifyingKey {
+            alpha_g1: g1(alpha),
+            beta_g1: G1Affine::zero(),
+            beta_g2: g2(beta),
+            gamma_g2: g2(gamma),
+            delta_g1: G1Affine::zero(),
+            delta_g2: g2(delta),
+            ic: vec![g1(ic0), g1(ic1)],
+        };
+        let proof = Proof {
+            a: g1(a),
+            b: G2Affine::one(),
+            c: g1(c),
+        };
+        (vk, proof)
+    }
+
+    #[test]
+    fn prepared_vkey_matches_bellman_verifier() {
+        let (vk, proof) = vkey_and_proof(fr(23));
+        let pvk = prepare_groth16_vkey(&vk);
+
+        assert!(verify_proof(&prepare_verifying_key(&vk), &proof, &[fr(23)]).unwrap());
+        assert_eq!(verify_groth16_prepared(&pvk, &proof, &[fr(23)]), Ok(true));
+        assert_eq!(verify_groth16_prepared(&pvk, &proof, &[fr(24)]), Ok(false));
+    }
+
+    #[test]
+    fn prepared_vkey_survives_serialization() {
+        let (vk, proof) = vkey_and_proof(fr(23));
+        let pvk = prepare_groth16_vkey(&vk);
+        let stored: Groth16PreparedVkeyStr =
+            cosmwasm_std::from_json(cosmwasm_std::to_json_vec(&pvk).unwrap()).unwrap();
+
+        assert_eq!(stored, pvk);
+        assert_eq!(
+            fq12_from_bytes(&stored.alpha_g1_beta_g2),
+            Ok(Bn256::pairing(vk.alpha_g1, vk.beta_g2))
+        );
+        assert_eq!(
+            verify_groth16_prepared(&stored, &proof, &[fr(23)]),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn wrong_number_of_inputs_is_rejected() {
+        let (vk, proof) = vkey_and_proof(fr(23));
+        assert_eq!(
+            verify_groth16_prepared(&prepare_groth16_vkey(&vk), &proof, &[fr(23), fr(1)]),
+            Err(CoreError::ErrorVerificationKey {})
+        );
+    }
+}
@@ -0,0 +1,15 @@
+pub mod curve;
+pub mod error;
+pub mod groth16_parser;
+pub mod groth16_verifier;
+pub mod plonk_parser;
+pub mod quinary_tree;
+pub mod types;
+pub mod utils;
+
+pub use crate::error::CoreError;
+pub use crate::quinary_tree::{IncrementalQuinaryTree, QuinaryProof};
+pub use crate::types::{
+    Groth16PreparedVkeyStr, Groth16ProofStr, Groth16VkeyStr, MessageData, PlonkProofStr,
+    PlonkVkeyStr, PubKey, QuinaryTreeRoot, SnarkjsProof, SnarkjsVkey, StateLeaf,
+};
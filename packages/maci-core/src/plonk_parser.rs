@@ -0,0 +1,156 @@
+use crate::curve::{decode_g1, decode_g2};
+use crate::error::CoreError;
+use crate::types::{PlonkProofStr, PlonkVkeyStr};
+use bellman_ce::plonk::better_cs::cs::PlonkConstraintSystemParams;
+use bellman_ce::plonk::better_cs::keys::{Proof, VerificationKey};
+use cosmwasm_std::ensure;
+use ff_ce::{from_hex, PrimeField};
+use pairing_ce::bn256::{G1Affine, G2Affine};
+use pairing_ce::{CurveAffine, Engine};
+
+/// convert the proof into the Affine/Fr type, which will be used to verify
+pub fn parse_plonk_proof<E, P>(pof: PlonkProofStr) -> Result<Proof<E, P>, CoreError>
+where
+    E: Engine<G1Affine = G1Affine, G2Affine = G2Affine>,
+    P: PlonkConstraintSystemParams<E>,
+{
+    let num_inputs = pof.num_inputs;
+    let n = pof.n;
+    // String -> Fr
+    let input_values = pof.input_values;
+    // Vec<u8> -> G1Affine
+    let wire_commitments = pof.wire_commitments;
+    // Vec<u8> -> G1Affine
+    let grand_product_commitment = pof.grand_product_commitment;
+    // Vec<u8> -> G1Affine
+    let quotient_poly_commitments = pof.quotient_poly_commitments;
+
+    // String -> Fr
+    let wire_values_at_z = pof.wire_values_at_z;
+    // String -> Fr
+    let wire_values_at_z_omega = pof.wire_values_at_z_omega;
+    // String -> Fr
+    let grand_product_at_z_omega = pof.grand_product_at_z_omega;
+    // String -> Fr
+    let quotient_polynomial_at_z = pof.quotient_polynomial_at_z;
+    // String -> Fr
+    let linearization_polynomial_at_z = pof.linearization_polynomial_at_z;
+    // String -> Fr
+    let permutation_polynomials_at_z = pof.permutation_polynomials_at_z;
+    // Vec<u8> -> G1Affine
+    let opening_at_z_proof = pof.opening_at_z_proof;
+    // Vec<u8> -> G1Affine
+    let opening_at_z_omega_proof = pof.opening_at_z_omega_proof;
+
+    // Vec<u8> -> G1Affine, uncompressed or compressed
+    let g1 = |bytes: Vec<u8>| decode_g1(&bytes).ok_or(CoreError::ErrorProof {});
+    let wire_commitments_affine = wire_commitments
+        .into_iter()
+        .map(g1)
+        .collect::<Result<Vec<E::G1Affine>, CoreError>>()?;
+    let grand_product_commitment_affine = g1(grand_product_commitment)?;
+    let quotient_poly_commitments_affine = quotient_poly_commitments
+        .into_iter()
+        .map(g1)
+        .collect::<Result<Vec<E::G1Affine>, CoreError>>()?;
+    let opening_at_z_proof_affine = g1(opening_at_z_proof)?;
+    let opening_at_z_omega_proof_affine = g1(opening_at_z_omega_proof)?;
+
+    // start transform the Prime Field type
+    let wire_values_at_z_res: Result<Vec<E::Fr>, CoreError> =
+        wire_values_at_z.into_iter().map(|x| parse_fr(&x)).collect();
+
+    // transform end
+
+    // return the proof
+    let mut final_proof = Proof::empty();
+    final_proof.num_inputs = num_inputs;
+    final_proof.n = n;
+    final_proof.input_values = input_values
+        .into_iter()
+        .map(|x| parse_fr(&x))
+        .collect::<Result<_, CoreError>>()?;
+    final_proof.wire_commitments = wire_commitments_affine;
+    final_proof.grand_product_commitment = grand_product_commitment_affine;
+    final_proof.quotient_poly_commitments = quotient_poly_commitments_affine;
+
+    final_proof.wire_values_at_z = wire_values_at_z_res?;
+    final_proof.wire_values_at_z_omega = wire_values_at_z_omega
+        .into_iter()
+        .map(|x| parse_fr(&x))
+        .collect::<Result<_, CoreError>>()?;
+    final_proof.grand_product_at_z_omega = parse_fr(&grand_product_at_z_omega)?;
+    final_proof.quotient_polynomial_at_z = parse_fr(&quotient_polynomial_at_z)?;
+    final_proof.linearization_polynomial_at_z = parse_fr(&linearization_polynomial_at_z)?;
+    final_proof.permutation_polynomials_at_z = permutation_polynomials_at_z
+        .into_iter()
+        .map(|x| parse_fr(&x))
+        .collect::<Result<_, CoreError>>()?;
+
+    final_proof.opening_at_z_proof = opening_at_z_proof_affine;
+    final_proof.opening_at_z_omega_proof = opening_at_z_omega_proof_affine;
+
+    Ok(final_proof)
+}
+
+/// convert the verification key into the affine type, which will be used in verification
+pub fn parse_plonk_vkey<E, P>(vk: PlonkVkeyStr) -> Result<VerificationKey<E, P>, CoreError>
+where
+    E: Engine<G1Affine = G1Affine, G2Affine = G2Affine>,
+    P: PlonkConstraintSystemParams<E>,
+{
+    let n = vk.n;
+    let num_inputs = vk.num_inputs;
+    let selector_commitments = vk.selector_commitments;
+    let next_step_selector_commitments = vk.next_step_selector_commitments;
+    let permutation_commitments = vk.permutation_commitments;
+
+    let non_residues = vk.non_residues;
+    let g2_elements = vk.g2_elements;
+
+    // Vec<u8> -> G1Affine/G2Affine, uncompressed or compressed
+    let g1 = |bytes: Vec<u8>| decode_g1(&bytes).ok_or(CoreError::ErrorVerificationKey {});
+    let selector_commitments_affine = selector_commitments
+        .into_iter()
+        .map(g1)
+        .collect::<Result<Vec<E::G1Affine>, CoreError>>()?;
+    let next_step_selector_commitments_affine = next_step_selector_commitments
+        .into_iter()
+        .map(g1)
+        .collect::<Result<Vec<E::G1Affine>, CoreError>>()?;
+    let permutation_commitments_affine = permutation_commitments
+        .into_iter()
+        .map(g1)
+        .collect::<Result<Vec<E::G1Affine>, CoreError>>()?;
+    let g2_elements_affine = g2_elements
+        .into_iter()
+        .map(|bytes| decode_g2(&bytes).ok_or(CoreError::ErrorVerificationKey {}))
+        .collect::<Result<Vec<E::G2Affine>, CoreError>>()?;
+    ensure!(
+        g2_elements_affine.len() <= 2,
+        CoreError::ErrorVerificationKey {}
+    );
+
+    let mut g2_elements_affine_arr: [E::G2Affine; 2] = [E::G2Affine::zero(); 2];
+    g2_elements_affine_arr[..g2_elements_affine.len()].copy_from_slice(&g2_elements_affine[..]);
+
+    // return verification key
+    Ok(VerificationKey {
+        n,
+        num_inputs,
+        selector_commitments: selector_commitments_affine,
+        next_step_selector_commitments: next_step_selector_commitments_affine,
+        permutation_commitments: permutation_commitments_affine,
+        non_residues: non_residues
+            .into_iter()
+            .map(|x| parse_fr(&x))
+            .collect::<Result<_, CoreError>>()?,
+        g2_elements: g2_elements_affine_arr,
+        _marker: std::marker::PhantomData,
+    })
+}
+
+// hex field element, values outside the field are rejected instead of panicking
+fn parse_fr<F: PrimeField>(hex: &str) -> Result<F, CoreError> {
+    from_hex(hex).map_err(|_| CoreError::InvalidPrimeField {})
+}
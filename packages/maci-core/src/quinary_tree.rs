@@ -0,0 +1,421 @@
+use crate::error::CoreError;
+use crate::utils::try_hash5;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint256;
+
+pub const DEGREE: usize = 5;
+
+/// Inclusion proof of one leaf, the 4 siblings of every level from the leaves up in the order
+/// the circuits' `QuinTreeInclusionProof` expects them
+#[cw_serde]
+pub struct QuinaryProof {
+    pub leaf_index: u64,
+    pub path_elements: Vec<[Uint256; 4]>,
+}
+
+impl QuinaryProof {
+    pub fn depth(&self) -> u32 {
+        self.path_elements.len() as u32
+    }
+
+    /// position of the node among its siblings at every level
+    pub fn path_indices(&self) -> Vec<usize> {
+        let mut index = self.leaf_index;
+        self.path_elements
+            .iter()
+            .map(|_| {
+                let position = (index % DEGREE as u64) as usize;
+                index /= DEGREE as u64;
+                position
+            })
+            .collect()
+    }
+}
+
+/// the root of an empty subtree at every height from 0 to `depth`
+pub fn zero_hashes(zero_leaf: Uint256, depth: u32) -> Result<Vec<Uint256>, CoreError> {
+    extend_zeros(vec![zero_leaf], depth)
+}
+
+/// root of a tree of `depth` holding `leaves` from index 0, every other leaf is `zero_leaf`
+pub fn compute_root(
+    leaves: &[Uint256],
+    zero_leaf: Uint256,
+    depth: u32,
+) -> Result<Uint256, CoreError> {
+    compute_root_with_zeros(leaves, &zero_hashes(zero_leaf, depth)?, depth)
+}
+
+/// `compute_root` with precomputed `zero_hashes`, missing heights are hashed from the last one
+pub fn compute_root_with_zeros(
+    leaves: &[Uint256],
+    zeros: &[Uint256],
+    depth: u32,
+) -> Result<Uint256, CoreError> {
+    check_capacity(leaves.len() as u64, depth)?;
+    let zeros = extend_zeros(zeros.to_vec(), depth)?;
+
+    let mut nodes = leaves.to_vec();
+    for zero in zeros.iter().take(depth as usize) {
+        nodes = hash_level(&nodes, *zero)?;
+    }
+    Ok(nodes.first().copied().unwrap_or(zeros[depth as usize]))
+}
+
+/// inclusion proof for the leaf at `leaf_index` of the tree described by `compute_root`
+pub fn compute_proof(
+    leaves: &[Uint256],
+    zero_leaf: Uint256,
+    depth: u32,
+    leaf_index: u64,
+) -> Result<QuinaryProof, CoreError> {
+    check_capacity(leaves.len() as u64, depth)?;
+    if !fits(leaf_index as u128 + 1, depth) {
+        return Err(CoreError::LeafIndexOutOfRange {
+            depth,
+            index: leaf_index,
+        });
+    }
+
+    let zeros = zero_hashes(zero_leaf, depth)?;
+    let mut nodes = leaves.to_vec();
+    let mut index = leaf_index as usize;
+    let mut path_elements = Vec::with_capacity(depth as usize);
+    for zero in zeros.iter().take(depth as usize) {
+        let start = index - index % DEGREE;
+        let siblings: Vec<Uint256> = (start..start + DEGREE)
+            .filter(|i| *i != index)
+            .map(|i| nodes.get(i).copied().unwrap_or(*zero))
+            .collect();
+        path_elements.push([siblings[0], siblings[1], siblings[2], siblings[3]]);
+
+        nodes = hash_level(&nodes, *zero)?;
+        index /= DEGREE;
+    }
+
+    Ok(QuinaryProof {
+        leaf_index,
+        path_elements,
+    })
+}
+
+/// root implied by `leaf` sitting where `proof` says
+pub fn compute_root_from_proof(leaf: Uint256, proof: &QuinaryProof) -> Result<Uint256, CoreError> {
+    if !fits(proof.leaf_index as u128 + 1, proof.depth()) {
+        return Err(CoreError::LeafIndexOutOfRange {
+            depth: proof.depth(),
+            index: proof.leaf_index,
+        });
+    }
+
+    let mut node = leaf;
+    for (siblings, position) in proof.path_elements.iter().zip(proof.path_indices()) {
+        let mut children = [Uint256::zero(); DEGREE];
+        let mut rest = siblings.iter();
+        for (i, child) in children.iter_mut().enumerate() {
+            *child = if i == position {
+                node
+            } else {
+                *rest.next().unwrap_or(&Uint256::zero())
+            };
+        }
+        node = try_hash5(children)?;
+    }
+    Ok(node)
+}
+
+/// check that `leaf` is in the tree with `root`
+pub fn verify_proof(root: Uint256, leaf: Uint256, proof: &QuinaryProof) -> Result<bool, CoreError> {
+    Ok(compute_root_from_proof(leaf, proof)? == root)
+}
+
+/// Append-only quinary tree that keeps only its frontier, the finished children of the rightmost
+/// unfinished node of every level. Appending hashes a level only when it fills up and the root
+/// is computed when it is needed, so a sign up costs far less than rewriting its path
+#[cw_serde]
+pub struct IncrementalQuinaryTree {
+    pub depth: u32,
+    pub leaf_count: u64,
+    /// roots of the empty subtrees of height 0 to `depth`
+    pub zeros: Vec<Uint256>,
+    pub frontier: Vec<Vec<Uint256>>,
+}
+
+impl IncrementalQuinaryTree {
+    /// empty tree of `depth` whose missing leaves are `zeros[0]`, `zeros` may be shorter than the
+    /// tree is deep
+    pub fn new(depth: u32, zeros: &[Uint256]) -> Result<Self, CoreError> {
+        let mut zeros = extend_zeros(zeros.to_vec(), depth)?;
+        zeros.truncate(depth as usize + 1);
+        Ok(Self {
+            depth,
+            leaf_count: 0,
+            zeros,
+            frontier: vec![vec![]; depth.max(1) as usize],
+        })
+    }
+
+    /// rebuild the frontier of a tree holding `leaf_count` leaves from its finished nodes,
+    /// `node(height, position)` is only called for nodes whose subtree is full
+    pub fn from_nodes<E: From<CoreError>>(
+        depth: u32,
+        zeros: &[Uint256],
+        leaf_count: u64,
+        mut node: impl FnMut(u32, u64) -> Result<Uint256, E>,
+    ) -> Result<Self, E> {
+        let mut tree = Self::new(depth, zeros)?;
+        check_capacity(leaf_count, depth)?;
+
+        let top = tree.frontier.len() - 1;
+        let mut finished = leaf_count;
+        for (height, level) in tree.frontier.iter_mut().enumerate() {
+            // the top level is never hashed into a parent, a full tree keeps all its 5 nodes
+            let start = if height == top {
+                0
+            } else {
+                finished - finished % DEGREE as u64
+            };
+            for position in start..finished {
+                level.push(node(height as u32, position)?);
+            }
+            finished /= DEGREE as u64;
+        }
+        tree.leaf_count = leaf_count;
+        Ok(tree)
+    }
+
+    /// append `leaf` and return its index
+    pub fn push(&mut self, leaf: Uint256) -> Result<u64, CoreError> {
+        check_capacity(self.leaf_count + 1, self.depth)?;
+
+        let top = self.frontier.len() - 1;
+        let mut node = leaf;
+        for (height, level) in self.frontier.iter_mut().enumerate() {
+            level.push(node);
+            if level.len() < DEGREE || height == top {
+                break;
+            }
+            node = try_hash5([level[0], level[1], level[2], level[3], level[4]])?;
+            level.clear();
+        }
+
+        self.leaf_count += 1;
+        Ok(self.leaf_count - 1)
+    }
+
+    pub fn root(&self) -> Result<Uint256, CoreError> {
+        if self.depth == 0 {
+            return Ok(self.frontier[0].first().copied().unwrap_or(self.zeros[0]));
+        }
+
+        // the unfinished node of each level, `None` while its subtree is still empty
+        let mut node: Option<Uint256> = None;
+        for (height, level) in self.frontier.iter().enumerate() {
+            if level.is_empty() && node.is_none() {
+                continue;
+            }
+            let mut children = [self.zeros[height]; DEGREE];
+            children[..level.len()].copy_from_slice(level);
+            if let Some(child) = node {
+                children[level.len()] = child;
+            }
+            node = Some(try_hash5(children)?);
+        }
+        Ok(node.unwrap_or(self.zeros[self.depth as usize]))
+    }
+}
+
+fn extend_zeros(mut zeros: Vec<Uint256>, depth: u32) -> Result<Vec<Uint256>, CoreError> {
+    if zeros.is_empty() {
+        zeros.push(Uint256::zero());
+    }
+    while zeros.len() <= depth as usize {
+        let zero = zeros[zeros.len() - 1];
+        zeros.push(try_hash5([zero; DEGREE])?);
+    }
+    Ok(zeros)
+}
+
+fn hash_level(nodes: &[Uint256], zero: Uint256) -> Result<Vec<Uint256>, CoreError> {
+    nodes
+        .chunks(DEGREE)
+        .map(|chunk| {
+            let mut children = [zero; DEGREE];
+            children[..chunk.len()].copy_from_slice(chunk);
+            try_hash5(children)
+        })
+        .collect()
+}
+
+// whether `count` leaves fit in a tree of `depth`, 5^depth only overflows for trees too deep to
+// ever be full
+fn fits(count: u128, depth: u32) -> bool {
+    match (DEGREE as u128).checked_pow(depth) {
+        Some(capacity) => count <= capacity,
+        None => true,
+    }
+}
+
+fn check_capacity(leaves: u64, depth: u32) -> Result<(), CoreError> {
+    if fits(leaves as u128, depth) {
+        Ok(())
+    } else {
+        Err(CoreError::TreeOverflow { depth, leaves })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::hash5;
+
+    fn leaves(count: u128) -> Vec<Uint256> {
+        (1..=count).map(Uint256::from_u128).collect()
+    }
+
+    #[test]
+    fn root_matches_hashing_by_hand() {
+        let l = leaves(7);
+        let z = zero_hashes(Uint256::zero(), 2).unwrap();
+        let level_1 = [
+            hash5([l[0], l[1], l[2], l[3], l[4]]),
+            hash5([l[5], l[6], z[0], z[0], z[0]]),
+            z[1],
+            z[1],
+            z[1],
+        ];
+        assert_eq!(compute_root(&l, Uint256::zero(), 2), Ok(hash5(level_1)));
+        assert_eq!(compute_root(&[], Uint256::zero(), 2), Ok(z[2]));
+    }
+
+    #[test]
+    fn proofs_verify_for_every_leaf() {
+        let l = leaves(7);
+        let zero_leaf = Uint256::from_u128(42);
+        let root = compute_root(&l, zero_leaf, 3).unwrap();
+
+        for index in 0..10u64 {
+            let leaf = l.get(index as usize).copied().unwrap_or(zero_leaf);
+            let proof = compute_proof(&l, zero_leaf, 3, index).unwrap();
+            assert_eq!(proof.depth(), 3);
+            assert_eq!(verify_proof(root, leaf, &proof), Ok(true));
+            assert_eq!(verify_proof(root, leaf + Uint256::one(), &proof), Ok(false));
+        }
+    }
+
+    #[test]
+    fn trees_deeper_than_the_zeros_table_are_supported() {
+        let l = leaves(3);
+        let root = compute_root(&l, Uint256::zero(), 12).unwrap();
+        let proof = compute_proof(&l, Uint256::zero(), 12, 2).unwrap();
+        assert_eq!(proof.path_indices()[0], 2);
+        assert_eq!(verify_proof(root, l[2], &proof), Ok(true));
+    }
+
+    #[test]
+    fn out_of_range_input_is_an_error() {
+        assert_eq!(
+            compute_root(&leaves(6), Uint256::zero(), 1),
+            Err(CoreError::TreeOverflow {
+                depth: 1,
+                leaves: 6
+            })
+        );
+        assert_eq!(
+            compute_proof(&leaves(2), Uint256::zero(), 1, 5),
+            Err(CoreError::LeafIndexOutOfRange { depth: 1, index: 5 })
+        );
+        let proof = QuinaryProof {
+            leaf_index: 25,
+            path_elements: vec![[Uint256::zero(); 4]; 2],
+        };
+        assert_eq!(
+            verify_proof(Uint256::zero(), Uint256::zero(), &proof),
+            Err(CoreError::LeafIndexOutOfRange {
+                depth: 2,
+                index: 25
+            })
+        );
+    }
+
+    // The roots the contracts stored before the incremental tree: every node was kept, keyed by
+    // its index, and each sign up rehashed the path from its leaf to the root
+    fn legacy_roots(l: &[Uint256], zeros: &[Uint256], depth: u32) -> Vec<Uint256> {
+        let mut nodes = std::collections::HashMap::new();
+        let leaf_idx0 = (5u128.pow(depth) - 1) / 4;
+        let mut roots = vec![];
+        for (i, leaf) in l.iter().enumerate() {
+            let mut idx = leaf_idx0 + i as u128;
+            nodes.insert(idx, *leaf);
+            let mut height = 0;
+            while idx > 0 {
+                let parent = (idx - 1) / 5;
+                let mut inputs = [zeros[height]; 5];
+                for (j, input) in inputs.iter_mut().enumerate() {
+                    if let Some(node) = nodes.get(&(parent * 5 + 1 + j as u128)) {
+                        *input = *node;
+                    }
+                }
+                nodes.insert(parent, hash5(inputs));
+                height += 1;
+                idx = parent;
+            }
+            roots.push(nodes[&0]);
+        }
+        roots
+    }
+
+    #[test]
+    fn incremental_root_matches_the_legacy_path_updates() {
+        let zero_leaf = Uint256::from_u128(42);
+        for depth in [1, 2, 3] {
+            let zeros = zero_hashes(zero_leaf, depth).unwrap();
+            let mut tree = IncrementalQuinaryTree::new(depth, &[zero_leaf]).unwrap();
+            let l = leaves(5u128.pow(depth));
+            let roots = legacy_roots(&l, &zeros, depth);
+            assert_eq!(tree.root(), Ok(zeros[depth as usize]));
+            for (i, leaf) in l.iter().enumerate() {
+                assert_eq!(tree.push(*leaf), Ok(i as u64));
+                assert_eq!(tree.root(), Ok(roots[i]));
+            }
+            assert_eq!(
+                tree.push(zero_leaf),
+                Err(CoreError::TreeOverflow {
+                    depth,
+                    leaves: l.len() as u64 + 1
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn frontier_is_rebuilt_from_finished_nodes() {
+        let zero_leaf = Uint256::zero();
+        let depth = 3;
+        let l = leaves(125);
+        // every node of the full tree, by height
+        let mut levels = vec![l.clone()];
+        for height in 0..depth as usize {
+            let next = hash_level(&levels[height], Uint256::zero()).unwrap();
+            levels.push(next);
+        }
+
+        for count in [0, 1, 5, 31, 124, 125] {
+            let mut pushed = IncrementalQuinaryTree::new(depth, &[zero_leaf]).unwrap();
+            for leaf in &l[..count] {
+                pushed.push(*leaf).unwrap();
+            }
+            let rebuilt = IncrementalQuinaryTree::from_nodes(
+                depth,
+                &[zero_leaf],
+                count as u64,
+                |height, position| -> Result<Uint256, CoreError> {
+                    assert!((position + 1) * 5u64.pow(height) <= count as u64);
+                    Ok(levels[height as usize][position as usize])
+                },
+            )
+            .unwrap();
+            assert_eq!(rebuilt, pushed);
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use crate::error::CoreError;
+use crate::quinary_tree::compute_root_with_zeros;
+use crate::utils::{hash2, hash5, uint256_from_hex_string};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint256;
+
+#[cw_serde]
+pub struct PubKey {
+    pub x: Uint256,
+    pub y: Uint256,
+}
+
+#[cw_serde]
+pub struct StateLeaf {
+    pub pub_key: PubKey,
+    pub voice_credit_balance: Uint256,
+    pub vote_option_tree_root: Uint256,
+    pub nonce: Uint256,
+}
+
+impl StateLeaf {
+    pub fn hash_state_leaf(&self) -> Uint256 {
+        let mut plaintext: [Uint256; 5] = [Uint256::from_u128(0); 5];
+
+        plaintext[0] = self.pub_key.x;
+        plaintext[1] = self.pub_key.y;
+        plaintext[2] = self.voice_credit_balance;
+        plaintext[3] = self.vote_option_tree_root;
+        plaintext[4] = self.nonce;
+        return hash5(plaintext);
+    }
+
+    pub fn hash_decativate_state_leaf(&self) -> Uint256 {
+        let mut plaintext: [Uint256; 5] = [Uint256::from_u128(0); 5];
+
+        plaintext[0] = self.pub_key.x;
+        plaintext[1] = self.pub_key.y;
+        plaintext[2] = self.voice_credit_balance;
+        return hash2([
+            hash5(plaintext),
+            uint256_from_hex_string(
+                "2066be41bebe6caf7e079360abe14fbf9118c62eabc42e2fe75e342b160a95bc",
+            ),
+        ]);
+    }
+
+    pub fn hash_new_key_state_leaf(&self, d: [Uint256; 4]) -> Uint256 {
+        let mut plaintext: [Uint256; 5] = [Uint256::from_u128(0); 5];
+
+        plaintext[0] = self.pub_key.x;
+        plaintext[1] = self.pub_key.y;
+        plaintext[2] = self.voice_credit_balance;
+        return hash2([
+            hash5(plaintext),
+            hash5([d[0], d[1], d[2], d[3], Uint256::from_u128(0u128)]),
+        ]);
+    }
+}
+
+#[cw_serde]
+pub struct MessageData {
+    pub data: [Uint256; 7],
+}
+
+#[cw_serde]
+pub struct Groth16ProofStr {
+    pub pi_a: Vec<u8>,
+    pub pi_b: Vec<u8>,
+    pub pi_c: Vec<u8>,
+}
+
+#[cw_serde]
+pub struct QuinaryTreeRoot {
+    pub zeros: [Uint256; 9],
+}
+
+impl QuinaryTreeRoot {
+    /// Root of a tree of `depth` holding `nodes`, too many leaves or leaves outside the field
+    /// are errors. Depths past the `zeros` table extend it by hashing
+    pub fn try_root_of(&self, depth: Uint256, nodes: Vec<Uint256>) -> Result<Uint256, CoreError> {
+        let depth = depth
+            .to_string()
+            .parse::<u32>()
+            .map_err(|_| CoreError::TreeOverflow {
+                depth: u32::MAX,
+                leaves: nodes.len() as u64,
+            })?;
+        compute_root_with_zeros(&nodes, &self.zeros, depth)
+    }
+}
+
+#[cw_serde]
+pub struct PlonkProofStr {
+    pub num_inputs: usize,
+    pub n: usize,
+    pub input_values: Vec<String>,
+    pub wire_commitments: Vec<Vec<u8>>,
+    pub grand_product_commitment: Vec<u8>,
+    pub quotient_poly_commitments: Vec<Vec<u8>>,
+    pub wire_values_at_z: Vec<String>,
+    pub wire_values_at_z_omega: Vec<String>,
+    pub grand_product_at_z_omega: String,
+    pub quotient_polynomial_at_z: String,
+    pub linearization_polynomial_at_z: String,
+    pub permutation_polynomials_at_z: Vec<String>,
+    pub opening_at_z_proof: Vec<u8>,
+    pub opening_at_z_omega_proof: Vec<u8>,
+}
+
+#[cw_serde]
+pub struct Groth16VkeyStr {
+    pub alpha_1: Vec<u8>,
+    pub beta_2: Vec<u8>,
+    pub gamma_2: Vec<u8>,
+    pub delta_2: Vec<u8>,
+    pub ic0: Vec<u8>,
+    pub ic1: Vec<u8>,
+}
+
+/// Groth16 proof as snarkjs writes it to `proof.json`: decimal coordinates, projective with
+/// `z = 1`, G2 coordinates as `[c0, c1]`
+#[cw_serde]
+pub struct SnarkjsProof {
+    pub pi_a: Vec<String>,
+    pub pi_b: Vec<Vec<String>>,
+    pub pi_c: Vec<String>,
+    pub protocol: Option<String>,
+    pub curve: Option<String>,
+}
+
+/// Groth16 verifying key as snarkjs writes it to `verification_key.json`
+#[cw_serde]
+pub struct SnarkjsVkey {
+    pub protocol: Option<String>,
+    pub curve: Option<String>,
+    #[serde(rename = "nPublic")]
+    pub n_public: Option<u32>,
+    pub vk_alpha_1: Vec<String>,
+    pub vk_beta_2: Vec<Vec<String>>,
+    pub vk_gamma_2: Vec<Vec<String>>,
+    pub vk_delta_2: Vec<Vec<String>>,
+    pub vk_alphabeta_12: Option<Vec<Vec<Vec<String>>>>,
+    #[serde(rename = "IC")]
+    pub ic: Vec<Vec<String>>,
+}
+
+/// Groth16 verifying key with e(alpha, beta) precomputed and gamma, delta negated, stored
+/// once per round so a verification skips `prepare_verifying_key`.
+#[cw_serde]
+pub struct Groth16PreparedVkeyStr {
+    pub alpha_g1_beta_g2: Vec<u8>,
+    pub neg_gamma_g2: Vec<u8>,
+    pub neg_delta_g2: Vec<u8>,
+    pub ic: Vec<Vec<u8>>,
+}
+
+#[cw_serde]
+pub struct PlonkVkeyStr {
+    pub n: usize,
+    pub num_inputs: usize,
+    pub selector_commitments: Vec<Vec<u8>>,
+    pub next_step_selector_commitments: Vec<Vec<u8>>,
+    pub permutation_commitments: Vec<Vec<u8>>,
+    pub non_residues: Vec<String>,
+    pub g2_elements: Vec<Vec<u8>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn qtr() -> QuinaryTreeRoot {
+        let mut zeros = [Uint256::zero(); 9];
+        for i in 1..9 {
+            zeros[i] = hash5([zeros[i - 1]; 5]);
+        }
+        QuinaryTreeRoot { zeros }
+    }
+
+    #[test]
+    fn empty_leaves_give_the_zero_root() {
+        let qtr = qtr();
+        assert_eq!(
+            qtr.try_root_of(Uint256::from_u128(2), vec![Uint256::zero(); 5]),
+            Ok(qtr.zeros[2])
+        );
+    }
+
+    #[test]
+    fn try_root_of_fills_missing_leaves_with_zeros() {
+        let qtr = qtr();
+        let leaf = Uint256::from_u128(7);
+        let level_1 = hash5([leaf, qtr.zeros[0], qtr.zeros[0], qtr.zeros[0], qtr.zeros[0]]);
+        let level_2 = hash5([
+            level_1,
+            qtr.zeros[1],
+            qtr.zeros[1],
+            qtr.zeros[1],
+            qtr.zeros[1],
+        ]);
+        assert_eq!(
+            qtr.try_root_of(Uint256::from_u128(2), vec![leaf]),
+            Ok(level_2)
+        );
+    }
+
+    #[test]
+    fn try_root_of_rejects_overflow_and_handles_deep_trees() {
+        let qtr = qtr();
+        let leaves = vec![Uint256::from_u128(7); 6];
+        assert_eq!(
+            qtr.try_root_of(Uint256::from_u128(1), leaves.clone()),
+            Err(CoreError::TreeOverflow {
+                depth: 1,
+                leaves: 6
+            })
+        );
+        assert_eq!(
+            qtr.try_root_of(Uint256::from_u128(2), leaves.clone()),
+            crate::quinary_tree::compute_root(&leaves, Uint256::zero(), 2)
+        );
+        assert_eq!(
+            qtr.try_root_of(Uint256::from_u128(10), leaves.clone()),
+            crate::quinary_tree::compute_root(&leaves, Uint256::zero(), 10)
+        );
+        assert_eq!(
+            qtr.try_root_of(Uint256::from_u128(2), vec![]),
+            Ok(qtr.zeros[2])
+        );
+    }
+}
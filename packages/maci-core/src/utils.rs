@@ -0,0 +1,317 @@
+use crate::error::CoreError;
+use cosmwasm_std::Uint256;
+// use num_bigint::BigUint;
+// use sha256::digest;
+use ff::*;
+use poseidon_rs::Poseidon;
+use sha2::{Digest, Sha256};
+pub type Fr = poseidon_rs::Fr; // alias
+
+// pub fn uint256_from_decimal_string(decimal_string: &str) -> Uint256 {
+//     assert!(
+//         decimal_string.len() <= 77,
+//         "the decimal length can't abrove 77"
+//     );
+
+//     let decimal_number = BigUint::parse_bytes(decimal_string.as_bytes(), 10)
+//         .expect("Failed to parse decimal string");
+
+//     let byte_array = decimal_number.to_bytes_be();
+
+//     let hex_string = hex::encode(byte_array);
+//     uint256_from_hex_string(&hex_string)
+// }
+
+/// for trusted constants only, invalid hex becomes zero. User input goes through
+/// `try_uint256_from_hex_string`
+pub fn uint256_from_hex_string(hex_string: &str) -> Uint256 {
+    let padded_hex_string = if hex_string.len() < 64 {
+        let padding_length = 64 - hex_string.len();
+        format!("{:0>width$}{}", "", hex_string, width = padding_length)
+    } else {
+        hex_string.to_string()
+    };
+
+    let res = hex_to_decimal(&padded_hex_string);
+    Uint256::from_be_bytes(res)
+}
+
+pub fn uint256_to_hex(data: Uint256) -> String {
+    hex::encode(data.to_be_bytes())
+}
+
+/// for trusted constants only, see `uint256_from_hex_string`
+pub fn hex_to_decimal(hex_bytes: &str) -> [u8; 32] {
+    let bytes = hex::decode(hex_bytes).unwrap_or_else(|_| vec![]);
+    let decimal_values: Vec<u8> = bytes.iter().cloned().collect();
+
+    let mut array: [u8; 32] = [0; 32];
+
+    if decimal_values.len() >= 32 {
+        array.copy_from_slice(&decimal_values[..32]);
+    } else {
+        array[..decimal_values.len()].copy_from_slice(&decimal_values);
+    }
+
+    array
+}
+
+/// for trusted constants only, see `uint256_from_hex_string`
+pub fn hex_to_uint256(hex_bytes: &str) -> Uint256 {
+    let bytes = hex::decode(hex_bytes).unwrap_or_else(|_| vec![]);
+    let decimal_values: Vec<u8> = bytes.iter().cloned().collect();
+
+    let mut array: [u8; 32] = [0; 32];
+
+    if decimal_values.len() >= 32 {
+        array.copy_from_slice(&decimal_values[..32]);
+    } else {
+        array[..decimal_values.len()].copy_from_slice(&decimal_values);
+    }
+
+    Uint256::from_be_bytes(array)
+}
+
+/// parse a big-endian hex number of at most 64 digits, rejecting invalid or longer input
+pub fn try_uint256_from_hex_string(hex_string: &str) -> Result<Uint256, CoreError> {
+    if hex_string.is_empty() {
+        return Err(CoreError::InvalidHex {});
+    }
+    if hex_string.len() > 64 {
+        return Err(CoreError::HexTooLong {
+            len: hex_string.len(),
+        });
+    }
+
+    let padded_hex_string = format!("{:0>64}", hex_string);
+    let mut bytes: [u8; 32] = [0; 32];
+    hex::decode_to_slice(padded_hex_string, &mut bytes).map_err(|_| CoreError::InvalidHex {})?;
+    Ok(Uint256::from_be_bytes(bytes))
+}
+
+/// parse a decimal number that fits in a Uint256
+pub fn try_uint256_from_decimal_string(decimal_string: &str) -> Result<Uint256, CoreError> {
+    decimal_string
+        .parse::<Uint256>()
+        .map_err(|_| CoreError::InvalidDecimal {})
+}
+
+/// the snark scalar field, the modulus of the poseidon field and of the proof inputs
+pub fn snark_scalar_field() -> Uint256 {
+    uint256_from_hex_string("30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001")
+}
+
+/// reject values at or above the snark scalar field, `PrimeField::from_str` reduces them
+/// instead of failing
+pub fn ensure_in_snark_field(data: Uint256) -> Result<Uint256, CoreError> {
+    if data >= snark_scalar_field() {
+        return Err(CoreError::InvalidPrimeField {});
+    }
+    Ok(data)
+}
+
+/// convert into the poseidon field, values at or above the snark scalar field are rejected
+pub fn try_fr_from_uint256(data: Uint256) -> Result<Fr, CoreError> {
+    let data = ensure_in_snark_field(data)?;
+    Fr::from_str(&data.to_string()).ok_or(CoreError::InvalidPrimeField {})
+}
+
+/// `hash2` for user supplied inputs
+pub fn try_hash2(data: [Uint256; 2]) -> Result<Uint256, CoreError> {
+    let inputs = data
+        .iter()
+        .map(|input| try_fr_from_uint256(*input))
+        .collect::<Result<Vec<Fr>, CoreError>>()?;
+    Ok(hash(inputs))
+}
+
+/// `hash5` for user supplied inputs
+pub fn try_hash5(data: [Uint256; 5]) -> Result<Uint256, CoreError> {
+    let inputs = data
+        .iter()
+        .map(|input| try_fr_from_uint256(*input))
+        .collect::<Result<Vec<Fr>, CoreError>>()?;
+    Ok(hash(inputs))
+}
+
+pub fn hash_uint256(data: Uint256) -> Uint256 {
+    let uint256_inputs = vec![Fr::from_str(&data.to_string()).unwrap()];
+
+    hash(uint256_inputs)
+}
+
+pub fn hash(message: Vec<Fr>) -> Uint256 {
+    let poseidon = Poseidon::new();
+
+    let hash_item = poseidon.hash(message).unwrap().to_string();
+    let hash_res = &hash_item[5..hash_item.len() - 1];
+
+    uint256_from_hex_string(hash_res)
+}
+
+pub fn hash2(data: [Uint256; 2]) -> Uint256 {
+    let uint256_inputs: Vec<Fr> = data
+        .iter()
+        .map(|input| Fr::from_str(&input.to_string()).unwrap())
+        .collect();
+
+    hash(uint256_inputs)
+}
+
+pub fn hash5(data: [Uint256; 5]) -> Uint256 {
+    let uint256_inputs: Vec<Fr> = data
+        .iter()
+        .map(|input| -> Fr { Fr::from_str(&input.to_string()).unwrap() })
+        .collect();
+    hash(uint256_inputs)
+}
+
+pub fn hash_256_uint256_list(arrays: &[Uint256]) -> String {
+    let total_length = arrays.len() * 32;
+    let mut result: Vec<u8> = Vec::with_capacity(total_length);
+
+    for array in arrays {
+        result.extend_from_slice(&array.to_be_bytes());
+    }
+
+    let hash_result = Sha256::digest(&result);
+
+    // Use hex crate to convert binary data to hexadecimal string
+    hex::encode(hash_result)
+}
+// pub fn hash_256_uint256_list(arrays: &[Uint256]) -> String {
+//     let total_length = arrays.len() * 32;
+//     let mut result: Vec<u8> = Vec::with_capacity(total_length);
+
+//     for array in arrays {
+//         result.extend_from_slice(&array.to_be_bytes());
+//     }
+
+//     // digest(result.as_slice())
+//     Sha256::digest(&result.concat())
+// }
+
+pub fn encode_packed(arrays: &[&[u8; 32]]) -> Vec<u8> {
+    let total_length = arrays.len() * 32;
+    let mut result: Vec<u8> = Vec::with_capacity(total_length);
+
+    for array in arrays {
+        result.extend_from_slice(*array);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uint256_from_hex_string_pads_short_input() {
+        assert_eq!(uint256_from_hex_string("ff"), Uint256::from_u128(255));
+        assert_eq!(
+            uint256_from_hex_string(&uint256_to_hex(Uint256::from_u128(123456789))),
+            Uint256::from_u128(123456789)
+        );
+    }
+
+    #[test]
+    fn try_uint256_from_hex_string_rejects_bad_input() {
+        assert_eq!(
+            try_uint256_from_hex_string("fff"),
+            Ok(Uint256::from_u128(4095))
+        );
+        assert_eq!(
+            try_uint256_from_hex_string(&"f".repeat(64)),
+            Ok(Uint256::MAX)
+        );
+        assert_eq!(
+            try_uint256_from_hex_string("xyz"),
+            Err(CoreError::InvalidHex {})
+        );
+        assert_eq!(
+            try_uint256_from_hex_string(""),
+            Err(CoreError::InvalidHex {})
+        );
+        assert_eq!(
+            try_uint256_from_hex_string(&"1".repeat(65)),
+            Err(CoreError::HexTooLong { len: 65 })
+        );
+        // the lenient helper turns the same input into zero
+        assert_eq!(uint256_from_hex_string("xyz"), Uint256::zero());
+    }
+
+    #[test]
+    fn try_uint256_from_decimal_string_rejects_bad_input() {
+        assert_eq!(
+            try_uint256_from_decimal_string("12345"),
+            Ok(Uint256::from_u128(12345))
+        );
+        assert_eq!(
+            try_uint256_from_decimal_string("0x1"),
+            Err(CoreError::InvalidDecimal {})
+        );
+        assert_eq!(
+            try_uint256_from_decimal_string(&"9".repeat(78)),
+            Err(CoreError::InvalidDecimal {})
+        );
+    }
+
+    #[test]
+    fn values_outside_the_field_are_rejected() {
+        let field = uint256_from_hex_string(
+            "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+        );
+        let max = field - Uint256::one();
+        assert!(try_fr_from_uint256(max).is_ok());
+        assert_eq!(
+            try_fr_from_uint256(field),
+            Err(CoreError::InvalidPrimeField {})
+        );
+        assert_eq!(
+            try_hash2([Uint256::zero(), max]),
+            Ok(hash2([Uint256::zero(), max]))
+        );
+        assert_eq!(
+            try_hash5([
+                Uint256::zero(),
+                Uint256::zero(),
+                field,
+                Uint256::zero(),
+                Uint256::zero()
+            ]),
+            Err(CoreError::InvalidPrimeField {})
+        );
+    }
+
+    #[test]
+    fn hash5_matches_quinary_zeros() {
+        let zero_1 = hash5([Uint256::zero(); 5]);
+        assert_eq!(
+            zero_1,
+            uint256_from_hex_string(
+                "2066be41bebe6caf7e079360abe14fbf9118c62eabc42e2fe75e342b160a95bc"
+            )
+        );
+        assert_eq!(
+            hash5([zero_1; 5]),
+            uint256_from_hex_string(
+                "2a956d37d8e73692877b104630a08cc6840036f235f2134b0606769a369d85c1"
+            )
+        );
+    }
+
+    #[test]
+    fn hash_256_uint256_list_is_sha256_of_packed_words() {
+        assert_eq!(
+            hash_256_uint256_list(&[]),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        let words = [Uint256::from_u128(1), Uint256::from_u128(2)];
+        let packed = encode_packed(&[&words[0].to_be_bytes(), &words[1].to_be_bytes()]);
+        assert_eq!(
+            hash_256_uint256_list(&words),
+            hex::encode(Sha256::digest(&packed))
+        );
+    }
+}
@@ -0,0 +1,357 @@
+//! Canonical events shared by amaci, maci, oracle-maci, registry and saas.
+//!
+//! Every event type is prefixed with `maci_` (the chain reports it as `wasm-maci_*`) and
+//! carries a `schema_version` attribute. Each field is its own attribute:
+//!
+//! - numbers are decimal strings and booleans are `true` / `false`
+//! - public keys are split into `<name>_x` and `<name>_y`
+//! - lists are JSON arrays of decimal strings, proofs are JSON objects
+//! - optional fields are omitted when not set
+//!
+//! The full field reference lives in this crate's README. Adding an optional attribute is
+//! backwards compatible; renaming, removing or re-encoding one bumps [`SCHEMA_VERSION`].
+
+use cosmwasm_std::{Addr, Event, Uint128, Uint256};
+
+pub const SCHEMA_VERSION: &str = "1";
+
+pub const SIGN_UP_EVENT: &str = "maci_sign_up";
+pub const PUBLISH_MESSAGE_EVENT: &str = "maci_publish_message";
+pub const PUBLISH_DEACTIVATE_MESSAGE_EVENT: &str = "maci_publish_deactivate_message";
+pub const UPLOAD_DEACTIVATE_MESSAGE_EVENT: &str = "maci_upload_deactivate_message";
+pub const PROCESS_DEACTIVATE_MESSAGE_EVENT: &str = "maci_process_deactivate_message";
+pub const ADD_KEY_EVENT: &str = "maci_add_key";
+pub const PROCESS_MESSAGE_EVENT: &str = "maci_process_message";
+pub const PROCESS_TALLY_EVENT: &str = "maci_process_tally";
+pub const TALLY_EVENT: &str = "maci_tally";
+pub const CLAIM_EVENT: &str = "maci_claim";
+pub const CREATE_ROUND_EVENT: &str = "maci_create_round";
+
+/// A voter joined the state tree
+pub struct SignUp {
+    pub state_idx: Uint256,
+    pub pubkey: [Uint256; 2],
+    pub balance: Uint256,
+}
+
+/// An encrypted vote message was appended to a message chain
+pub struct PublishMessage {
+    pub msg_chain_length: Uint256,
+    pub message: Vec<Uint256>,
+    pub enc_pub_key: [Uint256; 2],
+    pub question_id: Option<u64>,
+}
+
+/// An encrypted deactivate message was appended to the deactivate chain
+pub struct PublishDeactivateMessage {
+    pub dmsg_chain_length: Uint256,
+    pub num_sign_ups: Uint256,
+    pub message: Vec<Uint256>,
+    pub enc_pub_key: [Uint256; 2],
+}
+
+/// The operator published the processed deactivate leaves
+pub struct UploadDeactivateMessage {
+    pub operator: Addr,
+    pub deactivate_message: Vec<Vec<Uint256>>,
+}
+
+/// A batch of deactivate messages was proven
+pub struct ProcessDeactivateMessage {
+    pub processed_dmsg_count: Uint256,
+    pub new_deactivate_commitment: Uint256,
+    pub proof: String,
+}
+
+/// A voter registered a fresh key (`pre_add_new_key` sets `pre_deactivate`)
+pub struct AddKey {
+    pub state_idx: Uint256,
+    pub pubkey: [Uint256; 2],
+    pub balance: Uint256,
+    pub d: [Uint256; 4],
+    pub pre_deactivate: bool,
+}
+
+/// A batch of vote messages was proven
+pub struct ProcessMessage {
+    pub processed_msg_count: Uint256,
+    pub new_state_commitment: Uint256,
+    pub certification_system: String,
+    pub proof: String,
+    pub question_id: Option<u64>,
+}
+
+/// A batch of state leaves was tallied
+pub struct ProcessTally {
+    pub processed_user_count: Uint256,
+    pub new_tally_commitment: Uint256,
+    pub certification_system: String,
+    pub proof: String,
+    pub question_id: Option<u64>,
+}
+
+/// The final results of a round (or of one of its questions) were published
+pub struct Tally {
+    pub results: Vec<Uint256>,
+    pub total_result: Uint256,
+    pub question_id: Option<u64>,
+}
+
+/// The round funds were distributed
+pub struct Claim {
+    pub operator_reward: Uint128,
+    pub penalty_amount: Uint128,
+    pub fee_to_recipient: Uint128,
+    pub miss_rate: Uint256,
+    pub is_tally_timeout: bool,
+}
+
+/// A round contract was created
+pub struct CreateRound {
+    pub round_addr: Addr,
+    /// `amaci`, `maci` or `oracle_maci`
+    pub round_type: String,
+    pub code_id: Option<u64>,
+    pub caller: Option<Addr>,
+    pub admin: Option<Addr>,
+    pub operator: Option<Addr>,
+    pub round_title: String,
+    /// Nanoseconds since the epoch
+    pub voting_start: Option<u64>,
+    pub voting_end: Option<u64>,
+    pub coordinator_pubkey: [Uint256; 2],
+    pub max_vote_options: Uint256,
+    /// `0` one person one vote, `1` quadratic voting
+    pub circuit_type: String,
+    /// `groth16` or `plonk`
+    pub certification_system: String,
+}
+
+fn new_event(ty: &str) -> Event {
+    Event::new(ty).add_attribute("schema_version", SCHEMA_VERSION)
+}
+
+fn add_pubkey(event: Event, name: &str, pubkey: &[Uint256; 2]) -> Event {
+    event
+        .add_attribute(format!("{name}_x"), pubkey[0].to_string())
+        .add_attribute(format!("{name}_y"), pubkey[1].to_string())
+}
+
+fn add_question_id(event: Event, question_id: Option<u64>) -> Event {
+    match question_id {
+        Some(question_id) => event.add_attribute("question_id", question_id.to_string()),
+        None => event,
+    }
+}
+
+/// Encodes values as a JSON array of decimal strings
+pub fn json_list(values: &[Uint256]) -> String {
+    let values: Vec<String> = values.iter().map(|v| format!("\"{v}\"")).collect();
+    format!("[{}]", values.join(","))
+}
+
+fn json_matrix(rows: &[Vec<Uint256>]) -> String {
+    let rows: Vec<String> = rows.iter().map(|row| json_list(row)).collect();
+    format!("[{}]", rows.join(","))
+}
+
+impl From<SignUp> for Event {
+    fn from(e: SignUp) -> Self {
+        let event = new_event(SIGN_UP_EVENT).add_attribute("state_idx", e.state_idx.to_string());
+        add_pubkey(event, "pubkey", &e.pubkey).add_attribute("balance", e.balance.to_string())
+    }
+}
+
+impl From<PublishMessage> for Event {
+    fn from(e: PublishMessage) -> Self {
+        let event = new_event(PUBLISH_MESSAGE_EVENT)
+            .add_attribute("msg_chain_length", e.msg_chain_length.to_string())
+            .add_attribute("message", json_list(&e.message));
+        add_question_id(
+            add_pubkey(event, "enc_pub_key", &e.enc_pub_key),
+            e.question_id,
+        )
+    }
+}
+
+impl From<PublishDeactivateMessage> for Event {
+    fn from(e: PublishDeactivateMessage) -> Self {
+        let event = new_event(PUBLISH_DEACTIVATE_MESSAGE_EVENT)
+            .add_attribute("dmsg_chain_length", e.dmsg_chain_length.to_string())
+            .add_attribute("num_sign_ups", e.num_sign_ups.to_string())
+            .add_attribute("message", json_list(&e.message));
+        add_pubkey(event, "enc_pub_key", &e.enc_pub_key)
+    }
+}
+
+impl From<UploadDeactivateMessage> for Event {
+    fn from(e: UploadDeactivateMessage) -> Self {
+        new_event(UPLOAD_DEACTIVATE_MESSAGE_EVENT)
+            .add_attribute("operator", e.operator)
+            .add_attribute("deactivate_message", json_matrix(&e.deactivate_message))
+    }
+}
+
+impl From<ProcessDeactivateMessage> for Event {
+    fn from(e: ProcessDeactivateMessage) -> Self {
+        new_event(PROCESS_DEACTIVATE_MESSAGE_EVENT)
+            .add_attribute("processed_dmsg_count", e.processed_dmsg_count.to_string())
+            .add_attribute(
+                "new_deactivate_commitment",
+                e.new_deactivate_commitment.to_string(),
+            )
+            .add_attribute("certification_system", "groth16")
+            .add_attribute("proof", e.proof)
+    }
+}
+
+impl From<AddKey> for Event {
+    fn from(e: AddKey) -> Self {
+        let event = new_event(ADD_KEY_EVENT).add_attribute("state_idx", e.state_idx.to_string());
+        add_pubkey(event, "pubkey", &e.pubkey)
+            .add_attribute("balance", e.balance.to_string())
+            .add_attribute("d", json_list(&e.d))
+            .add_attribute("pre_deactivate", e.pre_deactivate.to_string())
+    }
+}
+
+impl From<ProcessMessage> for Event {
+    fn from(e: ProcessMessage) -> Self {
+        let event = new_event(PROCESS_MESSAGE_EVENT)
+            .add_attribute("processed_msg_count", e.processed_msg_count.to_string())
+            .add_attribute("new_state_commitment", e.new_state_commitment.to_string())
+            .add_attribute("certification_system", e.certification_system)
+            .add_attribute("proof", e.proof);
+        add_question_id(event, e.question_id)
+    }
+}
+
+impl From<ProcessTally> for Event {
+    fn from(e: ProcessTally) -> Self {
+        let event = new_event(PROCESS_TALLY_EVENT)
+            .add_attribute("processed_user_count", e.processed_user_count.to_string())
+            .add_attribute("new_tally_commitment", e.new_tally_commitment.to_string())
+            .add_attribute("certification_system", e.certification_system)
+            .add_attribute("proof", e.proof);
+        add_question_id(event, e.question_id)
+    }
+}
+
+impl From<Tally> for Event {
+    fn from(e: Tally) -> Self {
+        let event = new_event(TALLY_EVENT)
+            .add_attribute("results", json_list(&e.results))
+            .add_attribute("total_result", e.total_result.to_string());
+        add_question_id(event, e.question_id)
+    }
+}
+
+impl From<Claim> for Event {
+    fn from(e: Claim) -> Self {
+        new_event(CLAIM_EVENT)
+            .add_attribute("operator_reward", e.operator_reward.to_string())
+            .add_attribute("penalty_amount", e.penalty_amount.to_string())
+            .add_attribute("fee_to_recipient", e.fee_to_recipient.to_string())
+            .add_attribute("miss_rate", e.miss_rate.to_string())
+            .add_attribute("is_tally_timeout", e.is_tally_timeout.to_string())
+    }
+}
+
+impl From<CreateRound> for Event {
+    fn from(e: CreateRound) -> Self {
+        let mut event = new_event(CREATE_ROUND_EVENT)
+            .add_attribute("round_addr", e.round_addr)
+            .add_attribute("round_type", e.round_type);
+        if let Some(code_id) = e.code_id {
+            event = event.add_attribute("code_id", code_id.to_string());
+        }
+        for (key, addr) in [
+            ("caller", e.caller),
+            ("admin", e.admin),
+            ("operator", e.operator),
+        ] {
+            if let Some(addr) = addr {
+                event = event.add_attribute(key, addr);
+            }
+        }
+        event = event.add_attribute("round_title", e.round_title);
+        if let Some(voting_start) = e.voting_start {
+            event = event.add_attribute("voting_start", voting_start.to_string());
+        }
+        if let Some(voting_end) = e.voting_end {
+            event = event.add_attribute("voting_end", voting_end.to_string());
+        }
+        add_pubkey(event, "coordinator_pubkey", &e.coordinator_pubkey)
+            .add_attribute("max_vote_options", e.max_vote_options.to_string())
+            .add_attribute("circuit_type", e.circuit_type)
+            .add_attribute("certification_system", e.certification_system)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attribute<'a>(event: &'a Event, key: &str) -> &'a str {
+        &event
+            .attributes
+            .iter()
+            .find(|attr| attr.key == key)
+            .unwrap_or_else(|| panic!("missing attribute {key}"))
+            .value
+    }
+
+    #[test]
+    fn every_event_carries_the_schema_version() {
+        let event: Event = Claim {
+            operator_reward: Uint128::new(9),
+            penalty_amount: Uint128::new(1),
+            fee_to_recipient: Uint128::new(0),
+            miss_rate: Uint256::from_u128(90u128),
+            is_tally_timeout: false,
+        }
+        .into();
+        assert_eq!(event.ty, CLAIM_EVENT);
+        assert_eq!(attribute(&event, "schema_version"), SCHEMA_VERSION);
+        assert_eq!(attribute(&event, "is_tally_timeout"), "false");
+    }
+
+    #[test]
+    fn lists_are_json_arrays_of_decimal_strings() {
+        let event: Event = Tally {
+            results: vec![Uint256::from_u128(0u128), Uint256::from_u128(15u128)],
+            total_result: Uint256::from_u128(15u128),
+            question_id: None,
+        }
+        .into();
+        let results: Vec<String> = serde_json::from_str(attribute(&event, "results")).unwrap();
+        assert_eq!(results, vec!["0", "15"]);
+        assert!(event
+            .attributes
+            .iter()
+            .all(|attr| attr.key != "question_id"));
+
+        let event: Event = UploadDeactivateMessage {
+            operator: Addr::unchecked("operator"),
+            deactivate_message: vec![vec![Uint256::from_u128(1u128), Uint256::from_u128(2u128)]],
+        }
+        .into();
+        let rows: Vec<Vec<String>> =
+            serde_json::from_str(attribute(&event, "deactivate_message")).unwrap();
+        assert_eq!(rows, vec![vec!["1", "2"]]);
+    }
+
+    #[test]
+    fn pubkeys_are_split_into_coordinates() {
+        let event: Event = PublishMessage {
+            msg_chain_length: Uint256::from_u128(3u128),
+            message: vec![Uint256::from_u128(7u128); 7],
+            enc_pub_key: [Uint256::from_u128(11u128), Uint256::from_u128(12u128)],
+            question_id: Some(1),
+        }
+        .into();
+        assert_eq!(attribute(&event, "enc_pub_key_x"), "11");
+        assert_eq!(attribute(&event, "enc_pub_key_y"), "12");
+        assert_eq!(attribute(&event, "question_id"), "1");
+    }
+}